@@ -1,6 +1,7 @@
 extern crate version_check as rustc;
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(pear_nightly)");
     if let Some((version, channel, _)) = rustc::triple() {
         if version.at_least("1.31.0") && channel.supports_features() {
             println!("cargo:rustc-cfg=pear_nightly");
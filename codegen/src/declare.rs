@@ -0,0 +1,139 @@
+use syn::{punctuated::Punctuated, Token};
+use syn::parse::{Parse, ParseStream, Parser};
+use proc_macro2::TokenStream as TokenStream2;
+
+use crate::diagnostics::{Diagnostic, Spanned, SpanExt};
+
+/// The associated types of `Input` that can be fixed or bounded.
+const ASSOCIATED: &[&str] = &["Token", "Slice", "Many", "Marker", "Context"];
+
+/// An associated type of `Input`: fixed, as in `Slice = &'a str`, or bounded,
+/// as in `Many: Into<String>`.
+enum Associated {
+    Fixed(syn::Ident, Box<syn::Type>),
+    Bounded(syn::Ident, Punctuated<syn::TypeParamBound, Token![+]>),
+}
+
+impl Associated {
+    fn name(&self) -> &syn::Ident {
+        match self {
+            Associated::Fixed(name, _) | Associated::Bounded(name, _) => name,
+        }
+    }
+}
+
+impl Parse for Associated {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: syn::Ident = input.parse()?;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Ok(Associated::Fixed(name, input.parse()?))
+        } else {
+            input.parse::<Token![:]>()?;
+            let bounds = Punctuated::parse_separated_nonempty(input)?;
+            Ok(Associated::Bounded(name, bounds))
+        }
+    }
+}
+
+impl quote::ToTokens for Associated {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Associated::Fixed(name, ty) => quote::quote!(#name = #ty).to_tokens(tokens),
+            Associated::Bounded(name, bounds) => quote::quote!(#name: #bounds).to_tokens(tokens),
+        }
+    }
+}
+
+/// `pub Input<'a, T: Bound>: Super + Traits (Token = T, Many: Bound) where ..`
+pub struct Declare {
+    vis: syn::Visibility,
+    name: syn::Ident,
+    generics: syn::Generics,
+    supertraits: Punctuated<syn::TypeParamBound, Token![+]>,
+    associated: Punctuated<Associated, Token![,]>,
+}
+
+impl Parse for Declare {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        let mut generics: syn::Generics = input.parse()?;
+
+        // A bound like `Trait (..)` parses as `Fn`-like sugar, so the
+        // supertraits end at the group followed by the end or by `where`.
+        let mut supertraits = Punctuated::new();
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            let mut bounds = TokenStream2::new();
+            while !(input.peek(syn::token::Paren) && ends_declaration(input)) {
+                bounds.extend(Some(input.parse::<proc_macro2::TokenTree>()?));
+            }
+
+            supertraits = Punctuated::parse_terminated.parse2(bounds)?;
+        }
+
+        let content;
+        syn::parenthesized!(content in input);
+        let associated = content.parse_terminated(Associated::parse)?;
+        generics.where_clause = input.parse()?;
+        Ok(Declare { vis, name, generics, supertraits, associated })
+    }
+}
+
+/// Whether the token tree after the next one in `input` ends the declaration.
+fn ends_declaration(input: ParseStream) -> bool {
+    let after = input.fork();
+    let _ = after.parse::<proc_macro2::TokenTree>();
+    after.is_empty() || after.peek(Token![where])
+}
+
+impl Declare {
+    fn validate(&self) -> Result<(), Diagnostic> {
+        let mut seen: Vec<&syn::Ident> = vec![];
+        for assoc in self.associated.iter() {
+            let name = assoc.name();
+            if !ASSOCIATED.iter().any(|known| name == known) {
+                return Err(name.span().error(format!("`{}` is not an associated type of `Input`", name))
+                    .help("the associated types are `Token`, `Slice`, `Many`, `Marker`, and `Context`"));
+            }
+
+            if let Some(prev) = seen.iter().find(|prev| *prev == &name) {
+                return Err(name.span().error(format!("duplicate associated type `{}`", name))
+                    .span_note(prev.span(), "previously declared here"));
+            }
+
+            seen.push(name);
+        }
+
+        Ok(())
+    }
+
+    pub fn to_tokens(&self) -> Result<TokenStream2, Diagnostic> {
+        self.validate()?;
+
+        let (vis, name, associated) = (&self.vis, &self.name, &self.associated);
+        let supertraits = self.supertraits.iter();
+        let bound = quote!(::pear::input::Input<#associated> #(+ #supertraits)*);
+
+        let generics = &self.generics;
+        let where_clause = &generics.where_clause;
+        let (_, ty_generics, _) = generics.split_for_impl();
+
+        // The input the trait is implemented for must outlive every lifetime
+        // the trait is generic over, since its associated types may borrow.
+        let input = syn::Ident::new("__I", proc_macro2::Span::mixed_site());
+        let lifetimes = generics.lifetimes().map(|def| &def.lifetime);
+        let mut impl_generics = generics.clone();
+        impl_generics.params.push(syn::parse_quote!(#input));
+        impl_generics.make_where_clause().predicates
+            .push(syn::parse_quote!(#input: #bound #(+ #lifetimes)*));
+
+        let (impl_generics, _, impl_where_clause) = impl_generics.split_for_impl();
+        Ok(quote! {
+            #vis trait #name #generics: #bound #where_clause { }
+
+            impl #impl_generics #name #ty_generics for #input #impl_where_clause { }
+        })
+    }
+}
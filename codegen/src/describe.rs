@@ -0,0 +1,74 @@
+use proc_macro2::TokenStream as TokenStream2;
+
+use crate::diagnostics::{Diagnostic, Spanned, SpanExt};
+
+/// Returns the default description of a variant named `name`: its words in
+/// lowercase, i.e, `string literal` for `StringLiteral`.
+fn default_description(name: &str) -> String {
+    let mut description = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            description.push(' ');
+        }
+
+        description.extend(c.to_lowercase());
+    }
+
+    description
+}
+
+/// Returns the description given by `variant`'s `#[describe("...")]`
+/// attribute, if it has one.
+fn variant_description(variant: &syn::Variant) -> Result<Option<syn::LitStr>, Diagnostic> {
+    let mut description = None;
+    for attr in variant.attrs.iter().filter(|attr| attr.path.is_ident("describe")) {
+        if description.is_some() {
+            return Err(attr.span().error("duplicate `describe` attribute"));
+        }
+
+        let lit = attr.parse_args::<syn::LitStr>()
+            .map_err(|_| attr.span().error("`describe` requires a string description")
+                .help("use `#[describe(\"a description\")]`"))?;
+
+        description = Some(lit);
+    }
+
+    Ok(description)
+}
+
+pub fn derive_describe(input: syn::DeriveInput) -> Result<TokenStream2, Diagnostic> {
+    let data = match input.data {
+        syn::Data::Enum(ref data) => data,
+        _ => return Err(input.ident.span().error("`Describe` can only be derived for enums")),
+    };
+
+    let mut arms = vec![];
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let description = match variant_description(variant)? {
+            Some(lit) => lit,
+            None => syn::LitStr::new(&default_description(&ident.to_string()), ident.span()),
+        };
+
+        arms.push(quote!(Self::#ident { .. } => f.write_str(#description)));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::pear::input::Describe for #name #ty_generics #where_clause {
+            fn describe(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match *self {
+                    #(#arms,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::pear::input::Show for #name #ty_generics #where_clause {
+            #[inline(always)]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::pear::input::Describe::describe(self, f)
+            }
+        }
+    })
+}
@@ -161,12 +161,20 @@ mod imp {
     }
 }
 
+/// The diagnostics used on stable toolchains. The module is compiled on every
+/// toolchain, so that it's tested everywhere, but only used without
+/// `pear_nightly`.
 #[allow(dead_code)]
-#[cfg(not(pear_nightly))]
-mod imp {
-    use super::{Span, Spanned, SpanExt};
+mod fallback {
+    use proc_macro2::TokenStream as TokenStream2;
+
+    use super::Span;
     use super::{WARN_PREFIX, NOTE_PREFIX, HELP_PREFIX};
 
+    /// Marks a child encoded in a `syn::parse::Error` that had no span of its
+    /// own and was given its parent's.
+    const UNSPANNED_PREFIX: &str = "[unspanned] ";
+
     /// An enum representing a diagnostic level.
     #[derive(Copy, Clone, Debug)]
     pub enum Level {
@@ -182,6 +190,40 @@ mod imp {
         __NonExhaustive
     }
 
+    impl Level {
+        /// The name of the level as rendered by rustc.
+        fn name(self) -> &'static str {
+            match self {
+                Level::Warning => "warning",
+                Level::Note => "note",
+                Level::Help => "help",
+                _ => "error"
+            }
+        }
+
+        /// The prefix used to encode the level in a `syn::parse::Error`.
+        fn prefix(self) -> &'static str {
+            match self {
+                Level::Warning => WARN_PREFIX,
+                Level::Note => NOTE_PREFIX,
+                Level::Help => HELP_PREFIX,
+                _ => ""
+            }
+        }
+
+        /// Decodes a level encoded in `message` by `Level::prefix()`, returning
+        /// the level and the message with the prefix stripped.
+        fn decode(message: &str) -> (Level, &str) {
+            for &level in &[Level::Warning, Level::Note, Level::Help] {
+                if let Some(rest) = message.strip_prefix(level.prefix()) {
+                    return (level, rest);
+                }
+            }
+
+            (Level::Error, message)
+        }
+    }
+
     /// A structure representing a diagnostic message and associated children
     /// messages.
     #[derive(Clone, Debug)]
@@ -224,10 +266,10 @@ mod imp {
     }
 
     impl Diagnostic {
-        /// Creates a new diagno, Spannedstic with the given `level` and `message`.
+        /// Creates a new diagnostic with the given `level` and `message`.
         pub fn new<T: Into<String>>(level: Level, message: T) -> Diagnostic {
             Diagnostic {
-                level: level,
+                level,
                 message: message.into(),
                 spans: vec![],
                 children: vec![]
@@ -240,7 +282,7 @@ mod imp {
             where S: MultiSpan, T: Into<String>
         {
             Diagnostic {
-                level: level,
+                level,
                 message: message.into(),
                 spans: spans.into_spans(),
                 children: vec![]
@@ -253,13 +295,50 @@ mod imp {
         diagnostic_child_methods!(span_help, help, Level::Help);
 
         /// Emit the diagnostic.
+        ///
+        /// Stable `proc_macro` has no diagnostic API, so the diagnostic is
+        /// encoded into tokens that make rustc report it: `compile_error!` for
+        /// errors and the use of a `#[deprecated]` item for everything else.
+        /// Children without a span of their own are rendered into their
+        /// parent's message as rustc would (`= help: ...`) and thus share its
+        /// span; children with a span are emitted separately at that span.
+        ///
+        /// The returned tokens are valid in both item and statement position.
         pub fn emit_as_tokens(self) -> proc_macro::TokenStream {
-            // FIXME: Probably consider more than the first error.
-            let syn_error: syn::parse::Error = self.into();
-            syn_error.to_compile_error().into()
+            self.into_tokens().into()
+        }
+
+        fn into_tokens(self) -> TokenStream2 {
+            let span = self.spans.first().cloned().unwrap_or_else(Span::call_site);
+            let mut message = self.message;
+            let mut children = TokenStream2::new();
+            for child in self.children {
+                if child.spans.is_empty() {
+                    message.push_str(&format!("\n  = {}: {}", child.level.name(), child.message));
+                } else {
+                    children.extend(child.into_tokens());
+                }
+            }
+
+            let primary = match self.level {
+                Level::Error => quote_spanned!(span => ::core::compile_error! { #message }),
+                level => {
+                    let message = format!("{}: {}", level.name(), message);
+                    let ident = syn::Ident::new("____pear_diagnostic", span);
+                    quote_spanned!(span => const _: () = {
+                        #[deprecated(note = #message)]
+                        #[allow(non_upper_case_globals)]
+                        const #ident: () = ();
+                        #ident
+                    };)
+                }
+            };
+
+            quote!(#primary #children)
         }
     }
 
+    #[cfg(not(pear_nightly))]
     macro_rules! diagnostic_method {
         ($name:ident, $level:expr) => (
             /// Creates a new `Diagnostic` with the given `message` at the span
@@ -270,58 +349,110 @@ mod imp {
         )
     }
 
-    impl SpanExt for Span {
+    #[cfg(not(pear_nightly))]
+    impl super::SpanExt for Span {
         diagnostic_method!(error, Level::Error);
         diagnostic_method!(warning, Level::Warning);
         diagnostic_method!(note, Level::Note);
         diagnostic_method!(help, Level::Help);
 
         fn join(&self, _other: Span) -> Option<Span> {
-            Some(self.clone())
+            Some(*self)
         }
     }
 
     impl From<::syn::parse::Error> for Diagnostic {
         fn from(errors: ::syn::parse::Error) -> Diagnostic {
-            let mut diag = errors.span().error(errors.to_string());
-            for e in errors.into_iter().skip(1) {
-                diag = diag.span_error(e.span(), e.to_string());
-            }
+            let mut diags = errors.into_iter().map(|e| {
+                let message = e.to_string();
+                match message.strip_prefix(UNSPANNED_PREFIX) {
+                    Some(message) => {
+                        let (level, message) = Level::decode(message);
+                        Diagnostic::new(level, message)
+                    }
+                    None => {
+                        let (level, message) = Level::decode(&message);
+                        Diagnostic::spanned(e.span(), level, message)
+                    }
+                }
+            });
 
+            let mut diag = diags.next().expect("syn errors are non-empty");
+            diag.children.extend(diags);
             diag
         }
     }
 
-    impl Into<::syn::parse::Error> for Diagnostic {
-        fn into(self) -> ::syn::parse::Error {
-            let span = if self.spans.is_empty() {
-                proc_macro2::Span::call_site()
-            } else {
-                self.spans[0]
-            };
-
-            let msg_prefix = match self.level {
-                Level::Warning => WARN_PREFIX,
-                Level::Note => NOTE_PREFIX,
-                Level::Help => HELP_PREFIX,
-                _ => ""
-            };
-
-            let message = format!("{}{}", msg_prefix, self.message);
-            let mut error = ::syn::parse::Error::new(span.into(), message);
-            for child in self.children {
-                error.combine(child.into());
+    impl From<Diagnostic> for ::syn::parse::Error {
+        fn from(diag: Diagnostic) -> ::syn::parse::Error {
+            let span = diag.spans.first().cloned().unwrap_or_else(Span::call_site);
+            let message = format!("{}{}", diag.level.prefix(), diag.message);
+            let mut error = ::syn::parse::Error::new(span, message);
+            for child in diag.children {
+                match child.spans.is_empty() {
+                    true => {
+                        let message = child.level.prefix().to_string() + &child.message;
+                        let message = format!("{}{}", UNSPANNED_PREFIX, message);
+                        error.combine(::syn::parse::Error::new(span, message));
+                    }
+                    false => error.combine(child.into()),
+                }
             }
 
             error
         }
     }
 
-    impl<T: quote::ToTokens> Spanned for T {
+    #[cfg(not(pear_nightly))]
+    impl<T: quote::ToTokens> super::Spanned for T {
         fn span(&self) -> Span {
             syn::spanned::Spanned::span(self)
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn error(message: &str) -> Diagnostic {
+            Diagnostic::spanned(Span::call_site(), Level::Error, message)
+        }
+
+        #[test]
+        fn test_error_renders_unspanned_children() {
+            let diag = error("bad input").help("try this").note("for reference");
+            let tokens = diag.into_tokens().to_string();
+            assert!(tokens.contains("compile_error"));
+            assert!(tokens.contains(r#""bad input\n  = help: try this\n  = note: for reference""#));
+        }
+
+        #[test]
+        fn test_spanned_children_are_emitted_separately() {
+            let diag = error("bad input").span_warning(Span::call_site(), "odd input");
+            let tokens = diag.into_tokens().to_string();
+            assert!(tokens.contains(r#"compile_error ! { "bad input" }"#));
+            assert!(tokens.contains(r#"deprecated (note = "warning: odd input")"#));
+        }
+
+        #[test]
+        fn test_syn_error_round_trip() {
+            let diag = error("bad input").help("try this").span_note(Span::call_site(), "here");
+            let diag = Diagnostic::from(syn::parse::Error::from(diag));
+
+            assert!(matches!(diag.level, Level::Error));
+            assert_eq!(diag.message, "bad input");
+            assert_eq!(diag.children.len(), 2);
+            assert!(matches!(diag.children[0].level, Level::Help));
+            assert_eq!(diag.children[0].message, "try this");
+            assert!(diag.children[0].spans.is_empty());
+            assert!(matches!(diag.children[1].level, Level::Note));
+            assert_eq!(diag.children[1].message, "here");
+            assert_eq!(diag.children[1].spans.len(), 1);
+        }
+    }
 }
 
+#[cfg(not(pear_nightly))]
+use fallback as imp;
+
 pub use imp::Diagnostic;
@@ -0,0 +1,104 @@
+use proc_macro2::TokenStream as TokenStream2;
+
+use crate::diagnostics::{Diagnostic, Spanned, SpanExt};
+use crate::template::{Piece, parse_template, template, separator};
+
+/// Returns a pattern destructuring `path` into bindings for its fields and the
+/// statements that emit them as laid out by `attrs`' template, if any.
+fn emit_fields(
+    path: TokenStream2,
+    fields: &syn::Fields,
+    attrs: &[syn::Attribute],
+    span: proc_macro2::Span,
+) -> Result<(TokenStream2, Vec<TokenStream2>), Diagnostic> {
+    let mut members = vec![];
+    let mut bindings = vec![];
+    let mut emits = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let member = match field.ident {
+            Some(ref ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(i.into()),
+        };
+
+        let binding = syn::Ident::new(&format!("__emit_{}", i), proc_macro2::Span::call_site());
+        let emit = match separator(field, "emit")? {
+            Some(sep) => quote! {
+                ::pear::emit::Emit::emit(&::pear::emit::Separated(#binding, #sep), f)?;
+            },
+            None => quote!(::pear::emit::Emit::emit(#binding, f)?;),
+        };
+
+        members.push(member);
+        bindings.push(binding);
+        emits.push(emit);
+    }
+
+    let pattern = quote!(#path { #(#members: #bindings),* });
+    let template = match template(attrs, "emit")? {
+        Some(template) => template,
+        None if fields.is_empty() => return Err(span.error("a unit type or variant requires a template")
+            .help("use `#[emit(\"text\")]` to give the text it emits")),
+        None => return Ok((pattern, emits)),
+    };
+
+    let mut stmts = vec![];
+    for piece in parse_template(&template)? {
+        match piece {
+            Piece::Literal(string) => stmts.push(quote!(f.write_str(#string)?;)),
+            Piece::Field(name) => {
+                let i = fields.iter().enumerate()
+                    .position(|(i, field)| match field.ident {
+                        Some(ref ident) => *ident == name,
+                        None => i.to_string() == name,
+                    })
+                    .ok_or_else(|| template.span().error(format!("unknown field `{}` in template", name)))?;
+
+                stmts.push(emits[i].clone());
+            }
+        }
+    }
+
+    Ok((pattern, stmts))
+}
+
+pub fn derive_emit(input: syn::DeriveInput) -> Result<TokenStream2, Diagnostic> {
+    let mut arms = vec![];
+    match input.data {
+        syn::Data::Struct(ref data) => {
+            let span = input.ident.span();
+            let (pattern, stmts) = emit_fields(quote!(Self), &data.fields, &input.attrs, span)?;
+            arms.push(quote!(#pattern => { #(#stmts)* }));
+        }
+        syn::Data::Enum(ref data) => {
+            if let Some(attr) = input.attrs.iter().find(|attr| attr.path.is_ident("emit")) {
+                return Err(attr.span().error("`emit` templates apply to variants, not enums"));
+            }
+
+            for variant in &data.variants {
+                let (ident, span) = (&variant.ident, variant.ident.span());
+                let (pattern, stmts) = emit_fields(quote!(Self::#ident), &variant.fields, &variant.attrs, span)?;
+                arms.push(quote!(#pattern => { #(#stmts)* }));
+            }
+        }
+        syn::Data::Union(_) => return Err(input.ident.span().error("`Emit` cannot be derived for unions")),
+    }
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(::pear::emit::Emit));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::pear::emit::Emit for #name #ty_generics #where_clause {
+            fn emit(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(#arms)*
+                }
+
+                Ok(())
+            }
+        }
+    })
+}
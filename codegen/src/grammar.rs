@@ -0,0 +1,387 @@
+use syn::{punctuated::Punctuated, Token};
+use syn::visit_mut::{self, VisitMut};
+
+use crate::parser::{Case, Pattern, Parse};
+use crate::compact_source;
+
+/// A term in the EBNF production of a parser.
+#[derive(Clone)]
+enum Term {
+    /// A terminal, like `'a'`, a call to another parser, like `value`, or a
+    /// description of a token, like `? c.is_ascii_digit() ?`.
+    Symbol(String),
+    /// Alternatives, each a sequence of terms: `( a | b )`.
+    Choice(Vec<Vec<Term>>),
+    /// Zero or more repetitions of a sequence: `{ a }`.
+    Repeat(Vec<Term>),
+    /// An optional sequence: `[ a ]`.
+    Optional(Vec<Term>),
+}
+
+fn render(terms: &[Term]) -> String {
+    let rendered: Vec<_> = terms.iter()
+        .map(|term| match term {
+            Term::Symbol(symbol) => symbol.clone(),
+            Term::Repeat(terms) => group("{", &[render(terms)], "}"),
+            Term::Optional(terms) => group("[", &[render(terms)], "]"),
+            Term::Choice(alts) => {
+                let alts: Vec<_> = alts.iter().map(|alt| render(alt)).collect();
+                match alts.iter().any(|alt| alt.is_empty()) {
+                    true => group("[", &alts, "]"),
+                    false if alts.len() == 1 => alts[0].clone(),
+                    false => group("(", &alts, ")"),
+                }
+            }
+        })
+        .filter(|rendered| !rendered.is_empty())
+        .collect();
+
+    rendered.join(" ")
+}
+
+/// Renders the non-empty `alts` as `open a | b close`, or as nothing if there
+/// are none.
+fn group(open: &str, alts: &[String], close: &str) -> String {
+    let alts: Vec<_> = alts.iter().filter(|alt| !alt.is_empty()).cloned().collect();
+    match alts.is_empty() {
+        true => String::new(),
+        false => format!("{} {} {}", open, alts.join(" | "), close),
+    }
+}
+
+/// Returns the symbol for a call to a parser: the literal for `eat('a')` and
+/// `eat_slice("ab")`, the name of the parser for a call without arguments,
+/// and the call itself otherwise, with arguments other than literals and
+/// paths, like closures, elided as `..`.
+fn call_symbol(call: &syn::ExprCall) -> String {
+    let name = match *call.func {
+        syn::Expr::Path(ref path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    };
+
+    match (name.as_deref(), call.args.first()) {
+        (Some("eat") | Some("eat_slice"), Some(syn::Expr::Lit(lit))) if call.args.len() == 1 => {
+            compact_source(&lit.lit)
+        }
+        (Some(_), None) => compact_source(&call.func),
+        _ => {
+            let args: Vec<_> = call.args.iter()
+                .map(|arg| match arg {
+                    syn::Expr::Lit(_) | syn::Expr::Path(_) => compact_source(arg),
+                    _ => "..".into(),
+                })
+                .collect();
+
+            format!("{}({})", compact_source(&call.func), args.join(", "))
+        }
+    }
+}
+
+/// Returns the terms a call to a parser parses: a repetition of the token
+/// described by the predicate of a `take_while(..)`-like call and the
+/// [`call_symbol()`] otherwise.
+fn call_terms(call: &syn::ExprCall) -> Vec<Term> {
+    let name = match *call.func {
+        syn::Expr::Path(ref path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    };
+
+    let token = match call.args.first() {
+        Some(syn::Expr::Closure(closure)) if call.args.len() == 1 => compact_source(&closure.body),
+        Some(path@syn::Expr::Path(_)) if call.args.len() == 1 => compact_source(path),
+        _ => return vec![Term::Symbol(call_symbol(call))],
+    };
+
+    let token = Term::Symbol(format!("? {} ?", token));
+    match name.as_deref() {
+        Some("take_some_while") => vec![token.clone(), Term::Repeat(vec![token])],
+        Some("take_while" | "skip_while" | "take_while_fast" | "skip_while_fast") => {
+            vec![Term::Repeat(vec![token])]
+        }
+        _ => vec![Term::Symbol(call_symbol(call))],
+    }
+}
+
+/// Returns the terms parsed by `expr`, an expression passed to `parse_try!`.
+fn tried_terms(expr: &syn::Expr) -> Vec<Term> {
+    match parser_call(expr) {
+        Some(call) => call_terms(&call),
+        None => Sequence::of(expr).terms,
+    }
+}
+
+/// Returns the expression `expr` tries if it is `parse_try!(..)`.
+fn tried(expr: &syn::Expr) -> Option<syn::Expr> {
+    match expr {
+        syn::Expr::Macro(m) if m.mac.path.segments.last().is_some_and(|s| s.ident == "parse_try") => {
+            syn::parse2(m.mac.tokens.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Returns the terms of the parser `cond` tries if `cond` is
+/// `let pattern = parse_try!(..)`. These are parsed, not optionally, whenever
+/// the condition holds.
+fn guard(cond: &syn::Expr) -> Option<Vec<Term>> {
+    match cond {
+        syn::Expr::Let(v) => tried(&v.expr).map(|expr| tried_terms(&expr)),
+        _ => None,
+    }
+}
+
+/// If the last statement of `block` is `if parse_try!(..).is_none() { break }`,
+/// returns the terms of the separator it tries and `block` without it.
+fn separated(block: &syn::Block) -> Option<(Vec<Term>, syn::Block)> {
+    let v = match block.stmts.last()? {
+        syn::Stmt::Expr(syn::Expr::If(v)) | syn::Stmt::Semi(syn::Expr::If(v), _) => v,
+        _ => return None,
+    };
+
+    let breaks = match &*v.then_branch.stmts {
+        [syn::Stmt::Expr(syn::Expr::Break(b)) | syn::Stmt::Semi(syn::Expr::Break(b), _)] => {
+            b.label.is_none() && b.expr.is_none()
+        }
+        _ => false,
+    };
+
+    let separator = match *v.cond {
+        _ if !breaks || v.else_branch.is_some() => return None,
+        syn::Expr::MethodCall(ref call) if call.method == "is_none" && call.args.is_empty() => {
+            tried(&call.receiver)?
+        }
+        _ => return None,
+    };
+
+    let mut body = block.clone();
+    body.stmts.pop();
+    Some((tried_terms(&separator), body))
+}
+
+/// Returns the term for a loop whose iterations parse `head`, then `body`.
+/// If the loop ends when an iteration's `head` fails, `guarded` is `true`.
+/// Without a guard, a `while` loop may end before its first iteration, which
+/// `optional` indicates, and a `loop` may not.
+///
+/// A body that ends by breaking if a separator is absent parses a separated
+/// list: `item { separator item }`. A guarded list may end with a trailing
+/// separator, after which `head` fails, or be empty if the first `head` fails.
+fn repetition(head: Vec<Term>, body: &syn::Block, guarded: bool, optional: bool) -> Term {
+    let (separator, body) = match separated(body) {
+        Some((separator, body)) => (separator, body),
+        None => {
+            let mut item = head;
+            item.extend(Sequence::of_block(body).terms);
+            return Term::Repeat(item);
+        }
+    };
+
+    let mut item = head;
+    item.extend(Sequence::of_block(&body).terms);
+    let mut rest = separator.clone();
+    rest.extend(item.iter().cloned());
+
+    let mut list = item;
+    list.push(Term::Repeat(rest));
+    if guarded {
+        list.push(Term::Optional(separator));
+    }
+
+    match guarded || optional {
+        true => Term::Optional(list),
+        false => Term::Choice(vec![list]),
+    }
+}
+
+/// Returns the call to a parser `expr` is, if any: `rule(..)` or
+/// `self.rule(..)`.
+fn parser_call(expr: &syn::Expr) -> Option<syn::ExprCall> {
+    match expr {
+        syn::Expr::Call(call) => Some(call.clone()),
+        syn::Expr::MethodCall(call) => match *call.receiver {
+            syn::Expr::Path(ref p) if p.path.is_ident("self") => {
+                let (method, args) = (&call.method, &call.args);
+                Some(syn::parse_quote!(#method(#args)))
+            }
+            _ => None
+        },
+        _ => None
+    }
+}
+
+/// The sequence of terms an expression parses, in order.
+#[derive(Default)]
+struct Sequence {
+    terms: Vec<Term>,
+    /// Whether the expression unconditionally fails with a `parse_error!`.
+    fails: bool,
+}
+
+impl Sequence {
+    fn of(expr: &syn::Expr) -> Sequence {
+        let mut sequence = Sequence::default();
+        sequence.visit_expr_mut(&mut expr.clone());
+        sequence
+    }
+
+    fn of_block(block: &syn::Block) -> Sequence {
+        let mut sequence = Sequence::default();
+        sequence.visit_block_mut(&mut block.clone());
+        sequence
+    }
+
+    /// Pushes a choice between `alternatives`, leaving out those that fail.
+    fn push_choice<I: IntoIterator<Item = Sequence>>(&mut self, alternatives: I) {
+        let alts = alternatives.into_iter()
+            .filter(|alt| !alt.fails)
+            .map(|alt| alt.terms)
+            .collect();
+
+        self.terms.push(Term::Choice(alts));
+    }
+
+    fn push_switch(&mut self, tokens: proc_macro2::TokenStream) {
+        use syn::parse::Parser;
+
+        let parser = |input: syn::parse::ParseStream| {
+            <Punctuated<Case, Token![,]>>::parse_terminated_with(input, Case::syn_parse)
+        };
+
+        let cases = match parser.parse2(tokens) {
+            Ok(cases) => cases,
+            Err(_) => return,
+        };
+
+        let alternatives = cases.iter().flat_map(|case| match case.pattern {
+            Pattern::Wild(..) => vec![Sequence::of(&case.expr)],
+            Pattern::Calls(ref calls) => calls.iter().map(|call| {
+                let mut alt = Sequence::of(&case.expr);
+                alt.terms.insert(0, Term::Symbol(call_symbol(&call.expr)));
+                alt
+            }).collect(),
+        });
+
+        self.push_choice(alternatives.collect::<Vec<_>>());
+    }
+}
+
+impl VisitMut for Sequence {
+    fn visit_expr_try_mut(&mut self, v: &mut syn::ExprTry) {
+        match parser_call(&v.expr) {
+            Some(call) => self.terms.extend(call_terms(&call)),
+            None => visit_mut::visit_expr_try_mut(self, v),
+        }
+    }
+
+    fn visit_expr_while_mut(&mut self, v: &mut syn::ExprWhile) {
+        let term = match guard(&v.cond) {
+            Some(head) => repetition(head, &v.body, true, true),
+            None => repetition(Sequence::of(&v.cond).terms, &v.body, false, true),
+        };
+
+        self.terms.push(term);
+    }
+
+    fn visit_expr_loop_mut(&mut self, v: &mut syn::ExprLoop) {
+        self.terms.push(repetition(vec![], &v.body, false, false));
+    }
+
+    fn visit_expr_for_loop_mut(&mut self, v: &mut syn::ExprForLoop) {
+        self.visit_expr_mut(&mut v.expr);
+        self.terms.push(Term::Repeat(Sequence::of_block(&v.body).terms));
+    }
+
+    fn visit_expr_if_mut(&mut self, v: &mut syn::ExprIf) {
+        let mut then = Sequence::default();
+        match guard(&v.cond) {
+            Some(terms) => then.terms = terms,
+            None => self.visit_expr_mut(&mut v.cond),
+        }
+
+        then.visit_block_mut(&mut v.then_branch.clone());
+        let otherwise = match v.else_branch {
+            Some((_, ref expr)) => Sequence::of(expr),
+            None => Sequence::default(),
+        };
+
+        self.push_choice(vec![then, otherwise]);
+    }
+
+    fn visit_expr_match_mut(&mut self, v: &mut syn::ExprMatch) {
+        self.visit_expr_mut(&mut v.expr);
+        self.push_choice(v.arms.iter().map(|arm| Sequence::of(&arm.body)).collect::<Vec<_>>());
+    }
+
+    fn visit_macro_mut(&mut self, m: &mut syn::Macro) {
+        let name = match m.path.segments.last() {
+            Some(segment) => segment.ident.to_string(),
+            None => return,
+        };
+
+        match &*name {
+            "switch" => self.push_switch(m.tokens.clone()),
+            "parse_error" => self.fails = true,
+            "parse_try" => if let Ok(expr) = syn::parse2::<syn::Expr>(m.tokens.clone()) {
+                self.terms.push(Term::Optional(tried_terms(&expr)));
+            },
+            _ => {}
+        }
+    }
+
+    // Closures are predicates, like in `take_while(|c| ..)`, not grammar.
+    fn visit_expr_closure_mut(&mut self, _: &mut syn::ExprClosure) { }
+
+    // A statement like `switch! { .. }` is parsed as an item macro.
+    fn visit_item_mut(&mut self, item: &mut syn::Item) {
+        if let syn::Item::Macro(item) = item {
+            self.visit_macro_mut(&mut item.mac);
+        }
+    }
+}
+
+/// Returns the EBNF production for the parser `name` with body `block`, i.e,
+/// `pair = key ':' value ;`.
+pub fn production(name: &syn::Ident, block: &syn::Block) -> String {
+    format!("{} = {} ;", name, render(&Sequence::of_block(block).terms))
+}
+
+/// Returns `#[doc]` attributes for a "Grammar" section listing `productions`.
+pub fn doc_section(productions: &[String]) -> Vec<syn::Attribute> {
+    let listing = format!("```text\n{}\n```", productions.join("\n"));
+    vec![
+        syn::parse_quote!(#[doc = ""]),
+        syn::parse_quote!(#[doc = "# Grammar"]),
+        syn::parse_quote!(#[doc = ""]),
+        syn::parse_quote!(#[doc = #listing]),
+    ]
+}
+
+fn is_parser(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path.segments.last().is_some_and(|s| s.ident == "parser"))
+}
+
+/// Returns the productions of every `#[parser]` function and method in
+/// `items`, including those in nested modules.
+pub fn productions(items: &[syn::Item]) -> Vec<String> {
+    let mut productions = vec![];
+    for item in items {
+        match item {
+            syn::Item::Fn(f) if is_parser(&f.attrs) => {
+                productions.push(production(&f.sig.ident, &f.block));
+            }
+            syn::Item::Impl(imp) => for item in imp.items.iter() {
+                if let syn::ImplItem::Method(m) = item {
+                    if is_parser(&m.attrs) {
+                        productions.push(production(&m.sig.ident, &m.block));
+                    }
+                }
+            },
+            syn::Item::Mod(syn::ItemMod { content: Some((_, items)), .. }) => {
+                productions.extend(self::productions(items));
+            }
+            _ => {}
+        }
+    }
+
+    productions
+}
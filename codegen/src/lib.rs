@@ -9,6 +9,12 @@ extern crate syn;
 
 mod parser;
 mod diagnostics;
+mod describe;
+mod template;
+mod emit;
+mod parseable;
+mod grammar;
+mod declare;
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
@@ -38,29 +44,49 @@ struct ParserTransformer {
     input: syn::Expr,
     output: syn::Type,
     state: State,
+    /// Whether this is the body of a `raw` parser, where only the macros in
+    /// `RAW_MACROS` are transformed.
+    raw: bool,
 }
 
+/// The macros which are usable in `raw` parsers.
+const RAW_MACROS: &[&str] = &["parse_marker", "parse_mark", "parse_context", "parse_error", "state"];
+
 impl ParserTransformer {
     fn new(input: syn::Expr, output: syn::Type) -> ParserTransformer {
-        ParserTransformer { input, output, state: State::Start }
+        ParserTransformer { input, output, state: State::Start, raw: false }
+    }
+
+    fn raw(input: syn::Expr, output: syn::Type) -> ParserTransformer {
+        ParserTransformer { raw: true, ..ParserTransformer::new(input, output) }
     }
 }
 
 impl VisitMut for ParserTransformer {
     fn visit_expr_try_mut(&mut self, v: &mut syn::ExprTry) {
+        if self.raw {
+            return visit_mut::visit_expr_try_mut(self, v);
+        }
+
         let last_state = self.state;
         self.state = State::InTry;
         visit_mut::visit_expr_try_mut(self, v);
         self.state = last_state;
 
-        let expr = &v.expr;
-        let new_expr = quote_spanned!(expr.span() => #expr.map_err(|e| e.into()));
+        // Parser errors are converted by `ParseError::into_parse_error()`,
+        // foreign errors by the `IntoParseError` trait, imported by the body.
+        let (expr, input) = (&v.expr, &self.input);
+        let mark = parse_marker_ident(input.span());
+        let new_expr = quote_spanned!(expr.span() => match #expr {
+            Ok(___v) => Ok(___v),
+            Err(___e) => Err(___e.into_parse_error(#input, &*#mark)),
+        });
         let method_call: syn::Expr = syn::parse2(new_expr).expect("okay");
         v.expr = Box::new(method_call);
     }
 
     fn visit_expr_call_mut(&mut self, call: &mut syn::ExprCall) {
-        if let State::InTry = self.state {
+        if let (State::InTry, false) = (self.state, self.raw) {
             // TODO: Should we keep recursing?
             call.args.insert(0, self.input.clone());
 
@@ -71,10 +97,30 @@ impl VisitMut for ParserTransformer {
         }
     }
 
+    fn visit_expr_method_call_mut(&mut self, call: &mut syn::ExprMethodCall) {
+        // A call to another parser method: `self.rule()?`.
+        let is_self = match *call.receiver {
+            syn::Expr::Path(ref p) => p.path.is_ident("self"),
+            _ => false
+        };
+
+        if let (State::InTry, true, false) = (self.state, is_self, self.raw) {
+            call.args.insert(0, self.input.clone());
+            self.state = State::Start;
+        } else {
+            visit_mut::visit_expr_method_call_mut(self, call);
+        }
+    }
+
     fn visit_macro_mut(&mut self, m: &mut syn::Macro) {
         if let Some(ref segment) = m.path.segments.last() {
             let name = segment.ident.to_string();
-            if name == "switch" || name.starts_with("parse_") {
+            let transform = match self.raw {
+                true => RAW_MACROS.contains(&&*name),
+                false => name == "switch" || name == "state" || name.starts_with("parse_"),
+            };
+
+            if transform {
                 let (input, output) = (&self.input, &self.output);
                 let tokens = match syn::parse2::<syn::Expr>(m.tokens.clone()) {
                     Ok(mut expr) => {
@@ -101,7 +147,9 @@ impl VisitMut for ParserTransformer {
 fn extract_input_ident_ty(f: &syn::ItemFn) -> PResult<(syn::Ident, syn::Type)> {
     use syn::{FnArg::Typed, PatType, Pat::Ident, Type::Reference};
 
-    let first = f.sig.inputs.first().ok_or_else(|| {
+    // The input of a method is its first argument after `self`.
+    let skip = f.sig.receiver().is_some() as usize;
+    let first = f.sig.inputs.iter().nth(skip).ok_or_else(|| {
         let paren_span = f.sig.paren_token.span;
         paren_span.error("parsing functions require at least one input")
     })?;
@@ -119,6 +167,39 @@ fn extract_input_ident_ty(f: &syn::ItemFn) -> PResult<(syn::Ident, syn::Type)> {
     }
 }
 
+/// Replaces every `impl Trait` in `ty` with `_`. `impl Trait` is only allowed
+/// in a function's signature, but the return type is also used to annotate
+/// bindings in the function's body, where it can be inferred instead.
+fn infer_impl_trait(ty: &syn::Type) -> syn::Type {
+    struct Infer;
+
+    impl VisitMut for Infer {
+        fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+            match ty {
+                syn::Type::ImplTrait(_) => *ty = syn::parse_quote_spanned!(ty.span() => _),
+                _ => visit_mut::visit_type_mut(self, ty),
+            }
+        }
+    }
+
+    let mut ty = ty.clone();
+    Infer.visit_type_mut(&mut ty);
+    ty
+}
+
+/// The body of a parser to be wrapped by `wrapping_block()`: that of a
+/// function or of a closure.
+struct ParserBody<'a> {
+    name: String,
+    name_span: proc_macro2::Span,
+    input_ident: syn::Ident,
+    /// The type of the input, if it's known. A closure's may be inferred.
+    input_ty: Option<syn::Type>,
+    block: TokenStream2,
+    generic: Option<&'a syn::Generics>,
+    span: proc_macro2::Span,
+}
+
 fn wrapping_fn_block(
     function: &syn::ItemFn,
     scope: TokenStream2,
@@ -127,54 +208,146 @@ fn wrapping_fn_block(
 ) -> PResult<syn::Block> {
     let (input_ident, input_ty) = extract_input_ident_ty(&function)?;
     let fn_block = &function.block;
+    let body = ParserBody {
+        name: function.sig.ident.to_string(),
+        name_span: function.sig.ident.span(),
+        input_ident,
+        input_ty: Some(input_ty),
+        block: quote!(#fn_block),
+        generic: Some(&function.sig.generics).filter(|g| g.type_params().next().is_some()),
+        span: function.span(),
+    };
+
+    let new_block_tokens = wrapping_block(&body, scope, args, ret_ty)?;
+    syn::parse(new_block_tokens.into())
+        .map_err(|e| function.span().error(format!("bad function: {}", e)).into())
+}
+
+fn wrapping_block(
+    body: &ParserBody<'_>,
+    scope: TokenStream2,
+    args: &AttrArgs,
+    ret_ty: &syn::Type,
+) -> PResult<TokenStream2> {
+    let (input_ident, fn_block, span) = (&body.input_ident, &body.block, body.span);
+    let (marker_ty, rewind_to) = match body.input_ty {
+        Some(ref input_ty) => (
+            quote_spanned!(span => <#input_ty as #scope::input::Input>::Marker),
+            quote_spanned!(span => <#input_ty as #scope::input::Rewind>::rewind_to),
+        ),
+        None => (quote_spanned!(span => _), quote_spanned!(span => #scope::input::Rewind::rewind_to)),
+    };
 
-    let span = function.span();
     let mark_ident = parse_marker_ident(input_ident.span());
-    let info_ident = parser_info_ident(function.sig.ident.span());
+    let info_ident = parser_info_ident(body.name_span);
     let result_map = match args.raw.is_some() {
         true => quote_spanned!(span => (
-            |#info_ident, #mark_ident: &mut <#input_ty as #scope::input::Input>::Marker| {
+            |#info_ident, #mark_ident: &mut #marker_ty| {
                 #fn_block
             })
         ),
         false => quote_spanned!(span => (
-            |#info_ident, #mark_ident: &mut <#input_ty as #scope::input::Input>::Marker| {
+            |#info_ident, #mark_ident: &mut #marker_ty| {
                 use #scope::result::AsResult;
+                #[allow(unused_imports)]
+                use #scope::error::IntoParseError as _;
                 AsResult::as_result(#fn_block)
             }
         ))
     };
 
+    // A spanned body's output is wrapped in a `Spanned` with the input consumed.
+    let result_map = match args.spanned {
+        Some(span) => quote_spanned!(span => (|#info_ident, #mark_ident: &mut _| {
+            let ___start = #scope::input::Position::position(&*#input_ident);
+            let mut ___inner = #result_map;
+            let ___res = ___inner(#info_ident, #mark_ident);
+            ___res.map(|___value| {
+                let ___end = #scope::input::Position::position(&*#input_ident);
+                #scope::input::Spanned::new(___value, ___start..___end)
+            })
+        })),
+        None => result_map
+    };
+
+    // A left-recursive body is run by `grow_seed`, which passes it the input.
+    let result_map = match args.left_recursive {
+        Some(span) => {
+            if let Some(generics) = body.generic {
+                return Err(generics.span()
+                    .error("`left_recursive` parsers cannot be generic over types"));
+            }
+
+            quote_spanned!(span => (|#info_ident, #mark_ident: &mut _| {
+                static ___KEY: u8 = 0;
+                #scope::recursion::grow_seed(&___KEY, #input_ident, *#mark_ident,
+                    |#input_ident| #result_map(#info_ident, &mut *#mark_ident))
+            }))
+        }
+        None => result_map
+    };
+
+    // A complete parser additionally requires that the input be exhausted.
+    let result_map = match args.complete {
+        Some(span) => quote_spanned!(span => (|#info_ident, #mark_ident: &mut _| {
+            let mut ___inner = #result_map;
+            ___inner(#info_ident, #mark_ident).and_then(|___value| {
+                #scope::parsers::eof(#input_ident)
+                    .map(|_| ___value)
+                    .map_err(#scope::error::ParseError::into)
+            })
+        })),
+        None => result_map
+    };
+
     let rewind = args.rewind.map(|span| quote_spanned! { span =>
-        <#input_ty as #scope::input::Rewind>::rewind_to(#input_ident, &___mark);
+        #rewind_to(#input_ident, &___mark);
+    });
+
+    let commit = args.commit.is_some();
+
+    let map_err = args.map_err.as_ref().map(|f| quote_spanned! { f.span() =>
+        let mut ___res: #ret_ty = ___res.map_err(#f);
+    });
+
+    let label = args.label.as_ref().map(|label| quote_spanned! { label.span() =>
+        let mut ___res: #ret_ty = ___res.map_err(|___e| ___e.label(#label));
     });
 
     let peek = args.peek.map(|span| quote_spanned! { span =>
-        <#input_ty as #scope::input::Rewind>::rewind_to(#input_ident, &___mark);
+        else {
+            #rewind_to(#input_ident, &___mark);
+        }
     });
 
     let new_block_tokens = {
-        let (name, raw) = (&function.sig.ident, args.raw.is_some());
-        let name_str = name.to_string();
+        let (name_str, raw) = (&body.name, args.raw.is_some());
+        // The source is located at the function's name, not at the attribute.
+        let location = quote_spanned!(body.name_span =>
+            #scope::input::ParserSource::new(module_path!(), #name_str, file!(), line!(), column!())
+        );
+
         quote_spanned!(span => {
+            static ___SOURCE: #scope::input::ParserSource = #location;
+
             // FIXME: Get rid of this!
-            let ___info = #scope::input::ParserInfo { name: #name_str, raw: #raw };
-            if #scope::macros::is_parse_debug!() {
+            let ___info = #scope::input::ParserInfo::new(#name_str, #raw).with_source(&___SOURCE);
+
+            if #scope::debug::is_instrumented() || #scope::macros::is_parse_debug!() {
                 #scope::debug::parser_entry(&___info);
             }
 
             let mut ___mark = #scope::input::Input::mark(#input_ident, &___info);
             let mut ___res: #ret_ty = #result_map(&___info, &mut ___mark);
+            #map_err
+            #label
             if let Err(ref mut ___e) = ___res {
-                let ___ctxt = #scope::input::Input::context(#input_ident, &___mark);
-                ___e.push_context(___ctxt, ___info);
+                #scope::error::push_failure(#input_ident, ___e, &___mark, ___info, #commit);
                 #rewind
-            } else {
-                #peek
-            }
+            } #peek
 
             // FIXME: Get rid of this!
-            if #scope::macros::is_parse_debug!() {
+            if #scope::debug::is_instrumented() || #scope::macros::is_parse_debug!() {
                 let ___ctxt = #scope::input::Input::context(#input_ident, &___mark);
                 let ___show = ___ctxt.as_ref().map(|c| c as &dyn #scope::input::Show);
                 #scope::debug::parser_exit(&___info, ___res.is_ok(), ___show);
@@ -185,11 +358,59 @@ fn wrapping_fn_block(
         })
     };
 
-    syn::parse(new_block_tokens.into())
-        .map_err(|e| function.span().error(format!("bad function: {}", e)).into())
+    Ok(new_block_tokens)
+}
+
+/// Emits a `#[no_mangle] extern "C"` wrapper for the entry parser `function`.
+/// See `pear::ffi` for details.
+fn ffi_wrapper(
+    function: &syn::ItemFn,
+    symbol: Option<&syn::LitStr>,
+    scope: &TokenStream2,
+    span: proc_macro2::Span
+) -> PResult<TokenStream2> {
+    if let Some(receiver) = function.sig.receiver() {
+        return Err(receiver.span().error("`ffi` parsers cannot be methods"));
+    }
+
+    if function.sig.inputs.len() != 1 {
+        return Err(function.sig.inputs.span()
+            .error("`ffi` parsers cannot take arguments other than the input"));
+    }
+
+    if function.sig.generics.type_params().next().is_some() {
+        return Err(function.sig.generics.span()
+            .error("`ffi` parsers cannot be generic over types"));
+    }
+
+    let name = &function.sig.ident;
+    let wrapper = syn::Ident::new(&format!("{}_parse", name), name.span());
+    let doc = format!("C entry point for the [`{}`] parser. See `pear::ffi`.", name);
+    let export = match symbol {
+        Some(symbol) => quote_spanned!(span => #[export_name = #symbol]),
+        None => quote_spanned!(span => #[no_mangle]),
+    };
+
+    Ok(quote_spanned! { span =>
+        #[doc = #doc]
+        ///
+        /// # Safety
+        ///
+        /// `ptr` must be valid for reads of `len` bytes or be null with a `len`
+        /// of zero. `out` must be null or valid for writes.
+        #export
+        pub unsafe extern "C" fn #wrapper(
+            ___ptr: *const u8,
+            ___len: usize,
+            ___out: *mut #scope::ffi::FfiResult
+        ) -> #scope::ffi::FfiStatus {
+            #scope::ffi::call(___ptr, ___len, ___out, |___string| {
+                #scope::ffi::run(___string, |___input| #name(___input))
+            })
+        }
+    })
 }
 
-// FIXME: Add the now missing `inline` optimization.
 fn parser_attribute(input: TokenStream, args: &AttrArgs) -> PResult<TokenStream2> {
     let input: proc_macro2::TokenStream = input.into();
     let span = input.span();
@@ -201,25 +422,96 @@ fn parser_attribute(input: TokenStream, args: &AttrArgs) -> PResult<TokenStream2
         syn::ReturnType::Default => {
             return Err(function.sig.span().error("parse function requires return type"));
         },
-        syn::ReturnType::Type(_, ty) => (**ty).clone(),
+        syn::ReturnType::Type(_, ty) => infer_impl_trait(ty),
     };
 
-    if !args.raw.is_some() {
-        let (input_ident, _) = extract_input_ident_ty(&function)?;
-        let input_expr = syn::Expr::Path(syn::ExprPath {
-            attrs: vec![],
-            qself: None,
-            path: input_ident.clone().into()
-        });
-
-        let mut transformer = ParserTransformer::new(input_expr, ret_ty.clone());
-        visit_mut::visit_item_fn_mut(&mut transformer, &mut function);
+    let (input_ident, _) = extract_input_ident_ty(&function)?;
+    if args.doc_grammar.is_some() {
+        let production = grammar::production(&function.sig.ident, &function.block);
+        function.attrs.extend(grammar::doc_section(&[production]));
     }
 
+    let input_expr = syn::Expr::Path(syn::ExprPath {
+        attrs: vec![],
+        qself: None,
+        path: input_ident.clone().into()
+    });
+
+    let mut transformer = match args.raw.is_some() {
+        true => ParserTransformer::raw(input_expr, ret_ty.clone()),
+        false => ParserTransformer::new(input_expr, ret_ty.clone()),
+    };
+
+    visit_mut::visit_item_fn_mut(&mut transformer, &mut function);
+
     let scope = match args.raw.is_some() { true => quote!(crate), false => quote!(::pear) };
+    let ffi = match args.ffi {
+        Some(span) => Some(ffi_wrapper(&function, args.ffi_symbol.as_ref(), &scope, span)?),
+        None => None
+    };
+
+    if args.inline.is_some() {
+        function.attrs.push(syn::parse_quote!(#[inline]));
+    }
+
+    // A `ParseError` carries contexts and hints, so it's large by design.
+    function.attrs.push(syn::parse_quote!(#[allow(clippy::result_large_err)]));
+
     function.block = Box::new(wrapping_fn_block(&function, scope, args, &ret_ty)?);
+    Ok(quote!(#function #ffi))
+}
+
+/// Expands `parser!`: transforms the closure's body as `parser_attribute()`
+/// does a function's and wraps it in the same context and rewind handling.
+fn parser_closure_expr(input: ParserClosure) -> PResult<TokenStream2> {
+    use syn::{Pat, PatIdent, PatType};
+
+    let ParserClosure { name, mut args, mut closure } = input;
+
+    // The closures that wrap the body are the macro's, not the caller's.
+    let call_site = proc_macro2::Span::call_site();
+    args.spanned = args.spanned.map(|_| call_site);
+    args.complete = args.complete.map(|_| call_site);
 
-    Ok(quote!(#function))
+    let (input_ident, input_ty) = match closure.inputs[0] {
+        Pat::Ident(PatIdent { ref ident, .. }) => (ident.clone(), None),
+        Pat::Type(PatType { ref pat, ref ty, .. }) => match (&**pat, &**ty) {
+            (Pat::Ident(p), syn::Type::Reference(r)) => (p.ident.clone(), Some(*r.elem.clone())),
+            _ => return Err(closure.inputs.span().error("invalid type for parser input")),
+        },
+        ref input => return Err(input.span().error("invalid parser input")
+            .help("the input must be named: `|input| { .. }`")),
+    };
+
+    // Without a return type, the error is the default if the input's type is
+    // given. Otherwise, both are inferred from where the closure is used.
+    let ret_ty: syn::Type = match closure.output {
+        syn::ReturnType::Default => match input_ty {
+            Some(ref ty) => syn::parse_quote!(::pear::input::Result<_, #ty>),
+            None => syn::parse_quote!(_),
+        },
+        syn::ReturnType::Type(_, ref ty) => infer_impl_trait(ty),
+    };
+
+    let input_expr: syn::Expr = syn::parse_quote!(#input_ident);
+    let mut transformer = ParserTransformer::new(input_expr, ret_ty.clone());
+    visit_mut::visit_expr_mut(&mut transformer, &mut closure.body);
+
+    let span = closure.or1_token.span();
+    let body = &closure.body;
+    let body = ParserBody {
+        name: name.map(|name| name.value()).unwrap_or_else(|| "closure".into()),
+        name_span: span,
+        input_ident,
+        input_ty,
+        block: quote!({ #body }),
+        generic: None,
+        span: call_site,
+    };
+
+    let block = wrapping_block(&body, quote!(::pear), &args, &ret_ty)?;
+    *closure.body = syn::parse2(block).map_err(|e| span.error(format!("bad closure: {}", e)))?;
+    Ok(quote!(#closure))
 }
 
 #[proc_macro_attribute]
@@ -236,8 +528,45 @@ pub fn parser(args: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+/// Renders `tokens` as compact source text, i.e, `eat('a')` as opposed to the
+/// `eat ('a')` that `TokenStream`'s `Display` would produce.
+fn compact_source<T: quote::ToTokens>(tokens: &T) -> String {
+    let string = tokens.to_token_stream().to_string();
+    let mut compact = String::with_capacity(string.len());
+    let mut chars = string.chars().peekable();
+    while let Some(c) = chars.next() {
+        let prev = compact.chars().last();
+        let next = chars.peek().cloned();
+        let elide = c == ' ' && match (prev, next) {
+            (Some(p), _) if "(@.&[!".contains(p) => true,
+            (_, Some(n)) if "()@.,[]!?".contains(n) => true,
+            (Some(':'), Some(':')) => true,
+            _ => false,
+        };
+
+        if !elide {
+            compact.push(c);
+        }
+    }
+
+    compact
+}
+
+/// Emits code that records the selection of a case in the provenance log.
+fn provenance_record(context: &Context, case: usize, pattern: &str) -> TokenStream2 {
+    let (info, input, marker) = (&context.info, &context.input, &context.marker);
+    let scope = &context.scope;
+    quote! {
+        if #scope::provenance::is_recording() {
+            let ___ctxt = #scope::input::Input::context(#input, #marker);
+            let ___show = ___ctxt.as_ref().map(|c| c as &dyn #scope::input::Show);
+            #scope::provenance::push(#info, #case, #pattern, ___show);
+        }
+    }
+}
+
 impl Case {
-    fn to_tokens<'a, I>(context: &Context, mut cases: I) -> TokenStream2
+    fn to_tokens<'a, I>(context: &Context, mut cases: I, index: usize) -> TokenStream2
         where I: Iterator<Item = &'a Case>
     {
         let this = match cases.next() {
@@ -251,7 +580,10 @@ impl Case {
         visit_mut::visit_expr_mut(&mut transformer, &mut case_expr);
 
         match this.pattern {
-            Pattern::Wild(..) => quote!(#case_expr),
+            Pattern::Wild(..) => {
+                let record = provenance_record(context, index, "_");
+                quote!({ #record #case_expr })
+            }
             Pattern::Calls(ref calls) => {
                 let prefix = (0..calls.len()).into_iter().map(|i| {
                     match i {
@@ -260,31 +592,58 @@ impl Case {
                     }
                 });
 
-                let name = calls.iter().map(|call| {
-                    call.name.as_ref()
-                        .map(|c| c.clone())
-                        .unwrap_or(syn::Ident::new("_", call.span()))
+                // The output is bound with a `let` so that destructuring
+                // patterns, like `(k, v)@pair()`, must be irrefutable. An
+                // unnamed output isn't bound at all.
+                let output_pat = calls.iter().map(|call| match call.name {
+                    Some(_) => quote!(___output),
+                    None => quote_spanned!(call.span() => _),
+                });
+
+                let binding = calls.iter().map(|call| match call.name {
+                    Some(ref pat) => quote! {
+                        #[allow(clippy::let_unit_value)]
+                        let #pat = ___output;
+                    },
+                    None => quote!(),
                 });
 
                 // FIXME: We're repeating ourselves, aren't we? We alrady do
                 // this in the visitor.
+                //
+                // A committed failure is propagated immediately rather than
+                // falling through to the next case.
                 let call_expr = calls.iter().map(|call| {
-                    let mut call = call.expr.clone();
-                    call.args.insert(0, input.clone());
-                    call
+                    let call = call.call_with_input(input);
+                    quote_spanned!(call.span() => match #call {
+                        Err(___e) if ___e.committed => return Err(___e.into()),
+                        ___result => ___result
+                    })
                 });
 
+                let record = calls.iter()
+                    .map(|call| provenance_record(context, index, &compact_source(call)));
+
                 let case_expr = ::std::iter::repeat(&case_expr);
-                let rest_tokens = Case::to_tokens(context, cases);
+                // Without a `_` case, a switch evaluates to `()` when no case
+                // matches. `switch_fallthrough()` explains that when it isn't
+                // the switch's type.
+                let (rest_tokens, scope) = (Case::to_tokens(context, cases, index + 1), &context.scope);
+                let rest = match rest_tokens.is_empty() {
+                    true => quote_spanned!(this.span => else {
+                        #scope::macros::switch_fallthrough()
+                    }),
+                    false => quote!(else { #rest_tokens })
+                };
 
                 quote_spanned! { this.span =>
                     #(
-                        #prefix let Ok(#name) = #call_expr {
+                        #prefix let Ok(#output_pat) = #call_expr {
+                            #binding
+                            #record
                             #case_expr
                         }
-                     )* else {
-                        #rest_tokens
-                    }
+                     )* #rest
                 }
             }
         }
@@ -293,7 +652,7 @@ impl Case {
 
 impl Switch {
     fn to_tokens(&self) -> TokenStream2 {
-        Case::to_tokens(&self.context, self.cases.iter())
+        Case::to_tokens(&self.context, self.cases.iter(), 0)
     }
 }
 
@@ -303,7 +662,93 @@ pub fn switch(input: TokenStream) -> TokenStream {
     // way to get a SynParseStream from a TokenStream to not do that.
     use syn::parse::Parser;
     match Switch::syn_parse.parse(input) {
-        Ok(switch) => switch.to_tokens().into(),
+        Ok(switch) => {
+            let tokens = switch.to_tokens();
+            let warnings = switch.unreachable_calls();
+            if warnings.is_empty() {
+                return tokens.into();
+            }
+
+            let warnings = warnings.into_iter()
+                .map(|warning| TokenStream2::from(warning.emit_as_tokens()));
+
+            quote!({ #(#warnings)* #tokens }).into()
+        }
         Err(e) => Diagnostic::from(e).emit_as_tokens(),
     }
 }
+
+#[proc_macro_attribute]
+pub fn grammar(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let args = TokenStream2::from(args);
+        return args.span().error("`grammar` attribute takes no arguments").emit_as_tokens();
+    }
+
+    let mut module = match syn::parse::<syn::ItemMod>(input) {
+        Ok(syn::ItemMod { content: None, ref semi, .. }) => {
+            return semi.span().error("`grammar` attribute only supports inline modules")
+                .help("declare the module's items inside of it: `mod name { .. }`")
+                .emit_as_tokens();
+        }
+        Ok(module) => module,
+        Err(e) => return e.span().error("`grammar` attribute only supports modules")
+            .emit_as_tokens(),
+    };
+
+    let (_, items) = module.content.as_mut().expect("inline module");
+    let productions = grammar::productions(items);
+    let listing = productions.join("\n");
+    items.push(syn::parse_quote! {
+        /// The EBNF productions of the parsers in this module.
+        pub const GRAMMAR: &str = #listing;
+    });
+
+    module.attrs.extend(grammar::doc_section(&productions));
+    quote!(#module).into()
+}
+
+#[proc_macro]
+pub fn parse_declare(input: TokenStream) -> TokenStream {
+    let declare = syn::parse_macro_input!(input as declare::Declare);
+    match declare.to_tokens() {
+        Ok(tokens) => tokens.into(),
+        Err(diag) => diag.emit_as_tokens(),
+    }
+}
+
+#[proc_macro_derive(Describe, attributes(describe))]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match describe::derive_describe(input) {
+        Ok(tokens) => tokens.into(),
+        Err(diag) => diag.emit_as_tokens(),
+    }
+}
+
+#[proc_macro_derive(Emit, attributes(emit))]
+pub fn derive_emit(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match emit::derive_emit(input) {
+        Ok(tokens) => tokens.into(),
+        Err(diag) => diag.emit_as_tokens(),
+    }
+}
+
+#[proc_macro_derive(FromPearStr, attributes(parse))]
+pub fn derive_from_pear_str(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match parseable::derive_from_pear_str(input) {
+        Ok(tokens) => tokens.into(),
+        Err(diag) => diag.emit_as_tokens(),
+    }
+}
+
+#[proc_macro]
+pub fn parser_closure(input: TokenStream) -> TokenStream {
+    use syn::parse::Parser;
+    match ParserClosure::syn_parse.parse(input).map_err(Diagnostic::from).and_then(parser_closure_expr) {
+        Ok(tokens) => tokens.into(),
+        Err(diag) => diag.emit_as_tokens(),
+    }
+}
@@ -0,0 +1,121 @@
+use proc_macro2::TokenStream as TokenStream2;
+
+use crate::diagnostics::{Diagnostic, Spanned, SpanExt};
+use crate::template::{Piece, parse_template, template, separator};
+
+/// Returns a closure parsing the fields of `path` as laid out by `attrs`'
+/// `#[parse("...")]` template, if any, or in order otherwise.
+fn parse_fields(
+    path: TokenStream2,
+    fields: &syn::Fields,
+    attrs: &[syn::Attribute],
+    span: proc_macro2::Span,
+) -> Result<TokenStream2, Diagnostic> {
+    let mut members = vec![];
+    let mut bindings = vec![];
+    let mut parses = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let member = match field.ident {
+            Some(ref ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(i.into()),
+        };
+
+        let binding = syn::Ident::new(&format!("__field_{}", i), proc_macro2::Span::call_site());
+        let ty = &field.ty;
+        let parse = match separator(field, "parse")? {
+            Some(sep) => quote!(let #binding: #ty = ::pear::__private::separated(input, #sep)?;),
+            None => quote!(let #binding = <#ty as ::pear::FromPearStr>::from_pear_str(input)?;),
+        };
+
+        members.push(member);
+        bindings.push(binding);
+        parses.push(parse);
+    }
+
+    let stmts = match template(attrs, "parse")? {
+        Some(template) => {
+            let (mut stmts, mut parsed) = (vec![], vec![false; fields.len()]);
+            for piece in parse_template(&template)? {
+                match piece {
+                    Piece::Literal(string) => stmts.push(quote!(::pear::parsers::eat_slice(input, #string)?;)),
+                    Piece::Field(name) => {
+                        let i = fields.iter().enumerate()
+                            .position(|(i, field)| match field.ident {
+                                Some(ref ident) => *ident == name,
+                                None => i.to_string() == name,
+                            })
+                            .ok_or_else(|| template.span().error(format!("unknown field `{}` in template", name)))?;
+
+                        if std::mem::replace(&mut parsed[i], true) {
+                            return Err(template.span().error(format!("field `{}` appears twice in template", name))
+                                .help("a field can only be parsed once"));
+                        }
+
+                        stmts.push(parses[i].clone());
+                    }
+                }
+            }
+
+            for (i, binding) in bindings.iter().enumerate().filter(|(i, _)| !parsed[*i]) {
+                let ty = &fields.iter().nth(i).expect("field").ty;
+                stmts.push(quote!(let #binding: #ty = ::core::default::Default::default();));
+            }
+
+            stmts
+        }
+        None if fields.is_empty() => return Err(span.error("a unit type or variant requires a template")
+            .help("use `#[parse(\"text\")]` to give the text it parses from")),
+        None => parses,
+    };
+
+    Ok(quote! {
+        |input: &mut ::pear::input::Text<'__a>| -> ::pear::input::Result<Self, ::pear::input::Text<'__a>> {
+            #(#stmts)*
+            Ok(#path { #(#members: #bindings),* })
+        }
+    })
+}
+
+pub fn derive_from_pear_str(input: syn::DeriveInput) -> Result<TokenStream2, Diagnostic> {
+    let body = match input.data {
+        syn::Data::Struct(ref data) => {
+            let span = input.ident.span();
+            let parse = parse_fields(quote!(Self), &data.fields, &input.attrs, span)?;
+            quote!((#parse)(input))
+        }
+        syn::Data::Enum(ref data) => {
+            if let Some(attr) = input.attrs.iter().find(|attr| attr.path.is_ident("parse")) {
+                return Err(attr.span().error("`parse` templates apply to variants, not enums"));
+            }
+
+            let mut variants = vec![];
+            for variant in &data.variants {
+                let (ident, span) = (&variant.ident, variant.ident.span());
+                let parse = parse_fields(quote!(Self::#ident), &variant.fields, &variant.attrs, span)?;
+                variants.push(quote!(|input| ::pear::combinators::transaction(input, #parse)));
+            }
+
+            quote!(::pear::combinators::alt(input, &[#(#variants),*]))
+        }
+        syn::Data::Union(_) => {
+            return Err(input.ident.span().error("`FromPearStr` cannot be derived for unions"));
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(::pear::FromPearStr));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::pear::FromPearStr for #name #ty_generics #where_clause {
+            fn from_pear_str<'__a>(
+                input: &mut ::pear::input::Text<'__a>
+            ) -> ::pear::input::Result<Self, ::pear::input::Text<'__a>> {
+                #body
+            }
+        }
+    })
+}
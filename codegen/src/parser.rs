@@ -1,6 +1,7 @@
 use syn::{punctuated::Punctuated, Token};
 use syn::parse::{Parse as SynParse, ParseStream as SynParseStream};
-use proc_macro2::{Span, Delimiter};
+use proc_macro2::{Span, Delimiter, TokenStream as TokenStream2};
+use quote::ToTokens;
 
 use crate::diagnostics::{Diagnostic, SpanExt, Spanned};
 
@@ -48,11 +49,51 @@ impl<'a> ParseStreamExt for SynParseStream<'a> {
 
 #[derive(Debug)]
 pub struct CallPattern {
-    pub name: Option<syn::Ident>,
+    /// The pattern the parser's output is bound to: a name, as in `x@p()`, or
+    /// a destructuring pattern, as in `(k, v)@p()`.
+    pub name: Option<syn::Pat>,
     pub at: Option<Token![@]>,
+    /// The `self` of a call to a method of a parser struct: `self.rule()`.
+    pub receiver: Option<Token![self]>,
     pub expr: syn::ExprCall,
 }
 
+impl CallPattern {
+    /// Returns the names bound by the pattern, if any.
+    pub fn bindings(&self) -> Vec<syn::Ident> {
+        use syn::visit_mut::{self, VisitMut};
+
+        struct Collector(Vec<syn::Ident>);
+
+        impl VisitMut for Collector {
+            fn visit_pat_ident_mut(&mut self, pat: &mut syn::PatIdent) {
+                self.0.push(pat.ident.clone());
+                visit_mut::visit_pat_ident_mut(self, pat);
+            }
+        }
+
+        let mut collector = Collector(vec![]);
+        if let Some(ref pat) = self.name {
+            visit_mut::visit_pat_mut(&mut collector, &mut pat.clone());
+        }
+
+        collector.0
+    }
+
+    /// Returns the call with `input` as its first argument.
+    pub fn call_with_input(&self, input: &syn::Expr) -> proc_macro2::TokenStream {
+        let mut call = self.expr.clone();
+        call.args.insert(0, input.clone());
+        match self.receiver {
+            Some(ref receiver) => {
+                let (method, args) = (&call.func, &call.args);
+                quote_spanned!(call.span() => #receiver.#method(#args))
+            }
+            None => quote!(#call)
+        }
+    }
+}
+
 impl syn::parse::Parse for CallPattern {
     fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
         Self::syn_parse(input)
@@ -62,9 +103,10 @@ impl syn::parse::Parse for CallPattern {
 impl quote::ToTokens for CallPattern {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let (expr, at) = (&self.expr, &self.at);
+        let receiver = self.receiver.as_ref().map(|receiver| quote!(#receiver.));
         match self.name {
-            Some(ref name) => quote!(#name #at #expr).to_tokens(tokens),
-            None => expr.to_tokens(tokens)
+            Some(ref name) => quote!(#name #at #receiver #expr).to_tokens(tokens),
+            None => quote!(#receiver #expr).to_tokens(tokens)
         }
     }
 }
@@ -106,42 +148,69 @@ fn parse_expr_call(input: SynParseStream) -> syn::parse::Result<syn::ExprCall> {
 
 impl Parse for CallPattern {
     fn parse(input: SynParseStream) -> PResult<Self> {
+        // `x@p()` is itself a valid pattern, so names are tried before
+        // destructuring patterns like `(k, v)@p()`.
         let name_at = input.try_parse(|input| {
             let ident: syn::Ident = input.parse()?;
             let at = input.parse::<Token![@]>()?;
-            Ok((ident, at))
-        }).ok();
+            let pat = syn::PatIdent {
+                attrs: vec![], by_ref: None, mutability: None, ident, subpat: None
+            };
+
+            Ok((syn::Pat::Ident(pat), at))
+        }).or_else(|_| input.try_parse(|input| {
+            let pat: syn::Pat = input.parse()?;
+            let at = input.parse::<Token![@]>()?;
+            Ok((pat, at))
+        })).ok();
 
         let (name, at) = match name_at {
             Some((name, at)) => (Some(name), Some(at)),
             None => (None, None)
         };
 
-        Ok(CallPattern { name, at, expr: parse_expr_call(input)? })
+        let receiver = match input.peek(Token![self]) && input.peek2(Token![.]) {
+            true => {
+                let receiver = input.parse::<Token![self]>()?;
+                input.parse::<Token![.]>()?;
+                Some(receiver)
+            }
+            false => None
+        };
+
+        Ok(CallPattern { name, at, receiver, expr: parse_expr_call(input)? })
     }
 }
 
 impl Pattern {
-    fn validate(&self) -> PResult<()> {
-        if let Pattern::Calls(ref calls) = self {
-            let first_name = calls.first().and_then(|call| call.name.clone());
-            for call in calls.iter() {
-                if first_name != call.name {
-                    let mut err = if let Some(ref ident) = call.name {
-                        ident.span()
-                            .error("captured name differs from declaration")
-                    } else {
-                        call.expr.span()
-                            .error("expected capture name due to previous declaration")
-                    };
-
-                    err = match first_name {
-                        Some(p) => err.span_note(p.span(), "declared here"),
-                        None => err
-                    };
-
-                    return Err(err);
-                }
+    /// Checks that every name used in `body` that is bound by one alternative
+    /// of the pattern is bound by all of them. Alternatives may otherwise bind
+    /// different names, or none at all.
+    fn validate(&self, body: &syn::Expr) -> PResult<()> {
+        let calls = match self {
+            Pattern::Calls(ref calls) => calls,
+            Pattern::Wild(..) => return Ok(()),
+        };
+
+        let used = used_idents(body);
+        let bindings: Vec<_> = calls.iter().map(|call| call.bindings()).collect();
+        for binding in bindings.iter().flatten() {
+            if !used.contains(binding) {
+                continue;
+            }
+
+            let unbound = bindings.iter().position(|names| !names.contains(binding));
+            if let Some(call) = unbound.map(|i| &calls[i]) {
+                let err = match call.name {
+                    Some(ref pat) => pat.span()
+                        .error(format!("`{}` is not bound by this capture", binding)),
+                    None => call.expr.span()
+                        .error(format!("expected `{}@` capture", binding)),
+                };
+
+                return Err(err.span_note(binding.span(), format!("`{}` declared here", binding))
+                    .help(format!("`{}` is used in the case, so every alternative must \
+                        bind it", binding)));
             }
         }
 
@@ -149,6 +218,41 @@ impl Pattern {
     }
 }
 
+/// Returns every identifier that may be used as a variable in `expr`: those
+/// that make up single-segment paths, and, conservatively, every identifier in
+/// the tokens of macro invocations.
+fn used_idents(expr: &syn::Expr) -> Vec<syn::Ident> {
+    use syn::visit_mut::{self, VisitMut};
+
+    struct Collector(Vec<syn::Ident>);
+
+    impl VisitMut for Collector {
+        fn visit_expr_path_mut(&mut self, path: &mut syn::ExprPath) {
+            if let Some(ident) = path.path.get_ident() {
+                self.0.push(ident.clone());
+            }
+        }
+
+        fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+            fn idents(tokens: proc_macro2::TokenStream, out: &mut Vec<syn::Ident>) {
+                for tree in tokens {
+                    match tree {
+                        proc_macro2::TokenTree::Ident(ident) => out.push(ident),
+                        proc_macro2::TokenTree::Group(group) => idents(group.stream(), out),
+                        _ => {}
+                    }
+                }
+            }
+
+            idents(mac.tokens.clone(), &mut self.0);
+        }
+    }
+
+    let mut collector = Collector(vec![]);
+    visit_mut::visit_expr_mut(&mut collector, &mut expr.clone());
+    collector.0
+}
+
 impl Parse for Case {
     fn parse(input: SynParseStream) -> PResult<Self> {
         let case_span_start = input.cursor().span();
@@ -162,9 +266,9 @@ impl Parse for Case {
             Pattern::Calls(call_patterns)
         };
 
-        pattern.validate()?;
         input.parse::<Token![=>]>()?;
         let expr: syn::Expr = input.parse()?;
+        pattern.validate(&expr)?;
         let span = case_span_start.join(input.cursor().span()).unwrap();
 
         Ok(Case { pattern, expr, span })
@@ -177,11 +281,13 @@ pub struct Context {
     pub input: syn::Expr,
     pub marker: syn::Expr,
     pub output: syn::Type,
+    /// The path to `pear`: `::pear` unless given after the output type.
+    pub scope: TokenStream2,
 }
 
 impl Parse for Context {
     fn parse(stream: SynParseStream) -> PResult<Context> {
-        let (info, input, marker, output) = stream.parse_group(Delimiter::Bracket, |inner| {
+        let (info, input, marker, output, scope) = stream.parse_group(Delimiter::Bracket, |inner| {
             let info: syn::Ident = inner.parse()?;
             inner.parse::<Token![;]>()?;
             let input: syn::Expr = inner.parse()?;
@@ -189,10 +295,15 @@ impl Parse for Context {
             let marker: syn::Expr = inner.parse()?;
             inner.parse::<Token![;]>()?;
             let output: syn::Type = inner.parse()?;
-            Ok((info, input, marker, output))
+            let scope = match inner.parse::<Option<Token![;]>>()? {
+                Some(_) => inner.parse::<syn::Path>()?.into_token_stream(),
+                None => quote!(::pear),
+            };
+
+            Ok((info, input, marker, output, scope))
         })?;
 
-        Ok(Context { info, input, marker, output })
+        Ok(Context { info, input, marker, output, scope })
     }
 }
 
@@ -218,31 +329,277 @@ impl Parse for Switch {
     }
 }
 
+/// Parsers that consume no input when they fail.
+const NON_CONSUMING: &[&str] = &[
+    "eat", "eat_slice", "eat_any", "eat_any_of", "eat_slice_ci", "eat_if", "eat_slice_eq",
+    "peek", "peek_slice", "eof", "none",
+];
+
+fn is_non_consuming(call: &CallPattern) -> bool {
+    match *call.expr.func {
+        syn::Expr::Path(ref path) => path.path.segments.last()
+            .is_some_and(|segment| NON_CONSUMING.contains(&&*segment.ident.to_string())),
+        _ => false,
+    }
+}
+
+impl Switch {
+    /// Returns a warning for every call in a case that is identical to a call
+    /// to a parser in [`NON_CONSUMING`] in an earlier case. Since the earlier
+    /// call is tried first and fails without consuming input, the later one
+    /// fails the same way and never matches. Other parsers may consume input
+    /// before failing, so an identical call may succeed where the first
+    /// didn't.
+    pub fn unreachable_calls(&self) -> Vec<Diagnostic> {
+        let mut seen: Vec<(String, &CallPattern)> = vec![];
+        let mut warnings = vec![];
+        for case in self.cases.iter() {
+            let calls = match case.pattern {
+                Pattern::Calls(ref calls) => calls,
+                Pattern::Wild(..) => continue,
+            };
+
+            for call in calls.iter().filter(|call| call.receiver.is_none() && is_non_consuming(call)) {
+                let (receiver, expr) = (&call.receiver, &call.expr);
+                let key = quote!(#receiver #expr).to_string();
+                match seen.iter().find(|(k, _)| *k == key) {
+                    Some((_, first)) => {
+                        let warning = call.expr.span()
+                            .warning("unreachable case: this parser is tried earlier")
+                            .span_note(first.expr.span(), "first tried here")
+                            .help("the earlier call consumes nothing when it fails, so \
+                                   this one fails too: remove it or change the earlier one");
+
+                        warnings.push(warning);
+                    }
+                    None => seen.push((key, call)),
+                }
+            }
+        }
+
+        warnings
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AttrArgs {
     pub raw: Option<Span>,
     pub rewind: Option<Span>,
     pub peek: Option<Span>,
+    pub ffi: Option<Span>,
+    pub ffi_symbol: Option<syn::LitStr>,
+    pub commit: Option<Span>,
+    pub left_recursive: Option<Span>,
+    pub spanned: Option<Span>,
+    pub complete: Option<Span>,
+    pub inline: Option<Span>,
+    pub doc_grammar: Option<Span>,
+    pub label: Option<syn::LitStr>,
+    pub map_err: Option<syn::Expr>,
+}
+
+/// A single attribute argument: `name` or `name = value`.
+struct AttrArg {
+    name: syn::Ident,
+    value: Option<syn::Expr>,
+}
+
+impl SynParse for AttrArg {
+    fn parse(input: SynParseStream) -> syn::parse::Result<Self> {
+        let name = input.parse()?;
+        let value = match input.peek(Token![=]) {
+            true => { input.parse::<Token![=]>()?; Some(input.parse()?) }
+            false => None
+        };
+
+        Ok(AttrArg { name, value })
+    }
 }
 
 impl Parse for AttrArgs {
     fn parse(input: SynParseStream) -> PResult<Self> {
-        let args = input.call(<Punctuated<syn::Ident, Token![,]>>::parse_terminated)?;
-        let (mut raw, mut rewind, mut peek) = Default::default();
-        for case in args.iter() {
+        let args = input.call(<Punctuated<AttrArg, Token![,]>>::parse_terminated)?;
+        let (mut raw, mut rewind, mut peek, mut ffi, mut commit) = Default::default();
+        let (mut label, mut left_recursive, mut spanned, mut complete, mut inline) = Default::default();
+        let (mut map_err, mut doc_grammar, mut ffi_symbol) = (None, None, None);
+        let mut seen: Vec<&syn::Ident> = vec![];
+        for AttrArg { name: case, value } in args.iter() {
+            if let Some(prev) = seen.iter().find(|prev| *prev == &case) {
+                return Err(case.span().error(format!("duplicate attribute argument `{}`", case))
+                    .span_note(prev.span(), "previously given here"));
+            }
+
+            seen.push(case);
+            if case == "label" {
+                match value {
+                    Some(syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(value), .. })) => {
+                        label = Some(value.clone())
+                    }
+                    _ => return Err(case.span()
+                                    .error("`label` requires a string value")
+                                    .help("use `label = \"...\"`")),
+                }
+
+                continue;
+            } else if case == "map_err" {
+                match value {
+                    Some(value) => map_err = Some(value.clone()),
+                    None => return Err(case.span()
+                                       .error("`map_err` requires a value")
+                                       .help("use `map_err = path::to::function`")),
+                }
+
+                continue;
+            } else if let (true, Some(value)) = (case == "ffi", value) {
+                match value {
+                    syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(symbol), .. }) => {
+                        if !is_c_identifier(&symbol.value()) {
+                            return Err(symbol.span().error("`ffi` symbol must be a C identifier"));
+                        }
+
+                        ffi = Some(case.span());
+                        ffi_symbol = Some(symbol.clone());
+                    }
+                    _ => return Err(value.span()
+                                    .error("`ffi` takes a string value")
+                                    .help("use `ffi` or `ffi = \"symbol\"`")),
+                }
+
+                continue;
+            } else if let Some(value) = value {
+                return Err(value.span().error(format!("`{}` does not take a value", case)));
+            }
+
             if case == "raw" {
                 raw = Some(case.span());
             } else if case == "rewind" {
                 rewind = Some(case.span());
             } else if case == "peek" {
                 peek = Some(case.span());
+            } else if case == "ffi" {
+                ffi = Some(case.span());
+            } else if case == "commit" {
+                commit = Some(case.span());
+            } else if case == "left_recursive" {
+                left_recursive = Some(case.span());
+            } else if case == "spanned" {
+                spanned = Some(case.span());
+            } else if case == "complete" {
+                complete = Some(case.span());
+            } else if case == "inline" {
+                inline = Some(case.span());
+            } else if case == "doc_grammar" {
+                doc_grammar = Some(case.span());
             } else {
                 return Err(case.span()
                            .error(format!("unknown attribute argument `{}`", case))
-                           .help("supported arguments are: `rewind`, `peek`"));
+                           .help("supported arguments are: `rewind`, `peek`, `commit`, `label`, \
+                                  `map_err`, `left_recursive`, `spanned`, `complete`, `inline`, \
+                                  `doc_grammar`, `ffi`"));
+            }
+        }
+
+        let args = AttrArgs {
+            raw, rewind, peek, ffi, ffi_symbol, commit, left_recursive, spanned, complete,
+            inline, doc_grammar, label, map_err
+        };
+
+        args.validate()?;
+        Ok(args)
+    }
+}
+
+/// Returns `true` if `symbol` is a valid C identifier.
+fn is_c_identifier(symbol: &str) -> bool {
+    let mut chars = symbol.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl AttrArgs {
+    /// Rejects combinations of arguments that contradict one another or that
+    /// have no effect, which would otherwise surprise only at runtime.
+    fn validate(&self) -> PResult<()> {
+        let conflicts = [
+            (self.raw, "raw", self.rewind, "rewind",
+                "`raw` parsers manage their input themselves: rewind it in the body"),
+            (self.raw, "raw", self.peek, "peek",
+                "`raw` parsers manage their input themselves: rewind it in the body"),
+            (self.ffi, "ffi", self.rewind, "rewind",
+                "a failing `ffi` parser reports where it failed, which would always be \
+                 the start of the input: remove `rewind`"),
+        ];
+
+        for (first, first_name, second, second_name, help) in conflicts.iter() {
+            if let (Some(first), Some(second)) = (first, second) {
+                let message = format!("`{}` cannot be combined with `{}`", second_name, first_name);
+                return Err(second.error(message)
+                    .span_note(*first, format!("`{}` given here", first_name))
+                    .help(*help));
             }
         }
 
-        Ok(AttrArgs { raw, rewind, peek })
+        if let (Some(ffi), Some(peek)) = (self.ffi, self.peek) {
+            return Err(peek.error("`peek` is redundant on an `ffi` parser")
+                .span_note(ffi, "`ffi` given here")
+                .help("an `ffi` parser's input is discarded once it succeeds: remove `peek`"));
+        }
+
+        Ok(())
+    }
+}
+
+/// The input to `parser!`: an optional name and attribute arguments followed
+/// by a closure, i.e, `parser!("pair", rewind, |input| { .. })`.
+pub struct ParserClosure {
+    pub name: Option<syn::LitStr>,
+    pub args: AttrArgs,
+    pub closure: syn::ExprClosure,
+}
+
+impl Parse for ParserClosure {
+    fn parse(input: SynParseStream) -> PResult<Self> {
+        use syn::parse::Parser;
+
+        let name = match input.peek(syn::LitStr) {
+            true => Some(input.parse::<syn::LitStr>()?),
+            false => None
+        };
+
+        if name.is_some() && !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+
+        // The arguments are everything up to the closure, which begins with
+        // `|`, `||`, or `move`.
+        let mut args = proc_macro2::TokenStream::new();
+        while !input.is_empty() && !(input.peek(Token![|]) || input.peek(Token![||]) || input.peek(Token![move])) {
+            args.extend(Some(input.parse::<proc_macro2::TokenTree>()?));
+        }
+
+        let args = AttrArgs::syn_parse.parse2(args)?;
+        let unsupported = [
+            (args.raw, "raw"), (args.ffi, "ffi"), (args.inline, "inline"),
+            (args.doc_grammar, "doc_grammar"), (args.left_recursive, "left_recursive"),
+        ];
+
+        for (span, name) in unsupported.iter() {
+            if let Some(span) = span {
+                return Err(span.error(format!("`{}` is not supported on parser closures", name))
+                    .help("use a function attributed with `#[parser]` instead"));
+            }
+        }
+
+        let closure: syn::ExprClosure = input.parse().map_err(|_| {
+            input.span().error("expected a parser closure")
+                .help("use `parser!(|input| { .. })`")
+        })?;
+
+        if closure.inputs.len() != 1 {
+            return Err(closure.inputs.span().error("parser closures take exactly one input")
+                .help("use `parser!(|input| { .. })`"));
+        }
+
+        Ok(ParserClosure { name, args, closure })
     }
 }
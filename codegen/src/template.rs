@@ -0,0 +1,87 @@
+use crate::diagnostics::{Diagnostic, Spanned, SpanExt};
+
+/// A piece of a template, as in `#[emit("...")]` or `#[parse("...")]`.
+pub(crate) enum Piece {
+    Literal(String),
+    Field(String),
+}
+
+/// Parses `template` into literal text and `{field}` placeholders.
+pub(crate) fn parse_template(template: &syn::LitStr) -> Result<Vec<Piece>, Diagnostic> {
+    let (mut pieces, mut literal) = (vec![], String::new());
+    let string = template.value();
+    let mut chars = string.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => { chars.next(); literal.push('{'); }
+            '}' if chars.peek() == Some(&'}') => { chars.next(); literal.push('}'); }
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(template.span().error("unclosed `{` in template")
+                            .help("use `{{` for a literal `{`")),
+                    }
+                }
+
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                }
+
+                pieces.push(Piece::Field(field.trim().to_string()));
+            }
+            '}' => return Err(template.span().error("unmatched `}` in template")
+                .help("use `}}` for a literal `}`")),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+
+    Ok(pieces)
+}
+
+/// Returns the template given by a `#[name("...")]` attribute in `attrs`, if
+/// there is one.
+pub(crate) fn template(attrs: &[syn::Attribute], name: &str) -> Result<Option<syn::LitStr>, Diagnostic> {
+    let mut template = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+        if template.is_some() {
+            return Err(attr.span().error(format!("duplicate `{}` attribute", name)));
+        }
+
+        let lit = attr.parse_args::<syn::LitStr>()
+            .map_err(|_| attr.span().error(format!("`{}` requires a string template", name))
+                .help(format!("use `#[{}(\"{{field}} = {{value}}\")]`", name)))?;
+
+        template = Some(lit);
+    }
+
+    Ok(template)
+}
+
+/// Returns the separator given by a field's `#[name(separator = "...")]`
+/// attribute, if it has one.
+pub(crate) fn separator(field: &syn::Field, name: &str) -> Result<Option<syn::LitStr>, Diagnostic> {
+    let mut separator = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+        if separator.is_some() {
+            return Err(attr.span().error(format!("duplicate `{}` attribute", name)));
+        }
+
+        let lit = match attr.parse_args::<syn::MetaNameValue>() {
+            Ok(syn::MetaNameValue { ref path, lit: syn::Lit::Str(ref lit), .. })
+                if path.is_ident("separator") => lit.clone(),
+            _ => return Err(attr.span().error(format!("invalid `{}` attribute on field", name))
+                .help(format!("use `#[{}(separator = \", \")]`", name))),
+        };
+
+        separator = Some(lit);
+    }
+
+    Ok(separator)
+}
@@ -1,9 +1,19 @@
-use std::hash::Hash;
-use std::collections::{HashMap, BTreeMap};
+use core::borrow::Borrow;
+use core::convert::TryInto;
+use core::hash::Hasher;
+use core::ops::Range;
+use core::str::FromStr;
+use alloc::{vec, vec::Vec, boxed::Box, borrow::Cow, format, collections::BTreeMap};
 
-use crate::input::{Input, Rewind, Token, Result};
+#[cfg(feature = "std")] use std::hash::Hash;
+#[cfg(feature = "std")] use std::collections::HashMap;
+
+use crate::error::{ParseError, Expected, Error, IntoParseError};
+use crate::input::{Input, Rewind, Position, Limited, Token, Slice, Result, Checkpoint, Intern, ParserInfo};
+use crate::input::{Modal, Mode};
 use crate::macros::parser;
 use crate::parsers::*;
+use crate::parsers::binary::Byte;
 
 pub trait Collection {
     type Item;
@@ -23,6 +33,7 @@ impl<T> Collection for Vec<T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<K: Eq + Hash, V> Collection for HashMap<K, V> {
     type Item = (K, V);
 
@@ -49,10 +60,102 @@ impl<K: Ord, V> Collection for BTreeMap<K, V> {
     }
 }
 
+/// An ordered multimap: a collection of key-value pairs that preserves the
+/// order in which pairs were added and allows duplicate keys.
+///
+/// This is the natural [`Collection`] for header-like formats, where a key may
+/// legally appear more than once and the relative order of entries matters.
+/// Lookups are linear in the number of entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VecMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K: PartialEq, V> VecMap<K, V> {
+    /// Returns the first value associated with `key`, if any.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where K: Borrow<Q>, Q: PartialEq + ?Sized
+    {
+        self.entries.iter().find(|(k, _)| k.borrow() == key).map(|(_, v)| v)
+    }
+
+    /// Returns all of the values associated with `key` in insertion order.
+    pub fn get_all<'a, Q>(&'a self, key: &'a Q) -> impl Iterator<Item = &'a V> + 'a
+        where K: Borrow<Q>, Q: PartialEq + ?Sized
+    {
+        self.entries.iter().filter(move |(k, _)| k.borrow() == key).map(|(_, v)| v)
+    }
+
+    /// Returns `true` if there is at least one value associated with `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: PartialEq + ?Sized
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the distinct keys in the order in which they first appeared.
+    ///
+    /// Each key is compared against the distinct keys seen before it, so
+    /// iterating takes time proportional to the number of entries times the
+    /// number of distinct keys.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        let mut seen: Vec<&K> = vec![];
+        self.entries.iter()
+            .map(|(k, _)| k)
+            .filter(move |k| match seen.contains(k) {
+                true => false,
+                false => { seen.push(k); true }
+            })
+    }
+}
+
+impl<K, V> VecMap<K, V> {
+    /// Returns an iterator over all of the key-value pairs in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Returns the number of key-value pairs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the key-value pairs in insertion order.
+    pub fn into_vec(self) -> Vec<(K, V)> {
+        self.entries
+    }
+}
+
+impl<K, V> Collection for VecMap<K, V> {
+    type Item = (K, V);
+
+    fn new() -> Self {
+        VecMap { entries: vec![] }
+    }
+
+    fn add(&mut self, item: Self::Item) {
+        self.entries.push(item);
+    }
+}
+
+impl<K, V> IntoIterator for VecMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = alloc::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
 /// Parses `p` until `p` fails, returning the last successful `p`.
 #[parser(raw)]
-pub fn last_of_many<I, O, P>(input: &mut I, mut p: P) -> Result<O, I>
-    where I: Input, P: FnMut(&mut I) -> Result<O, I>
+pub fn last_of_many<I, O, P, E>(input: &mut I, mut p: P) -> Result<O, I, E>
+    where I: Input, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>
 {
     loop {
         let output = p(input)?;
@@ -64,22 +167,79 @@ pub fn last_of_many<I, O, P>(input: &mut I, mut p: P) -> Result<O, I>
 
 /// Skips all tokens that match `f` before and after a `p`, returning `p`.
 #[parser(raw)]
-pub fn surrounded<I, O, F, P>(input: &mut I, mut p: P, mut f: F) -> Result<O, I>
+pub fn surrounded<I, O, F, P, E>(input: &mut I, mut p: P, mut f: F) -> Result<O, I, E>
     where I: Input,
+          E: Error<I>,
           F: FnMut(&I::Token) -> bool,
-          P: FnMut(&mut I) -> Result<O, I>
+          P: FnMut(&mut I) -> Result<O, I, E>
 {
-    skip_while(input, &mut f)?;
+    skip_while(input, &mut f).map_err(ParseError::into)?;
     let output = p(input)?;
-    skip_while(input, &mut f)?;
+    skip_while(input, &mut f).map_err(ParseError::into)?;
     Ok(output)
 }
 
+/// Parses a here-document or raw block: a body ended by a terminator that's
+/// determined by its opening. `open` parses the opening and returns the
+/// terminator, which may be built from the input it consumed. Everything up to
+/// the terminator is then taken as the body, and the terminator is consumed.
+/// Returns the body. An unterminated body fails with a
+/// [committed](cut()) error.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// // A shell-style here-document: `<<TAG`, a newline, and lines up to `TAG`.
+/// #[parser]
+/// fn here_doc<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     heredoc(|input| {
+///         eat_slice(input, "<<")?;
+///         let tag = take_some_while(input, |c: &char| c.is_ascii_uppercase())?;
+///         eat(input, '\n')?;
+///         Ok(format!("\n{}", tag))
+///     })?
+/// }
+///
+/// // A Rust-style raw string: `r###"..."###`.
+/// #[parser]
+/// fn raw_string<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     heredoc(|input| {
+///         eat(input, 'r')?;
+///         let hashes = take_while(input, |&c| c == '#')?;
+///         eat(input, '"')?;
+///         Ok(format!("\"{}", hashes))
+///     })?
+/// }
+///
+/// let doc = parse!(here_doc: &mut Text::from("<<END\nhello\nEND\nEND"));
+/// assert!(doc.is_err());
+///
+/// let doc = parse!(here_doc: &mut Text::from("<<END\nhello\nEND"));
+/// assert_eq!(doc.unwrap(), "hello");
+///
+/// let string = parse!(raw_string: &mut Text::from(r####"r##"a "# b"##"####));
+/// assert_eq!(string.unwrap(), r##"a "# b"##);
+///
+/// assert!(parse!(raw_string: &mut Text::from(r###"r##"a "#"###)).is_err());
+/// ```
+#[parser(raw)]
+pub fn heredoc<I, S, P>(input: &mut I, open: P) -> Result<I::Many, I>
+    where I: Input + Rewind, S: Slice<I>, P: FnOnce(&mut I) -> Result<S, I>
+{
+    let terminator = open(input)?;
+    let body = take_while_window(input, terminator.len(), |s| &terminator != s)?;
+    eat_slice(input, terminator).map_err(ParseError::commit)?;
+    Ok(body)
+}
+
 /// Parses as many `p` as possible until EOF is reached, collecting them into a
 /// `C`. Fails if `p` every fails. `C` may be empty.
 #[parser(raw)]
-pub fn collect<C, I, O, P>(input: &mut I, mut p: P) -> Result<C, I>
-    where C: Collection<Item=O>, I: Input, P: FnMut(&mut I) -> Result<O, I>
+pub fn collect<C, I, O, P, E>(input: &mut I, mut p: P) -> Result<C, I, E>
+    where C: Collection<Item=O>, I: Input, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>
 {
     let mut collection = C::new();
     loop {
@@ -94,8 +254,8 @@ pub fn collect<C, I, O, P>(input: &mut I, mut p: P) -> Result<C, I>
 /// Parses as many `p` as possible until EOF is reached, collecting them into a
 /// `C`. Fails if `p` ever fails. `C` is not allowed to be empty.
 #[parser(raw)]
-pub fn collect_some<C, I, O, P>(input: &mut I, mut p: P) -> Result<C, I>
-    where C: Collection<Item=O>, I: Input, P: FnMut(&mut I) -> Result<O, I>
+pub fn collect_some<C, I, O, P, E>(input: &mut I, mut p: P) -> Result<C, I, E>
+    where C: Collection<Item=O>, I: Input, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>
 {
     let mut collection = C::new();
     loop {
@@ -106,11 +266,130 @@ pub fn collect_some<C, I, O, P>(input: &mut I, mut p: P) -> Result<C, I>
     }
 }
 
+/// Parses as many `item`s as possible until EOF is reached, passing them to
+/// `consumer` in chunks of `n`. Fails if `item` ever fails. Returns the total
+/// number of items parsed.
+///
+/// Unlike [`collect()`], at most `n` items are held in memory at once, making
+/// `chunked` suitable for streaming enormous inputs into a sink, such as a
+/// database, in batches. Every chunk passed to `consumer` has exactly `n`
+/// items except the last, which may have fewer but is never empty. If `item`
+/// fails, the items parsed since the last flush are discarded.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+#[parser(raw)]
+pub fn chunked<I, O, P, F, E>(
+    input: &mut I,
+    n: usize,
+    mut item: P,
+    mut consumer: F,
+) -> Result<usize, I, E>
+    where I: Input, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>, F: FnMut(Vec<O>)
+{
+    assert!(n > 0, "chunk size must be nonzero");
+
+    let mut count = 0;
+    let mut chunk = Vec::with_capacity(n);
+    while eof(input).is_err() {
+        chunk.push(item(input)?);
+        count += 1;
+
+        if chunk.len() == n {
+            consumer(core::mem::replace(&mut chunk, Vec::with_capacity(n)));
+        }
+    }
+
+    if !chunk.is_empty() {
+        consumer(chunk);
+    }
+
+    Ok(count)
+}
+
+/// Returns an iterator that lazily parses as many `p` as possible until EOF is
+/// reached, yielding each output as it is parsed.
+///
+/// Unlike [`collect()`], no output is held onto: each is the caller's to
+/// process and drop before the next is parsed. The iterator stops at EOF or at
+/// the first failure of `p`; [`ParserIterator::finish()`] then returns the
+/// error `p` failed with, if any.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn line<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     let line = take_some_while(|c: &char| *c != '\n')?;
+///     eat('\n')?;
+///     line
+/// }
+///
+/// let mut input = Text::from("a\nbc\nd");
+/// let mut lines = iterate(&mut input, line);
+/// let lengths: Vec<usize> = lines.by_ref().map(|line| line.len()).collect();
+/// assert_eq!(lengths, [1, 2]);
+/// assert!(lines.finish().is_err());
+/// ```
+pub fn iterate<I, O, P, E>(input: &mut I, p: P) -> ParserIterator<'_, I, P, E>
+    where I: Input, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>
+{
+    ParserIterator { input, p, error: None, done: false }
+}
+
+/// An iterator over the outputs of a parser. See [`iterate()`].
+pub struct ParserIterator<'i, I: Input, P, E> {
+    input: &'i mut I,
+    p: P,
+    error: Option<ParseError<I::Context, E>>,
+    done: bool,
+}
+
+impl<I: Input, P, E> ParserIterator<'_, I, P, E> {
+    /// Consumes the iterator, returning the error the parser failed with, if
+    /// iteration stopped because of one.
+    pub fn finish(self) -> Result<(), I, E> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<I, O, P, E> Iterator for ParserIterator<'_, I, P, E>
+    where I: Input, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>
+{
+    type Item = O;
+
+    fn next(&mut self) -> Option<O> {
+        if self.done || eof(self.input).is_ok() {
+            self.done = true;
+            return None;
+        }
+
+        match (self.p)(self.input) {
+            Ok(output) => Some(output),
+            Err(e) => {
+                self.error = Some(e);
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl<I, O, P, E> core::iter::FusedIterator for ParserIterator<'_, I, P, E>
+    where I: Input, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E> { }
+
 /// Parses as many `p` as possible until EOF is reached or `p` fails, collecting
 /// them into a `C`. `C` may be empty.
 #[parser(raw)]
-pub fn try_collect<C, I, O, P>(input: &mut I, mut p: P) -> Result<C, I>
-    where C: Collection<Item=O>, I: Input + Rewind, P: FnMut(&mut I) -> Result<O, I>
+pub fn try_collect<C, I, O, P, E>(input: &mut I, mut p: P) -> Result<C, I, E>
+    where C: Collection<Item=O>, I: Input + Rewind, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>
 {
     let mut collection = C::new();
     loop {
@@ -118,43 +397,302 @@ pub fn try_collect<C, I, O, P>(input: &mut I, mut p: P) -> Result<C, I>
             return Ok(collection);
         }
 
-        // FIXME: We should be able to call `parse_marker!` here.
-        let start = input.mark(&crate::input::ParserInfo {
-            name: "try_collect",
-            raw: true
-        });
+        let mut checkpoint = Checkpoint::named(input, "try_collect");
 
-        match p(input) {
-            Ok(val) => collection.add(val),
-            Err(_) => {
-                input.rewind_to(&start);
-                break;
+        match p(&mut checkpoint) {
+            Ok(val) => {
+                checkpoint.commit();
+                collection.add(val);
+            }
+            Err(e) if e.committed => {
+                checkpoint.commit();
+                return Err(e);
             }
+            Err(_) => break,
         }
     }
 
     Ok(collection)
 }
 
+/// Parses as many `p` as possible until EOF is reached or `p` fails, folding
+/// each into an accumulator, which starts as `init`, with `f`. Returns the
+/// final accumulator. Like [`try_collect()`], but without building a
+/// collection.
+///
+/// Because each `p` is folded as soon as it is parsed, `fold_many` is
+/// well-suited to building left-associated values:
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn number<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?.parse::<i64>().unwrap()
+/// }
+///
+/// #[parser]
+/// fn operation<'a>(input: &mut Text<'a>) -> Result<(char, i64), Text<'a>> {
+///     (eat_if(|&c| c == '+' || c == '-')?, number()?)
+/// }
+///
+/// #[parser]
+/// fn expr<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+///     let first = number()?;
+///     fold_many(first, operation, |acc, (op, n)| match op {
+///         '+' => acc + n,
+///         _ => acc - n,
+///     })?
+/// }
+///
+/// assert_eq!(parse!(expr: &mut Text::from("10-3+4-2")).unwrap(), 9);
+/// ```
+#[parser(raw)]
+pub fn fold_many<A, I, O, P, F, E>(input: &mut I, init: A, mut p: P, mut f: F) -> Result<A, I, E>
+    where I: Input + Rewind, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>, F: FnMut(A, O) -> A
+{
+    let mut acc = init;
+    loop {
+        if eof(input).is_ok() {
+            return Ok(acc);
+        }
+
+        let mut checkpoint = Checkpoint::named(input, "fold_many");
+
+        match p(&mut checkpoint) {
+            Ok(val) => {
+                checkpoint.commit();
+                acc = f(acc, val);
+            }
+            Err(e) if e.committed => {
+                checkpoint.commit();
+                return Err(e);
+            }
+            Err(_) => break,
+        }
+    }
+
+    Ok(acc)
+}
+
+/// Parses `p` until `terminator` succeeds, collecting each into a `C`. Fails
+/// if `p` ever fails. `C` may be empty.
+///
+/// The terminator is only looked ahead at: whatever it consumes is rewound, so
+/// it remains to be parsed after `collect_until` returns. This covers
+/// constructs like "statements until `}`" where the terminator is eaten by the
+/// caller, without needing a separator as [`delimited_collect()`] does.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn statement<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     let statement = take_some_while(|&c| c != ';' && c != '}')?;
+///     eat(';')?;
+///     statement
+/// }
+///
+/// #[parser]
+/// fn block<'a>(input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+///     eat('{')?;
+///     let statements: Vec<_> = collect_until(statement, |i| peek(i, '}'))?;
+///     eat('}')?;
+///     statements
+/// }
+///
+/// let statements = parse!(block: &mut Text::from("{a=1;b=2;}")).unwrap();
+/// assert_eq!(statements, ["a=1", "b=2"]);
+/// ```
+#[parser(raw)]
+pub fn collect_until<C, I, O, X, P, T, E, F>(
+    input: &mut I,
+    mut p: P,
+    mut terminator: T,
+) -> Result<C, I, E>
+    where C: Collection<Item=O>,
+          I: Input + Rewind,
+          E: Error<I>,
+          P: FnMut(&mut I) -> Result<O, I, E>,
+          T: FnMut(&mut I) -> Result<X, I, F>,
+{
+    let mut collection = C::new();
+    loop {
+        let mut lookahead = Checkpoint::named(input, "collect_until");
+        if terminator(&mut lookahead).is_ok() {
+            return Ok(collection);
+        }
+
+        lookahead.rollback();
+        collection.add(p(input)?);
+    }
+}
+
+/// Tries `end` ahead of an item: returns its output if it succeeds or, if it
+/// fails, its error, with the input rewound. Committed errors are returned.
+#[allow(clippy::type_complexity)]
+fn try_end<I, X, T, E>(
+    input: &mut I,
+    end: &mut T,
+    name: &'static str,
+) -> Result<core::result::Result<X, ParseError<I::Context, E>>, I, E>
+    where I: Rewind, T: FnMut(&mut I) -> Result<X, I, E>
+{
+    let mut checkpoint = Checkpoint::named(input, name);
+    match end(&mut checkpoint) {
+        Ok(output) => {
+            checkpoint.commit();
+            Ok(Ok(output))
+        }
+        Err(e) if e.committed => {
+            checkpoint.commit();
+            Err(e)
+        }
+        Err(e) => Ok(Err(e)),
+    }
+}
+
+/// Parses `p` until `end` succeeds, collecting the outputs of `p` into a `C`.
+/// Returns the collection and the output of `end`. Gramatically, this is:
+///
+/// item* END
+///
+/// Before each `item`, `end` is tried and, if it fails, rewound. If `item`
+/// then fails too, the error lists what both expected. Unlike
+/// [`collect_until()`], `end` is consumed.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn comment<'a>(input: &mut Text<'a>) -> Result<Vec<char>, Text<'a>> {
+///     eat_slice("/*")?;
+///     let (chars, _): (Vec<char>, _) = many_till(eat_any, |i| eat_slice(i, "*/"))?;
+///     chars
+/// }
+///
+/// let chars = parse!(comment: &mut Text::from("/* a*b */")).unwrap();
+/// assert_eq!(chars.into_iter().collect::<String>(), " a*b ");
+/// ```
+#[parser(raw)]
+pub fn many_till<C, I, O, X, P, T, E>(input: &mut I, mut p: P, mut end: T) -> Result<(C, X), I, E>
+    where C: Collection<Item=O>,
+          I: Rewind,
+          E: Error<I>,
+          P: FnMut(&mut I) -> Result<O, I, E>,
+          T: FnMut(&mut I) -> Result<X, I, E>,
+{
+    let mut collection = C::new();
+    loop {
+        let end_error = match try_end(input, &mut end, "many_till")? {
+            Ok(output) => return Ok((collection, output)),
+            Err(e) => e,
+        };
+
+        match p(input) {
+            Ok(item) => collection.add(item),
+            Err(e) if e.committed => return Err(e),
+            Err(e) => return Err(merge(e, end_error)),
+        }
+    }
+}
+
+/// Parses many `separator` delimited `item`s until `end` succeeds, collecting
+/// the `item`s into a `C`. Returns the collection and the output of `end`.
+/// Gramatically, this is:
+///
+/// (item (SEPERATOR item)*)? END
+///
+/// Like [`many_till()`], `end` is tried, and rewound if it fails, before the
+/// first `item` and before each separator.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     take_some_while(|c: &char| c.is_alphabetic())?
+/// }
+///
+/// #[parser]
+/// fn list<'a>(input: &mut Text<'a>) -> Result<(Vec<&'a str>, char), Text<'a>> {
+///     separated_until(word, ',', |i| eat_any_of(i, &['.', '!'][..]))?
+/// }
+///
+/// assert_eq!(parse!(list: &mut Text::from("a,b,c!")).unwrap(), (vec!["a", "b", "c"], '!'));
+/// assert_eq!(parse!(list: &mut Text::from(".")).unwrap(), (vec![], '.'));
+/// assert!(parse!(list: &mut Text::from("a,.")).is_err());
+/// ```
+#[parser(raw)]
+pub fn separated_until<C, I, S, O, X, P, T, E>(
+    input: &mut I,
+    mut item: P,
+    seperator: S,
+    mut end: T,
+) -> Result<(C, X), I, E>
+    where C: Collection<Item=O>,
+          I: Rewind,
+          E: Error<I>,
+          S: Token<I> + Clone,
+          P: FnMut(&mut I) -> Result<O, I, E>,
+          T: FnMut(&mut I) -> Result<X, I, E>,
+{
+    let mut collection = C::new();
+    let mut first = true;
+    loop {
+        let end_error = match try_end(input, &mut end, "separated_until")? {
+            Ok(output) => return Ok((collection, output)),
+            Err(e) => e,
+        };
+
+        if !first {
+            if let Err(e) = eat(input, seperator.clone()) {
+                return Err(merge(e.into(), end_error));
+            }
+        }
+
+        match item(input) {
+            Ok(val) => collection.add(val),
+            Err(e) if e.committed || !first => return Err(e),
+            Err(e) => return Err(merge(e, end_error)),
+        }
+
+        first = false;
+    }
+}
+
 /// Parses many `separator` delimited `p`s, the entire collection of which must
 /// start with `start` and end with `end`. `item` Gramatically, this is:
 ///
 /// START (item SEPERATOR)* END
 #[parser(raw)]
-pub fn delimited_collect<C, I, T, S, O, P>(
+pub fn delimited_collect<C, I, T, S, O, P, E>(
     input: &mut I,
     start: T,
     mut item: P,
     seperator: S,
     end: T,
-) -> Result<C, I>
+) -> Result<C, I, E>
     where C: Collection<Item=O>,
           I: Input,
+          E: Error<I>,
           T: Token<I> + Clone,
           S: Into<Option<T>>,
-          P: FnMut(&mut I) -> Result<O, I>,
+          P: FnMut(&mut I) -> Result<O, I, E>,
 {
-    eat(input, start)?;
+    eat(input, start).map_err(ParseError::into)?;
 
     let seperator = seperator.into();
     let mut collection = C::new();
@@ -167,7 +705,7 @@ pub fn delimited_collect<C, I, T, S, O, P>(
 
         if let Some(seperator) = seperator.clone() {
             if eat(input, seperator).is_err(){
-                eat(input, end.clone())?;
+                eat(input, end.clone()).map_err(ParseError::into)?;
                 break;
             }
         }
@@ -176,19 +714,143 @@ pub fn delimited_collect<C, I, T, S, O, P>(
     Ok(collection)
 }
 
+/// Like [`delimited_collect()`], but recovers from failures instead of
+/// returning the first. Returns the collection of the `item`s that parsed
+/// alongside the errors of those that didn't.
+///
+/// When an `item` fails, or an `item` is followed by neither `separator` nor
+/// `end`, the error is recorded and tokens are skipped up to the next
+/// `separator` or `end` that isn't nested inside of another `start`/`end`
+/// pair, and parsing resumes after it. If the input ends before `end` is
+/// found, the missing `end` is recorded as an error as well. The combinator
+/// itself only fails if the input doesn't begin with `start`, making it
+/// suitable for tooling, like an editor, that wants as much of a malformed
+/// document as can be salvaged along with every diagnostic at once.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn number<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>().unwrap()
+/// }
+///
+/// #[parser]
+/// fn item<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+///     surrounded(number, |c: &char| *c == ' ')?
+/// }
+///
+/// let mut input = Text::from("[1, x, 3 4, [5, 6], 7]");
+/// let (numbers, errors): (Vec<_>, _) =
+///     delimited_collect_recover(&mut input, '[', item, ',', ']').unwrap();
+///
+/// assert_eq!(numbers, [1, 3, 7]);
+/// assert_eq!(errors.len(), 3);
+/// ```
+#[parser(raw)]
+#[allow(clippy::type_complexity)]
+pub fn delimited_collect_recover<C, I, T, S, O, P, E>(
+    input: &mut I,
+    start: T,
+    mut item: P,
+    seperator: S,
+    end: T,
+) -> Result<(C, Vec<ParseError<I::Context, E>>), I, E>
+    where C: Collection<Item=O>,
+          I: Input,
+          E: Error<I>,
+          T: Token<I> + Clone,
+          S: Into<Option<T>>,
+          P: FnMut(&mut I) -> Result<O, I, E>,
+{
+    eat(input, start.clone()).map_err(ParseError::into)?;
+
+    let seperator = seperator.into();
+    let (mut collection, mut errors) = (C::new(), vec![]);
+    loop {
+        if eat(input, end.clone()).is_ok() {
+            break;
+        }
+
+        let failed = match item(input) {
+            Ok(item) => { collection.add(item); false }
+            Err(e) => { errors.push(e); true }
+        };
+
+        if let Some(seperator) = seperator.clone() {
+            if eat(input, seperator.clone()).is_ok() {
+                continue;
+            }
+
+            match eat(input, end.clone()) {
+                Ok(_) => break,
+                Err(e) if !failed => errors.push(e.into()),
+                Err(_) => { /* the item's error is more informative */ }
+            }
+
+            if !skip_to_delimiter(input, &start, Some(&seperator), &end) {
+                if failed {
+                    errors.extend(eat(input, end).map_err(ParseError::into).err());
+                }
+
+                break;
+            }
+
+            let _ = eat(input, seperator);
+        } else if failed && !skip_to_delimiter(input, &start, None, &end) {
+            errors.extend(eat(input, end).map_err(ParseError::into).err());
+            break;
+        }
+    }
+
+    Ok((collection, errors))
+}
+
+/// Skips tokens until the current token is `seperator` or `end` and isn't
+/// nested inside of a `start`/`end` pair. Returns `false` if EOF is reached
+/// first.
+fn skip_to_delimiter<I: Input, T: Token<I>>(
+    input: &mut I,
+    start: &T,
+    seperator: Option<&T>,
+    end: &T,
+) -> bool {
+    let mut depth = 0usize;
+    loop {
+        let token = match input.token() {
+            Some(token) => token,
+            None => return false,
+        };
+
+        if depth == 0 && (*end == token || seperator.is_some_and(|s| *s == token)) {
+            return true;
+        } else if *end == token {
+            depth -= 1;
+        } else if *start == token {
+            depth += 1;
+        }
+
+        input.eat(|_| true);
+    }
+}
+
 /// Parses many `separator` delimited `p`s. Gramatically, this is:
 ///
 /// item (SEPERATOR item)*
 #[parser(raw)]
-pub fn series<C, I, S, O, P>(
+pub fn series<C, I, S, O, P, E>(
     input: &mut I,
     mut item: P,
     seperator: S,
-) -> Result<C, I>
+) -> Result<C, I, E>
     where C: Collection<Item=O>,
           I: Input,
+          E: Error<I>,
           S: Token<I> + Clone,
-          P: FnMut(&mut I) -> Result<O, I>,
+          P: FnMut(&mut I) -> Result<O, I, E>,
 {
     let mut collection = C::new();
     loop {
@@ -201,58 +863,136 @@ pub fn series<C, I, S, O, P>(
     Ok(collection)
 }
 
-/// Parses many `separator` delimited `p`s with an optional trailing separator.
+/// Parses as many `separator` delimited `item`s as possible, stopping at the
+/// first `item` that fails, collecting them into a `C`. `C` may be empty.
 /// Gramatically, this is:
 ///
-/// item (SEPERATOR item)* SEPERATOR?
+/// (item (SEPERATOR item)*)?
+///
+/// Like [`try_collect()`], an `item` that fails is rewound, as is the
+/// separator preceding it, so that parsing can resume right after the last
+/// successfully parsed `item`. Committed errors are returned.
 #[parser(raw)]
-pub fn trailing_series<C, I, S, O, P>(
+pub fn try_series<C, I, S, O, P, E>(
     input: &mut I,
     mut item: P,
     seperator: S,
-) -> Result<C, I>
+) -> Result<C, I, E>
     where C: Collection<Item=O>,
-          I: Input,
+          I: Input + Rewind,
+          E: Error<I>,
           S: Token<I> + Clone,
-          P: FnMut(&mut I) -> Result<O, I>,
+          P: FnMut(&mut I) -> Result<O, I, E>,
 {
     let mut collection = C::new();
-    let mut have_some = false;
+    let mut first = true;
     loop {
-        match item(input) {
-            Ok(item) => collection.add(item),
-            Err(e) => if have_some {
-                break
-            } else {
-                return Err(e)
-            }
+        let mut checkpoint = Checkpoint::named(input, "try_series");
+        if !first && eat(&mut *checkpoint, seperator.clone()).is_err() {
+            break;
         }
 
-        if eat(input, seperator.clone()).is_err() {
-            break;
+        match item(&mut checkpoint) {
+            Ok(val) => {
+                checkpoint.commit();
+                collection.add(val);
+            }
+            Err(e) if e.committed => {
+                checkpoint.commit();
+                return Err(e);
+            }
+            Err(_) => break,
         }
 
-        have_some = true;
+        first = false;
     }
 
     Ok(collection)
 }
 
-/// Parses many `separator` delimited `p`s that are collectively prefixed with
-/// `prefix`. Gramatically, this is:
+/// Parses many `separator` delimited `item`s, folding each into an accumulator,
+/// which starts as `init`, with `f`. Returns the final accumulator. Like
+/// [`series()`], but without building a collection. Gramatically, this is:
 ///
-/// PREFIX (item SEPERATOR)*
+/// item (SEPERATOR item)*
 #[parser(raw)]
-pub fn prefixed_series<C, I, T, O, P>(
+pub fn separated_fold<A, I, S, O, P, F, E>(
     input: &mut I,
-    prefix: T,
+    init: A,
+    mut item: P,
+    seperator: S,
+    mut f: F,
+) -> Result<A, I, E>
+    where I: Input,
+          S: Token<I> + Clone,
+          P: FnMut(&mut I) -> Result<O, I, E>,
+          F: FnMut(A, O) -> A,
+{
+    let mut acc = init;
+    loop {
+        acc = f(acc, item(input)?);
+        if eat(input, seperator.clone()).is_err() {
+            break;
+        }
+    }
+
+    Ok(acc)
+}
+
+/// Parses many `separator` delimited `p`s with an optional trailing separator.
+/// Gramatically, this is:
+///
+/// item (SEPERATOR item)* SEPERATOR?
+#[parser(raw)]
+pub fn trailing_series<C, I, S, O, P, E>(
+    input: &mut I,
+    mut item: P,
+    seperator: S,
+) -> Result<C, I, E>
+    where C: Collection<Item=O>,
+          I: Input,
+          E: Error<I>,
+          S: Token<I> + Clone,
+          P: FnMut(&mut I) -> Result<O, I, E>,
+{
+    let mut collection = C::new();
+    let mut have_some = false;
+    loop {
+        match item(input) {
+            Ok(item) => collection.add(item),
+            Err(e) => if have_some && !e.committed {
+                break
+            } else {
+                return Err(e)
+            }
+        }
+
+        if eat(input, seperator.clone()).is_err() {
+            break;
+        }
+
+        have_some = true;
+    }
+
+    Ok(collection)
+}
+
+/// Parses many `separator` delimited `p`s that are collectively prefixed with
+/// `prefix`. Gramatically, this is:
+///
+/// PREFIX (item SEPERATOR)*
+#[parser(raw)]
+pub fn prefixed_series<C, I, T, O, P, E>(
+    input: &mut I,
+    prefix: T,
     item: P,
     seperator: T,
-) -> Result<C, I>
+) -> Result<C, I, E>
     where C: Collection<Item=O>,
           I: Input,
+          E: Error<I>,
           T: Token<I> + Clone,
-          P: FnMut(&mut I) -> Result<O, I>,
+          P: FnMut(&mut I) -> Result<O, I, E>,
 {
     if eat(input, prefix).is_err() {
         return Ok(C::new());
@@ -260,3 +1000,1150 @@ pub fn prefixed_series<C, I, T, O, P>(
 
     series(input, item, seperator)
 }
+
+/// Runs `p`, marking any error it returns as committed.
+///
+/// A committed error signals that parsing has reached a point of no return:
+/// combinators that would otherwise recover from a failure by trying an
+/// alternative or stopping early, such as `switch!`, `parse_try!`,
+/// [`try_collect()`], [`trailing_series()`], and [`alt()`], instead propagate
+/// the committed error. Use `cut` once the input has been identified
+/// unambiguously so that an error reports what actually went wrong rather
+/// than that a different alternative was expected:
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn string_body<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     let contents = take_while(|&c| c != '"')?;
+///     eat('"')?;
+///     contents
+/// }
+///
+/// #[parser]
+/// fn string<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     eat('"')?;
+///     cut(string_body)?
+/// }
+///
+/// #[parser]
+/// fn value<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     switch! {
+///         s@string() => s,
+///         _ => take_some_while(|c: &char| c.is_alphanumeric())?
+///     }
+/// }
+///
+/// // Without `cut`, the error would be that an alphanumeric was expected.
+/// let error = parse!(value: &mut Text::from("\"abc")).unwrap_err();
+/// assert!(error.committed);
+/// assert!(error.to_string().starts_with("expected token `\"` but none was found"));
+/// ```
+///
+/// The `#[parser(commit)]` attribute argument similarly marks every error
+/// returned by a parser as committed.
+#[parser(raw)]
+pub fn cut<I: Input, O, P, E>(input: &mut I, p: P) -> Result<O, I, E>
+    where E: Error<I>, P: FnOnce(&mut I) -> Result<O, I, E>
+{
+    p(input).map_err(|e| e.commit())
+}
+
+/// Runs `p` as a transaction: if `p` fails, the input is rewound to where it
+/// was before `p` ran, as if `p` never consumed anything.
+///
+/// Unlike the `#[parser(rewind)]` attribute argument, `transaction` applies
+/// to any parser, including closures and parsers from other crates. The input
+/// is guarded by a [`Checkpoint`], which is only committed once `p` succeeds.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn key<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     let key = take_some_while(|c: &char| c.is_alphabetic())?;
+///     eat('=')?;
+///     key
+/// }
+///
+/// #[parser]
+/// fn setting<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+///     let key = transaction(input, key).unwrap_or("default");
+///     (key, take_some_while(|c: &char| c.is_alphanumeric())?)
+/// }
+///
+/// assert_eq!(parse!(setting: &mut Text::from("mode=fast")).unwrap(), ("mode", "fast"));
+/// assert_eq!(parse!(setting: &mut Text::from("fast")).unwrap(), ("default", "fast"));
+/// ```
+#[parser(raw)]
+pub fn transaction<I, O, P, E>(input: &mut I, p: P) -> Result<O, I, E>
+    where I: Rewind, E: Error<I>, P: FnOnce(&mut I) -> Result<O, I, E>
+{
+    let mut checkpoint = Checkpoint::named(input, "transaction");
+    let output = p(&mut checkpoint)?;
+    checkpoint.commit();
+    Ok(output)
+}
+
+/// Runs `p`, returning `None` if it fails, rewound to where `p` began, or its
+/// output otherwise.
+///
+/// `opt` is the cheap building block for optional syntax. Since the error of
+/// a failed `p` is discarded, nothing need be spent on it: with the `std`
+/// feature, which tracks suppression per thread, errors are suppressed while
+/// `p` runs, so parsers don't render what they expected, `#[parser]`
+/// functions don't record their contexts, and a failed `p` doesn't allocate.
+/// Without `std`, errors aren't suppressed, and a failed `p` costs as much as
+/// it would outside of `opt`.
+///
+/// A [committed](ParseError::commit()) error is not recovered from but
+/// returned as it was suppressed: it says what was found but not what was
+/// expected, and it has no contexts from within `p`. To report a committed
+/// error in full, run `p` outside of `opt`.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn sign<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+///     eat_any_of(&['+', '-'][..])?
+/// }
+///
+/// #[parser]
+/// fn integer<'a>(input: &mut Text<'a>) -> Result<(Option<char>, &'a str), Text<'a>> {
+///     let sign = opt(sign)?;
+///     (sign, take_some_while(|c: &char| c.is_ascii_digit())?)
+/// }
+///
+/// assert_eq!(parse!(integer: &mut Text::from("-12")).unwrap(), (Some('-'), "12"));
+/// assert_eq!(parse!(integer: &mut Text::from("12")).unwrap(), (None, "12"));
+/// ```
+#[parser(raw)]
+pub fn opt<I, O, P, E>(input: &mut I, mut p: P) -> Result<Option<O>, I, E>
+    where I: Rewind, E: Error<I>, P: FnMut(&mut I) -> Result<O, I, E>
+{
+    let info = ParserInfo::new("opt", true);
+    let mark = input.mark(&info);
+    let result = match crate::error::quietly(|| p(input)) {
+        Ok(output) => Ok(Some(output)),
+        Err(e) if e.committed => Err(e),
+        Err(_) => {
+            input.rewind_to(&mark);
+            Ok(None)
+        }
+    };
+
+    input.unmark(&info, result.is_ok(), mark);
+    result
+}
+
+/// Runs `p`, then requires that the input has reached EOF, failing with
+/// `p`'s output discarded if anything remains.
+///
+/// Top-level entry points built with `complete` fail on trailing garbage by
+/// construction, without callers having to remember to go through `parse!`.
+/// The `#[parser(complete)]` attribute argument similarly checks for EOF
+/// after the parser's body succeeds.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn number<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?
+/// }
+///
+/// #[parser(complete)]
+/// fn document<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     number()?
+/// }
+///
+/// assert_eq!(complete(&mut Text::from("123"), number).unwrap(), "123");
+/// assert!(complete(&mut Text::from("123abc"), number).is_err());
+/// assert!(document(&mut Text::from("123abc")).is_err());
+/// ```
+#[parser(raw)]
+pub fn complete<I: Input, O, P, E>(input: &mut I, p: P) -> Result<O, I, E>
+    where E: Error<I>, P: FnOnce(&mut I) -> Result<O, I, E>
+{
+    let output = p(input)?;
+    eof(input).map_err(ParseError::into)?;
+    Ok(output)
+}
+
+/// Runs `p`, replacing what was expected in any error it returns with
+/// `label`.
+///
+/// Labels present domain-level expectations, like "a JSON value", in place of
+/// the low-level token expectations of the parser that happened to fail. What
+/// was found is kept, as are free-form error messages. The `#[parser(label =
+/// "...")]` attribute argument similarly labels every error returned by a
+/// parser.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn digits<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?
+/// }
+///
+/// #[parser]
+/// fn port<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     eat(':')?;
+///     label("a port number", digits)?
+/// }
+///
+/// let error = parse!(port: &mut Text::from(":http")).unwrap_err();
+/// assert!(error.to_string().starts_with("expected a port number but found `h`"));
+/// ```
+#[parser(raw)]
+pub fn label<I: Input, O, P>(input: &mut I, label: &str, p: P) -> Result<O, I>
+    where P: FnOnce(&mut I) -> Result<O, I>
+{
+    p(input).map_err(|e| e.label(label))
+}
+
+/// Runs `p`, attaching the help message `help` to any error it returns, as
+/// [`ParseError::with_help()`] does.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn assignment<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+///     let name = take_some_while(|c: &char| c.is_alphabetic())?;
+///     with_help("use `=` to assign values", |i| eat(i, '='))?;
+///     (name, take_while(|_| true)?)
+/// }
+///
+/// let error = parse!(assignment: &mut Text::from("x: 1")).unwrap_err();
+/// assert_eq!(error.help().next(), Some("use `=` to assign values"));
+/// ```
+#[parser(raw)]
+pub fn with_help<I: Input, O, P, E, H>(input: &mut I, help: H, p: P) -> Result<O, I, E>
+    where P: FnOnce(&mut I) -> Result<O, I, E>, H: Into<Cow<'static, str>>
+{
+    p(input).map_err(|e| e.with_help(help))
+}
+
+/// Runs `p`, then checks its output with `predicate`. If the check fails, the
+/// input is rewound to where `p` began and the error is `message`, about the
+/// span of input `p` consumed.
+///
+/// `verify` validates values that parse but are out of range or otherwise
+/// invalid, reporting the problem at the value itself, without hand-written
+/// marking and rewinding. The error is built with [`Error::from_context()`],
+/// so custom errors also receive the value's span.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Position, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn number<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>()?
+/// }
+///
+/// #[parser]
+/// fn port<'a>(input: &mut Text<'a>) -> Result<u16, Text<'a>> {
+///     eat(':')?;
+///     verify(number, |&n| n <= 65535, "port must be ≤ 65535")? as u16
+/// }
+///
+/// assert_eq!(parse!(port: &mut Text::from(":8080")).unwrap(), 8080);
+///
+/// let mut input = Text::from(":99999");
+/// let error = port(&mut input).unwrap_err();
+/// assert!(error.to_string().starts_with("port must be ≤ 65535"));
+/// assert_eq!(input.position(), 1);
+/// ```
+#[parser(raw)]
+pub fn verify<I, O, P, F, M, E>(input: &mut I, p: P, predicate: F, message: M) -> Result<O, I, E>
+    where I: Rewind,
+          E: Error<I>,
+          P: FnOnce(&mut I) -> Result<O, I, E>,
+          F: FnOnce(&O) -> bool,
+          M: Into<Cow<'static, str>>
+{
+    let info = ParserInfo::new("verify", false);
+    let mark = input.mark(&info);
+    let output = match p(input) {
+        Ok(output) if predicate(&output) => output,
+        Ok(_) => {
+            let mut error = ParseError::new(E::from_context(message.into(), input.context(&mark)));
+            error.push_context(input.context(&mark), info);
+            input.rewind_to(&mark);
+            input.unmark(&info, false, mark);
+            return Err(error);
+        }
+        Err(e) => {
+            input.unmark(&info, false, mark);
+            return Err(e);
+        }
+    };
+
+    input.unmark(&info, true, mark);
+    Ok(output)
+}
+
+/// Runs `p`, converting its output with `f`.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     take_some_while(|c: &char| c.is_alphabetic())?
+/// }
+///
+/// #[parser]
+/// fn shout<'a>(input: &mut Text<'a>) -> Result<String, Text<'a>> {
+///     map(word, str::to_uppercase)?
+/// }
+///
+/// assert_eq!(parse!(shout: &mut Text::from("pear")).unwrap(), "PEAR");
+/// ```
+#[parser(raw)]
+pub fn map<I, O, U, P, F, E>(input: &mut I, p: P, f: F) -> Result<U, I, E>
+    where I: Input, E: Error<I>, P: FnOnce(&mut I) -> Result<O, I, E>, F: FnOnce(O) -> U
+{
+    p(input).map(f)
+}
+
+/// Runs `p`, converting its output with `f`, which may fail. If `f` fails,
+/// its error becomes a `ParseError` about the span of input `p` consumed.
+///
+/// `map_res` is the glue between parsing and constructing semantic values
+/// from parsed text, such as numbers from digits, with errors pointing at the
+/// text that failed to convert. `f`'s error is converted with
+/// [`IntoParseError`](crate::error::IntoParseError), as by `?` in a
+/// `#[parser]` function: its `Display` becomes the error's message.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn byte<'a>(input: &mut Text<'a>) -> Result<u8, Text<'a>> {
+///     map_res(|i| take_some_while(i, |c: &char| c.is_ascii_digit()), str::parse::<u8>)?
+/// }
+///
+/// assert_eq!(parse!(byte: &mut Text::from("255")).unwrap(), 255);
+///
+/// let error = parse!(byte: &mut Text::from("256")).unwrap_err();
+/// let span = error.contexts[0].context.as_ref().unwrap();
+/// assert!(error.to_string().starts_with("number too large"));
+/// assert_eq!((span.start.2, span.end.2), (0, 3));
+/// ```
+#[parser(raw)]
+pub fn map_res<I, O, U, X, P, F, E>(input: &mut I, p: P, f: F) -> Result<U, I, E>
+    where I: Input,
+          E: Error<I>,
+          P: FnOnce(&mut I) -> Result<O, I, E>,
+          F: FnOnce(O) -> core::result::Result<U, X>,
+          X: core::fmt::Display
+{
+    let info = ParserInfo::new("map_res", false);
+    let mark = input.mark(&info);
+    let result = p(input).and_then(|output| f(output).map_err(|e| {
+        let mut error = e.into_parse_error(input, &mark);
+        error.push_context(input.context(&mark), info);
+        error
+    }));
+
+    input.unmark(&info, result.is_ok(), mark);
+    result
+}
+
+/// How much input [`from_str()`] takes: a number of tokens, exactly, or as
+/// many tokens as match a predicate, at least one.
+pub trait TakeBy<I: Input> {
+    /// Takes the input `self` describes.
+    fn take_by(self, input: &mut I) -> Result<I::Many, I>;
+}
+
+impl<I: Input> TakeBy<I> for usize {
+    fn take_by(self, input: &mut I) -> Result<I::Many, I> {
+        let mut taken = 0;
+        let many = input.take(|_| { let take = taken < self; taken += take as usize; take });
+        if taken < self {
+            return Err(ParseError::new(Expected::Token(None, input.token())));
+        }
+
+        Ok(many)
+    }
+}
+
+impl<I: Input, F: FnMut(&I::Token) -> bool> TakeBy<I> for F {
+    fn take_by(self, input: &mut I) -> Result<I::Many, I> {
+        take_some_while(input, self)
+    }
+}
+
+/// Takes a slice of text by length or predicate, as described by
+/// [`TakeBy`], and parses it into a `T` with [`FromStr`]. If `T::from_str`
+/// fails, its error becomes a `ParseError`, as in [`map_res()`], about the
+/// slice.
+///
+/// `from_str` adapts types that already know how to parse themselves from a
+/// string, such as those of other crates, to parsers that find where their
+/// text begins and ends.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use std::net::Ipv4Addr;
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn host<'a>(input: &mut Text<'a>) -> Result<(Ipv4Addr, u16), Text<'a>> {
+///     let addr = from_str(|c: &char| c.is_ascii_digit() || *c == '.')?;
+///     eat(':')?;
+///     (addr, from_str(4)?)
+/// }
+///
+/// let (addr, port) = parse!(host: &mut Text::from("127.0.0.1:8080")).unwrap();
+/// assert_eq!((addr, port), (Ipv4Addr::LOCALHOST, 8080));
+///
+/// let error = parse!(host: &mut Text::from("127.0.0:8080")).unwrap_err();
+/// let span = error.contexts[0].context.as_ref().unwrap();
+/// assert_eq!(error.error.to_string(), "invalid IPv4 address syntax");
+/// assert_eq!((span.start.2, span.end.2), (0, 7));
+/// ```
+#[parser(raw)]
+pub fn from_str<'a, T, I, L>(input: &mut I, len_or_pred: L) -> Result<T, I>
+    where I: Input<Many = &'a str>, T: FromStr, T::Err: core::fmt::Display, L: TakeBy<I>
+{
+    let slice = len_or_pred.take_by(input)?;
+    slice.parse().map_err(|e: T::Err| e.into_parse_error(input, &parse_marker!()))
+}
+
+/// Runs `p` in `mode`: with whitespace significant, or not, regardless of
+/// the mode of the parser calling `with_mode`. The input's mode is restored
+/// once `p` returns. See [`Lexed`](crate::input::Lexed).
+#[parser(raw)]
+pub fn with_mode<I, O, P, E>(input: &mut I, mode: Mode, p: P) -> Result<O, I, E>
+    where I: Modal, E: Error<I>, P: FnOnce(&mut I) -> Result<O, I, E>
+{
+    input.push_mode(mode);
+    let result = p(input);
+    input.pop_mode();
+    result
+}
+
+/// Runs `p`, returning its output along with the span of input it consumed.
+///
+/// The span is a range of [`Position`]s: byte offsets for text inputs and
+/// token indices for token inputs.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     take_some_while(|c: &char| c.is_alphabetic())?
+/// }
+///
+/// #[parser]
+/// fn second<'a>(input: &mut Text<'a>) -> Result<(&'a str, std::ops::Range<usize>), Text<'a>> {
+///     word()?;
+///     eat(' ')?;
+///     spanned(word)?
+/// }
+///
+/// let mut input = Text::from("héllo world");
+/// assert_eq!(second(&mut input).unwrap(), ("world", 7..12));
+/// ```
+#[parser(raw)]
+pub fn spanned<I, O, P, E>(input: &mut I, p: P) -> Result<(O, Range<usize>), I, E>
+    where I: Position, E: Error<I>, P: FnOnce(&mut I) -> Result<O, I, E>
+{
+    let start = input.position();
+    let output = p(input)?;
+    Ok((output, start..input.position()))
+}
+
+/// Runs `p`, returning the symbol for the slice it parsed, interned by the
+/// input's interner. See [`Interned`](crate::input::Interned).
+#[parser(raw)]
+pub fn interned<I, P, E>(input: &mut I, p: P) -> Result<I::Symbol, I, E>
+    where I: Intern, E: Error<I>, P: FnOnce(&mut I) -> Result<I::Slice, I, E>
+{
+    let slice = p(input)?;
+    Ok(input.intern(slice))
+}
+
+/// Runs `p` on the next `n` units of input, bytes for text inputs and tokens
+/// otherwise, as a [`Limited`] input.
+///
+/// `p` sees the input end after `n` units, so it can neither consume nor look
+/// at anything past them, but it need not consume all of them. Use
+/// [`length_value()`] to require that it does.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Limited, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn digits<'a, 'l>(input: &mut Limited<'l, Text<'a>>) -> Result<&'a str, Limited<'l, Text<'a>>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?
+/// }
+///
+/// #[parser]
+/// fn year<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     limit(4, digits)?
+/// }
+///
+/// let mut input = Text::from("20240115");
+/// assert_eq!(year(&mut input).unwrap(), "2024");
+/// assert_eq!(year(&mut input).unwrap(), "0115");
+/// assert!(year(&mut input).is_err());
+/// ```
+#[parser(raw)]
+pub fn limit<I, O, P>(input: &mut I, n: usize, p: P) -> Result<O, I>
+    where I: Input, P: for<'l> FnOnce(&mut Limited<'l, I>) -> Result<O, Limited<'l, I>>
+{
+    p(&mut Limited::new(input, n))
+}
+
+/// Parses a length with `len`, then parses a value of exactly that length with
+/// `value`.
+///
+/// `value` parses a [`Limited`] input which ends after `len` units of input:
+/// bytes for text inputs and tokens otherwise. Fails if fewer than `len` units
+/// remain, if `value` fails, including by reading past the end of the
+/// [`Limited`] input, or if `value` does not consume all of it. This is the
+/// building block of type-length-value (TLV) formats:
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Cursor, Limited, Result};
+/// use pear::{macros::*, combinators::*};
+/// use pear::parsers::{eof, binary::*};
+///
+/// type Input<'a> = Cursor<'a, u8>;
+///
+/// #[parser]
+/// fn pair<'a, 'l>(input: &mut Limited<'l, Input<'a>>) -> Result<(u16, u8), Limited<'l, Input<'a>>> {
+///     (be_u16()?, u8()?)
+/// }
+///
+/// #[parser]
+/// fn record<'a>(input: &mut Input<'a>) -> Result<(u16, u8), Input<'a>> {
+///     length_value(u8, pair)?
+/// }
+///
+/// assert_eq!(parse!(record: &mut Cursor::from(&[3, 1, 2, 3][..])).unwrap(), (0x102, 3));
+///
+/// // The declared length is too short, too long, or exceeds the input.
+/// assert!(parse!(record: &mut Cursor::from(&[2, 1, 2, 3][..])).is_err());
+/// assert!(parse!(record: &mut Cursor::from(&[4, 1, 2, 3, 4][..])).is_err());
+/// assert!(parse!(record: &mut Cursor::from(&[4, 1, 2, 3][..])).is_err());
+/// ```
+#[parser(raw)]
+pub fn length_value<I, N, O, L, V>(input: &mut I, len: L, value: V) -> Result<O, I>
+    where I: Input,
+          N: TryInto<usize>,
+          L: FnOnce(&mut I) -> Result<N, I>,
+          V: for<'l> FnOnce(&mut Limited<'l, I>) -> Result<O, Limited<'l, I>>
+{
+    let n = len(input)?.try_into().map_err(|_| {
+        ParseError::new(Expected::Other("length does not fit in a `usize`".into()))
+    })?;
+
+    if !input.has(n) {
+        let expected = format!("{} more units of input", n);
+        return Err(ParseError::new(Expected::OneOf(vec![expected], None)));
+    }
+
+    let mut limited = Limited::new(input, n);
+    let output = value(&mut limited)?;
+    eof(&mut limited)?;
+    Ok(output)
+}
+
+/// Takes the next `len` bytes, feeding each through `hasher`, then parses a
+/// checksum of them with `digest` and validates it against the hasher's
+/// [`finish()`](Hasher::finish()). Returns the bytes taken.
+///
+/// `hasher` is any [`Hasher`], like a CRC implementation, and is used as
+/// given: seed it before calling `checksummed`. Fails if fewer than `len`
+/// bytes remain, if `digest` fails, or if the checksum doesn't match:
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use core::hash::Hasher;
+///
+/// use pear::input::{Cursor, Result};
+/// use pear::{macros::*, combinators::*};
+/// use pear::parsers::binary::*;
+///
+/// type Input<'a> = Cursor<'a, u8>;
+///
+/// /// The sum of the bytes, modulo 256.
+/// #[derive(Default)]
+/// struct Sum8(u8);
+///
+/// impl Hasher for Sum8 {
+///     fn write(&mut self, bytes: &[u8]) {
+///         bytes.iter().for_each(|b| self.0 = self.0.wrapping_add(*b));
+///     }
+///
+///     fn finish(&self) -> u64 {
+///         self.0 as u64
+///     }
+/// }
+///
+/// // A block: a length byte, that many bytes, and their checksum byte.
+/// #[parser]
+/// fn block<'a>(input: &mut Input<'a>) -> Result<&'a [u8], Input<'a>> {
+///     let len = u8()?;
+///     checksummed(len as usize, u8, Sum8::default())?
+/// }
+///
+/// assert_eq!(parse!(block: &mut Cursor::from(&[3, 1, 2, 3, 6][..])).unwrap(), [1, 2, 3]);
+/// assert!(parse!(block: &mut Cursor::from(&[3, 1, 2, 3, 7][..])).is_err());
+/// assert!(parse!(block: &mut Cursor::from(&[3, 1, 2][..])).is_err());
+/// ```
+#[parser(raw)]
+pub fn checksummed<I, C, D, H>(input: &mut I, len: usize, digest: D, mut hasher: H) -> Result<I::Many, I>
+    where I: Input,
+          I::Token: Byte,
+          C: Into<u64>,
+          D: FnOnce(&mut I) -> Result<C, I>,
+          H: Hasher
+{
+    if !input.has(len) {
+        let expected = format!("{} more bytes", len);
+        return Err(ParseError::new(Expected::OneOf(vec![expected], None)));
+    }
+
+    let mut remaining = len;
+    let region = input.take(|token| match remaining {
+        0 => false,
+        _ => { remaining -= 1; hasher.write_u8(token.byte()); true }
+    });
+
+    let (computed, found) = (hasher.finish(), digest(input)?.into());
+    if computed != found {
+        let message = format!("expected checksum {:#x} but found {:#x}", computed, found);
+        return Err(ParseError::new(Expected::Other(message.into())));
+    }
+
+    Ok(region)
+}
+
+/// A pointer to a parser function taking no arguments beyond its input.
+/// Non-capturing closures coerce to this type.
+pub type ParserFn<I, O, E = crate::input::Expected<I>> = fn(&mut I) -> Result<O, I, E>;
+
+/// A parser of any type, including a capturing closure, called through
+/// dynamic dispatch. Parsers of different types with the same input and output
+/// can be stored together, as in a table of rules chosen at runtime. Create one
+/// with [`boxed()`].
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn number<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?
+/// }
+///
+/// let quote = '"';
+/// let mut rules: Vec<BoxedParser<'_, Text<'_>, &str>> = vec![
+///     boxed(number),
+///     boxed(move |input| delimited(input, quote, |_| true, quote)),
+/// ];
+///
+/// let mut input = Text::from("\"pear\"");
+/// assert!(rules[0](&mut input).is_err());
+/// assert_eq!(rules[1](&mut input).unwrap(), "pear");
+/// ```
+pub type BoxedParser<'a, I, O, E = crate::input::Expected<I>> =
+    Box<dyn FnMut(&mut I) -> Result<O, I, E> + 'a>;
+
+/// Boxes the parser `p` into a [`BoxedParser`].
+pub fn boxed<'a, I: Input, O, E, P>(p: P) -> BoxedParser<'a, I, O, E>
+    where P: FnMut(&mut I) -> Result<O, I, E> + 'a
+{
+    Box::new(p)
+}
+
+/// Tries each parser in `alternatives` in order, returning the result of the
+/// first to succeed. If all fail, returns their errors merged with
+/// [`ParseError::merge()`], so that the error lists everything that was
+/// expected. Like `switch!`, does not rewind between alternatives: each
+/// alternative should consume no input on failure.
+///
+/// [`ParseError::merge()`]: crate::error::ParseError::merge()
+#[parser(raw)]
+pub fn alt<I, O, E>(input: &mut I, alternatives: &[ParserFn<I, O, E>]) -> Result<O, I, E>
+    where I: Input, E: Error<I>
+{
+    let mut error = None;
+    for alternative in alternatives {
+        match alternative(input) {
+            Ok(output) => return Ok(output),
+            Err(e) if e.committed => return Err(e),
+            Err(e) => error = Some(match error {
+                Some(error) => merge(error, e),
+                None => e,
+            }),
+        }
+    }
+
+    Err(error.unwrap_or_else(|| {
+        ParseError::new(E::from_expected(Expected::Token(None, input.token())))
+    }))
+}
+
+/// A member of a [`permute()`]: a parser, which must succeed exactly once, or
+/// an [`Optional`] parser, which may.
+pub trait Member<I: Input, E> {
+    /// The output of the member's parser.
+    type Value;
+    /// The output of the member in a permutation.
+    type Output;
+
+    /// Runs the member's parser.
+    fn parse(&mut self, input: &mut I) -> Result<Self::Value, I, E>;
+
+    /// Returns the output of the member given its parser's output, if it
+    /// succeeded, or `None` if the member is required but didn't succeed.
+    fn finish(value: Option<Self::Value>) -> Option<Self::Output>;
+}
+
+impl<I, O, E, F> Member<I, E> for F
+    where I: Input, F: FnMut(&mut I) -> Result<O, I, E>
+{
+    type Value = O;
+    type Output = O;
+
+    fn parse(&mut self, input: &mut I) -> Result<O, I, E> {
+        self(input)
+    }
+
+    fn finish(value: Option<O>) -> Option<O> {
+        value
+    }
+}
+
+/// An optional [`Member`] of a permutation. Create one with [`optional()`].
+#[derive(Debug, Copy, Clone)]
+pub struct Optional<P>(P);
+
+/// Marks the parser `p` as an optional member of a [`permute()`]: its output
+/// is `None` if it doesn't succeed.
+pub fn optional<P>(p: P) -> Optional<P> {
+    Optional(p)
+}
+
+impl<I: Input, E, P: Member<I, E>> Member<I, E> for Optional<P> {
+    type Value = P::Value;
+    type Output = Option<P::Output>;
+
+    fn parse(&mut self, input: &mut I) -> Result<P::Value, I, E> {
+        self.0.parse(input)
+    }
+
+    fn finish(value: Option<P::Value>) -> Option<Self::Output> {
+        Some(value.and_then(|value| P::finish(Some(value))))
+    }
+}
+
+/// A tuple of [`Member`]s which can be parsed in any order. Implemented for
+/// tuples of up to eight members.
+pub trait Permutation<I: Input, E> {
+    /// A tuple of the outputs of the members.
+    type Output;
+
+    /// Parses the members in any order. See [`permute()`].
+    fn permute(&mut self, input: &mut I) -> Result<Self::Output, I, E>;
+}
+
+/// Tries `member` if it hasn't yet succeeded, storing its output in `value`
+/// or its error in `error`, after rewinding. Returns `true` if it succeeded.
+fn try_member<I, E, M>(
+    input: &mut I,
+    member: &mut M,
+    value: &mut Option<M::Value>,
+    error: &mut Option<ParseError<I::Context, E>>,
+) -> Result<bool, I, E>
+    where I: Rewind, M: Member<I, E>
+{
+    if value.is_some() {
+        return Ok(false);
+    }
+
+    let info = ParserInfo::new("permute", true);
+    let mark = input.mark(&info);
+    let result = member.parse(input);
+    input.unmark(&info, result.is_ok(), mark);
+    match result {
+        Ok(output) => *value = Some(output),
+        Err(e) if e.committed => return Err(e),
+        Err(e) => {
+            input.rewind_to(&mark);
+            *error = Some(e);
+        }
+    }
+
+    Ok(value.is_some())
+}
+
+macro_rules! impl_permutation {
+    ($($M:ident $value:ident $error:ident),+) => {
+        impl<I, E, $($M),+> Permutation<I, E> for ($($M,)+)
+            where I: Rewind, E: Error<I>, $($M: Member<I, E>),+
+        {
+            type Output = ($($M::Output,)+);
+
+            #[allow(non_snake_case)]
+            fn permute(&mut self, input: &mut I) -> Result<Self::Output, I, E> {
+                let ($($M,)+) = self;
+                $(let (mut $value, mut $error) = (None, None);)+
+                loop {
+                    let mut progress = false;
+                    $(progress |= try_member(input, $M, &mut $value, &mut $error)?;)+
+                    if !progress {
+                        break;
+                    }
+                }
+
+                let mut missing: Option<ParseError<I::Context, E>> = None;
+                let output = ($(match $M::finish($value) {
+                    Some(output) => Some(output),
+                    None => {
+                        let e = $error.expect("a required member failed");
+                        missing = Some(match missing.take() {
+                            Some(missing) => merge(missing, e),
+                            None => e,
+                        });
+
+                        None
+                    }
+                },)+);
+
+                match (output, missing) {
+                    (($(Some($value),)+), None) => Ok(($($value,)+)),
+                    (_, missing) => Err(missing.expect("a required member is missing")),
+                }
+            }
+        }
+    };
+}
+
+impl_permutation!(A a ea);
+impl_permutation!(A a ea, B b eb);
+impl_permutation!(A a ea, B b eb, C c ec);
+impl_permutation!(A a ea, B b eb, C c ec, D d ed);
+impl_permutation!(A a ea, B b eb, C c ec, D d ed, F f ef);
+impl_permutation!(A a ea, B b eb, C c ec, D d ed, F f ef, G g eg);
+impl_permutation!(A a ea, B b eb, C c ec, D d ed, F f ef, G g eg, H h eh);
+impl_permutation!(A a ea, B b eb, C c ec, D d ed, F f ef, G g eg, H h eh, J j ej);
+
+/// Parses each of the `members`, a tuple of parsers, exactly once, in any
+/// order, returning a tuple of their outputs in the order of `members`.
+///
+/// Members marked with [`optional()`] may instead be absent, in which case
+/// their output is `None`. Members are tried repeatedly, in order, until none
+/// of those remaining succeeds; a member that fails is rewound. If a required
+/// member never succeeds, fails with the errors of the missing members
+/// merged, so that the error lists everything that was still expected.
+/// Committed errors are returned immediately.
+///
+/// `permute` suits header-like syntax, where fields may appear in any order:
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn field<'a>(input: &mut Text<'a>, name: &str) -> Result<&'a str, Text<'a>> {
+///     eat_slice(name)?;
+///     eat_slice(": ")?;
+///     let value = take_while(|&c| c != '\n')?;
+///     eat('\n')?;
+///     value
+/// }
+///
+/// #[parser]
+/// fn headers<'a>(input: &mut Text<'a>) -> Result<(&'a str, Option<&'a str>), Text<'a>> {
+///     permute((|i: &mut _| field(i, "Host"), optional(|i: &mut _| field(i, "Accept"))))?
+/// }
+///
+/// let text = "Accept: */*\nHost: pear.rs\n";
+/// assert_eq!(parse!(headers: &mut Text::from(text)).unwrap(), ("pear.rs", Some("*/*")));
+/// assert_eq!(parse!(headers: &mut Text::from("Host: a\n")).unwrap(), ("a", None));
+/// assert!(parse!(headers: &mut Text::from("Accept: */*\n")).is_err());
+/// ```
+#[parser(raw)]
+pub fn permute<I, E, P>(input: &mut I, mut members: P) -> Result<P::Output, I, E>
+    where I: Rewind, E: Error<I>, P: Permutation<I, E>
+{
+    members.permute(input)
+}
+
+/// Merges the error of a failed alternative, `other`, into `error`, as
+/// [`ParseError::merge()`] does, but for any error type.
+pub(crate) fn merge<C, I: Input, E: Error<I>>(mut error: ParseError<C, E>, other: ParseError<C, E>) -> ParseError<C, E> {
+    error.hints.extend(other.hints);
+    ParseError {
+        error: error.error.merge(other.error),
+        code: if error.code == other.code { error.code } else { None },
+        committed: error.committed || other.committed,
+        incomplete: error.incomplete.or(other.incomplete),
+        contexts: error.contexts,
+        hints: error.hints,
+    }
+}
+
+/// The associativity of an infix operator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Assoc {
+    /// `a + b + c` groups as `(a + b) + c`.
+    Left,
+    /// `a ^ b ^ c` groups as `a ^ (b ^ c)`.
+    Right,
+}
+
+/// A prefix operator parsed by [`precedence()`]: its binding power and a
+/// function that applies it to its operand.
+pub struct Prefix<O> {
+    pub power: u8,
+    pub fold: fn(O) -> O,
+}
+
+/// An infix operator parsed by [`precedence()`]: its binding power,
+/// associativity, and a function that applies it to its operands.
+pub struct Infix<O> {
+    pub power: u8,
+    pub assoc: Assoc,
+    pub fold: fn(O, O) -> O,
+}
+
+/// A postfix operator parsed by [`precedence()`]: its binding power and a
+/// function that applies it to its operand.
+pub struct Postfix<O> {
+    pub power: u8,
+    pub fold: fn(O) -> O,
+}
+
+impl<O> Prefix<O> {
+    /// A prefix operator with binding power `power`.
+    pub fn new(power: u8, fold: fn(O) -> O) -> Self {
+        Prefix { power, fold }
+    }
+}
+
+impl<O> Infix<O> {
+    /// A left-associative infix operator with binding power `power`.
+    pub fn left(power: u8, fold: fn(O, O) -> O) -> Self {
+        Infix { power, assoc: Assoc::Left, fold }
+    }
+
+    /// A right-associative infix operator with binding power `power`.
+    pub fn right(power: u8, fold: fn(O, O) -> O) -> Self {
+        Infix { power, assoc: Assoc::Right, fold }
+    }
+}
+
+impl<O> Postfix<O> {
+    /// A postfix operator with binding power `power`.
+    pub fn new(power: u8, fold: fn(O) -> O) -> Self {
+        Postfix { power, fold }
+    }
+}
+
+/// Parses an expression of `atom`s joined by `prefix`, `infix`, and `postfix`
+/// operators using precedence climbing (Pratt parsing).
+///
+/// Each operator parser parses one operator and returns its binding power,
+/// where a higher power binds more tightly, and a function that builds the
+/// expression it forms. An operator parser that doesn't find an operator
+/// should fail without consuming input; [`none()`] is a parser that never
+/// finds one. Prefix operators bind more tightly than infix operators of the
+/// same power, except for right-associative ones, so that `-2^2` is `-(2^2)`.
+/// Operators that bind too weakly to continue the current operand are
+/// rewound and left for an enclosing level.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// #[parser]
+/// fn number<'a>(input: &mut Text<'a>) -> Result<f64, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?.parse::<f64>().unwrap()
+/// }
+///
+/// #[parser]
+/// fn infix<'a>(input: &mut Text<'a>) -> Result<Infix<f64>, Text<'a>> {
+///     switch! {
+///         eat('+') => Infix::left(1, |a, b| a + b),
+///         eat('-') => Infix::left(1, |a, b| a - b),
+///         eat('*') => Infix::left(2, |a, b| a * b),
+///         _ => { eat('^')?; Infix::right(3, f64::powf) }
+///     }
+/// }
+///
+/// #[parser]
+/// fn prefix<'a>(input: &mut Text<'a>) -> Result<Prefix<f64>, Text<'a>> {
+///     eat('-')?;
+///     Prefix::new(2, |a: f64| -a)
+/// }
+///
+/// #[parser]
+/// fn expr<'a>(input: &mut Text<'a>) -> Result<f64, Text<'a>> {
+///     precedence(number, prefix, infix, none)?
+/// }
+///
+/// assert_eq!(parse!(expr: &mut Text::from("1+2*3-4")).unwrap(), 3.0);
+/// assert_eq!(parse!(expr: &mut Text::from("2^3^2")).unwrap(), 512.0);
+/// assert_eq!(parse!(expr: &mut Text::from("-2^2*-1")).unwrap(), 4.0);
+/// ```
+#[parser(raw)]
+pub fn precedence<I, O, A, P, F, S, E>(
+    input: &mut I,
+    mut atom: A,
+    mut prefix: P,
+    mut infix: F,
+    mut postfix: S,
+) -> Result<O, I, E>
+    where I: Rewind,
+          E: Error<I>,
+          A: FnMut(&mut I) -> Result<O, I, E>,
+          P: FnMut(&mut I) -> Result<Prefix<O>, I, E>,
+          F: FnMut(&mut I) -> Result<Infix<O>, I, E>,
+          S: FnMut(&mut I) -> Result<Postfix<O>, I, E>,
+{
+    climb(input, 0, &mut atom, &mut prefix, &mut infix, &mut postfix)
+}
+
+/// A parser for [`precedence()`] that never finds an operator.
+#[parser(raw)]
+pub fn none<I: Input, T, E: Error<I>>(input: &mut I) -> Result<T, I, E> {
+    Err(ParseError::new(E::from_expected(Expected::Token(None, input.token()))))
+}
+
+/// Parses an expression whose operators all bind with at least `min`, in
+/// terms of the doubled powers computed by `climb`'s callers.
+fn climb<I, O, A, P, F, S, E>(
+    input: &mut I,
+    min: u16,
+    atom: &mut A,
+    prefix: &mut P,
+    infix: &mut F,
+    postfix: &mut S,
+) -> Result<O, I, E>
+    where I: Rewind,
+          E: Error<I>,
+          A: FnMut(&mut I) -> Result<O, I, E>,
+          P: FnMut(&mut I) -> Result<Prefix<O>, I, E>,
+          F: FnMut(&mut I) -> Result<Infix<O>, I, E>,
+          S: FnMut(&mut I) -> Result<Postfix<O>, I, E>,
+{
+    let info = crate::input::ParserInfo::new("precedence", true);
+    let mut lhs = match prefix(input) {
+        Ok(op) => {
+            let operand = climb(input, 2 * op.power as u16 + 1, atom, prefix, infix, postfix)?;
+            (op.fold)(operand)
+        }
+        Err(e) if e.committed => return Err(e),
+        Err(e) => atom(input).map_err(|atom_error| merge(atom_error, e))?,
+    };
+
+    loop {
+        let mark = input.mark(&info);
+        match postfix(input) {
+            Ok(op) if 2 * op.power as u16 >= min => {
+                input.unmark(&info, true, mark);
+                lhs = (op.fold)(lhs);
+                continue;
+            }
+            Ok(_) => {
+                input.rewind_to(&mark);
+                input.unmark(&info, false, mark);
+                break;
+            }
+            Err(e) if e.committed => {
+                input.unmark(&info, false, mark);
+                return Err(e);
+            }
+            Err(_) => input.unmark(&info, false, mark),
+        }
+
+        let mark = input.mark(&info);
+        let (right, fold) = match infix(input) {
+            Ok(op) => {
+                let (left, right) = match op.assoc {
+                    Assoc::Left => (2 * op.power as u16, 2 * op.power as u16 + 1),
+                    Assoc::Right => (2 * op.power as u16 + 1, 2 * op.power as u16),
+                };
+
+                if left < min {
+                    input.rewind_to(&mark);
+                    input.unmark(&info, false, mark);
+                    break;
+                }
+
+                input.unmark(&info, true, mark);
+                (right, op.fold)
+            }
+            Err(e) => {
+                input.unmark(&info, false, mark);
+                match e.committed {
+                    true => return Err(e),
+                    false => break,
+                }
+            }
+        };
+
+        let rhs = climb(input, right, atom, prefix, infix, postfix)?;
+        lhs = fold(lhs, rhs);
+    }
+
+    Ok(lhs)
+}
@@ -1,11 +1,21 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
+//! Parse tree tracing, enabled via the `PARSE_DEBUG` environment variable
+//! and limited to particular parsers via [`trace`](crate::trace).
+//!
+//! Parser activity is also reported to `log` or `tracing`, with the features
+//! of the same name, via [`trace`](crate::trace).
+//!
+//! Tracing requires the `std` feature. Without it, the entry and exit hooks
+//! called by generated code are no-ops.
+
+#[cfg(feature = "std")] use std::cell::RefCell;
+#[cfg(feature = "std")] use std::collections::HashMap;
 
 use crate::input::{Show, ParserInfo};
-use crate::macros::is_parse_debug;
 
+#[cfg(feature = "std")]
 type Index = usize;
 
+#[cfg(feature = "std")]
 struct Tree<T> {
     // All of the nodes in the tree live in this vector.
     nodes: Vec<T>,
@@ -18,6 +28,7 @@ struct Tree<T> {
     stack: Vec<Index>
 }
 
+#[cfg(feature = "std")]
 impl<T> Tree<T> {
     fn new() -> Tree<T> {
         Tree {
@@ -67,23 +78,27 @@ impl<T> Tree<T> {
     }
 }
 
+#[cfg(feature = "std")]
 struct Info {
     parser: ParserInfo,
     context: Option<String>,
     success: Option<bool>,
 }
 
+#[cfg(feature = "std")]
 impl Info {
     fn new(parser: ParserInfo) -> Info {
         Info { parser, context: None, success: None }
     }
 }
 
+#[cfg(feature = "std")]
 thread_local! {
     #[doc(hidden)]
     static PARSE_TREE: RefCell<Tree<Info>> = RefCell::new(Tree::new());
 }
 
+#[cfg(feature = "std")]
 fn debug_print(sibling_map: &mut Vec<bool>, node: Index) {
     let parent_count = sibling_map.len();
     for (i, &has_siblings) in sibling_map.iter().enumerate() {
@@ -128,7 +143,7 @@ fn debug_print(sibling_map: &mut Vec<bool>, node: Index) {
         }
 
         #[cfg(not(feature = "color"))]
-        println!("{}{} ({})", info.name, success, ctxt);
+        println!("{}{} ({})", info.parser.name, success, ctxt);
 
         let children = tree.get_children(node);
         let num_children = children.len();
@@ -141,17 +156,36 @@ fn debug_print(sibling_map: &mut Vec<bool>, node: Index) {
     });
 }
 
+/// Returns `true` if `parser` is traced: raw parsers only with
+/// `PARSE_DEBUG=full`, and only those matching any [trace
+/// filter](crate::trace).
+#[cfg(feature = "std")]
+fn is_traced(parser: &ParserInfo) -> bool {
+    let enabled = match parser.raw {
+        true => crate::is_parse_debug!("full"),
+        false => crate::is_parse_debug!(),
+    };
+
+    enabled && crate::trace::is_traced(parser)
+}
+
 // TODO: Take in &[&dyn Show] to display parser input parameters.
+#[cfg(feature = "std")]
 #[doc(hidden)]
 pub fn parser_entry(parser: &ParserInfo) {
-    if (parser.raw && is_parse_debug!("full")) || (!parser.raw && is_parse_debug!()) {
+    crate::export::enter(parser);
+    crate::trace::log_entry(parser);
+    if is_traced(parser) {
         PARSE_TREE.with(|key| key.borrow_mut().push(Info::new(*parser)));
     }
 }
 
+#[cfg(feature = "std")]
 #[doc(hidden)]
 pub fn parser_exit(parser: &ParserInfo, success: bool, ctxt: Option<&dyn Show>) {
-    if (parser.raw && is_parse_debug!("full")) || (!parser.raw && is_parse_debug!()) {
+    crate::export::exit(success);
+    crate::trace::log_exit(parser, success, ctxt);
+    if is_traced(parser) {
         let done = PARSE_TREE.with(|key| {
             let mut tree = key.borrow_mut();
             let index = tree.pop_level();
@@ -178,6 +212,30 @@ pub fn parser_exit(parser: &ParserInfo, success: bool, ctxt: Option<&dyn Show>)
     }
 }
 
+/// Returns `true` if the entry and exit hooks have anything to do besides
+/// `PARSE_DEBUG` tracing: if a call graph is being recorded or parser activity
+/// is being logged or captured. Always `false` without the `instrument`
+/// feature, so that parsers pay nothing for the checks.
+#[doc(hidden)]
+#[inline(always)]
+pub fn is_instrumented() -> bool {
+    #[cfg(feature = "instrument")]
+    return crate::export::is_recording() || crate::trace::is_tracing();
+
+    #[cfg(not(feature = "instrument"))]
+    return false;
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[inline(always)]
+pub fn parser_entry(_: &ParserInfo) { }
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[inline(always)]
+pub fn parser_exit(_: &ParserInfo, _: bool, _: Option<&dyn Show>) { }
+
 // FIXME: Remove the global state with a wrapping input like the one below.
 // Major caveat: the blanket Token impls in `input` prevent a blanket input
 // here.
@@ -0,0 +1,160 @@
+//! Emitting values back to text: the inverse of parsing.
+//!
+//! [`Emit`] writes a value as the text its parser reads. A type that
+//! implements both [`FromPearStr`] and `Emit` round-trips: parsing the text
+//! it emits yields the same value, which [`round_trips()`] checks. Since
+//! `Emit` writes one canonical form, text that parses to a value needn't be
+//! the text the value emits; [`reformat()`] parses text and emits it
+//! canonically, as a formatter would. Text that reformats to itself is in
+//! canonical form.
+//!
+//! `Emit` can be derived for AST types with [`#[derive(Emit)]`](crate::macros#deriveemit).
+//! A struct or variant emits its fields in order, or as laid out by a
+//! `#[emit("...")]` template, where `{name}` (or `{0}` for a tuple field)
+//! emits a field and `{{` and `}}` are literal braces. A field that's a
+//! sequence is emitted with `#[emit(separator = "...")]` between its items.
+//! A variant without fields must have a template.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::{parser, switch, Emit};
+//! use pear::{parsers::*, combinators::*};
+//! use pear::FromPearStr;
+//!
+//! #[derive(Debug, PartialEq, Emit)]
+//! enum Value {
+//!     #[emit("nil")]
+//!     Nil,
+//!     Int(u32),
+//!     #[emit("[{0}]")]
+//!     List(#[emit(separator = ",")] Vec<Value>),
+//! }
+//!
+//! impl FromPearStr for Value {
+//!     #[parser]
+//!     fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>> {
+//!         switch! {
+//!             eat_slice("nil") => Value::Nil,
+//!             peek('[') => Value::List(delimited_collect('[', Value::from_pear_str, ',', ']')?),
+//!             _ => Value::Int(u32::from_pear_str()?)
+//!         }
+//!     }
+//! }
+//!
+//! let value = Value::List(vec![Value::Int(1), Value::Nil]);
+//! assert_eq!(pear::emit::to_string(&value), "[1,nil]");
+//! assert!(pear::emit::round_trips(&value));
+//! assert_eq!(pear::emit::reformat::<Value>("[1,[],nil,]").unwrap(), "[1,[],nil]");
+//! ```
+//!
+//! `Emit` is implemented for `bool`, `char`, the primitive integer types,
+//! `str`, and `String`, which emit as they display, and for references,
+//! `Box`, `Option`, slices, and `Vec`. `None` emits nothing, and a slice or
+//! `Vec` emits its items without separators.
+
+use core::fmt;
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::input::{Text, Result};
+use crate::{FromPearStr, parse_str};
+
+/// A type that can be written back to text. See the [module
+/// documentation](self).
+pub trait Emit {
+    /// Writes `self` as text to `f`.
+    fn emit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// Displays a value by [emitting](Emit) it.
+pub struct Emitted<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Emit + ?Sized> fmt::Display for Emitted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.emit(f)
+    }
+}
+
+/// Emits the items of a sequence with a separator between each.
+pub struct Separated<'a, C: ?Sized>(pub &'a C, pub &'a str);
+
+impl<'a, C: ?Sized, T: Emit + 'a> Emit for Separated<'a, C>
+    where &'a C: IntoIterator<Item = &'a T>
+{
+    fn emit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, item) in self.0.into_iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.1)?;
+            }
+
+            item.emit(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Emits `value` to a string.
+pub fn to_string<T: Emit + ?Sized>(value: &T) -> String {
+    alloc::format!("{}", Emitted(value))
+}
+
+/// Returns `true` if parsing the text `value` emits yields `value`.
+pub fn round_trips<T: FromPearStr + Emit + PartialEq>(value: &T) -> bool {
+    parse_str::<T>(&to_string(value)).is_ok_and(|parsed| parsed == *value)
+}
+
+/// Parses all of `text` as a `T` and emits it, returning the canonical form
+/// of `text`.
+pub fn reformat<T: FromPearStr + Emit>(text: &str) -> Result<String, Text<'_>> {
+    parse_str::<T>(text).map(|value| to_string(&value))
+}
+
+macro_rules! impl_emit_with_display {
+    ($($T:ty),*) => ($(
+        impl Emit for $T {
+            #[inline(always)]
+            fn emit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+    )*)
+}
+
+impl_emit_with_display!(bool, char, str, String);
+impl_emit_with_display!(u8, u16, u32, u64, u128, usize);
+impl_emit_with_display!(i8, i16, i32, i64, i128, isize);
+
+impl<T: Emit + ?Sized> Emit for &T {
+    fn emit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::emit(self, f)
+    }
+}
+
+impl<T: Emit + ?Sized> Emit for Box<T> {
+    fn emit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::emit(self, f)
+    }
+}
+
+impl<T: Emit> Emit for Option<T> {
+    fn emit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Some(value) => value.emit(f),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Emit> Emit for [T] {
+    fn emit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Separated(self, "").emit(f)
+    }
+}
+
+impl<T: Emit> Emit for Vec<T> {
+    fn emit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().emit(f)
+    }
+}
@@ -1,50 +1,706 @@
-use crate::input::{Show, ParserInfo};
+use core::marker::PhantomData;
+use core::iter::FromIterator;
+use alloc::{vec, vec::Vec, boxed::Box, string::{String, ToString}, borrow::Cow};
+
+use crate::input::{Input, Show, ParserInfo};
 
 pub use crate::expected::Expected;
 
+/// A machine-readable classification of a [`ParseError`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// The input did not match the expected token(s) or slice(s).
+    Unexpected,
+    /// The input ended where more was expected.
+    UnexpectedEof,
+    /// Input remained where its end was expected.
+    TrailingInput,
+    /// A [`Fueled`](crate::input::Fueled) input ran out of fuel.
+    FuelExhausted,
+    /// An input that can grow ended before the parse could complete. See
+    /// [`ParseError::incomplete`].
+    Incomplete,
+    /// Any other, uncoded error.
+    Other,
+    /// A user-defined error code.
+    Custom(&'static str),
+}
+
+impl From<&'static str> for ErrorCode {
+    fn from(code: &'static str) -> Self {
+        ErrorCode::Custom(code)
+    }
+}
+
+impl core::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ErrorCode::Unexpected => write!(f, "unexpected"),
+            ErrorCode::UnexpectedEof => write!(f, "unexpected-eof"),
+            ErrorCode::TrailingInput => write!(f, "trailing-input"),
+            ErrorCode::FuelExhausted => write!(f, "fuel-exhausted"),
+            ErrorCode::Incomplete => write!(f, "incomplete"),
+            ErrorCode::Other => write!(f, "other"),
+            ErrorCode::Custom(code) => write!(f, "{}", code),
+        }
+    }
+}
+
+/// An error type that parsers and combinators can fail with.
+///
+/// By default, parsers fail with an [`Expected`]. A grammar may instead use
+/// its own error type `E` by returning a `Result<O, I, E>`; the combinators
+/// are generic over any `E: Error<I>`, constructing errors with
+/// [`Error::from_expected()`] and merging the errors of failed alternatives
+/// with [`Error::merge()`]. The `From` supertrait allows the built-in
+/// parsers, which fail with an `Expected`, to be called with `?` from such a
+/// grammar.
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use pear::input::{Input, Text};
+/// use pear::error::Expected;
+///
+/// #[derive(Debug)]
+/// enum ConfigError {
+///     Syntax(String),
+///     Invalid(Cow<'static, str>),
+/// }
+///
+/// impl<'a> From<Expected<char, &'a str>> for ConfigError {
+///     fn from(expected: Expected<char, &'a str>) -> Self {
+///         ConfigError::Syntax(expected.to_string())
+///     }
+/// }
+///
+/// impl<'a> pear::Error<Text<'a>> for ConfigError {
+///     fn from_context(message: Cow<'static, str>, _: Option<<Text<'a> as Input>::Context>) -> Self {
+///         ConfigError::Invalid(message)
+///     }
+/// }
+/// ```
+pub trait Error<I: Input>: From<Expected<I::Token, I::Slice>> {
+    /// Returns an error for a failure to find what was `expected`.
+    fn from_expected(expected: Expected<I::Token, I::Slice>) -> Self {
+        Self::from(expected)
+    }
+
+    /// Returns an error with a free-form `message` about the input spanned by
+    /// `context`, if known. Used for failures that are not about unexpected
+    /// tokens, such as a value that parsed but is invalid.
+    fn from_context(message: Cow<'static, str>, _context: Option<I::Context>) -> Self {
+        Self::from(Expected::Other(message))
+    }
+
+    /// Merges the error of a failed alternative, `other`, into this one. By
+    /// default, `self` is kept and `other` is discarded.
+    fn merge(self, _other: Self) -> Self {
+        self
+    }
+}
+
+impl<I: Input> Error<I> for Expected<I::Token, I::Slice> {
+    /// Merges the expected sets with [`Expected::merge()`].
+    fn merge(self, other: Self) -> Self {
+        Expected::merge(self, other)
+    }
+}
+
+/// An error that can be relabeled with a domain-level description of what was
+/// expected, as by [`ResultExt::expected()`](crate::result::ResultExt::expected()).
+pub trait Label {
+    /// Replaces what was expected with `label`.
+    fn label(self, label: String) -> Self;
+}
+
+impl<T, S> Label for Expected<T, S> {
+    /// Relabels the error with [`Expected::label()`].
+    fn label(self, label: String) -> Self {
+        Expected::label(self, label)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParseContext<C> {
     pub parser: ParserInfo,
     pub context: Option<C>,
 }
 
+/// How much more input an incomplete parse needs, as reported by
+/// [`Input::needed()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Needed {
+    /// An unknown amount.
+    Unknown,
+    /// At least this many more units of input, as measured by
+    /// [`Input::slice()`].
+    Size(usize),
+}
+
+/// A mechanically applicable fix for an error: replacing the input spanned by
+/// `context` with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion<C> {
+    /// The context spanning the input to replace.
+    pub context: C,
+    /// The text to replace it with.
+    pub replacement: Cow<'static, str>,
+}
+
+/// Additional information attached to a [`ParseError`] to help resolve it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hint<C> {
+    /// Advice on how to fix the error.
+    Help(Cow<'static, str>),
+    /// Background on why the error occurred.
+    Note(Cow<'static, str>),
+    /// A fix that can be applied mechanically.
+    Suggestion(Suggestion<C>),
+}
+
+impl<C> Hint<C> {
+    /// Converts the context of a suggestion with `f`, dropping the hint if `f`
+    /// returns `None`.
+    pub(crate) fn map_context<D, F>(self, f: F) -> Option<Hint<D>>
+        where F: FnOnce(C) -> Option<D>
+    {
+        Some(match self {
+            Hint::Help(help) => Hint::Help(help),
+            Hint::Note(note) => Hint::Note(note),
+            Hint::Suggestion(Suggestion { context, replacement }) => {
+                Hint::Suggestion(Suggestion { context: f(context)?, replacement })
+            }
+        })
+    }
+}
+
+/// The hints attached to a [`ParseError`], in the order they were attached.
+///
+/// As most errors are discarded during backtracking, and few carry hints,
+/// `Hints` is a boxed slice that allocates only once a hint is attached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hints<C>(Option<Box<[Hint<C>]>>);
+
+impl<C> Hints<C> {
+    /// Returns an empty list of hints.
+    pub const fn new() -> Self {
+        Hints(None)
+    }
+
+    /// Appends `hint`.
+    pub fn push(&mut self, hint: Hint<C>) {
+        let mut hints = self.0.take().map_or_else(Vec::new, Vec::from);
+        hints.push(hint);
+        self.0 = Some(hints.into_boxed_slice());
+    }
+}
+
+impl<C> Default for Hints<C> {
+    fn default() -> Self {
+        Hints::new()
+    }
+}
+
+impl<C> core::ops::Deref for Hints<C> {
+    type Target = [Hint<C>];
+
+    fn deref(&self) -> &[Hint<C>] {
+        self.0.as_deref().unwrap_or(&[])
+    }
+}
+
+impl<C> Extend<Hint<C>> for Hints<C> {
+    fn extend<T: IntoIterator<Item = Hint<C>>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|hint| self.push(hint))
+    }
+}
+
+impl<C> FromIterator<Hint<C>> for Hints<C> {
+    fn from_iter<T: IntoIterator<Item = Hint<C>>>(iter: T) -> Self {
+        let mut hints = Hints::new();
+        hints.extend(iter);
+        hints
+    }
+}
+
+impl<C> IntoIterator for Hints<C> {
+    type Item = Hint<C>;
+    type IntoIter = vec::IntoIter<Hint<C>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.map_or_else(Vec::new, Vec::from).into_iter()
+    }
+}
+
+impl<'a, C> IntoIterator for &'a Hints<C> {
+    type Item = &'a Hint<C>;
+    type IntoIter = core::slice::Iter<'a, Hint<C>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParseError<C, E> {
     pub error: E,
+    /// An explicitly assigned error code. When `None`, the code is derived from
+    /// `error`; see [`ParseError::code()`]. Few errors are assigned one, so it's
+    /// boxed to keep errors small.
+    pub code: Option<Box<ErrorCode>>,
+    /// Whether the error is committed: non-recoverable by alternatives. See
+    /// [`cut()`](crate::combinators::cut()).
+    pub committed: bool,
+    /// If the input ended before the parse could complete but may grow, how
+    /// much more of it is needed. An incomplete error is not a failure: the
+    /// parse may succeed once more input is available. Incomplete errors are
+    /// always committed, so alternatives aren't tried in their stead.
+    pub incomplete: Option<Needed>,
     pub contexts: Vec<ParseContext<C>>,
+    /// Help, notes, and suggestions for resolving the error, in the order they
+    /// were attached. See [`ParseError::with_help()`].
+    pub hints: Hints<C>,
 }
 
 impl<C, E> ParseError<C, E> {
     pub fn new(error: E) -> ParseError<C, E> {
         ParseError {
             error: error.into(),
-            contexts: vec![]
+            code: None,
+            committed: false,
+            incomplete: None,
+            contexts: vec![],
+            hints: Hints::new(),
         }
     }
 
+    /// Returns an incomplete error which needs `needed` more input: committed
+    /// and with the code [`ErrorCode::Incomplete`].
+    pub fn incomplete(error: E, needed: Needed) -> ParseError<C, E> {
+        let mut error = ParseError::new(error);
+        error.set_incomplete(needed);
+        error
+    }
+
+    /// Returns `true` if this error is [incomplete](ParseError::incomplete).
+    pub fn is_incomplete(&self) -> bool {
+        self.incomplete.is_some()
+    }
+
+    /// Marks this error incomplete, needing `needed` more input, and
+    /// committed, with the code [`ErrorCode::Incomplete`].
+    pub fn set_incomplete(&mut self, needed: Needed) {
+        self.incomplete = Some(needed);
+        self.code = Some(Box::new(ErrorCode::Incomplete));
+        self.committed = true;
+    }
+
+    /// Marks this error as committed. A committed error is not recovered from
+    /// by combinators that try alternatives, such as `switch!`, but is instead
+    /// propagated to the caller.
+    pub fn commit(mut self) -> Self {
+        self.committed = true;
+        self
+    }
+
+    /// Assigns the error code `code` to this error.
+    pub fn with_code<K: Into<ErrorCode>>(mut self, code: K) -> Self {
+        self.code = Some(Box::new(code.into()));
+        self
+    }
+
+    /// Attaches the help message `help`: advice on how to fix the error.
+    ///
+    /// ```rust
+    /// use pear::input::{Text, ParseError};
+    /// use pear::parsers::*;
+    ///
+    /// let mut input = Text::from("'pear'");
+    /// let error = eat(&mut input, '"')
+    ///     .map_err(|e| e.with_help("strings are quoted with `\"`"))
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.help().collect::<Vec<_>>(), ["strings are quoted with `\"`"]);
+    /// assert!(error.to_string().ends_with("= help: strings are quoted with `\"`"));
+    /// ```
+    pub fn with_help<H: Into<Cow<'static, str>>>(mut self, help: H) -> Self {
+        self.hints.push(Hint::Help(help.into()));
+        self
+    }
+
+    /// Attaches the note `note`: background on why the error occurred.
+    pub fn with_note<N: Into<Cow<'static, str>>>(mut self, note: N) -> Self {
+        self.hints.push(Hint::Note(note.into()));
+        self
+    }
+
+    /// Attaches a suggestion to replace the input spanned by `context` with
+    /// `replacement`.
+    pub fn with_suggestion<R>(mut self, context: C, replacement: R) -> Self
+        where R: Into<Cow<'static, str>>
+    {
+        let replacement = replacement.into();
+        self.hints.push(Hint::Suggestion(Suggestion { context, replacement }));
+        self
+    }
+
+    /// Returns the attached help messages, in order.
+    pub fn help(&self) -> impl Iterator<Item = &str> {
+        self.hints.iter().filter_map(|hint| match hint {
+            Hint::Help(help) => Some(&**help),
+            _ => None
+        })
+    }
+
+    /// Returns the attached notes, in order.
+    pub fn notes(&self) -> impl Iterator<Item = &str> {
+        self.hints.iter().filter_map(|hint| match hint {
+            Hint::Note(note) => Some(&**note),
+            _ => None
+        })
+    }
+
+    /// Returns the attached suggestions, in order.
+    pub fn suggestions(&self) -> impl Iterator<Item = &Suggestion<C>> {
+        self.hints.iter().filter_map(|hint| match hint {
+            Hint::Suggestion(suggestion) => Some(suggestion),
+            _ => None
+        })
+    }
+
+    /// Returns the stack of parsers that were executing when the error
+    /// occurred, innermost first.
+    pub fn parsers(&self) -> impl Iterator<Item = &ParserInfo> {
+        self.contexts.iter().map(|c| &c.parser)
+    }
+
     pub fn push_context(&mut self, context: Option<C>, parser: ParserInfo) {
         self.contexts.push(ParseContext { context, parser })
     }
 
+    /// Converts this error into one with error `E2`, as [`into()`](Self::into())
+    /// does. Called by `?` in `#[parser]` functions, taking precedence over
+    /// [`IntoParseError`], which converts foreign errors.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn into_parse_error<I, E2>(self, _: &mut I, _: &I::Marker) -> ParseError<C, E2>
+        where I: Input<Context = C>, E2: From<E>
+    {
+        self.into()
+    }
+
     #[inline(always)]
     pub fn into<E2: From<E>>(self) -> ParseError<C, E2> {
         ParseError {
             error: self.error.into(),
+            code: self.code,
+            committed: self.committed,
+            incomplete: self.incomplete,
             contexts: self.contexts,
+            hints: self.hints,
         }
     }
 }
 
-impl<C: Show, E: std::fmt::Display> std::fmt::Display for ParseError<C, E> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.error)?;
+/// Conversion of a foreign error, one that isn't a [`ParseError`], into a
+/// `ParseError`.
+///
+/// In a `#[parser]` function, `?` converts the error of any expression that
+/// isn't a parser call with this trait, so that results from outside of Pear
+/// can be propagated directly. A foreign error becomes an error built with
+/// [`Error::from_context()`], its message the foreign error's `Display`, about
+/// the input consumed by the function so far.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// #[parser]
+/// fn byte<'a>(input: &mut Text<'a>) -> Result<u8, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u8>()?
+/// }
+///
+/// assert_eq!(parse!(byte: &mut Text::from("255")).unwrap(), 255);
+///
+/// let error = parse!(byte: &mut Text::from("256")).unwrap_err();
+/// assert!(error.to_string().starts_with("number too large"));
+/// ```
+pub trait IntoParseError<I: Input, E> {
+    /// Converts `self` into a `ParseError` about the input consumed since
+    /// `mark`.
+    fn into_parse_error(self, input: &mut I, mark: &I::Marker) -> ParseError<I::Context, E>;
+}
+
+impl<I: Input, E: Error<I>, T: core::fmt::Display> IntoParseError<I, E> for T {
+    fn into_parse_error(self, input: &mut I, mark: &I::Marker) -> ParseError<I::Context, E> {
+        let context = input.context(mark);
+        ParseError::new(E::from_context(self.to_string().into(), context))
+    }
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static QUIET: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+/// Returns `true` while errors are suppressed by
+/// [`opt()`](crate::combinators::opt()): nothing reads them, so parsers skip
+/// rendering what they expected and recording contexts.
+#[inline(always)]
+pub(crate) fn is_quiet() -> bool {
+    #[cfg(feature = "std")]
+    return QUIET.with(|q| q.get() > 0);
+
+    #[cfg(not(feature = "std"))]
+    return false;
+}
+
+/// Runs `f` with errors suppressed. See [`is_quiet()`].
+pub(crate) fn quietly<R, F: FnOnce() -> R>(f: F) -> R {
+    #[cfg(feature = "std")]
+    struct Quiet;
+
+    #[cfg(feature = "std")]
+    impl Drop for Quiet {
+        fn drop(&mut self) {
+            QUIET.with(|q| q.set(q.get() - 1));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    let _quiet = {
+        QUIET.with(|q| q.set(q.get() + 1));
+        Quiet
+    };
+
+    f()
+}
+
+/// Records the context of `parser`, which started at `mark` and failed with
+/// `error`, and marks `error` committed if `commit`. If the input is
+/// [halted](Input::halted()), `error` is committed and given the halting code.
+/// If the input reports that it [needs](Input::needed()) more input, `error`
+/// is marked incomplete. The
+/// context isn't recorded while errors are suppressed by
+/// [`opt()`](crate::combinators::opt()).
+///
+/// Generated by `#[parser]`; not intended to be called directly. Kept out of
+/// line so that each parser's success path stays small.
+#[doc(hidden)]
+#[cold]
+#[inline(never)]
+pub fn push_failure<I: Input, E>(
+    input: &mut I,
+    error: &mut ParseError<I::Context, E>,
+    mark: &I::Marker,
+    parser: ParserInfo,
+    commit: bool
+) {
+    if !is_quiet() {
+        let context = input.context(mark);
+        error.push_context(context, parser);
+    }
+
+    error.committed |= commit;
+    if let Some(code) = input.halted() {
+        error.code = Some(Box::new(code));
+        error.committed = true;
+    }
+
+    if error.incomplete.is_none() {
+        if let Some(needed) = input.needed() {
+            error.set_incomplete(needed);
+        }
+    }
+}
+
+impl<C, T, S> ParseError<C, Expected<T, S>> {
+    /// Returns the code of this error: the explicitly assigned code, if any,
+    /// or else the code derived from the kind of error.
+    pub fn code(&self) -> ErrorCode {
+        self.code.as_deref().copied().unwrap_or_else(|| self.error.code())
+    }
+
+    /// Returns the rendered tokens or slices that were expected, if known.
+    pub fn expected(&self) -> Vec<&str> {
+        self.error.expected()
+    }
+
+    /// Returns the token that was found instead of what was expected, if any.
+    pub fn found(&self) -> Option<&T> {
+        self.error.found_token()
+    }
+
+    /// Replaces what was expected with `label`. See [`Expected::label()`].
+    pub fn label<L: Into<String>>(mut self, label: L) -> Self {
+        self.error = self.error.label(label);
+        self
+    }
+
+    /// Merges the error of a failed alternative into this one.
+    ///
+    /// The expected sets are merged with [`Expected::merge()`], and `self`'s
+    /// contexts are retained. The explicit code is kept only if both errors
+    /// agree on it. The hints of both are kept, `self`'s first.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.hints.extend(other.hints);
+        ParseError {
+            error: self.error.merge(other.error),
+            code: if self.code == other.code { self.code } else { None },
+            committed: self.committed || other.committed,
+            incomplete: self.incomplete.or(other.incomplete),
+            contexts: self.contexts,
+            hints: self.hints,
+        }
+    }
+}
+
+/// A builder for custom [`ParseError`]s.
+///
+/// ```rust
+/// use pear::error::{ErrorBuilder, ErrorCode};
+/// use pear::input::{ParseError, Text};
+///
+/// let error: ParseError<Text<'_>> = ErrorBuilder::new()
+///     .code("E0012")
+///     .expected("a digit")
+///     .expected("a sign")
+///     .found('x')
+///     .build();
+///
+/// assert_eq!(error.code(), ErrorCode::Custom("E0012"));
+/// assert_eq!(error.expected(), vec!["a digit", "a sign"]);
+/// assert_eq!(error.found(), Some(&'x'));
+/// assert_eq!(error.to_string(), "[E0012] expected one of a digit, a sign but found `x`");
+/// ```
+pub struct ErrorBuilder<T, S> {
+    code: Option<ErrorCode>,
+    expected: Vec<String>,
+    found: Option<T>,
+    message: Option<Cow<'static, str>>,
+    _slice: PhantomData<S>,
+}
+
+impl<T, S> ErrorBuilder<T, S> {
+    /// Creates a new builder for an error with no code, expectations, or
+    /// message.
+    pub fn new() -> Self {
+        ErrorBuilder {
+            code: None,
+            expected: vec![],
+            found: None,
+            message: None,
+            _slice: PhantomData,
+        }
+    }
+
+    /// Sets the error code.
+    pub fn code<K: Into<ErrorCode>>(mut self, code: K) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Adds `expected` to the set of expected items.
+    pub fn expected<E: Into<String>>(mut self, expected: E) -> Self {
+        self.expected.push(expected.into());
+        self
+    }
+
+    /// Sets the token that was found.
+    pub fn found(mut self, token: T) -> Self {
+        self.found = Some(token);
+        self
+    }
+
+    /// Sets a free-form message. A message takes precedence over any expected
+    /// items and found token when rendering the error.
+    pub fn message<M: Into<Cow<'static, str>>>(mut self, message: M) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Builds the error.
+    pub fn build<C>(self) -> ParseError<C, Expected<T, S>> {
+        let error = match (self.message, self.expected.len()) {
+            (Some(message), _) => Expected::Other(message),
+            (None, 0) => Expected::Token(None, self.found),
+            (None, 1) => Expected::Token(self.expected.into_iter().next(), self.found),
+            (None, _) => Expected::OneOf(self.expected, self.found),
+        };
+
+        ParseError {
+            error,
+            code: self.code.map(Box::new),
+            committed: false,
+            incomplete: None,
+            contexts: vec![],
+            hints: Hints::new(),
+        }
+    }
+}
+
+impl<T, S> Default for ErrorBuilder<T, S> {
+    fn default() -> Self {
+        ErrorBuilder::new()
+    }
+}
+
+/// Renders the error followed by one line per context: `[code] error`, then
+/// ` + parser at context` for each parser that failed, innermost first, and
+/// finally one line per hint: ` = help: message`, ` = note: message`, or
+/// ` = suggestion: replace context with `replacement``.
+///
+/// The alternate form, `{:#}`, is structured: the error is rendered in its
+/// alternate form, which for [`Expected`] lists what was expected and found
+/// on their own lines, and each context reads `in parser at context`.
+impl<C: Show, E: core::fmt::Display> core::fmt::Display for ParseError<C, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(ErrorCode::Custom(code)) = self.code.as_deref() {
+            write!(f, "[{}] ", code)?;
+        }
+
+        if f.alternate() {
+            write!(f, "{:#}", self.error)?;
+        } else {
+            write!(f, "{}", self.error)?;
+        }
+
         for ctxt in &self.contexts {
-            write!(f, "\n + {}", ctxt.parser.name)?;
+            match f.alternate() {
+                true => write!(f, "\n  in {}", ctxt.parser.name)?,
+                false => write!(f, "\n + {}", ctxt.parser.name)?,
+            }
+
             if let Some(ctxt) = &ctxt.context {
                 write!(f, " at {}", ctxt as &dyn Show)?;
             }
         }
 
+        for hint in &self.hints {
+            match hint {
+                Hint::Help(help) => write!(f, "\n = help: {}", help)?,
+                Hint::Note(note) => write!(f, "\n = note: {}", note)?,
+                Hint::Suggestion(Suggestion { context, replacement }) => {
+                    let context = context as &dyn Show;
+                    write!(f, "\n = suggestion: replace {} with `{}`", context, replacement)?
+                }
+            }
+        }
+
         Ok(())
     }
 }
+
+/// The error's [`source()`](std::error::Error::source()) is that of `E`: `E`
+/// itself is already part of the error's `Display`.
+#[cfg(feature = "std")]
+impl<C, E> std::error::Error for ParseError<C, E>
+    where C: Show + core::fmt::Debug, E: std::error::Error + 'static
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
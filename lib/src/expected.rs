@@ -1,7 +1,8 @@
-use std::fmt;
-use std::borrow::Cow;
+use core::fmt;
+use alloc::{vec, vec::Vec, string::String, borrow::{Cow, ToOwned}};
 
 use crate::input::Show;
+use crate::error::ErrorCode;
 
 pub enum Expected<Token, Slice> {
     // Token(Option<I::Token>, Option<I::Token>),
@@ -9,9 +10,102 @@ pub enum Expected<Token, Slice> {
     Token(Option<String>, Option<Token>),
     Slice(Option<String>, Option<Slice>),
     Eof(Option<Token>),
+    /// One of several alternatives was expected. Produced by merging the
+    /// errors of alternatives with [`Expected::merge()`].
+    OneOf(Vec<String>, Option<Token>),
     Other(Cow<'static, str>),
 }
 
+impl<T, S> Expected<T, S> {
+    /// Returns the error code corresponding to this kind of error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Expected::Token(_, None) | Expected::Slice(_, None) => ErrorCode::UnexpectedEof,
+            Expected::OneOf(_, None) => ErrorCode::UnexpectedEof,
+            Expected::Token(..) | Expected::Slice(..) | Expected::OneOf(..) => {
+                ErrorCode::Unexpected
+            }
+            Expected::Eof(_) => ErrorCode::TrailingInput,
+            Expected::Other(_) => ErrorCode::Other,
+        }
+    }
+
+    /// Returns the rendered tokens or slices that were expected, if known.
+    pub fn expected(&self) -> Vec<&str> {
+        match self {
+            Expected::Token(Some(e), _) | Expected::Slice(Some(e), _) => vec![e],
+            Expected::OneOf(set, _) => set.iter().map(|s| s.as_str()).collect(),
+            Expected::Eof(_) => vec!["EOF"],
+            _ => vec![],
+        }
+    }
+
+    /// Returns the token that was found instead of what was expected, if any.
+    pub fn found_token(&self) -> Option<&T> {
+        match self {
+            Expected::Token(_, found) | Expected::Eof(found) | Expected::OneOf(_, found) => {
+                found.as_ref()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the slice that was found instead of what was expected, if any.
+    pub fn found_slice(&self) -> Option<&S> {
+        match self {
+            Expected::Slice(_, found) => found.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Replaces what was expected with `label`, keeping what was found.
+    ///
+    /// An [`Expected::Other`] is returned unchanged: its message is already
+    /// more specific than a label.
+    pub fn label<L: Into<String>>(self, label: L) -> Self {
+        match self {
+            Expected::Token(_, found) | Expected::Eof(found) | Expected::OneOf(_, found) => {
+                Expected::OneOf(vec![label.into()], found)
+            }
+            Expected::Slice(_, found) => Expected::Slice(Some(label.into()), found),
+            Expected::Other(message) => Expected::Other(message),
+        }
+    }
+
+    /// Merges the expectations of two failed alternatives into one.
+    ///
+    /// If both `self` and `other` know what was expected, the result is a
+    /// [`Expected::OneOf`] containing the union of their expected sets, in
+    /// order and without duplicates, and the first found token of the two.
+    /// Otherwise, the result is whichever of the two knows what was expected,
+    /// preferring `self`.
+    pub fn merge(self, other: Self) -> Self {
+        let (ours, theirs) = (self.expected(), other.expected());
+        if theirs.is_empty() {
+            return self;
+        } else if ours.is_empty() {
+            return other;
+        }
+
+        let mut set: Vec<String> = ours.into_iter().map(String::from).collect();
+        for expected in theirs {
+            if !set.iter().any(|e| e == expected) {
+                set.push(expected.into());
+            }
+        }
+
+        let found = match self {
+            Expected::Token(_, t) | Expected::Eof(t) | Expected::OneOf(_, t) if t.is_some() => t,
+            _ => match other {
+                Expected::Token(_, t) | Expected::Eof(t) | Expected::OneOf(_, t) => t,
+                _ => None,
+            }
+        };
+
+        Expected::OneOf(set, found)
+    }
+}
+
 impl<T: ToOwned, S: ?Sized + ToOwned> Expected<T, &S> {
     pub fn into_owned(self) -> Expected<T::Owned, S::Owned> {
         use Expected::*;
@@ -20,6 +114,7 @@ impl<T: ToOwned, S: ?Sized + ToOwned> Expected<T, &S> {
             Token(e, v) => Token(e, v.map(|v| v.to_owned())),
             Slice(e, v) => Slice(e, v.map(|v| v.to_owned())),
             Eof(v) => Eof(v.map(|v| v.to_owned())),
+            OneOf(e, v) => OneOf(e, v.map(|v| v.to_owned())),
             Other(v) => Other(v),
         }
     }
@@ -49,6 +144,9 @@ impl<T: fmt::Debug, S: fmt::Debug> fmt::Debug for Expected<T, S> {
             Expected::Eof(v) => {
                 f.debug_tuple("Expected::Eof").field(&v).finish()
             }
+            Expected::OneOf(e, v) => {
+                f.debug_tuple("Expected::OneOf").field(&e).field(&v).finish()
+            }
             Expected::Other(v) => {
                 f.debug_tuple("Expected::Other").field(&v).finish()
             }
@@ -62,13 +160,38 @@ impl<T: Clone, S: Clone> Clone for Expected<T, S> {
             Expected::Token(e, f) => Expected::Token(e.clone(), f.clone()),
             Expected::Slice(e, f) => Expected::Slice(e.clone(), f.clone()),
             Expected::Eof(f) => Expected::Eof(f.clone()),
+            Expected::OneOf(e, f) => Expected::OneOf(e.clone(), f.clone()),
             Expected::Other(v) => Expected::Other(v.clone())
         }
     }
 }
 
+/// The alternate form, `{:#}`, follows the message with an `expected:` line
+/// listing what was expected and a `found:` line with what was found instead,
+/// when known.
 impl<T: Show, S: Show> fmt::Display for Expected<T, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_message(f)?;
+        if !f.alternate() {
+            return Ok(());
+        }
+
+        let expected = self.expected();
+        if !expected.is_empty() {
+            write!(f, "\n  expected: {}", expected.join(", "))?;
+        }
+
+        match (self.found_token(), self.found_slice()) {
+            (Some(token), _) => write!(f, "\n  found: {}", token as &dyn Show),
+            (_, Some(slice)) => write!(f, "\n  found: {}", slice as &dyn Show),
+            _ if self.code() == ErrorCode::UnexpectedEof => write!(f, "\n  found: EOF"),
+            _ => Ok(())
+        }
+    }
+}
+
+impl<T: Show, S: Show> Expected<T, S> {
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Expected::Token(Some(ref expected), Some(ref found)) => {
                 let found = found as &dyn Show;
@@ -105,6 +228,25 @@ impl<T: Show, S: Show> fmt::Display for Expected<T, S> {
                 let found = found as &dyn Show;
                 write!(f, "expected EOF but found {}", found)
             }
+            Expected::OneOf(ref expected, ref found) => {
+                if expected.len() != 1 {
+                    write!(f, "expected one of ")?;
+                } else {
+                    write!(f, "expected ")?;
+                }
+
+                for (i, e) in expected.iter().enumerate() {
+                    match i {
+                        0 => write!(f, "{}", e)?,
+                        _ => write!(f, ", {}", e)?,
+                    }
+                }
+
+                match found {
+                    Some(found) => write!(f, " but found {}", found as &dyn Show),
+                    None => write!(f, " but none was found"),
+                }
+            }
             Expected::Other(ref other) => {
                 write!(f, "{}", other)
             }
@@ -112,6 +254,9 @@ impl<T: Show, S: Show> fmt::Display for Expected<T, S> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<T: Show + fmt::Debug, S: Show + fmt::Debug> std::error::Error for Expected<T, S> { }
+
 #[cfg(test)]
 mod tests {
     use super::Expected;
@@ -121,4 +266,18 @@ mod tests {
         let expected: Expected<char, &str> = Expected::Slice(None, Some("hi"));
         let _owned: Expected<char, String> = expected.into_owned();
     }
+
+    #[test]
+    fn test_merge() {
+        let a: Expected<char, &str> = Expected::Token(Some("'a'".into()), Some('c'));
+        let b = Expected::Slice(Some("\"bc\"".into()), Some("cd"));
+        let c = Expected::Token(Some("'a'".into()), Some('c'));
+        let merged = a.merge(b).merge(c);
+        assert_eq!(merged.expected(), vec!["'a'", "\"bc\""]);
+        assert_eq!(merged.found_token(), Some(&'c'));
+
+        let other: Expected<char, &str> = Expected::Other("nope".into());
+        let merged = other.merge(Expected::Eof(None));
+        assert_eq!(merged.expected(), vec!["EOF"]);
+    }
 }
@@ -0,0 +1,446 @@
+//! Exports the grammar exercised by a parse as railroad diagrams or GraphViz.
+//!
+//! While recording is enabled via [`record()`], the same parser entry and exit
+//! hooks that drive `PARSE_DEBUG` tracing build a [`CallGraph`]: for every
+//! parser that succeeds, the distinct sequences of parsers it successfully
+//! called. Raw parsers, like [`eat()`](crate::parsers::eat()), are the
+//! terminals of the grammar; all other parsers are its rules. The graph thus
+//! contains every rule reachable from the entry parser _on the recorded
+//! input_: recording a few representative inputs, and [merging](CallGraph::merge())
+//! the results, gives a fuller picture of the grammar.
+//!
+//! A graph is rendered with [`CallGraph::to_svg()`], as one railroad diagram
+//! per rule, or with [`CallGraph::to_dot()`], as a GraphViz digraph.
+//!
+//! Recording requires the `instrument` feature. Without it, parsers don't
+//! check whether they're being recorded, and [`record()`] isn't available.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::{parser, parse};
+//! use pear::parsers::*;
+//!
+//! #[parser]
+//! fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+//!     take_some_while(|c: &char| c.is_alphabetic())?
+//! }
+//!
+//! #[parser]
+//! fn pair<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+//!     let key = word()?;
+//!     eat('=')?;
+//!     (key, word()?)
+//! }
+//!
+//! let (result, graph) = pear::export::record(|| parse!(pair: &mut Text::from("a=b")));
+//! assert!(result.is_ok());
+//! assert_eq!(graph.entry(), Some("pair"));
+//! assert!(graph.to_dot().contains("\"pair\" -> \"word\";"));
+//! assert!(graph.to_svg().starts_with("<svg"));
+//! ```
+
+#[cfg(feature = "std")] use std::cell::RefCell;
+use alloc::{vec, format, vec::Vec, string::String, boxed::Box};
+use core::fmt::Write;
+
+#[cfg(feature = "std")] use crate::input::ParserInfo;
+
+/// A call made by a rule, or a run of identical consecutive calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// The name of the parser called.
+    pub parser: &'static str,
+    /// Whether the parser is raw, and thus a terminal of the grammar.
+    pub terminal: bool,
+    /// Whether the parser was called one or more times in a row.
+    pub repeated: bool,
+}
+
+/// A rule of a grammar: a non-raw parser and the ways it succeeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// The name of the parser.
+    pub name: &'static str,
+    /// The distinct sequences of successful calls the parser made, in the
+    /// order they were first seen.
+    pub alternatives: Vec<Vec<Step>>,
+}
+
+/// The rules exercised by a parse, as recorded by [`record()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallGraph {
+    entry: Option<&'static str>,
+    rules: Vec<Rule>,
+}
+
+impl CallGraph {
+    /// Returns the name of the entry parser, if any parser ran.
+    pub fn entry(&self) -> Option<&'static str> {
+        self.entry
+    }
+
+    /// Returns the rules in the graph, in breadth-first order from the entry.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Returns the rule for the parser named `name`, if there is one.
+    pub fn rule(&self, name: &str) -> Option<&Rule> {
+        self.rules.iter().find(|rule| rule.name == name)
+    }
+
+    /// Returns `true` if no rule succeeded while recording.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Adds the rules and alternatives of `other` to `self`. The entry of
+    /// `self`, if it has one, is kept.
+    pub fn merge(&mut self, other: CallGraph) {
+        self.entry = self.entry.or(other.entry);
+        for rule in other.rules {
+            for steps in rule.alternatives {
+                self.add(rule.name, steps);
+            }
+        }
+
+        self.sort();
+    }
+
+    /// Renders the graph as a GraphViz digraph with an edge from every rule to
+    /// every parser it calls. Rules are drawn as boxes, the entry in bold, and
+    /// terminals as ellipses.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph grammar {\n");
+        for rule in &self.rules {
+            let style = match Some(rule.name) == self.entry {
+                true => ", style=bold",
+                false => "",
+            };
+
+            let _ = writeln!(dot, "    \"{}\" [shape=box{}];", rule.name, style);
+        }
+
+        let mut terminals: Vec<&str> = vec![];
+        let mut edges: Vec<(&str, &str)> = vec![];
+        for rule in &self.rules {
+            for step in rule.alternatives.iter().flatten() {
+                if step.terminal && !terminals.contains(&step.parser) {
+                    terminals.push(step.parser);
+                    let _ = writeln!(dot, "    \"{}\" [shape=ellipse];", step.parser);
+                }
+
+                if !edges.contains(&(rule.name, step.parser)) {
+                    edges.push((rule.name, step.parser));
+                }
+            }
+        }
+
+        for (from, to) in edges {
+            let _ = writeln!(dot, "    \"{}\" -> \"{}\";", from, to);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph as an SVG document with a railroad diagram for every
+    /// rule: a choice between its alternatives, each a sequence of calls.
+    /// Terminals are drawn as rounded boxes and rules as square ones. Repeated
+    /// calls loop back on themselves.
+    pub fn to_svg(&self) -> String {
+        let diagrams: Vec<_> = self.rules.iter()
+            .map(|rule| (rule.name, Node::rule(rule)))
+            .collect();
+
+        let width = diagrams.iter()
+            .map(|(_, node)| node.size().width + 2 * (PAD + GAP))
+            .max()
+            .unwrap_or(2 * PAD);
+
+        let mut body = String::new();
+        let mut top = PAD;
+        for (name, node) in &diagrams {
+            let size = node.size();
+            let y = top + TITLE + size.up;
+            let end = PAD + GAP + size.width;
+            let _ = write!(body, "<text class=\"title\" x=\"{}\" y=\"{}\">{}</text>",
+                PAD, top + 14, escape(name));
+
+            path(&mut body, format!("M{} {}v{}m0 {}h{}", PAD, y - 8, 16, -8, GAP));
+            node.draw(&mut body, PAD + GAP, y);
+            path(&mut body, format!("M{} {}h{}m0 {}v{}", end, y, GAP, -8, 16));
+            top = y + size.down + 2 * GAP;
+        }
+
+        let mut svg = String::new();
+        let _ = write!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" \
+            height=\"{1}\" viewBox=\"0 0 {0} {1}\">", width, top);
+        svg.push_str("<style>path, rect { fill: none; stroke: black; } \
+            text { font: 13px monospace; text-anchor: middle; } \
+            text.title { font-weight: bold; text-anchor: start; }</style>");
+        svg.push_str(&body);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Adds `steps` as an alternative of the rule `name` if it isn't one yet.
+    fn add(&mut self, name: &'static str, steps: Vec<Step>) {
+        let index = match self.rules.iter().position(|rule| rule.name == name) {
+            Some(index) => index,
+            None => {
+                self.rules.push(Rule { name, alternatives: vec![] });
+                self.rules.len() - 1
+            }
+        };
+
+        let rule = &mut self.rules[index];
+        if !rule.alternatives.contains(&steps) {
+            rule.alternatives.push(steps);
+        }
+    }
+
+    /// Sorts the rules in breadth-first order from the entry. Rules that
+    /// aren't reachable from the entry keep their relative order at the end.
+    fn sort(&mut self) {
+        let mut order: Vec<&'static str> = self.entry.into_iter().collect();
+        let mut i = 0;
+        while i < order.len() {
+            if let Some(rule) = self.rule(order[i]) {
+                for step in rule.alternatives.iter().flatten() {
+                    if !step.terminal && !order.contains(&step.parser) {
+                        order.push(step.parser);
+                    }
+                }
+            }
+
+            i += 1;
+        }
+
+        self.rules.sort_by_key(|rule| {
+            order.iter().position(|name| *name == rule.name).unwrap_or(order.len())
+        });
+    }
+}
+
+/// A parser that is running while recording.
+#[cfg(feature = "std")]
+struct Frame {
+    info: ParserInfo,
+    steps: Vec<Step>,
+}
+
+#[cfg(feature = "std")]
+struct Recording {
+    graph: CallGraph,
+    stack: Vec<Frame>,
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with call graph recording enabled, returning the result of `f`
+/// and the graph of the rules that succeeded while it ran.
+///
+/// Calls may be nested; an inner call receives only the rules that ran while
+/// it was running.
+#[cfg(feature = "instrument")]
+pub fn record<R, F: FnOnce() -> R>(f: F) -> (R, CallGraph) {
+    let recording = Recording { graph: CallGraph::default(), stack: vec![] };
+    let outer = RECORDING.with(|r| r.borrow_mut().replace(recording));
+    let result = f();
+    let recording = RECORDING.with(|r| std::mem::replace(&mut *r.borrow_mut(), outer));
+    let mut graph = recording.map(|r| r.graph).unwrap_or_default();
+    graph.sort();
+    (result, graph)
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn is_recording() -> bool {
+    #[cfg(feature = "instrument")]
+    return RECORDING.with(|r| r.borrow().is_some());
+
+    #[cfg(not(feature = "instrument"))]
+    return false;
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn enter(parser: &ParserInfo) {
+    RECORDING.with(|r| {
+        if let Some(ref mut recording) = *r.borrow_mut() {
+            if recording.stack.is_empty() && !parser.raw {
+                recording.graph.entry.get_or_insert(parser.name);
+            }
+
+            recording.stack.push(Frame { info: *parser, steps: vec![] });
+        }
+    });
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn exit(success: bool) {
+    RECORDING.with(|r| {
+        let mut r = r.borrow_mut();
+        let recording = match *r {
+            Some(ref mut recording) => recording,
+            None => return,
+        };
+
+        let frame = match recording.stack.pop() {
+            Some(frame) if success => frame,
+            _ => return,
+        };
+
+        let (name, terminal) = (frame.info.name, frame.info.raw);
+        if !terminal {
+            recording.graph.add(name, frame.steps);
+        }
+
+        if let Some(parent) = recording.stack.last_mut().filter(|f| !f.info.raw) {
+            match parent.steps.last_mut() {
+                Some(last) if last.parser == name => last.repeated = true,
+                _ => parent.steps.push(Step { parser: name, terminal, repeated: false }),
+            }
+        }
+    });
+}
+
+/// Padding around diagrams, the gap between elements, and the height of a
+/// diagram's title.
+const PAD: usize = 10;
+const GAP: usize = 10;
+const TITLE: usize = 30;
+
+/// Half the height of a box.
+const HALF: usize = 11;
+
+/// An element of a railroad diagram. The track enters an element on its left
+/// and leaves it on its right, at the same height.
+enum Node {
+    Box { name: &'static str, terminal: bool },
+    Sequence(Vec<Node>),
+    Choice(Vec<Node>),
+    Repeat(Box<Node>),
+}
+
+/// The size of a node: its width, and its extent above and below the track.
+struct Size {
+    width: usize,
+    up: usize,
+    down: usize,
+}
+
+impl Node {
+    fn rule(rule: &Rule) -> Node {
+        let mut alternatives: Vec<_> = rule.alternatives.iter()
+            .map(|steps| Node::Sequence(steps.iter().map(|step| {
+                let node = Node::Box { name: step.parser, terminal: step.terminal };
+                match step.repeated {
+                    true => Node::Repeat(Box::new(node)),
+                    false => node,
+                }
+            }).collect()))
+            .collect();
+
+        match alternatives.len() {
+            1 => alternatives.remove(0),
+            _ => Node::Choice(alternatives),
+        }
+    }
+
+    fn size(&self) -> Size {
+        match self {
+            Node::Box { name, .. } => Size { width: 8 * name.len() + 20, up: HALF, down: HALF },
+            Node::Sequence(nodes) => nodes.iter().map(Node::size).fold(
+                Size { width: 0, up: 0, down: 0 },
+                |acc, size| Size {
+                    width: acc.width + size.width + if acc.width > 0 { GAP } else { 0 },
+                    up: acc.up.max(size.up),
+                    down: acc.down.max(size.down),
+                }),
+            Node::Repeat(node) => {
+                let size = node.size();
+                Size { width: size.width + 2 * GAP, up: size.up, down: size.down + GAP }
+            }
+            Node::Choice(nodes) => {
+                let sizes: Vec<_> = nodes.iter().map(Node::size).collect();
+                Size {
+                    width: sizes.iter().map(|s| s.width).max().unwrap_or(0) + 4 * GAP,
+                    up: sizes.first().map_or(0, |s| s.up),
+                    down: sizes.first().map_or(0, |s| s.down) + sizes.iter().skip(1)
+                        .map(|s| GAP + s.up + s.down)
+                        .sum::<usize>(),
+                }
+            }
+        }
+    }
+
+    /// Draws the node into `svg` with its track entering at `(x, y)`.
+    fn draw(&self, svg: &mut String, x: usize, y: usize) {
+        match self {
+            Node::Box { name, terminal } => {
+                let width = self.size().width;
+                let radius = if *terminal { HALF } else { 0 };
+                let _ = write!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                    rx=\"{}\"/><text x=\"{}\" y=\"{}\">{}</text>",
+                    x, y - HALF, width, 2 * HALF, radius, x + width / 2, y + 4, escape(name));
+            }
+            Node::Sequence(nodes) => {
+                let mut x = x;
+                for (i, node) in nodes.iter().enumerate() {
+                    if i > 0 {
+                        path(svg, format!("M{} {}h{}", x, y, GAP));
+                        x += GAP;
+                    }
+
+                    node.draw(svg, x, y);
+                    x += node.size().width;
+                }
+            }
+            Node::Repeat(node) => {
+                let (size, inner) = (self.size(), node.size());
+                path(svg, format!("M{} {}h{}", x, y, GAP));
+                node.draw(svg, x + GAP, y);
+                path(svg, format!("M{} {}h{}", x + GAP + inner.width, y, GAP));
+                path(svg, format!("M{} {}V{}H{}V{}",
+                    x + size.width - GAP, y, y + size.down, x + GAP, y));
+            }
+            Node::Choice(nodes) => {
+                let width = self.size().width;
+                let (left, right) = (x + GAP, x + width - GAP);
+                path(svg, format!("M{} {}h{}M{} {}h{}", x, y, GAP, right, y, GAP));
+
+                // Each alternative's track is below the previous alternative.
+                let (mut track, mut above) = (y, 0);
+                for (i, node) in nodes.iter().enumerate() {
+                    let size = node.size();
+                    if i > 0 {
+                        track += above + GAP + size.up;
+                    }
+
+                    path(svg, format!("M{} {}h{}", left, track, GAP));
+                    node.draw(svg, left + GAP, track);
+                    path(svg, format!("M{} {}H{}", left + GAP + size.width, track, right));
+                    above = size.down;
+                }
+
+                path(svg, format!("M{} {}V{}M{} {}V{}", left, y, track, right, y, track));
+            }
+        }
+    }
+}
+
+fn path(svg: &mut String, d: String) {
+    let _ = write!(svg, "<path d=\"{}\"/>", d);
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
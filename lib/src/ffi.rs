@@ -0,0 +1,215 @@
+//! Support for calling parsers from C.
+//!
+//! Annotating an entry parser with `#[parser(ffi)]` generates, in addition to
+//! the parser itself, a `#[no_mangle] extern "C"` function named
+//! `<parser>_parse` with the C signature:
+//!
+//! ```c
+//! FfiStatus <parser>_parse(const uint8_t *ptr, size_t len, FfiResult *out);
+//! ```
+//!
+//! where `FfiStatus` and `FfiResult` are the `repr(C)` [`FfiStatus`] and
+//! [`FfiResult`]. This module requires the `ffi` feature.
+//!
+//! The wrapper validates that `ptr[..len]` is UTF-8, parses it into the
+//! parser's input type via `From<&str>`, runs the parser, and fills `out` with
+//! either a handle to the boxed value or the position and message of the
+//! error. Panics are caught at the boundary and reported as
+//! [`FfiStatus::Panic`]. Regardless of the outcome, the result must be
+//! released with [`pear_result_free()`].
+//!
+//! The parser must take no arguments other than its input, its input must
+//! implement [`FfiInput`], and its value must be `'static`.
+//!
+//! The wrapper's symbol is unmangled, so it must be unique across the entire
+//! program: two `ffi` parsers with the same name, even in different modules or
+//! crates, fail to link. To export a parser under another symbol, give it as
+//! `#[parser(ffi = "symbol")]`. The wrapper is still named `<parser>_parse` in
+//! Rust.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::parser;
+//! use pear::parsers::*;
+//! use pear::ffi::{FfiResult, FfiStatus, pear_result_free};
+//!
+//! #[parser(ffi)]
+//! fn digits<'a>(input: &mut Text<'a>) -> Result<String, Text<'a>> {
+//!     take_some_while(|c: &char| c.is_ascii_digit())?.to_string()
+//! }
+//!
+//! let mut result = FfiResult::default();
+//! let status = unsafe { digits_parse(b"123".as_ptr(), 3, &mut result) };
+//! assert_eq!(status, FfiStatus::Ok);
+//! assert_eq!(unsafe { &*(result.value as *const String) }, "123");
+//! unsafe { pear_result_free(&mut result) };
+//! ```
+
+use std::ffi::{CString, c_char, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::input::{Input, Show, Expected, Result};
+
+/// The outcome of a call to a generated `extern "C"` parser wrapper.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FfiStatus {
+    /// The parser succeeded. `value` points to the parsed value.
+    Ok = 0,
+    /// The parser failed. `error_offset` is the byte offset at which parsing
+    /// stopped, and `error_message` describes the error.
+    ParseError = 1,
+    /// The input was not valid UTF-8. `error_offset` is the byte offset of the
+    /// first invalid byte.
+    InvalidUtf8 = 2,
+    /// The parser panicked. `error_message` contains the panic message, if
+    /// it was a string.
+    Panic = 3,
+    /// The input pointer was null while the length was nonzero, or the output
+    /// pointer was null. `out` is left untouched.
+    NullPointer = 4,
+}
+
+/// The out-struct filled by a generated `extern "C"` parser wrapper.
+///
+/// Release with [`pear_result_free()`] once it is no longer needed.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FfiResult {
+    /// The status of the call.
+    pub status: FfiStatus,
+    /// On success, an opaque handle to the boxed parsed value. Null otherwise.
+    pub value: *mut c_void,
+    /// Drops `value`. Null if `value` is null.
+    pub value_drop: Option<unsafe extern "C" fn(*mut c_void)>,
+    /// The byte offset of the error, if any. Zero on success.
+    pub error_offset: usize,
+    /// A NUL-terminated description of the error, if any. Null on success.
+    pub error_message: *mut c_char,
+}
+
+impl Default for FfiResult {
+    fn default() -> Self {
+        FfiResult {
+            status: FfiStatus::Ok,
+            value: std::ptr::null_mut(),
+            value_drop: None,
+            error_offset: 0,
+            error_message: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// An input that can be constructed by a generated `extern "C"` wrapper.
+pub trait FfiInput<'a>: Input + From<&'a str> {
+    /// Returns the number of bytes not yet consumed.
+    fn remaining(&self) -> usize;
+}
+
+impl<'a> FfiInput<'a> for &'a str {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Frees the value and error message held by `result`, if any, and resets it
+/// to its default state. Does nothing if `result` is null.
+///
+/// # Safety
+///
+/// `result` must be null or point to an `FfiResult` filled by a generated
+/// wrapper that has not since been freed.
+#[no_mangle]
+pub unsafe extern "C" fn pear_result_free(result: *mut FfiResult) {
+    let result = match result.as_mut() {
+        Some(result) => result,
+        None => return,
+    };
+
+    if let (Some(value_drop), false) = (result.value_drop, result.value.is_null()) {
+        value_drop(result.value);
+    }
+
+    if !result.error_message.is_null() {
+        drop(CString::from_raw(result.error_message));
+    }
+
+    *result = FfiResult::default();
+}
+
+unsafe extern "C" fn drop_boxed<T>(value: *mut c_void) {
+    drop(Box::from_raw(value as *mut T));
+}
+
+fn c_string(string: String) -> *mut c_char {
+    // Interior NULs would truncate the message in C anyway.
+    let string = string.replace('\0', "\u{FFFD}");
+    CString::new(string).expect("no NULs").into_raw()
+}
+
+/// Runs `parser` on `string`, returning the value or the byte offset at which
+/// parsing stopped and the rendered error.
+#[doc(hidden)]
+pub fn run<'a, I, T, P>(string: &'a str, parser: P) -> std::result::Result<T, (usize, String)>
+    where I: FfiInput<'a>,
+          P: FnOnce(&mut I) -> Result<T, I>,
+          I::Context: Show,
+          Expected<I>: core::fmt::Display
+{
+    let mut input = I::from(string);
+    parser(&mut input).map_err(|e| (string.len() - input.remaining(), e.to_string()))
+}
+
+/// The body of a generated `extern "C"` wrapper.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` bytes or be null with a `len` of
+/// zero. `out` must be null or valid for writes.
+#[doc(hidden)]
+pub unsafe fn call<T, F>(ptr: *const u8, len: usize, out: *mut FfiResult, f: F) -> FfiStatus
+    where T: 'static, F: FnOnce(&str) -> std::result::Result<T, (usize, String)>
+{
+    if out.is_null() || (ptr.is_null() && len != 0) {
+        return FfiStatus::NullPointer;
+    }
+
+    let bytes = match ptr.is_null() {
+        true => &[][..],
+        false => std::slice::from_raw_parts(ptr, len),
+    };
+
+    let mut result = FfiResult::default();
+    match std::str::from_utf8(bytes) {
+        Err(e) => {
+            result.status = FfiStatus::InvalidUtf8;
+            result.error_offset = e.valid_up_to();
+            result.error_message = c_string(e.to_string());
+        }
+        Ok(string) => match catch_unwind(AssertUnwindSafe(|| f(string))) {
+            Ok(Ok(value)) => {
+                result.value = Box::into_raw(Box::new(value)) as *mut c_void;
+                result.value_drop = Some(drop_boxed::<T>);
+            }
+            Ok(Err((offset, message))) => {
+                result.status = FfiStatus::ParseError;
+                result.error_offset = offset;
+                result.error_message = c_string(message);
+            }
+            Err(panic) => {
+                let message = panic.downcast_ref::<&str>().map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned());
+
+                result.status = FfiStatus::Panic;
+                result.error_message = message.map(c_string)
+                    .unwrap_or(std::ptr::null_mut());
+            }
+        }
+    }
+
+    let status = result.status;
+    out.write(result);
+    status
+}
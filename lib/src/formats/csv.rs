@@ -0,0 +1,98 @@
+//! Comma-separated values, as described by [RFC 4180], in any [`Dialect`].
+//!
+//! A file is a series of [`records()`], separated by newlines (`\n` or
+//! `\r\n`), with an optional trailing newline. A [`record()`] is a series of
+//! [`field()`]s separated by the dialect's delimiter. A field is either
+//! unquoted, running until the next delimiter or newline, or quoted, in which
+//! case it may contain delimiters and newlines, and a quote is written twice.
+//! Fields are borrowed from the input unless they contain escaped quotes.
+//!
+//! ```rust
+//! use pear::input::Text;
+//! use pear::formats::csv::{self, Dialect};
+//!
+//! let mut input = Text::from("name,motto\r\npear,\"sweet, \"\"juicy\"\"\"\r\n");
+//! let records = csv::records(&mut input, Dialect::default()).unwrap();
+//! assert_eq!(records, [["name", "motto"], ["pear", "sweet, \"juicy\""]]);
+//!
+//! let dialect = Dialect { delimiter: '\t', ..Dialect::default() };
+//! let record = csv::record(&mut Text::from("a\tb,c"), dialect).unwrap();
+//! assert_eq!(record, ["a", "b,c"]);
+//! ```
+//!
+//! [RFC 4180]: https://www.rfc-editor.org/rfc/rfc4180
+
+use alloc::{vec::Vec, string::String, borrow::Cow};
+
+use crate::input::{Input, Result};
+use crate::macros::parser;
+use crate::parsers::*;
+use crate::combinators::*;
+
+/// The characters delimiting fields and quoting them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Dialect {
+    /// The character separating fields. Defaults to `,`.
+    pub delimiter: char,
+    /// The character quoting fields. Defaults to `"`.
+    pub quote: char,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Dialect { delimiter: ',', quote: '"' }
+    }
+}
+
+/// Parses a single field, quoted or unquoted. An unquoted field may be empty.
+#[parser(raw)]
+pub fn field<'a, I>(input: &mut I, dialect: Dialect) -> Result<Cow<'a, str>, I>
+    where I: Input<Token = char, Slice = &'a str, Many = &'a str>
+{
+    let Dialect { delimiter, quote } = dialect;
+    if eat(input, quote).is_err() {
+        let field = take_while(input, |&c| c != delimiter && c != '\n' && c != '\r')?;
+        return Ok(Cow::Borrowed(field));
+    }
+
+    let mut owned: Option<String> = None;
+    loop {
+        let chunk = take_while(input, |&c| c != quote)?;
+        eat(input, quote)?;
+        if eat(input, quote).is_err() {
+            return Ok(match owned {
+                Some(mut string) => { string.push_str(chunk); Cow::Owned(string) }
+                None => Cow::Borrowed(chunk),
+            });
+        }
+
+        let string = owned.get_or_insert_with(String::new);
+        string.push_str(chunk);
+        string.push(quote);
+    }
+}
+
+/// Parses a record: one or more fields separated by the dialect's delimiter.
+/// Consumes a `\r` ending the record, but not the `\n` after it.
+#[parser(raw)]
+pub fn record<'a, I>(input: &mut I, dialect: Dialect) -> Result<Vec<Cow<'a, str>>, I>
+    where I: Input<Token = char, Slice = &'a str, Many = &'a str>
+{
+    peek_any(input)?;
+    let record = series(input, |i| field(i, dialect), dialect.delimiter)?;
+    let _ = eat(input, '\r');
+    Ok(record)
+}
+
+/// Parses as many newline-separated records as possible, allowing a trailing
+/// newline. Succeeds with no records on empty input.
+#[parser(raw)]
+pub fn records<'a, I>(input: &mut I, dialect: Dialect) -> Result<Vec<Vec<Cow<'a, str>>>, I>
+    where I: Input<Token = char, Slice = &'a str, Many = &'a str>
+{
+    if input.token().is_none() {
+        return Ok(Vec::new());
+    }
+
+    trailing_series(input, |i| record(i, dialect), '\n')
+}
@@ -0,0 +1,3 @@
+//! Parsers for common formats, built from Pear's parsers and combinators.
+
+pub mod csv;
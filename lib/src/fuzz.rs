@@ -0,0 +1,142 @@
+//! Helpers for fuzzing parsers with `cargo-fuzz` and libFuzzer.
+//!
+//! A fuzz target feeds arbitrary bytes to a parser and relies on the parser
+//! to panic when something is wrong. Parsers rarely panic on their own: a
+//! parser that misbehaves usually returns a plausible result. The helpers
+//! here run a parser over fuzzer-provided input and panic if it breaks any
+//! invariant that every parser must uphold:
+//!
+//!   * The parser consumes no more than the input: its position after running
+//!     is neither before its position before running nor past the end.
+//!   * Rewinding the input to its position before the parser ran restores
+//!     that position.
+//!   * The parser is deterministic: run again on the rewound input, it
+//!     succeeds or fails as it did before, having consumed as much.
+//!   * The contexts in an error span the input: they're in bounds and, for
+//!     text, begin and end at character boundaries and carry the text they
+//!     span.
+//!
+//! [`fuzz_str()`] checks a parser of [`Text`], and [`fuzz_bytes()`] a parser of
+//! a byte [`Cursor`]. [`check()`] checks a parser of any input. A fuzz target
+//! is then a single line:
+//!
+//! ```rust,ignore
+//! // fuzz/fuzz_targets/json.rs
+//! #![no_main]
+//!
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+//!     pear::fuzz::fuzz_str(data, |input| my_grammar::json::value(input));
+//! });
+//! ```
+//!
+//! The helpers also work as ordinary test drivers over a corpus of inputs:
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::parser;
+//! use pear::parsers::*;
+//!
+//! #[parser]
+//! fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+//!     take_some_while(|c: &char| c.is_alphabetic())?
+//! }
+//!
+//! for data in ["", "pear", "pé4r", "\u{fffd}", "123"] {
+//!     pear::fuzz::fuzz_str(data.as_bytes(), |input| word(input));
+//! }
+//! ```
+
+use crate::error::ParseError;
+use crate::input::{Rewind, Position, ParserInfo, Text, Cursor, Span, Extent, Result};
+
+/// The parser info used to mark the input before running a checked parser.
+const FUZZ: ParserInfo = ParserInfo::new("fuzz", true);
+
+/// Runs `parser` on `input`, which has `len` units of input in total, as
+/// measured by [`Position`], and panics if it breaks any of the [invariants
+/// of parsers](self). The input is left where the parser left it. Returns
+/// the parser's first result.
+pub fn check<I, O, E, P>(input: &mut I, len: usize, mut parser: P) -> Result<O, I, E>
+    where I: Rewind + Position, P: FnMut(&mut I) -> Result<O, I, E>
+{
+    let start = input.position();
+    let mark = input.mark(&FUZZ);
+    let result = parser(input);
+    let end = input.position();
+    assert!(start <= end, "parser moved the input backward from {} to {}", start, end);
+    assert!(end <= len, "parser consumed past the end of the input: {} > {}", end, len);
+
+    input.rewind_to(&mark);
+    assert_eq!(input.position(), start, "rewinding didn't restore the position");
+
+    let again = parser(input);
+    assert_eq!(again.is_ok(), result.is_ok(), "parser isn't deterministic: its result changed");
+    assert_eq!(input.position(), end, "parser isn't deterministic: it consumed a different amount");
+
+    input.unmark(&FUZZ, result.is_ok(), mark);
+    result
+}
+
+/// Checks `parser` with [`check()`] on `data` as [`Text`], returning the
+/// parser's result, and panics if any context in an error doesn't span the
+/// text. Data that isn't valid UTF-8 is skipped: returns `None` without
+/// running the parser.
+pub fn fuzz_str<'a, O, E, P>(data: &'a [u8], parser: P) -> Option<Result<O, Text<'a>, E>>
+    where P: FnMut(&mut Text<'a>) -> Result<O, Text<'a>, E>
+{
+    let text = core::str::from_utf8(data).ok()?;
+    let result = check(&mut Text::from(text), text.len(), parser);
+    if let Err(error) = &result {
+        check_spans(text, error);
+    }
+
+    Some(result)
+}
+
+/// Checks `parser` with [`check()`] on `data` as a byte [`Cursor`], returning
+/// the parser's result, and panics if any context in an error doesn't span
+/// the bytes.
+pub fn fuzz_bytes<'a, O, E, P>(data: &'a [u8], parser: P) -> Result<O, Cursor<'a, u8>, E>
+    where P: FnMut(&mut Cursor<'a, u8>) -> Result<O, Cursor<'a, u8>, E>
+{
+    let result = check(&mut Cursor::from(data), data.len(), parser);
+    if let Err(error) = &result {
+        for context in &error.contexts {
+            if let Some(extent) = &context.context {
+                check_extent(data, context.parser.name, extent);
+            }
+        }
+    }
+
+    result
+}
+
+fn check_spans<E>(text: &str, error: &ParseError<Span<'_>, E>) {
+    for context in &error.contexts {
+        let (name, span) = match &context.context {
+            Some(span) => (context.parser.name, span),
+            None => continue,
+        };
+
+        let (start, end) = (span.start.2, span.end.2);
+        assert!(start <= end && end <= text.len(),
+            "context of `{}` is out of bounds: {}..{} of {}", name, start, end, text.len());
+
+        assert!(text.is_char_boundary(start) && text.is_char_boundary(end),
+            "context of `{}` splits a character: {}..{}", name, start, end);
+
+        if let Some(snippet) = span.snippet {
+            assert_eq!(snippet, &text[start..end], "snippet of `{}` isn't the text it spans", name);
+        }
+    }
+}
+
+fn check_extent(data: &[u8], name: &str, extent: &Extent<'_, u8>) {
+    let (start, end) = (extent.start, extent.end);
+    assert!(start <= end && end <= data.len(),
+        "context of `{}` is out of bounds: {}..{} of {}", name, start, end, data.len());
+
+    assert_eq!(extent.values, &data[start..end], "context of `{}` isn't the data it spans", name);
+}
@@ -0,0 +1,104 @@
+//! A registry of named rules, extensible at runtime.
+//!
+//! A [`Grammar`] maps rule names to [boxed parsers](BoxedParser) with the same
+//! input and output. A base grammar written with `#[parser]` functions can
+//! look up the rule for a construct by name, as read from the input, letting
+//! plugins extend a format by registering rules of their own: user-defined
+//! directives, say. Rules run as if they were `#[parser]` functions named by
+//! their rule name, so errors in them carry their names as context.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::{macros::*, parsers::*};
+//! use pear::grammar::Grammar;
+//!
+//! type Directives<'a> = Grammar<'static, Text<'a>, String>;
+//!
+//! // `@name arguments`, where `name` is any registered directive.
+//! #[parser]
+//! fn directive<'a>(input: &mut Text<'a>, directives: &mut Directives<'a>) -> Result<String, Text<'a>> {
+//!     eat('@')?;
+//!     let name = take_some_while(|c: &char| c.is_alphabetic())?;
+//!     eat(' ')?;
+//!     directives.parse(name, input)?
+//! }
+//!
+//! let mut directives = Directives::new();
+//! directives.register("upper", |input: &mut Text<'_>| {
+//!     Ok(take_while(input, |_| true)?.to_uppercase())
+//! });
+//!
+//! let output = directive(&mut Text::from("@upper pear"), &mut directives);
+//! assert_eq!(output.unwrap(), "PEAR");
+//!
+//! let error = directive(&mut Text::from("@lower pear"), &mut directives);
+//! assert!(error.unwrap_err().to_string().contains("unknown rule `lower`"));
+//! ```
+
+use alloc::{format, collections::BTreeMap};
+
+use crate::error::{Error, ParseError};
+use crate::input::{Input, Expected, Result};
+use crate::combinators::{BoxedParser, boxed};
+
+/// A registry of named rules. See the [module documentation](self).
+pub struct Grammar<'a, I: Input, O, E = Expected<I>> {
+    rules: BTreeMap<&'static str, BoxedParser<'a, I, O, E>>,
+}
+
+impl<'a, I: Input, O, E: Error<I>> Grammar<'a, I, O, E> {
+    /// Returns a grammar with no rules.
+    pub fn new() -> Self {
+        Grammar { rules: BTreeMap::new() }
+    }
+
+    /// Registers `rule` under `name`, returning the rule it replaces, if any.
+    pub fn register<P>(&mut self, name: &'static str, rule: P) -> Option<BoxedParser<'a, I, O, E>>
+        where P: FnMut(&mut I) -> Result<O, I, E> + 'a
+    {
+        self.rules.insert(name, boxed(rule))
+    }
+
+    /// Removes and returns the rule registered under `name`, if any.
+    pub fn unregister(&mut self, name: &str) -> Option<BoxedParser<'a, I, O, E>> {
+        self.rules.remove(name)
+    }
+
+    /// Returns `true` if a rule is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.rules.contains_key(name)
+    }
+
+    /// Returns the names of the registered rules in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.rules.keys().copied()
+    }
+
+    /// Returns the rule registered under `name`, if any.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut BoxedParser<'a, I, O, E>> {
+        self.rules.get_mut(name)
+    }
+
+    /// Parses `input` with the rule registered under `name`, as a parser named
+    /// `name`. Fails without consuming input if there is no such rule.
+    pub fn parse(&mut self, name: &str, input: &mut I) -> Result<O, I, E> {
+        let name = match self.rules.get_key_value(name) {
+            Some((name, _)) => *name,
+            None => {
+                let message = format!("unknown rule `{}`", name);
+                return Err(ParseError::new(E::from_context(message.into(), None)));
+            }
+        };
+
+        let rule = self.rules.get_mut(name).expect("registered rule");
+        crate::wrap::parser(name, rule)(input)
+    }
+}
+
+impl<I: Input, O, E: Error<I>> Default for Grammar<'_, I, O, E> {
+    fn default() -> Self {
+        Grammar::new()
+    }
+}
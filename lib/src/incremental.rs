@@ -0,0 +1,234 @@
+//! Incremental re-parsing for editors and language servers.
+//!
+//! A [`Document`] owns a source text and a [`MemoTable`] of the results of
+//! previous parses, keyed by the range of input they were parsed from. Rules
+//! wrapped in the [`memo()`] combinator consult the table before running: if a
+//! result for the rule was recorded at the current position, it is reused and
+//! the input skips ahead past it. [`Document::edit()`] splices new text into
+//! the source and invalidates only the entries whose input overlaps the edit,
+//! shifting entries after it, so the next parse reuses every untouched
+//! subtree.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::incremental::{Document, Memoized, memo};
+//! use pear::{macros::*, parsers::*, combinators::*};
+//!
+//! type Input<'a> = Memoized<'a, Text<'a>>;
+//!
+//! #[parser]
+//! fn word<'a>(input: &mut Input<'a>) -> Result<String, Input<'a>> {
+//!     let word = take_some_while(|c: &char| c.is_alphabetic())?;
+//!     eat(';')?;
+//!     word.to_string()
+//! }
+//!
+//! #[parser]
+//! fn words<'a>(input: &mut Input<'a>) -> Result<Vec<String>, Input<'a>> {
+//!     let words: Vec<_> = collect(|i| memo(i, "word", word))?;
+//!     words
+//! }
+//!
+//! let mut document = Document::new("alpha;beta;gamma;");
+//! assert_eq!(document.parse(words).unwrap(), ["alpha", "beta", "gamma"]);
+//!
+//! // Only `beta;` is invalidated: `alpha;` and `gamma;` are reused.
+//! document.edit(7..10, "oo");
+//! assert_eq!(document.source(), "alpha;boo;gamma;");
+//! assert_eq!(document.memo_table().len(), 2);
+//! assert_eq!(document.parse(words).unwrap(), ["alpha", "boo", "gamma"]);
+//! ```
+//!
+//! A memoized result is assumed to depend only on the input it consumed and
+//! the single token that follows it, which is invalidated along with it. Rules
+//! that look further ahead, or that depend on state other than the input,
+//! should not be memoized. Memoized outputs must be `'static`; use owned
+//! values rather than borrowed slices.
+
+use core::any::Any;
+use core::ops::Range;
+use alloc::{boxed::Box, string::String, collections::BTreeMap};
+
+use crate::input::{Input, Rewind, Position, Slice, Text, Result};
+use crate::macros::parser;
+
+/// A memoized result: the end of the input it was parsed from and the output.
+struct Entry {
+    end: usize,
+    value: Box<dyn Any>,
+}
+
+/// A table of memoized parse results keyed by rule name and start position.
+#[derive(Default)]
+pub struct MemoTable {
+    entries: BTreeMap<(usize, &'static str), Entry>,
+}
+
+impl MemoTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        MemoTable::default()
+    }
+
+    /// Returns the number of memoized results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no memoized results.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the range of input the result of `rule` at `start` was parsed
+    /// from, if one is memoized.
+    pub fn get(&self, rule: &'static str, start: usize) -> Option<Range<usize>> {
+        self.entries.get(&(start, rule)).map(|entry| start..entry.end)
+    }
+
+    /// Removes all memoized results.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Adjusts the table for the replacement of the input in `range` with
+    /// `len` units of new input. Results which depend on the replaced input
+    /// are removed, and those after it are shifted.
+    pub fn edit(&mut self, range: Range<usize>, len: usize) {
+        let entries = core::mem::take(&mut self.entries);
+        self.entries = entries.into_iter()
+            .filter_map(|((start, rule), entry)| {
+                if entry.end < range.start {
+                    Some(((start, rule), entry))
+                } else if start >= range.end && start > range.start {
+                    let shift = |n: usize| n - range.end + range.start + len;
+                    Some(((shift(start), rule), Entry { end: shift(entry.end), ..entry }))
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+}
+
+/// An input which carries a [`MemoTable`] for use by [`memo()`].
+///
+/// Apart from carrying the table, a `Memoized` input behaves exactly like its
+/// inner input.
+pub struct Memoized<'m, I> {
+    input: I,
+    table: &'m mut MemoTable,
+}
+
+impl<'m, I: Input> Memoized<'m, I> {
+    /// Wraps `input`, memoizing results in `table`.
+    pub fn new(input: I, table: &'m mut MemoTable) -> Self {
+        Memoized { input, table }
+    }
+
+    /// Returns the memo table.
+    pub fn table(&mut self) -> &mut MemoTable {
+        self.table
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+}
+
+/// Runs `p` unless its result at the current position is memoized, in which
+/// case the memoized result is returned and the input skips past it. `rule`
+/// names `p` in the memo table. Only successful results are memoized.
+#[parser(raw)]
+pub fn memo<'m, I, O, P>(input: &mut Memoized<'m, I>, rule: &'static str, p: P) -> Result<O, Memoized<'m, I>>
+    where I: Rewind<Marker = usize> + Position,
+          O: Clone + 'static,
+          P: FnOnce(&mut Memoized<'m, I>) -> Result<O, Memoized<'m, I>>
+{
+    let start = input.position();
+    if let Some(entry) = input.table.entries.get(&(start, rule)) {
+        if let Some(value) = entry.value.downcast_ref::<O>() {
+            let (value, end) = (value.clone(), entry.end);
+            input.rewind_to(&end);
+            return Ok(value);
+        }
+    }
+
+    let value = p(input)?;
+    let entry = Entry { end: input.position(), value: Box::new(value.clone()) };
+    input.table.entries.insert((start, rule), entry);
+    Ok(value)
+}
+
+/// A source text and the memoized results of parsing it.
+#[derive(Default)]
+pub struct Document {
+    source: String,
+    table: MemoTable,
+}
+
+impl Document {
+    /// Creates a document with the source text `source` and an empty table.
+    pub fn new<S: Into<String>>(source: S) -> Self {
+        Document { source: source.into(), table: MemoTable::new() }
+    }
+
+    /// Returns the source text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the memo table.
+    pub fn memo_table(&self) -> &MemoTable {
+        &self.table
+    }
+
+    /// Parses the entire source with `parser`, reusing and recording memoized
+    /// results.
+    pub fn parse<'d, T, P>(&'d mut self, parser: P) -> Result<T, Memoized<'d, Text<'d>>>
+        where P: FnOnce(&mut Memoized<'d, Text<'d>>) -> Result<T, Memoized<'d, Text<'d>>>
+    {
+        let mut input = Memoized::new(Text::from(&*self.source), &mut self.table);
+        let value = parser(&mut input)?;
+        crate::parsers::eof(&mut input)?;
+        Ok(value)
+    }
+
+    /// Replaces the source text in the byte range `range` with `new_text`,
+    /// invalidating the memoized results that depend on it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or does not lie on `char`
+    /// boundaries.
+    pub fn edit(&mut self, range: Range<usize>, new_text: &str) {
+        self.source.replace_range(range.clone(), new_text);
+        self.table.edit(range, new_text.len());
+    }
+}
+
+impl<'m, 'a, I> Slice<Memoized<'m, I>> for &str where I: Input<Slice = &'a str> { }
+
+impl<I: Rewind> Rewind for Memoized<'_, I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position> Position for Memoized<'_, I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input> Input for Memoized<'_, I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker, Context;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           mark, context, unmark, needed, halted;
+    }
+}
@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+
+use crate::input::{Input, Rewind, Position, ParserInfo};
+use crate::parsers::binary::Byte;
+
+/// An input adapter exposing a byte input as a sequence of bits.
+///
+/// Bits are read most significant first, as in network protocol headers such
+/// as DNS's. A `Bits` input's tokens are `bool`s; [`Bits::take_bits()`] and
+/// the [`bits()`](crate::parsers::binary::bits) parser read several at once as
+/// an unsigned integer. A byte of the inner input is consumed only once all of
+/// its bits have been read; [`Bits::align()`] skips the rest of a partially
+/// read byte.
+///
+/// The inner input's slices must be views of its bytes, as those of a
+/// [`Cursor`](crate::input::Cursor) over a `&[u8]` are. Contexts are those of
+/// the inner input and so have byte granularity.
+#[derive(Debug)]
+pub struct Bits<I> {
+    input: I,
+    bit: u8,
+}
+
+impl<I: Input> Bits<I> where I::Token: Byte, I::Slice: AsRef<[u8]> {
+    /// Wraps `input`, starting at the most significant bit of its first byte.
+    pub fn new(input: I) -> Self {
+        Bits { input, bit: 0 }
+    }
+
+    /// Returns the offset, from 0 to 7, of the current bit in the current
+    /// byte.
+    pub fn bit_offset(&self) -> u8 {
+        self.bit
+    }
+
+    /// Returns `true` if the current bit is the first of a byte.
+    pub fn is_aligned(&self) -> bool {
+        self.bit == 0
+    }
+
+    /// Skips the remaining bits of the current byte, if it is partially read.
+    /// Returns the number of skipped bits.
+    pub fn align(&mut self) -> u8 {
+        if self.is_aligned() {
+            return 0;
+        }
+
+        let skipped = 8 - self.bit;
+        self.advance(skipped as usize);
+        skipped
+    }
+
+    /// Reads the next `n` bits as an unsigned integer, most significant bit
+    /// first, if there are at least `n` remaining. Otherwise, returns `None`
+    /// and consumes nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than 64.
+    pub fn take_bits(&mut self, n: u32) -> Option<u64> {
+        assert!(n <= 64, "cannot take more than 64 bits at once");
+        let value = self.peek_bits(0, n as usize, |bits| {
+            bits.fold(0, |value, bit| value << 1 | bit as u64)
+        })?;
+
+        self.advance(n as usize);
+        Some(value)
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner input if the current bit is
+    /// the first of a byte, allowing byte-level parsing to resume.
+    pub fn inner_mut(&mut self) -> Option<&mut I> {
+        match self.is_aligned() {
+            true => Some(&mut self.input),
+            false => None,
+        }
+    }
+
+    /// Returns the inner input, discarding a partially read byte's position.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    /// Calls `f` with an iterator over the `n` bits starting `skip` bits past
+    /// the current one, if there are that many remaining.
+    fn peek_bits<T, F>(&mut self, skip: usize, n: usize, f: F) -> Option<T>
+        where F: FnOnce(&mut dyn Iterator<Item = bool>) -> T
+    {
+        let offset = (self.bit as usize).checked_add(skip)?;
+        let end = offset.checked_add(n)?;
+        let slice = self.input.slice(end.div_ceil(8))?;
+        let bytes = slice.as_ref();
+        if bytes.len() * 8 < end {
+            return None;
+        }
+
+        Some(f(&mut (offset..end).map(|i| bytes[i / 8] >> (7 - i % 8) & 1 == 1)))
+    }
+
+    /// Consumes `n` bits, which must remain.
+    fn advance(&mut self, n: usize) {
+        let end = self.bit as usize + n;
+        for _ in 0..end / 8 {
+            self.input.eat(|_| true);
+        }
+
+        self.bit = (end % 8) as u8;
+    }
+}
+
+impl<I: Input> From<I> for Bits<I> where I::Token: Byte, I::Slice: AsRef<[u8]> {
+    fn from(input: I) -> Self {
+        Bits::new(input)
+    }
+}
+
+impl<I: Rewind> Rewind for Bits<I> where I::Token: Byte, I::Slice: AsRef<[u8]> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(&marker.0);
+        self.bit = marker.1;
+    }
+}
+
+impl<I: Position> Position for Bits<I> where I::Token: Byte, I::Slice: AsRef<[u8]> {
+    /// Returns the offset of the current position in bits.
+    fn position(&self) -> usize {
+        self.input.position() * 8 + self.bit as usize
+    }
+}
+
+impl<I: Input> Input for Bits<I> where I::Token: Byte, I::Slice: AsRef<[u8]> {
+    delegate_input! {
+        to input: I;
+        type Context;
+        fn halted;
+    }
+
+    type Token = bool;
+    type Slice = Vec<bool>;
+    type Many = Vec<bool>;
+
+    type Marker = (I::Marker, u8);
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        let bit = self.bit;
+        self.input.token().map(|byte| byte.byte() >> (7 - bit) & 1 == 1)
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.peek_bits(0, n, |bits| bits.collect())
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        self.peek_bits(offset, len, |bits| bits.collect())
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.advance(1);
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.advance(n);
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut many = Vec::new();
+        while let Some(token) = self.eat(&mut cond) {
+            many.push(token);
+        }
+
+        many
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut skipped = 0;
+        while self.eat(&mut cond).is_some() {
+            skipped += 1;
+        }
+
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.input.has((self.bit as usize + n).div_ceil(8))
+    }
+
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        (self.input.mark(info), self.bit)
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        self.input.context(&mark.0)
+    }
+
+    fn unmark(&mut self, info: &ParserInfo, success: bool, mark: Self::Marker) {
+        self.input.unmark(info, success, mark.0)
+    }
+}
@@ -0,0 +1,89 @@
+use crate::input::{Text, Cursor, Show, Length, Token, Slice};
+
+/// A token or slice which compares equal to input regardless of ASCII case.
+///
+/// Wrapping the expected token or slice passed to a parser like
+/// [`eat()`](crate::parsers::eat()) or
+/// [`eat_slice()`](crate::parsers::eat_slice()) makes the parser match input
+/// that differs only in the case of ASCII letters, without allocating a
+/// lowercase copy of either. Non-ASCII characters must match exactly. The
+/// [`eat_slice_ci()`](crate::parsers::eat_slice_ci()) and
+/// [`peek_slice_ci()`](crate::parsers::peek_slice_ci()) parsers wrap their
+/// argument automatically.
+///
+/// Wrapped tokens and slices can be matched against [`Text`], `&str`, and
+/// [`Cursor<u8>`](Cursor) inputs.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, AsciiCaseInsensitive, Result};
+/// use pear::{macros::*, parsers::*};
+///
+/// #[parser]
+/// fn chunked<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     eat_slice(AsciiCaseInsensitive("transfer-encoding"))?;
+///     eat(':')?;
+///     skip_while(|&c| c == ' ')?;
+///     eat_slice_ci("chunked")?
+/// }
+///
+/// let result = parse!(chunked: &mut Text::from("Transfer-Encoding: CHUNKED"));
+/// assert_eq!(result.unwrap(), "CHUNKED");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct AsciiCaseInsensitive<T>(pub T);
+
+impl PartialEq<char> for AsciiCaseInsensitive<char> {
+    fn eq(&self, other: &char) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<u8> for AsciiCaseInsensitive<u8> {
+    fn eq(&self, other: &u8) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&u8> for AsciiCaseInsensitive<u8> {
+    fn eq(&self, other: &&u8) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for AsciiCaseInsensitive<&str> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&[u8]> for AsciiCaseInsensitive<&[u8]> {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl<T: Show> Show for AsciiCaseInsensitive<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Show::fmt(&self.0, f)
+    }
+}
+
+impl<T: Length> Length for AsciiCaseInsensitive<T> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Token<Text<'_>> for AsciiCaseInsensitive<char> { }
+
+impl Token<&str> for AsciiCaseInsensitive<char> { }
+
+impl Token<Cursor<'_, u8>> for AsciiCaseInsensitive<u8> { }
+
+impl Slice<Text<'_>> for AsciiCaseInsensitive<&str> { }
+
+impl Slice<&str> for AsciiCaseInsensitive<&str> { }
+
+impl Slice<Cursor<'_, u8>> for AsciiCaseInsensitive<&[u8]> { }
@@ -0,0 +1,287 @@
+use core::mem::ManuallyDrop;
+
+use crate::input::{Input, Show, Rewind, Slice, ParserInfo};
+
+/// An input that consumes all of `A` followed by all of `B`.
+///
+/// This is the input analog of `std::io::Read::chain()` and is useful, for
+/// example, to prepend synthesized text to user-provided input. Both inputs
+/// must agree on their `Token` and `Slice` types.
+///
+/// Tokens flow seamlessly across the boundary: `eat`, `take`, `skip`, and
+/// `has` all treat the two inputs as one. Slices, however, are borrowed from
+/// a single input and thus cannot straddle the boundary; a `slice(n)` that
+/// would do so returns `None`. Similarly, the `Many` returned by `take()` is a
+/// pair of the tokens taken from `A` and those taken from `B`.
+///
+/// Contexts spanning the boundary are reported as [`ChainContext::Both`], a
+/// pair of the context from the mark to the end of `A` and the context from
+/// the start of `B` to the current position.
+///
+/// A `Chain` holds a marker into `B` at its start for as long as it lives, so
+/// that it can always rewind across the boundary. The marker is released when
+/// the `Chain` is dropped or taken apart with [`Chain::into_inner()`].
+pub struct Chain<A: Input, B: Input> {
+    first: A,
+    second: B,
+    second_start: B::Marker,
+}
+
+/// The marker type of a [`Chain`]: a marker into one of the two inputs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ChainMarker<A, B> {
+    /// A marker into the first input.
+    First(A),
+    /// A marker into the second input.
+    Second(B),
+}
+
+/// The context type of a [`Chain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainContext<A, B> {
+    /// The context lies entirely within the first input.
+    First(A),
+    /// The context lies entirely within the second input.
+    Second(B),
+    /// The context begins in the first input and ends in the second.
+    Both(A, B),
+}
+
+impl<A: Show, B: Show> Show for ChainContext<A, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ChainContext::First(a) => write!(f, "{}", a as &dyn Show),
+            ChainContext::Second(b) => write!(f, "{}", b as &dyn Show),
+            ChainContext::Both(a, b) => {
+                write!(f, "{} and {}", a as &dyn Show, b as &dyn Show)
+            }
+        }
+    }
+}
+
+impl<A, B> Chain<A, B>
+    where A: Input, B: Input<Token = A::Token, Slice = A::Slice>
+{
+    /// Creates a new input that consumes `first` followed by `second`.
+    pub fn new(first: A, mut second: B) -> Self {
+        let second_start = second.mark(&CHAIN_INFO);
+        Chain { first, second, second_start }
+    }
+
+    /// Returns the two inputs, in order, releasing the marker into `second`.
+    pub fn into_inner(self) -> (A, B) {
+        let mut chain = ManuallyDrop::new(self);
+        chain.release();
+
+        // SAFETY: `chain` is never dropped, so each input is moved out once.
+        unsafe { (core::ptr::read(&chain.first), core::ptr::read(&chain.second)) }
+    }
+
+    /// Returns `true` if the first input has been fully consumed.
+    #[inline(always)]
+    fn in_second(&mut self) -> bool {
+        !self.first.has(1)
+    }
+
+    /// Returns how much of the first input remains, given that less than
+    /// `bound` does, by binary search over `has()`.
+    fn first_remaining(&mut self, bound: usize) -> usize {
+        let (mut lo, mut hi) = (0, bound);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            match self.first.has(mid) {
+                true => lo = mid,
+                false => hi = mid,
+            }
+        }
+
+        lo
+    }
+}
+
+impl<A: Input, B: Input> Chain<A, B> {
+    /// Releases the marker at the start of the second input.
+    fn release(&mut self) {
+        self.second.unmark(&CHAIN_INFO, true, self.second_start);
+    }
+}
+
+impl<A: Input, B: Input> Drop for Chain<A, B> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// The info of the marker a [`Chain`] holds into its second input.
+const CHAIN_INFO: ParserInfo = ParserInfo::new("chain", true);
+
+impl<'a, A, B> Slice<Chain<A, B>> for &str
+    where A: Input<Slice = &'a str>, B: Input<Token = A::Token, Slice = A::Slice> { }
+
+impl<'a, T, A, B> Slice<Chain<A, B>> for &[T]
+    where T: PartialEq + Show + 'a,
+          A: Input<Slice = &'a [T]>,
+          B: Input<Token = A::Token, Slice = A::Slice> { }
+
+impl<A, B> Rewind for Chain<A, B>
+    where A: Rewind, B: Rewind<Token = A::Token, Slice = A::Slice>
+{
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        match marker {
+            ChainMarker::First(m) => {
+                self.second.rewind_to(&self.second_start);
+                self.first.rewind_to(m);
+            }
+            ChainMarker::Second(m) => self.second.rewind_to(m),
+        }
+    }
+}
+
+impl<A, B> Input for Chain<A, B>
+    where A: Input, B: Input<Token = A::Token, Slice = A::Slice>
+{
+    type Token = A::Token;
+    type Slice = A::Slice;
+    type Many = (A::Many, B::Many);
+
+    type Marker = ChainMarker<A::Marker, B::Marker>;
+    type Context = ChainContext<A::Context, B::Context>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        match self.in_second() {
+            false => self.first.token(),
+            true => self.second.token(),
+        }
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        match self.in_second() {
+            false => self.first.slice(n),
+            true => self.second.slice(n),
+        }
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything. Like a slice, the
+    /// window can't straddle the boundary between the inputs.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        let end = offset.checked_add(len)?;
+        if self.first.has(end) {
+            return self.first.peek_slice_at(offset, len);
+        }
+
+        match self.first_remaining(end) {
+            remaining if offset >= remaining => self.second.peek_slice_at(offset - remaining, len),
+            _ => None,
+        }
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        match self.in_second() {
+            false => self.first.peek(cond),
+            true => self.second.peek(cond),
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        match self.in_second() {
+            false => self.first.peek_slice(n, cond),
+            true => self.second.peek_slice(n, cond),
+        }
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        match self.in_second() {
+            false => self.first.eat(cond),
+            true => self.second.eat(cond),
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        match self.in_second() {
+            false => self.first.eat_slice(n, cond),
+            true => self.second.eat_slice(n, cond),
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let first = self.first.take(&mut cond);
+        let second = match self.in_second() {
+            true => self.second.take(cond),
+            false => self.second.take(|_| false),
+        };
+
+        (first, second)
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let skipped = self.first.skip(&mut cond);
+        match self.in_second() {
+            true => skipped + self.second.skip(cond),
+            false => skipped,
+        }
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        let in_first = (0..n).take_while(|&i| self.first.has(i + 1)).count();
+        in_first == n || self.second.has(n - in_first)
+    }
+
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        match self.in_second() {
+            false => ChainMarker::First(self.first.mark(info)),
+            true => ChainMarker::Second(self.second.mark(info)),
+        }
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        match mark {
+            ChainMarker::First(m) if !self.in_second() => {
+                self.first.context(m).map(ChainContext::First)
+            }
+            ChainMarker::First(m) => {
+                let first = self.first.context(m);
+                let second = self.second.context(&self.second_start);
+                match (first, second) {
+                    (Some(a), Some(b)) => Some(ChainContext::Both(a, b)),
+                    (Some(a), None) => Some(ChainContext::First(a)),
+                    (None, Some(b)) => Some(ChainContext::Second(b)),
+                    (None, None) => None,
+                }
+            }
+            ChainMarker::Second(m) => self.second.context(m).map(ChainContext::Second),
+        }
+    }
+
+    fn unmark(&mut self, info: &ParserInfo, success: bool, mark: Self::Marker) {
+        match mark {
+            ChainMarker::First(m) => self.first.unmark(info, success, m),
+            ChainMarker::Second(m) => self.second.unmark(info, success, m),
+        }
+    }
+}
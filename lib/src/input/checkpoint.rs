@@ -0,0 +1,96 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::input::{Rewind, ParserInfo};
+
+/// A guard which rewinds an input to where it was created when dropped,
+/// unless committed.
+///
+/// Speculative parsing, trying a parser and backtracking if it fails, is
+/// typically written as a pair of [`Input::mark()`] and [`Rewind::rewind_to()`]
+/// calls, and every early return between the two is a chance to forget the
+/// rewind. A `Checkpoint` instead mutably borrows the input, dereferencing to
+/// it so that parsers can run on it as usual, and rewinds it when dropped. A
+/// successful parse is kept with [`Checkpoint::commit()`]. The
+/// [`transaction()`] combinator wraps a parser in a checkpoint that is
+/// committed exactly when the parser succeeds.
+///
+/// ```rust
+/// use pear::input::{Text, Checkpoint};
+/// use pear::parsers::*;
+///
+/// let mut input = Text::from("key=value");
+/// {
+///     let mut checkpoint = Checkpoint::new(&mut input);
+///     eat_slice(&mut *checkpoint, "key").unwrap();
+///     assert!(eat(&mut *checkpoint, ':').is_err());
+/// }
+///
+/// // The checkpoint wasn't committed, so "key" was not consumed.
+/// let mut checkpoint = Checkpoint::new(&mut input);
+/// assert_eq!(take_while(&mut *checkpoint, |&c| c != '=').unwrap(), "key");
+/// checkpoint.commit();
+/// assert_eq!(eat_any(&mut input).unwrap(), '=');
+/// ```
+///
+/// [`Input::mark()`]: crate::input::Input::mark()
+/// [`transaction()`]: crate::combinators::transaction()
+pub struct Checkpoint<'a, I: Rewind> {
+    input: &'a mut I,
+    info: ParserInfo,
+    marker: I::Marker,
+    committed: bool,
+}
+
+impl<'a, I: Rewind> Checkpoint<'a, I> {
+    /// Marks the current position of `input` as a checkpoint.
+    pub fn new(input: &'a mut I) -> Self {
+        Checkpoint::named(input, "checkpoint")
+    }
+
+    /// Marks the current position of `input` as a checkpoint, passing `name`
+    /// to [`Input::mark()`](crate::input::Input::mark()) and
+    /// [`Input::unmark()`](crate::input::Input::unmark()) as the name of a raw
+    /// parser.
+    pub fn named(input: &'a mut I, name: &'static str) -> Self {
+        let info = ParserInfo::new(name, true);
+        let marker = input.mark(&info);
+        Checkpoint { input, info, marker, committed: false }
+    }
+
+    /// Returns the marker of the checkpointed position.
+    pub fn marker(&self) -> &I::Marker {
+        &self.marker
+    }
+
+    /// Keeps everything consumed since the checkpoint was created.
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+
+    /// Rewinds the input to the checkpoint. Equivalent to dropping `self`.
+    pub fn rollback(self) { }
+}
+
+impl<I: Rewind> Deref for Checkpoint<'_, I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        self.input
+    }
+}
+
+impl<I: Rewind> DerefMut for Checkpoint<'_, I> {
+    fn deref_mut(&mut self) -> &mut I {
+        self.input
+    }
+}
+
+impl<I: Rewind> Drop for Checkpoint<'_, I> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.input.rewind_to(&self.marker);
+        }
+
+        self.input.unmark(&self.info, self.committed, self.marker);
+    }
+}
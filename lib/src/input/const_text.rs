@@ -0,0 +1,199 @@
+/// A class of ASCII bytes, matched by [`ConstText::eat_if()`],
+/// [`ConstText::skip_while()`], and [`ConstText::take_while()`].
+///
+/// Closures cannot be called in `const fn`, so classes are described by value
+/// instead. Non-ASCII bytes never belong to any class, ensuring that consuming
+/// a run of bytes in a class always ends on a `char` boundary.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AsciiClass {
+    /// `0` through `9`.
+    Digit,
+    /// `0` through `9`, `a` through `f`, and `A` through `F`.
+    HexDigit,
+    /// `a` through `z` and `A` through `Z`.
+    Alpha,
+    /// `Digit` or `Alpha`.
+    Alphanumeric,
+    /// Space, tab, line feed, form feed, and carriage return.
+    Whitespace,
+    /// Bytes in the inclusive range `start..=end`.
+    Range(u8, u8),
+    /// Any of the given bytes.
+    OneOf(&'static [u8]),
+}
+
+impl AsciiClass {
+    /// Returns `true` if `byte` belongs to this class.
+    pub const fn contains(&self, byte: u8) -> bool {
+        if !byte.is_ascii() {
+            return false;
+        }
+
+        match *self {
+            AsciiClass::Digit => byte.is_ascii_digit(),
+            AsciiClass::HexDigit => byte.is_ascii_hexdigit(),
+            AsciiClass::Alpha => byte.is_ascii_alphabetic(),
+            AsciiClass::Alphanumeric => byte.is_ascii_alphanumeric(),
+            AsciiClass::Whitespace => byte.is_ascii_whitespace(),
+            AsciiClass::Range(start, end) => start <= byte && byte <= end,
+            AsciiClass::OneOf(bytes) => {
+                let mut i = 0;
+                while i < bytes.len() {
+                    if bytes[i] == byte {
+                        return true;
+                    }
+
+                    i += 1;
+                }
+
+                false
+            }
+        }
+    }
+}
+
+/// A minimal string input whose primitive parsers are `const fn`.
+///
+/// `ConstText` makes it possible to validate or decompose string literals at
+/// compile time, for instance, to parse an embedded version string into a
+/// `const`. Since trait methods cannot be `const`, `ConstText` does not
+/// implement [`Input`](crate::input::Input); instead, it provides `const`
+/// analogs of the [`eat`](crate::parsers::eat),
+/// [`eat_slice`](crate::parsers::eat_slice), and
+/// [`skip_while`](crate::parsers::skip_while) parsers as inherent methods. On
+/// failure, nothing is consumed. Use [`Text`](crate::input::Text) for parsing
+/// at runtime.
+///
+/// ```rust
+/// use pear::input::{ConstText, AsciiClass};
+///
+/// const fn number(input: &mut ConstText<'_>) -> u32 {
+///     let digits = input.take_while(AsciiClass::Digit).as_bytes();
+///     assert!(!digits.is_empty(), "expected a number");
+///
+///     let (mut i, mut value) = (0, 0);
+///     while i < digits.len() {
+///         value = value * 10 + (digits[i] - b'0') as u32;
+///         i += 1;
+///     }
+///
+///     value
+/// }
+///
+/// const fn version(string: &str) -> (u32, u32) {
+///     let mut input = ConstText::new(string);
+///     let major = number(&mut input);
+///     assert!(input.eat(b'.').is_some(), "expected '.'");
+///     let minor = number(&mut input);
+///     assert!(input.is_empty(), "unexpected trailing input");
+///     (major, minor)
+/// }
+///
+/// const VERSION: (u32, u32) = version("1.42");
+/// assert_eq!(VERSION, (1, 42));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConstText<'a> {
+    current: &'a str,
+    start: &'a str,
+}
+
+impl<'a> ConstText<'a> {
+    /// Creates a new input over `string`.
+    pub const fn new(string: &'a str) -> Self {
+        ConstText { current: string, start: string }
+    }
+
+    /// Returns the remaining, unconsumed input.
+    pub const fn current(&self) -> &'a str {
+        self.current
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub const fn position(&self) -> usize {
+        self.start.len() - self.current.len()
+    }
+
+    /// Returns `true` if the input has been fully consumed.
+    pub const fn is_empty(&self) -> bool {
+        self.current.is_empty()
+    }
+
+    /// Returns the current byte, if there is one.
+    pub const fn peek(&self) -> Option<u8> {
+        match self.current.as_bytes() {
+            [byte, ..] => Some(*byte),
+            [] => None,
+        }
+    }
+
+    /// Consumes the first `n` bytes of the input, returning them.
+    const fn advance(&mut self, n: usize) -> &'a str {
+        let (consumed, rest) = self.current.split_at(n);
+        self.current = rest;
+        consumed
+    }
+
+    /// Eats the current byte if it is `byte`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte` is not ASCII.
+    pub const fn eat(&mut self, byte: u8) -> Option<u8> {
+        assert!(byte.is_ascii(), "ConstText::eat() requires an ASCII byte");
+        match self.peek() {
+            Some(b) if b == byte => {
+                self.advance(1);
+                Some(b)
+            }
+            _ => None
+        }
+    }
+
+    /// Eats the current byte if it belongs to `class`.
+    pub const fn eat_if(&mut self, class: AsciiClass) -> Option<u8> {
+        match self.peek() {
+            Some(b) if class.contains(b) => {
+                self.advance(1);
+                Some(b)
+            }
+            _ => None
+        }
+    }
+
+    /// Eats the current slice if it is `slice`.
+    pub const fn eat_slice(&mut self, slice: &str) -> Option<&'a str> {
+        let (current, slice) = (self.current.as_bytes(), slice.as_bytes());
+        if current.len() < slice.len() {
+            return None;
+        }
+
+        let mut i = 0;
+        while i < slice.len() {
+            if current[i] != slice[i] {
+                return None;
+            }
+
+            i += 1;
+        }
+
+        Some(self.advance(slice.len()))
+    }
+
+    /// Skips bytes while they belong to `class`. Returns the number of skipped
+    /// bytes.
+    pub const fn skip_while(&mut self, class: AsciiClass) -> usize {
+        self.take_while(class).len()
+    }
+
+    /// Takes bytes while they belong to `class`, returning them.
+    pub const fn take_while(&mut self, class: AsciiClass) -> &'a str {
+        let bytes = self.current.as_bytes();
+        let mut n = 0;
+        while n < bytes.len() && class.contains(bytes[n]) {
+            n += 1;
+        }
+
+        self.advance(n)
+    }
+}
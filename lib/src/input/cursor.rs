@@ -1,6 +1,6 @@
-use std::fmt::Debug;
+use core::fmt::Debug;
 
-use crate::input::{Input, Show, Rewind, ParserInfo};
+use crate::input::{Input, Show, Rewind, Position, ParserInfo, FastScan, FastPredicate};
 
 pub struct Cursor<'a, T> {
     pub start: &'a [T],
@@ -19,6 +19,12 @@ impl<'a, T: PartialEq + Show> Rewind for Cursor<'a, T> {
     }
 }
 
+impl<T: PartialEq + Show> Position for Cursor<'_, T> {
+    fn position(&self) -> usize {
+        self.start.len() - self.items.len()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub struct Extent<'a, T> {
     pub start: usize,
@@ -27,7 +33,7 @@ pub struct Extent<'a, T> {
 }
 
 impl<T: Show> Show for Extent<'_, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}:{} {}", self.start, self.end, &self.values as &dyn Show)
     }
 }
@@ -53,6 +59,12 @@ impl<'a, T: PartialEq + Show> Input for Cursor<'a, T> {
         self.items.get(..n)
     }
 
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        self.items.get(offset..offset.checked_add(len)?)
+    }
+
     /// Checks if the current token fulfills `cond`.
     fn peek<F>(&mut self, mut cond: F) -> bool
         where F: FnMut(&Self::Token) -> bool
@@ -101,7 +113,7 @@ impl<'a, T: PartialEq + Show> Input for Cursor<'a, T> {
         where F: FnMut(&Self::Token) -> bool
     {
         let matches = self.items.iter()
-            .skip_while(cond)
+            .take_while(cond)
             .count();
 
         let value = &self.items[..matches];
@@ -135,3 +147,12 @@ impl<'a, T: PartialEq + Show> Input for Cursor<'a, T> {
         Some(Extent { start: *mark, end, values })
     }
 }
+
+impl FastScan for Cursor<'_, u8> {
+    fn scan<P: FastPredicate + ?Sized>(&mut self, pred: &P) -> Self::Many {
+        let n = pred.prefix_len(self.items);
+        let value = &self.items[..n];
+        self.items = &self.items[n..];
+        value
+    }
+}
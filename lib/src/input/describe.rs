@@ -0,0 +1,91 @@
+use core::fmt;
+
+use crate::input::Show;
+
+/// A token or slice with a human-readable description, like "a digit" or
+/// "string literal".
+///
+/// Errors render tokens and slices with [`Show`]. For characters and strings,
+/// that's the value itself, but for the tokens of token-stream inputs, which
+/// typically implement `Show` with [`impl_show_with!`]`(Debug, ..)`, it's
+/// `Debug` output like `Str("hi")` or `LeftParen`. Token types can instead
+/// implement `Describe` and derive `Show` from it, either with
+/// [`impl_show_with!`]`(Describe, ..)` or, for enums, with
+/// [`#[derive(Describe)]`](crate::macros#derive-macros):
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Cursor, Result};
+/// use pear::macros::{parser, parse, Describe};
+/// use pear::parsers::*;
+///
+/// #[derive(Debug, PartialEq, Describe)]
+/// enum Token {
+///     #[describe("an integer")]
+///     Int(i64),
+///     #[describe("`+`")]
+///     Plus,
+///     Semicolon,
+/// }
+///
+/// #[parser]
+/// fn sum<'a>(input: &mut Cursor<'a, Token>) -> Result<(), Cursor<'a, Token>> {
+///     eat_any()?;
+///     eat(&Token::Plus)?;
+///     eat_any()?;
+/// }
+///
+/// let tokens = [Token::Int(1), Token::Semicolon];
+/// let error = parse!(sum: &mut Cursor::from(&tokens[..])).unwrap_err();
+/// assert_eq!(error.error.to_string(), "expected token `+` but found semicolon");
+/// ```
+///
+/// [`impl_show_with!`]: crate::macros#impl_show_with
+pub trait Describe {
+    /// Writes the description of `self` to `f`.
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl<T: Describe + ?Sized> Describe for &T {
+    #[inline(always)]
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::describe(self, f)
+    }
+}
+
+macro_rules! impl_describe_with_show {
+    ($($T:ty),* $(,)?) => ($(
+        impl Describe for $T {
+            #[inline(always)]
+            fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Show::fmt(self, f)
+            }
+        }
+    )*)
+}
+
+impl_describe_with_show! {
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize, bool,
+    alloc::string::String, char, alloc::borrow::Cow<'static, str>,
+}
+
+impl Describe for str {
+    #[inline(always)]
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`", self)
+    }
+}
+
+impl<T: Describe> Describe for [T] {
+    /// Describes each element in turn, separated by spaces.
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 { write!(f, " ")?; }
+            value.describe(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use core::fmt;
+#[cfg(feature = "encoding")] use alloc::{borrow::Cow, string::String};
+
+use crate::input::Text;
+
+/// A text encoding.
+///
+/// Text files often begin with a byte order mark (BOM) identifying their
+/// encoding. [`Encoding::sniff()`] detects one, and [`Text::from_bytes()`]
+/// strips one from UTF-8 text before parsing it. With the `encoding` feature,
+/// [`decode()`] also transcodes UTF-16 and Latin-1 text to UTF-8.
+///
+/// Transcoding is eager, not streaming: the entire input is converted into a
+/// `String` before parsing begins, so decoding UTF-16 or Latin-1 text reads
+/// every byte and allocates up to twice its size. UTF-8 text is never
+/// copied.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Utf8,
+    /// UTF-16, little-endian.
+    Utf16Le,
+    /// UTF-16, big-endian.
+    Utf16Be,
+    /// ISO-8859-1, in which every byte is the character of the same value.
+    /// Never identified by a BOM.
+    Latin1,
+}
+
+impl Encoding {
+    /// Returns the encoding identified by the BOM that `bytes` begins with,
+    /// and the length of the BOM, or `None` if `bytes` doesn't begin with
+    /// one.
+    ///
+    /// ```rust
+    /// use pear::input::Encoding;
+    ///
+    /// assert_eq!(Encoding::sniff(b"\xEF\xBB\xBFhi"), Some((Encoding::Utf8, 3)));
+    /// assert_eq!(Encoding::sniff(b"\xFF\xFEh\0i\0"), Some((Encoding::Utf16Le, 2)));
+    /// assert_eq!(Encoding::sniff(b"hi"), None);
+    /// ```
+    pub fn sniff(bytes: &[u8]) -> Option<(Encoding, usize)> {
+        match bytes {
+            [0xEF, 0xBB, 0xBF, ..] => Some((Encoding::Utf8, 3)),
+            [0xFF, 0xFE, ..] => Some((Encoding::Utf16Le, 2)),
+            [0xFE, 0xFF, ..] => Some((Encoding::Utf16Be, 2)),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the encoding.
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Latin1 => "Latin-1",
+        }
+    }
+
+    /// Decodes `bytes`, which are in this encoding, to UTF-8. A BOM for this
+    /// encoding at the start of `bytes` is stripped. UTF-8 text is borrowed;
+    /// text in any other encoding is transcoded, all at once, into a new
+    /// `String`.
+    #[cfg(feature = "encoding")]
+    pub fn decode(self, bytes: &[u8]) -> Result<Cow<'_, str>, DecodeError> {
+        let bom = match Encoding::sniff(bytes) {
+            Some((encoding, len)) if encoding == self => len,
+            _ => 0,
+        };
+
+        let invalid = |offset| DecodeError::Invalid { encoding: self, offset };
+        match self {
+            Encoding::Utf8 => core::str::from_utf8(&bytes[bom..])
+                .map(Cow::Borrowed)
+                .map_err(|e| invalid(bom + e.valid_up_to())),
+            Encoding::Utf16Le => decode_utf16(&bytes[bom..], u16::from_le_bytes)
+                .map(Cow::Owned)
+                .map_err(|offset| invalid(bom + offset)),
+            Encoding::Utf16Be => decode_utf16(&bytes[bom..], u16::from_be_bytes)
+                .map(Cow::Owned)
+                .map_err(|offset| invalid(bom + offset)),
+            Encoding::Latin1 => Ok(Cow::Owned(bytes.iter().map(|&b| b as char).collect())),
+        }
+    }
+}
+
+/// Decodes UTF-16 `bytes`, reading code units with `unit`. Returns the offset
+/// of the first invalid code unit on error.
+#[cfg(feature = "encoding")]
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Result<String, usize> {
+    let units = bytes.chunks(2).map(|pair| match *pair {
+        [a, b] => Ok(unit([a, b])),
+        _ => Err(()),
+    });
+
+    let mut string = String::with_capacity(bytes.len() / 2);
+    let mut offset = 0;
+    for c in char::decode_utf16(units.map_while(|unit| unit.ok())) {
+        let c = c.map_err(|_| offset)?;
+        offset += c.len_utf16() * 2;
+        string.push(c);
+    }
+
+    match offset == bytes.len() {
+        true => Ok(string),
+        false => Err(offset),
+    }
+}
+
+/// Decodes `bytes` to UTF-8 in the encoding identified by their BOM, or as
+/// UTF-8 if they don't begin with one, stripping the BOM. UTF-8 text is
+/// borrowed; UTF-16 text is transcoded, all at once, into a new `String`.
+///
+/// ```rust
+/// use pear::input::{Text, Encoding, decode};
+/// use pear::parsers::*;
+///
+/// let bytes = b"\xFE\xFF\0h\0i";
+/// let text = decode(bytes).unwrap();
+/// assert_eq!(take_while(&mut Text::from(&*text), |_| true).unwrap(), "hi");
+///
+/// let text = Encoding::Latin1.decode(b"caf\xE9").unwrap();
+/// assert_eq!(text, "café");
+/// ```
+#[cfg(feature = "encoding")]
+pub fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, DecodeError> {
+    Encoding::sniff(bytes).map_or(Encoding::Utf8, |(encoding, _)| encoding).decode(bytes)
+}
+
+/// An error decoding bytes to text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The bytes aren't valid in `encoding`, starting at byte `offset`.
+    Invalid { encoding: Encoding, offset: usize },
+    /// The bytes are in an encoding that would need to be transcoded, which
+    /// requires the `encoding` feature.
+    Unsupported(Encoding),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Invalid { encoding, offset } => {
+                write!(f, "invalid {} at byte {}", encoding.name(), offset)
+            }
+            DecodeError::Unsupported(encoding) => {
+                write!(f, "{} text must be transcoded", encoding.name())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError { }
+
+impl<'a> Text<'a> {
+    /// Returns a `Text` input over the UTF-8 text in `bytes`, stripping a
+    /// leading BOM, if any. Fails if `bytes` aren't UTF-8 or begin with a BOM
+    /// for another encoding; use [`decode()`] to transcode those.
+    ///
+    /// ```rust
+    /// use pear::input::{Text, DecodeError, Encoding};
+    /// use pear::parsers::*;
+    ///
+    /// let mut input = Text::from_bytes(b"\xEF\xBB\xBFkey = value").unwrap();
+    /// assert_eq!(take_while(&mut input, |&c| c != ' ').unwrap(), "key");
+    ///
+    /// let error = Text::from_bytes(b"\xFF\xFEk\0").unwrap_err();
+    /// assert_eq!(error, DecodeError::Unsupported(Encoding::Utf16Le));
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Text<'a>, DecodeError> {
+        let bom = match Encoding::sniff(bytes) {
+            Some((Encoding::Utf8, len)) => len,
+            Some((encoding, _)) => return Err(DecodeError::Unsupported(encoding)),
+            None => 0,
+        };
+
+        core::str::from_utf8(&bytes[bom..])
+            .map(Text::from)
+            .map_err(|e| DecodeError::Invalid { encoding: Encoding::Utf8, offset: bom + e.valid_up_to() })
+    }
+}
@@ -0,0 +1,229 @@
+use alloc::{vec, boxed::Box};
+
+use crate::error::{Error, ErrorCode, ParseError, Hints};
+use crate::input::{Input, Show, Rewind, Position, Slice, Result};
+
+/// An input which can only be consumed so much, protecting against grammars
+/// that take superlinear time on adversarial input.
+///
+/// A `Fueled` input starts with a budget of `fuel`. Every token consumed from
+/// it burns one unit of fuel, and every slice consumed burns as many units as
+/// its length. Rewinding doesn't refund fuel, so a grammar that backtracks
+/// over the same input again and again burns fuel each time. Peeking is free.
+///
+/// Once there isn't enough fuel left for a consumption, it is refused and the
+/// input is [exhausted](Fueled::is_exhausted()). An exhausted input is
+/// [halted](Input::halted()): it refuses to be read at all, the next parser to
+/// fail fails with a committed error with the code
+/// [`ErrorCode::FuelExhausted`], so no alternative is tried, and
+/// [`eof()`](crate::parsers::eof()) fails. A parser that was cut short by the
+/// refusal may nonetheless succeed with a truncated value, so pass the result
+/// of a parse through [`Fueled::check()`], which fails once the input is
+/// exhausted whether or not the parse succeeded.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Fueled, Result};
+/// use pear::error::ErrorCode;
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = Fueled<Text<'a>>;
+///
+/// #[parser]
+/// fn word<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+///     take_some_while(|c: &char| c.is_alphabetic())?
+/// }
+///
+/// let mut input = Fueled::new(Text::from("hello"), 100);
+/// assert_eq!(parse!(word: &mut input).unwrap(), "hello");
+/// assert_eq!(input.remaining(), 95);
+///
+/// let mut input = Fueled::new(Text::from("hello"), 3);
+/// let result = parse!(word: &mut input);
+/// let error = input.check(result).unwrap_err();
+/// assert_eq!(error.code(), ErrorCode::FuelExhausted);
+/// assert!(error.committed);
+/// ```
+#[derive(Debug)]
+pub struct Fueled<I> {
+    input: I,
+    remaining: usize,
+    exhausted: bool,
+}
+
+impl<I: Input> Fueled<I> {
+    /// Wraps `input` with a budget of `fuel` units.
+    pub fn new(input: I, fuel: usize) -> Self {
+        Fueled { input, remaining: fuel, exhausted: false }
+    }
+
+    /// Returns the number of units of fuel left.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns `true` if a consumption was refused for lack of fuel.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Adds `fuel` units to the budget and clears the exhausted state.
+    pub fn refuel(&mut self, fuel: usize) {
+        self.remaining = self.remaining.saturating_add(fuel);
+        self.exhausted = false;
+    }
+
+    /// If the input is exhausted, fails with a committed error reading "fuel
+    /// exhausted" and with the code [`ErrorCode::FuelExhausted`], even if
+    /// `result` is a success: the value may have been truncated by a refused
+    /// consumption. The contexts and hints of an original error are kept.
+    /// Otherwise, returns `result` unchanged.
+    pub fn check<O, E: Error<Self>>(&self, result: Result<O, Self, E>) -> Result<O, Self, E> {
+        if !self.exhausted {
+            return result;
+        }
+
+        let (contexts, hints) = match result {
+            Ok(_) => (vec![], Hints::new()),
+            Err(e) => (e.contexts, e.hints),
+        };
+
+        Err(ParseError {
+            error: E::from_context("fuel exhausted".into(), None),
+            code: Some(Box::new(ErrorCode::FuelExhausted)),
+            committed: true,
+            incomplete: None,
+            contexts,
+            hints,
+        })
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    /// Burns `n` units of fuel if there are that many and the input isn't
+    /// exhausted, returning `true`. Otherwise, marks the input exhausted and
+    /// returns `false`.
+    fn burn(remaining: &mut usize, exhausted: &mut bool, n: usize) -> bool {
+        if *exhausted {
+            return false;
+        }
+
+        match remaining.checked_sub(n) {
+            Some(left) => { *remaining = left; true }
+            None => { *exhausted = true; false }
+        }
+    }
+}
+
+impl<'a, I> Slice<Fueled<I>> for &str where I: Input<Slice = &'a str> { }
+
+impl<'a, T, I> Slice<Fueled<I>> for &[T]
+    where T: PartialEq + Show + 'a, I: Input<Slice = &'a [T]> { }
+
+impl<I: Rewind> Rewind for Fueled<I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position> Position for Fueled<I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input> Input for Fueled<I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker, Context;
+        fn mark, context, unmark, needed;
+    }
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.input.token().filter(|_| !self.exhausted)
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.input.slice(n).filter(|_| !self.exhausted)
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        self.input.peek_slice_at(offset, len).filter(|_| !self.exhausted)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        !self.exhausted && self.input.peek(cond)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        !self.exhausted && self.input.peek_slice(n, cond)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let (remaining, exhausted) = (&mut self.remaining, &mut self.exhausted);
+        self.input.eat(|t| cond(t) && Self::burn(remaining, exhausted, 1))
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let (remaining, exhausted) = (&mut self.remaining, &mut self.exhausted);
+        self.input.eat_slice(n, |s| cond(s) && Self::burn(remaining, exhausted, n))
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let (remaining, exhausted) = (&mut self.remaining, &mut self.exhausted);
+        self.input.take(|t| cond(t) && Self::burn(remaining, exhausted, 1))
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let (remaining, exhausted) = (&mut self.remaining, &mut self.exhausted);
+        self.input.skip(|t| cond(t) && Self::burn(remaining, exhausted, 1))
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        !self.exhausted && self.input.has(n)
+    }
+
+    fn halted(&self) -> Option<ErrorCode> {
+        match self.exhausted {
+            true => Some(ErrorCode::FuelExhausted),
+            false => self.input.halted(),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use unicode_segmentation::{UnicodeSegmentation, GraphemeCursor};
+
+use crate::input::{Input, Rewind, Position, Text, Span, Token, Slice, ParserInfo};
+
+/// Returns the length in bytes of the grapheme cluster at the start of `s`.
+fn grapheme_len(s: &str) -> usize {
+    s.graphemes(true).next().map_or(0, str::len)
+}
+
+/// A text input whose tokens are grapheme clusters rather than `char`s.
+///
+/// A grapheme cluster is a user-perceived character, such as `e` followed by a
+/// combining acute accent, a flag made of two regional indicators, or an
+/// emoji sequence joined by zero-width joiners. `Graphemes` never splits one:
+/// each token is a `&str` of a whole cluster, and a slice which would end
+/// inside of a cluster is treated as missing. As with [`Text`], slices are
+/// measured in bytes and contexts are [`Span`]s.
+///
+/// Clusters are the extended grapheme clusters of [UAX #29], as segmented by
+/// the [`unicode-segmentation`](https://docs.rs/unicode-segmentation) crate.
+/// This input requires the `graphemes` feature.
+///
+/// [UAX #29]: https://www.unicode.org/reports/tr29/
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Graphemes, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// #[parser]
+/// fn cells<'a>(input: &mut Graphemes<'a>) -> Result<Vec<&'a str>, Graphemes<'a>> {
+///     let mut cells = vec![];
+///     while let Ok(cell) = eat_any(input) {
+///         cells.push(cell);
+///     }
+///
+///     cells
+/// }
+///
+/// let result = parse!(cells: &mut Graphemes::from("e\u{301}🇫🇷👩‍👩‍👧!"));
+/// assert_eq!(result.unwrap(), ["e\u{301}", "🇫🇷", "👩‍👩‍👧", "!"]);
+/// ```
+#[derive(Debug)]
+pub struct Graphemes<'a> {
+    current: &'a str,
+    start: &'a str,
+}
+
+impl<'a> Graphemes<'a> {
+    /// Returns `true` if `n` bytes into the current input is the boundary of
+    /// a grapheme cluster.
+    fn is_boundary(&self, n: usize) -> bool {
+        let mut cursor = GraphemeCursor::new(self.position() + n, self.start.len(), true);
+        cursor.is_boundary(self.start, 0).unwrap_or(false)
+    }
+}
+
+impl<'a> From<&'a str> for Graphemes<'a> {
+    #[inline(always)]
+    fn from(start: &'a str) -> Graphemes<'a> {
+        Graphemes { start, current: start }
+    }
+}
+
+impl<'a, 'b> Token<Graphemes<'a>> for &'b str { }
+
+impl<'a, 'b> Slice<Graphemes<'a>> for &'b str { }
+
+impl Rewind for Graphemes<'_> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.current = &self.start[*marker..];
+    }
+}
+
+impl Position for Graphemes<'_> {
+    fn position(&self) -> usize {
+        self.start.len() - self.current.len()
+    }
+}
+
+impl<'a> Input for Graphemes<'a> {
+    type Token = &'a str;
+    type Slice = &'a str;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = Span<'a>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        match grapheme_len(self.current) {
+            0 => None,
+            n => Some(&self.current[..n]),
+        }
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        let slice = self.current.get(..n)?;
+        match self.is_boundary(n) {
+            true => Some(slice),
+            false => None,
+        }
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        let end = offset.checked_add(len)?;
+        let slice = self.current.get(offset..end)?;
+        match self.is_boundary(offset) && self.is_boundary(end) {
+            true => Some(slice),
+            false => None,
+        }
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.current = &self.current[token.len()..];
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.current = &self.current[n..];
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut consumed = 0;
+        loop {
+            let n = grapheme_len(&self.current[consumed..]);
+            if n == 0 || !cond(&&self.current[consumed..consumed + n]) {
+                break;
+            }
+
+            consumed += n;
+        }
+
+        let value = &self.current[..consumed];
+        self.current = &self.current[consumed..];
+        value
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut skipped = 0;
+        while self.eat(&mut cond).is_some() {
+            skipped += 1;
+        }
+
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.current.len() >= n
+    }
+
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.position()
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        let mut text = Text::from(self.start);
+        text.rewind_to(&self.position());
+        text.context(mark)
+    }
+}
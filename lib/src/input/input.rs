@@ -1,4 +1,7 @@
+use core::ops::Range;
+
 use crate::input::{Show, Length};
+use crate::error::{Needed, ErrorCode};
 
 pub trait Token<I: Input>: Show + PartialEq<I::Token> { }
 
@@ -8,10 +11,71 @@ impl<I: Input> Token<I> for I::Token { }
 
 impl<I: Input> Slice<I> for I::Slice { }
 
+/// The name and kind of a parser, passed to [`Input::mark()`] and
+/// [`Input::unmark()`]. Construct one with [`ParserInfo::new()`]; more fields
+/// may be added in the future.
 #[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
 pub struct ParserInfo {
     pub name: &'static str,
     pub raw: bool,
+    /// Where the parser is defined. Set for functions attributed with
+    /// `#[parser]`; `None` for parsers built at runtime.
+    pub source: Option<&'static ParserSource>,
+}
+
+impl ParserInfo {
+    /// Returns the info of a parser named `name` with no source.
+    pub const fn new(name: &'static str, raw: bool) -> Self {
+        ParserInfo { name, raw, source: None }
+    }
+
+    /// Returns `self` with the source `source`.
+    pub const fn with_source(self, source: &'static ParserSource) -> Self {
+        ParserInfo { source: Some(source), ..self }
+    }
+
+    /// Returns the [stable ID](ParserSource::id) of the parser, if it has a
+    /// source.
+    pub fn id(&self) -> Option<u64> {
+        self.source.map(|source| source.id)
+    }
+}
+
+/// Where a `#[parser]` function is defined, generated by the attribute.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ParserSource {
+    /// The path of the module defining the parser, as by `module_path!()`.
+    pub module: &'static str,
+    /// The file defining the parser, as by `file!()`.
+    pub file: &'static str,
+    /// The line the parser is defined on.
+    pub line: u32,
+    /// The column the parser is defined at.
+    pub column: u32,
+    /// A hash of the parser's module path and name, stable across builds and
+    /// unique unless the parser is renamed or moved to another module. Suited
+    /// to keying memoization tables and filtering traces.
+    pub id: u64,
+}
+
+impl ParserSource {
+    #[doc(hidden)]
+    pub const fn new(module: &'static str, name: &'static str, file: &'static str, line: u32, column: u32) -> Self {
+        // FNV-1a over `module::name`.
+        const fn hash(mut hash: u64, bytes: &[u8]) -> u64 {
+            let mut i = 0;
+            while i < bytes.len() {
+                hash = (hash ^ bytes[i] as u64).wrapping_mul(0x100000001b3);
+                i += 1;
+            }
+
+            hash
+        }
+
+        let id = hash(hash(hash(0xcbf29ce484222325, module.as_bytes()), b"::"), name.as_bytes());
+        ParserSource { module, file, line, column, id }
+    }
 }
 
 pub trait Rewind: Sized + Input {
@@ -19,6 +83,55 @@ pub trait Rewind: Sized + Input {
     fn rewind_to(&mut self, marker: &Self::Marker);
 }
 
+pub trait Position: Input {
+    /// Returns the offset of the current position from the start of the input:
+    /// in bytes for text inputs and in tokens otherwise.
+    fn position(&self) -> usize;
+}
+
+/// A value along with the span of input it was parsed from.
+///
+/// Spans are ranges of [`Position`]s. `#[parser(spanned)]` parsers return a
+/// `Spanned` value: the body of a parser declared to return
+/// `Result<Spanned<T>, I>` evaluates to a `T`, and the span of input it
+/// consumed is captured automatically.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Range<usize>,
+}
+
+impl<T> Spanned<T> {
+    /// Pairs `value` with `span`.
+    pub fn new(value: T, span: Range<usize>) -> Self {
+        Spanned { value, span }
+    }
+
+    /// Returns the value, discarding the span.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Maps the value with `f`, keeping the span.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Spanned<U> {
+        Spanned { value: f(self.value), span: self.span }
+    }
+}
+
+impl<T> core::ops::Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Show> Show for Spanned<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Show::fmt(&self.value, f)
+    }
+}
+
 pub trait Input: Sized {
     type Token: Token<Self>;
     type Slice: Slice<Self>;
@@ -41,6 +154,23 @@ pub trait Input: Sized {
     fn peek_slice<F>(&mut self, n: usize, cond: F) -> bool
         where F: FnMut(&Self::Slice) -> bool;
 
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything. Offsets
+    /// and sizes are measured in the same units as [`Input::slice()`].
+    ///
+    /// This allows a parser to inspect several upcoming tokens, as an LL(k)
+    /// grammar requires, without marking and rewinding the input.
+    ///
+    /// The default implementation can only look at the current slice: it
+    /// returns `self.slice(len)` if `offset` is `0` and `None` otherwise.
+    /// Inputs that can look further ahead override it.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        match offset {
+            0 => self.slice(len),
+            _ => None,
+        }
+    }
+
     /// Checks if the current token fulfills `cond`. If so, the token is
     /// consumed and returned. Otherwise, returns `None`.
     fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
@@ -77,4 +207,23 @@ pub trait Input: Sized {
 
     #[allow(unused_variables)]
     fn unmark(&mut self, info: &ParserInfo, success: bool, mark: Self::Marker) { }
+
+    /// Returns how much more input is needed if the input can grow, like a
+    /// stream still being received, and a parser looked past its current end.
+    /// A parser that fails while more input is needed fails with an
+    /// [incomplete](crate::error::ParseError::incomplete) error. By default,
+    /// inputs can't grow, and this method returns `None`.
+    fn needed(&self) -> Option<Needed> {
+        None
+    }
+
+    /// Returns the code of the error parsing must stop with if the input
+    /// refuses to be read any further, like a [`Fueled`](crate::input::Fueled)
+    /// input out of fuel. A parser that fails on a halted input fails with a
+    /// committed error with that code, so that no alternative is tried, and
+    /// [`eof()`](crate::parsers::eof()) fails on one. By default, inputs never
+    /// halt, and this method returns `None`.
+    fn halted(&self) -> Option<ErrorCode> {
+        None
+    }
 }
@@ -0,0 +1,223 @@
+use core::convert::TryFrom;
+
+use alloc::vec::Vec;
+
+use crate::input::{Input, Show, Rewind, Position, Slice, Text};
+
+/// Identifies a file registered in an [`InputSet`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(pub(crate) u32);
+
+impl FileId {
+    /// Returns the index of the file in the order files were registered.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A set of named files that one parse session reads from.
+///
+/// A format with includes or imports parses many files as one unit. Files are
+/// registered with an `InputSet`, which identifies each with a [`FileId`], and
+/// parsed with the [`FileInput`] returned by [`InputSet::input()`], whose
+/// contexts identify the file they came from. Inputs borrow the text of their
+/// file, not the set, so more files can be registered as they are discovered,
+/// while outputs from earlier files are still alive.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Input as _, InputSet, FileInput, Text, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = FileInput<'a, Text<'a>>;
+///
+/// // A file is a list of words, each on its own line.
+/// #[parser]
+/// fn words<'a>(input: &mut Input<'a>) -> Result<Vec<&'a str>, Input<'a>> {
+///     let mut words = vec![];
+///     while input.token().is_some() {
+///         let word = take_some_while(|c: &char| c.is_alphabetic())?;
+///         eat('\n')?;
+///         words.push(word);
+///     }
+///
+///     words
+/// }
+///
+/// let mut files = InputSet::new();
+/// let main = files.add("main.txt", "a\nb\n");
+/// let lib = files.add("lib.txt", "c\nd!\n");
+///
+/// assert_eq!(parse!(words: &mut files.input(main)).unwrap(), ["a", "b"]);
+///
+/// let error = parse!(words: &mut files.input(lib)).unwrap_err();
+/// let context = error.contexts.last().unwrap().context.unwrap();
+/// assert_eq!((context.file, context.name), (lib, "lib.txt"));
+/// assert_eq!(context.context.end, (2, 2, 3));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InputSet<'s> {
+    files: Vec<(&'s str, &'s str)>,
+}
+
+impl<'s> InputSet<'s> {
+    /// Returns a set with no files.
+    pub fn new() -> Self {
+        InputSet { files: Vec::new() }
+    }
+
+    /// Registers the file named `name` whose contents are `text`, returning
+    /// its identifier. Names need not be unique.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` files are registered.
+    pub fn add(&mut self, name: &'s str, text: &'s str) -> FileId {
+        let id = u32::try_from(self.files.len()).expect("too many files");
+        self.files.push((name, text));
+        FileId(id)
+    }
+
+    /// Returns the identifier of the first file registered as `name`, if any.
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.files.iter()
+            .position(|&(n, _)| n == name)
+            .map(|i| FileId(i as u32))
+    }
+
+    /// Returns the name of the file `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` was not returned by this set.
+    pub fn name(&self, id: FileId) -> &'s str {
+        self.files[id.index()].0
+    }
+
+    /// Returns the contents of the file `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` was not returned by this set.
+    pub fn text(&self, id: FileId) -> &'s str {
+        self.files[id.index()].1
+    }
+
+    /// Returns the number of registered files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if no files are registered.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns the identifiers of the registered files, in order.
+    pub fn ids(&self) -> impl Iterator<Item = FileId> {
+        (0..self.files.len() as u32).map(FileId)
+    }
+
+    /// Returns an input over the contents of the file `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` was not returned by this set.
+    pub fn input(&self, id: FileId) -> FileInput<'s, Text<'s>> {
+        FileInput::new(id, self.name(id), Text::from(self.text(id)))
+    }
+}
+
+/// The context type of a [`FileInput`]: the inner input's context along with
+/// the file it lies in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FileContext<'s, C> {
+    /// The identifier of the file.
+    pub file: FileId,
+    /// The name of the file.
+    pub name: &'s str,
+    /// The context within the file.
+    pub context: C,
+}
+
+impl<C: Show> Show for FileContext<'_, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}", self.name, &self.context as &dyn Show)
+    }
+}
+
+/// An input over one file of a parse session whose contexts identify the file.
+///
+/// Apart from its contexts, which are [`FileContext`]s, a `FileInput` behaves
+/// exactly like its inner input. It is usually obtained from an [`InputSet`],
+/// but any input can be wrapped with [`FileInput::new()`].
+#[derive(Debug)]
+pub struct FileInput<'s, I> {
+    input: I,
+    file: FileId,
+    name: &'s str,
+}
+
+impl<'s, I: Input> FileInput<'s, I> {
+    /// Wraps `input`, the contents of the file `file` named `name`.
+    pub fn new(file: FileId, name: &'s str, input: I) -> Self {
+        FileInput { input, file, name }
+    }
+
+    /// Returns the identifier of the file.
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    /// Returns the name of the file.
+    pub fn name(&self) -> &'s str {
+        self.name
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<'a, I> Slice<FileInput<'_, I>> for &str where I: Input<Slice = &'a str> { }
+
+impl<'a, T, I> Slice<FileInput<'_, I>> for &[T]
+    where T: PartialEq + Show + 'a, I: Input<Slice = &'a [T]> { }
+
+impl<I: Rewind> Rewind for FileInput<'_, I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position> Position for FileInput<'_, I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<'s, I: Input> Input for FileInput<'s, I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           mark, unmark, needed, halted;
+    }
+
+    type Context = FileContext<'s, I::Context>;
+
+    /// Returns the inner input's context from `mark` to the current position,
+    /// along with the file.
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        let context = self.input.context(mark)?;
+        Some(FileContext { file: self.file, name: self.name, context })
+    }
+}
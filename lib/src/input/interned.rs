@@ -0,0 +1,204 @@
+#[cfg(feature = "std")] use std::collections::HashMap;
+#[cfg(not(feature = "std"))] use alloc::collections::BTreeMap as HashMap;
+use alloc::{vec::Vec, boxed::Box};
+
+use crate::input::{Input, Show, Rewind, Position, Slice};
+
+/// A compact handle to a string interned by a [`StringInterner`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the index of the symbol: the number of distinct strings that
+    /// were interned before it.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Maps slices of input to symbols, as used by an [`Interned`] input.
+///
+/// Implement this trait to plug in an existing interner. [`StringInterner`]
+/// is a simple implementation for text inputs.
+pub trait Interner<S> {
+    /// The symbol identifying an interned slice.
+    type Symbol;
+
+    /// Returns the symbol for `slice`, the same for every equal slice.
+    fn intern(&mut self, slice: S) -> Self::Symbol;
+}
+
+/// An input that can intern its slices: see [`Interned`].
+pub trait Intern: Input {
+    /// The symbol identifying an interned slice.
+    type Symbol;
+
+    /// Returns the symbol for `slice`, the same for every equal slice.
+    fn intern(&mut self, slice: Self::Slice) -> Self::Symbol;
+}
+
+/// An interner of strings into [`Symbol`]s, which are resolved back into
+/// strings with [`StringInterner::resolve()`]. Each distinct string is
+/// allocated once, no matter how many times it's interned.
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    strings: Vec<Box<str>>,
+    symbols: HashMap<Box<str>, Symbol>,
+}
+
+impl StringInterner {
+    /// Returns a new, empty interner.
+    pub fn new() -> Self {
+        StringInterner::default()
+    }
+
+    /// Returns the symbol for `string` if it has been interned.
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.symbols.get(string).copied()
+    }
+
+    /// Returns the string interned as `symbol`, if it was interned by this
+    /// interner.
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.strings.get(symbol.index()).map(|string| &**string)
+    }
+
+    /// Returns the number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if no string has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl<S: AsRef<str>> Interner<S> for StringInterner {
+    type Symbol = Symbol;
+
+    fn intern(&mut self, slice: S) -> Symbol {
+        let string = slice.as_ref();
+        if let Some(symbol) = self.get(string) {
+            return symbol;
+        }
+
+        let symbol = Symbol(self.strings.len() as u32);
+        self.strings.push(string.into());
+        self.symbols.insert(string.into(), symbol);
+        symbol
+    }
+}
+
+/// An input that interns slices with an [`Interner`], so that grammars heavy
+/// in identifiers can produce compact symbols instead of allocating a
+/// `String` for every identifier they parse.
+///
+/// Slices are interned with [`Intern::intern()`], called directly on the
+/// input or via the [`interned()`](crate::combinators::interned())
+/// combinator. Interned slices stay interned when the input is rewound. Apart
+/// from interning, an `Interned` input behaves exactly like its inner input.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Interned, Intern, StringInterner, Symbol, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::{parsers::*, combinators::*};
+///
+/// type Input<'a> = Interned<Text<'a>, StringInterner>;
+///
+/// #[parser]
+/// fn word<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+///     take_some_while(|c: &char| c.is_alphabetic())?
+/// }
+///
+/// #[parser]
+/// fn ident<'a>(input: &mut Input<'a>) -> Result<Symbol, Input<'a>> {
+///     let name = word()?;
+///     input.intern(name)
+/// }
+///
+/// #[parser]
+/// fn idents<'a>(input: &mut Input<'a>) -> Result<Vec<Symbol>, Input<'a>> {
+///     let symbols: Vec<_> = series(|i| interned(i, word), ' ')?;
+///     symbols
+/// }
+///
+/// let mut input = Interned::new(Text::from("a b a"), StringInterner::new());
+/// let symbols = parse!(idents: &mut input).unwrap();
+/// assert_eq!(symbols[0], symbols[2]);
+/// assert_ne!(symbols[0], symbols[1]);
+/// assert_eq!(input.interner().resolve(symbols[1]), Some("b"));
+/// assert_eq!(input.interner().len(), 2);
+///
+/// let mut input = Interned::new(Text::from("z"), StringInterner::new());
+/// let z = parse!(ident: &mut input).unwrap();
+/// assert_eq!(input.interner().resolve(z), Some("z"));
+/// ```
+#[derive(Debug)]
+pub struct Interned<I, N> {
+    input: I,
+    interner: N,
+}
+
+impl<I: Input, N> Interned<I, N> {
+    /// Wraps `input`, interning slices with `interner`.
+    pub fn new(input: I, interner: N) -> Self {
+        Interned { input, interner }
+    }
+
+    /// Returns a reference to the interner.
+    pub fn interner(&self) -> &N {
+        &self.interner
+    }
+
+    /// Returns a mutable reference to the interner.
+    pub fn interner_mut(&mut self) -> &mut N {
+        &mut self.interner
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input and the interner.
+    pub fn into_inner(self) -> (I, N) {
+        (self.input, self.interner)
+    }
+}
+
+impl<I: Input, N: Interner<I::Slice>> Intern for Interned<I, N> {
+    type Symbol = N::Symbol;
+
+    fn intern(&mut self, slice: Self::Slice) -> Self::Symbol {
+        self.interner.intern(slice)
+    }
+}
+
+impl<'a, I, N> Slice<Interned<I, N>> for &str where I: Input<Slice = &'a str> { }
+
+impl<'a, T, I, N> Slice<Interned<I, N>> for &[T]
+    where T: PartialEq + Show + 'a, I: Input<Slice = &'a [T]> { }
+
+impl<I: Rewind, N> Rewind for Interned<I, N> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position, N> Position for Interned<I, N> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input, N> Input for Interned<I, N> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker, Context;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           mark, context, unmark, needed, halted;
+    }
+}
@@ -0,0 +1,220 @@
+use core::iter::Fuse;
+use core::ops::Range;
+use alloc::{vec, vec::Vec, collections::VecDeque};
+
+use crate::input::{Input, Show, Rewind, Position, Slice, ParserInfo};
+
+/// An input over the items of any iterator.
+///
+/// Items are pulled from the iterator lazily and kept in an internal lookahead
+/// buffer which grows only as large as the grammar requires: `slice(n)` and
+/// `has(n)` buffer `n` items, while `token()` buffers one. Consumed items are
+/// retained only while a marker is outstanding so that the input can be
+/// rewound to it; once every marker has been unmarked, they are dropped.
+///
+/// The context of an `IterInput` is the range of item indices, relative to the
+/// start of the iterator, between the mark and the current position.
+pub struct IterInput<I: Iterator> {
+    iter: Fuse<I>,
+    buffer: VecDeque<I::Item>,
+    /// The index of `buffer[0]` relative to the start of the iterator.
+    offset: usize,
+    /// The index of the current item relative to the start of the iterator.
+    position: usize,
+    /// The number of outstanding markers.
+    marks: usize,
+}
+
+impl<I: Iterator> IterInput<I> {
+    /// Creates a new input that reads items from `iter`.
+    pub fn new(iter: I) -> Self {
+        IterInput {
+            iter: iter.fuse(),
+            buffer: VecDeque::new(),
+            offset: 0,
+            position: 0,
+            marks: 0,
+        }
+    }
+
+    /// Returns the index of the current item relative to the start of the
+    /// iterator.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Buffers items until at least `n` are available past the current
+    /// position. Returns `true` if there are at least `n` such items.
+    fn fill(&mut self, n: usize) -> bool {
+        let needed = (self.position - self.offset) + n;
+        while self.buffer.len() < needed {
+            match self.iter.next() {
+                Some(item) => self.buffer.push_back(item),
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Returns the `i`th buffered item past the current position, if any.
+    fn get(&self, i: usize) -> Option<&I::Item> {
+        self.buffer.get((self.position - self.offset) + i)
+    }
+
+    /// Advances the current position by `n` items, dropping consumed items if
+    /// no marker is outstanding.
+    fn consume(&mut self, n: usize) {
+        self.position += n;
+        if self.marks == 0 {
+            self.buffer.drain(..(self.position - self.offset));
+            self.offset = self.position;
+        }
+    }
+}
+
+impl<I> Slice<IterInput<I>> for &[I::Item]
+    where I: Iterator, I::Item: Clone + PartialEq + Show { }
+
+impl<I: Iterator> Rewind for IterInput<I>
+    where I::Item: Clone + PartialEq + Show
+{
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        assert!(*marker >= self.offset, "marker points to a dropped item");
+        self.position = *marker;
+    }
+}
+
+impl<I: Iterator> Position for IterInput<I>
+    where I::Item: Clone + PartialEq + Show
+{
+    fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<I: Iterator> Input for IterInput<I>
+    where I::Item: Clone + PartialEq + Show
+{
+    type Token = I::Item;
+    type Slice = Vec<I::Item>;
+    type Many = Vec<I::Item>;
+
+    type Marker = usize;
+    type Context = Range<usize>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.fill(1);
+        self.get(0).cloned()
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        if !self.fill(n) {
+            return None;
+        }
+
+        Some((0..n).map(|i| self.get(i).cloned().expect("filled")).collect())
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        let end = offset.checked_add(len)?;
+        if !self.fill(end) {
+            return None;
+        }
+
+        Some((offset..end).map(|i| self.get(i).cloned().expect("filled")).collect())
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.fill(1);
+        self.get(0).map(cond).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        if self.peek(cond) {
+            let token = self.token();
+            self.consume(1);
+            return token;
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.consume(n);
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut many = vec![];
+        while let Some(token) = self.eat(&mut cond) {
+            many.push(token);
+        }
+
+        many
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut skipped = 0;
+        while self.peek(&mut cond) {
+            self.consume(1);
+            skipped += 1;
+        }
+
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.fill(n)
+    }
+
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.marks += 1;
+        self.position
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        Some(*mark..self.position)
+    }
+
+    fn unmark(&mut self, _: &ParserInfo, _: bool, _: Self::Marker) {
+        self.marks = self.marks.saturating_sub(1);
+        self.consume(0);
+    }
+}
@@ -0,0 +1,139 @@
+use core::ops::Range;
+
+use crate::error::ParseError;
+use crate::input::{Input, Show, Rewind, Position, Slice};
+
+/// An input which defers computing error contexts until they are needed.
+///
+/// When a parser fails, the context of every enclosing parser is computed and
+/// pushed onto the error. For inputs like [`Text`](crate::input::Text), whose
+/// contexts carry line and column numbers, this requires scanning the input
+/// from its start, and during backtracking, most such errors are discarded
+/// anyway. A `LazyContext` input instead records each context as the range of
+/// positions it spans, which is free to compute. The full contexts can be
+/// recovered for an error that is to be reported with
+/// [`LazyContext::resolve()`].
+///
+/// Deferring contexts makes failing parsers cheaper, but not free: an error
+/// still allocates to record its contexts and to render what was expected.
+/// To keep a speculative parser whose error is discarded from allocating at
+/// all, run it with [`opt()`](crate::combinators::opt()), which suppresses
+/// errors.
+///
+/// Apart from its contexts, a `LazyContext` input behaves exactly like its
+/// inner input. The inner input's markers must be its [`Position`]s, as those
+/// of [`Text`](crate::input::Text) and [`Cursor`](crate::input::Cursor) are.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, LazyContext, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = LazyContext<Text<'a>>;
+///
+/// #[parser]
+/// fn pair<'a>(input: &mut Input<'a>) -> Result<(char, char), Input<'a>> {
+///     let a = eat_any()?;
+///     eat('\n')?;
+///     (a, eat('b')?)
+/// }
+///
+/// let mut input = LazyContext::new(Text::from("a\nc"));
+/// let error = parse!(pair: &mut input).unwrap_err();
+/// assert_eq!(error.contexts[1].context, Some(0..2));
+///
+/// let error = input.resolve(error);
+/// let span = error.contexts[1].context.unwrap();
+/// assert_eq!((span.start, span.end), ((1, 1, 0), (2, 1, 2)));
+/// ```
+#[derive(Debug)]
+pub struct LazyContext<I> {
+    input: I,
+}
+
+impl<I: Position<Marker = usize>> LazyContext<I> {
+    /// Wraps `input`, deferring the computation of its contexts.
+    pub fn new(input: I) -> Self {
+        LazyContext { input }
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I: Rewind<Marker = usize> + Position> LazyContext<I> {
+    /// Computes the full contexts of `error`, which was produced by parsing
+    /// this input, using the inner input. The position of the input is
+    /// unchanged. Suggestions whose contexts can't be resolved are dropped.
+    pub fn resolve<E>(&mut self, error: ParseError<Range<usize>, E>) -> ParseError<I::Context, E> {
+        let current = self.input.position();
+        let input = &mut self.input;
+        let mut resolve = |range: Range<usize>| {
+            input.rewind_to(&range.end);
+            input.context(&range.start)
+        };
+
+        let contexts = error.contexts.into_iter()
+            .map(|ctxt| {
+                let context = ctxt.context.and_then(&mut resolve);
+                crate::error::ParseContext { parser: ctxt.parser, context }
+            })
+            .collect();
+
+        let hints = error.hints.into_iter()
+            .filter_map(|hint| hint.map_context(&mut resolve))
+            .collect();
+
+        self.input.rewind_to(&current);
+        ParseError {
+            error: error.error,
+            code: error.code,
+            committed: error.committed,
+            incomplete: error.incomplete,
+            contexts,
+            hints,
+        }
+    }
+}
+
+impl<'a, I> Slice<LazyContext<I>> for &str where I: Position<Marker = usize, Slice = &'a str> { }
+
+impl<'a, T, I> Slice<LazyContext<I>> for &[T]
+    where T: PartialEq + Show + 'a, I: Position<Marker = usize, Slice = &'a [T]> { }
+
+impl<I: Rewind<Marker = usize> + Position> Rewind for LazyContext<I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position<Marker = usize>> Position for LazyContext<I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Position<Marker = usize>> Input for LazyContext<I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           mark, unmark, needed, halted;
+    }
+
+    type Context = Range<usize>;
+
+    /// Returns the range of positions from `mark` to the current position.
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        Some(*mark..self.input.position())
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::{vec::Vec, string::String};
+
 /// Trait implemented for types that have a length as required by the
 /// [`Input::Slice`](crate::input::Input::Slice) associated type.
 pub trait Length {
@@ -72,3 +74,10 @@ impl Length for String {
         String::len(self)
     }
 }
+
+impl<A: Length, B: Length> Length for (A, B) {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.0.len() + self.1.len()
+    }
+}
@@ -0,0 +1,237 @@
+use alloc::{vec, vec::Vec};
+
+use crate::input::{Input, Show, Rewind, Position, Slice};
+
+/// Whether whitespace, or trivia in general, is significant to a [`Modal`]
+/// input.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Trivia is skipped before each token.
+    SkipWhitespace,
+    /// Trivia is significant: it is read like any other input.
+    KeepWhitespace,
+}
+
+/// An input with a stack of [`Mode`]s, the top of which determines whether
+/// whitespace is significant.
+///
+/// [`with_mode()`](crate::combinators::with_mode()) pushes a mode while a
+/// parser runs. [`Lexed`] is the `Modal` input.
+pub trait Modal: Input {
+    /// Returns the current mode.
+    fn mode(&self) -> Mode;
+
+    /// Makes `mode` the current mode until it's popped.
+    fn push_mode(&mut self, mode: Mode);
+
+    /// Restores the mode that was current before the last push. The initial
+    /// mode is never popped.
+    fn pop_mode(&mut self);
+}
+
+/// An input that skips trivia, like whitespace and comments, before each
+/// token, so that parsers needn't.
+///
+/// A `Lexed` input wraps an inner input and a function that skips trivia in
+/// it. Before any token is read, the function is called to skip past
+/// whatever trivia precedes the token. Parsers of the wrapped input thus see
+/// only significant input: `eat_slice("let")` followed by
+/// `take_some_while(is_alphabetic)` reads a keyword and a name separated by
+/// any amount of whitespace.
+///
+/// Trivia isn't always insignificant: inside a string literal or a here-doc,
+/// it's part of the value. `Lexed` is [`Modal`]: parsers run by
+/// [`with_mode()`] with [`Mode::KeepWhitespace`] see all of the input, while
+/// the rest of the grammar continues to skip trivia.
+///
+/// Trivia is skipped lazily, when a token is read, so positions and contexts
+/// taken between tokens may precede trivia that's yet to be skipped.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Lexed, Mode, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// type Input<'a> = Lexed<Text<'a>>;
+///
+/// fn whitespace(input: &mut Text<'_>) {
+///     let _ = skip_while(input, |c: &char| c.is_whitespace());
+/// }
+///
+/// #[parser]
+/// fn string<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+///     eat('"')?;
+///     with_mode(Mode::KeepWhitespace, |input| {
+///         let string = take_while(input, |&c| c != '"')?;
+///         eat(input, '"')?;
+///         Ok(string)
+///     })?
+/// }
+///
+/// #[parser]
+/// fn binding<'a>(input: &mut Input<'a>) -> Result<(&'a str, &'a str), Input<'a>> {
+///     eat_slice("let")?;
+///     let name = take_some_while(|c: &char| c.is_alphabetic())?;
+///     eat('=')?;
+///     let value = string()?;
+///     eat(';')?;
+///     (name, value)
+/// }
+///
+/// let mut input = Lexed::new(Text::from("let  x =\n\" a  b \" ;\n"), whitespace);
+/// assert_eq!(parse!(binding: &mut input).unwrap(), ("x", " a  b "));
+/// ```
+///
+/// [`with_mode()`]: crate::combinators::with_mode()
+#[derive(Debug)]
+pub struct Lexed<I> {
+    input: I,
+    skip: fn(&mut I),
+    modes: Vec<Mode>,
+}
+
+impl<I: Input> Lexed<I> {
+    /// Wraps `input`, skipping trivia in it with `skip`, which should consume
+    /// any trivia at the start of the input and nothing else. The initial
+    /// mode is [`Mode::SkipWhitespace`].
+    pub fn new(input: I, skip: fn(&mut I)) -> Self {
+        Lexed { input, skip, modes: vec![Mode::SkipWhitespace] }
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    /// Skips trivia if it's insignificant in the current mode.
+    #[inline(always)]
+    fn skip_trivia(&mut self) {
+        if self.mode() == Mode::SkipWhitespace {
+            (self.skip)(&mut self.input);
+        }
+    }
+}
+
+impl<I: Input> Modal for Lexed<I> {
+    fn mode(&self) -> Mode {
+        *self.modes.last().expect("initial mode")
+    }
+
+    fn push_mode(&mut self, mode: Mode) {
+        self.modes.push(mode);
+    }
+
+    fn pop_mode(&mut self) {
+        if self.modes.len() > 1 {
+            self.modes.pop();
+        }
+    }
+}
+
+impl<'a, I> Slice<Lexed<I>> for &str where I: Input<Slice = &'a str> { }
+
+impl<'a, T, I> Slice<Lexed<I>> for &[T]
+    where T: PartialEq + Show + 'a, I: Input<Slice = &'a [T]> { }
+
+impl<I: Rewind> Rewind for Lexed<I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position> Position for Lexed<I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input> Input for Lexed<I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker, Context;
+        fn mark, context, unmark, needed, halted;
+    }
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.skip_trivia();
+        self.input.token()
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.skip_trivia();
+        self.input.slice(n)
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        self.skip_trivia();
+        self.input.peek_slice_at(offset, len)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.skip_trivia();
+        self.input.peek(cond)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.skip_trivia();
+        self.input.peek_slice(n, cond)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.skip_trivia();
+        self.input.eat(cond)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.skip_trivia();
+        self.input.eat_slice(n, cond)
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.skip_trivia();
+        self.input.take(cond)
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.skip_trivia();
+        self.input.skip(cond)
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.skip_trivia();
+        self.input.has(n)
+    }
+}
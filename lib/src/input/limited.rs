@@ -0,0 +1,199 @@
+use crate::input::{Input, Show, Rewind, Position, Slice, ParserInfo};
+
+/// The number of units of an input a token occupies: its length in bytes for
+/// inputs of `char` tokens sliced as `&str`, such as [`Text`], and one
+/// otherwise, matching the units of the input's `has()` and `slice()`.
+///
+/// [`Text`]: crate::input::Text
+trait Width: Input {
+    fn width(token: &Self::Token) -> usize;
+}
+
+impl<I: Input> Width for I {
+    #[inline(always)]
+    default fn width(_: &Self::Token) -> usize {
+        1
+    }
+}
+
+impl<'a, I: Input<Token = char, Slice = &'a str>> Width for I {
+    #[inline(always)]
+    fn width(c: &char) -> usize {
+        c.len_utf8()
+    }
+}
+
+/// An input restricted to a prefix of an inner input.
+///
+/// A `Limited` input ends after at most `n` units of its inner input: the
+/// units of [`Input::has()`] and [`Input::slice()`], that is, bytes for text
+/// inputs and tokens otherwise. Past the limit, a `Limited` input behaves as
+/// if the inner input had ended, so parsers see the limit as EOF. Input
+/// consumed through a `Limited` is consumed from the inner input.
+///
+/// Nested structures with declared sizes can thus be parsed without a
+/// malformed inner structure reading into its surroundings. The [`limit()`]
+/// combinator runs a parser on a `Limited` input; [`length_value()`]
+/// additionally parses the limit and requires that it be reached.
+///
+/// [`limit()`]: crate::combinators::limit()
+/// [`length_value()`]: crate::combinators::length_value()
+pub struct Limited<'a, I> {
+    input: &'a mut I,
+    remaining: usize,
+}
+
+impl<'a, I: Input> Limited<'a, I> {
+    /// Restricts `input` to its next `n` units.
+    pub fn new(input: &'a mut I, n: usize) -> Self {
+        Limited { input, remaining: n }
+    }
+
+    /// Returns the number of units remaining before the limit.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        self.input
+    }
+}
+
+impl<'a, 'b, I> Slice<Limited<'b, I>> for &str where I: Input<Slice = &'a str> { }
+
+impl<'a, 'b, T, I> Slice<Limited<'b, I>> for &[T]
+    where T: PartialEq + Show + 'a, I: Input<Slice = &'a [T]> { }
+
+impl<I: Rewind> Rewind for Limited<'_, I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(&marker.0);
+        self.remaining = marker.1;
+    }
+}
+
+impl<I: Position> Position for Limited<'_, I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input> Input for Limited<'_, I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Context;
+        fn needed, halted;
+    }
+
+    type Marker = (I::Marker, usize);
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.input.token().filter(|t| I::width(t) <= self.remaining)
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        match n <= self.remaining {
+            true => self.input.slice(n),
+            false => None,
+        }
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        match offset.checked_add(len)? <= self.remaining {
+            true => self.input.peek_slice_at(offset, len),
+            false => None,
+        }
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let remaining = self.remaining;
+        let token = self.input.eat(|t| I::width(t) <= remaining && cond(t))?;
+        self.remaining -= I::width(&token);
+        Some(token)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        if n > self.remaining {
+            return None;
+        }
+
+        let slice = self.input.eat_slice(n, cond)?;
+        self.remaining -= n;
+        Some(slice)
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let remaining = &mut self.remaining;
+        self.input.take(|t| {
+            let take = I::width(t) <= *remaining && cond(t);
+            if take {
+                *remaining -= I::width(t);
+            }
+
+            take
+        })
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let remaining = &mut self.remaining;
+        self.input.skip(|t| {
+            let skip = I::width(t) <= *remaining && cond(t);
+            if skip {
+                *remaining -= I::width(t);
+            }
+
+            skip
+        })
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        n <= self.remaining && self.input.has(n)
+    }
+
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        (self.input.mark(info), self.remaining)
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        self.input.context(&mark.0)
+    }
+
+    fn unmark(&mut self, info: &ParserInfo, success: bool, mark: Self::Marker) {
+        self.input.unmark(info, success, mark.0)
+    }
+}
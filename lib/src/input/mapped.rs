@@ -0,0 +1,94 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::input::{Input, Cursor, Text, FileId, FileInput, Encoding, DecodeError};
+
+/// A file mapped into memory.
+///
+/// Mapping a file makes its contents addressable without reading them: pages
+/// are loaded by the operating system as they're accessed, so a parser over a
+/// multi-gigabyte log touches only the memory it needs. The inputs returned
+/// by [`FileInput::mmap()`] and [`FileInput::mmap_text()`] borrow the mapped
+/// contents directly, without copying, and their contexts carry offsets into
+/// the file.
+#[derive(Debug)]
+pub struct MappedFile {
+    map: Mmap,
+    name: String,
+}
+
+impl MappedFile {
+    /// Maps the file at `path` into memory. The file is named by `path`, as
+    /// displayed.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, by this or any other process, while the
+    /// `MappedFile` is alive: the mapped contents change with the file, which
+    /// is undefined behavior for the inputs borrowing them.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<MappedFile> {
+        let path = path.as_ref();
+        let map = Mmap::map(&File::open(path)?)?;
+        Ok(MappedFile { map, name: path.display().to_string() })
+    }
+
+    /// Returns the name of the file.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the contents of the file.
+    pub fn bytes(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+impl<'s> FileInput<'s, Cursor<'s, u8>> {
+    /// Returns a byte input over the contents of the memory-mapped `file`. Its
+    /// identifier is that of the first file in an [`InputSet`](crate::input::InputSet).
+    ///
+    /// ```rust
+    /// # fn main() -> std::io::Result<()> {
+    /// use pear::input::{FileInput, MappedFile};
+    /// use pear::parsers::*;
+    ///
+    /// let path = std::env::temp_dir().join("pear-mmap-doc.bin");
+    /// std::fs::write(&path, b"\x89PNG\r\n")?;
+    ///
+    /// // SAFETY: Nothing modifies the file while it's mapped.
+    /// let file = unsafe { MappedFile::open(&path)? };
+    /// let mut input = FileInput::mmap(&file);
+    /// assert!(eat_slice(&mut input, &b"\x89PNG"[..]).is_ok());
+    /// # drop(file);
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn mmap(file: &'s MappedFile) -> Self {
+        FileInput::new(FileId(0), file.name(), Cursor::from(file.bytes()))
+    }
+}
+
+impl<'s> FileInput<'s, Text<'s>> {
+    /// Returns a text input over the contents of the memory-mapped `file`,
+    /// which must be UTF-8. A leading BOM is skipped, but spans' offsets
+    /// remain offsets into the file. Validating the contents as UTF-8 reads
+    /// the entire file.
+    pub fn mmap_text(file: &'s MappedFile) -> Result<Self, DecodeError> {
+        if let Some((encoding, _)) = Encoding::sniff(file.bytes()) {
+            if encoding != Encoding::Utf8 {
+                return Err(DecodeError::Unsupported(encoding));
+            }
+        }
+
+        let string = core::str::from_utf8(file.bytes())
+            .map_err(|e| DecodeError::Invalid { encoding: Encoding::Utf8, offset: e.valid_up_to() })?;
+
+        let mut text = Text::from(string);
+        text.eat(|&c| c == '\u{FEFF}');
+        Ok(FileInput::new(FileId(0), file.name(), text))
+    }
+}
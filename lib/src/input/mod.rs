@@ -4,16 +4,72 @@ mod string;
 mod cursor;
 mod text;
 mod text_file;
+mod encoding;
+mod shared;
+mod iter;
+mod chain;
+mod const_text;
+mod versioned;
+mod stateful;
+mod lexed;
+mod normalize_newlines;
+mod interned;
+mod tree_capture;
+mod bits;
+mod limited;
+mod fueled;
+mod case_insensitive;
+mod slice_eq;
+#[cfg(feature = "graphemes")] mod graphemes;
+mod lazy_context;
+mod remapped;
+mod input_set;
+#[cfg(feature = "mmap")] mod mapped;
+mod fast;
+mod token_set;
+mod checkpoint;
+#[cfg(feature = "std")] mod profiled;
 mod show;
+mod describe;
 
-pub use input::{Input, Rewind, Token, Slice, ParserInfo};
+pub use input::{Input, Rewind, Position, Spanned, Token, Slice, ParserInfo, ParserSource};
 pub use cursor::{Cursor, Extent};
 pub use text::{Text, Span};
+pub use encoding::{Encoding, DecodeError};
+#[cfg(feature = "encoding")] pub use encoding::decode;
+pub use shared::{SharedText, SharedStr, SharedSpan};
+#[cfg(feature = "bytes")] pub use shared::SharedBytes;
+pub use iter::IterInput;
+pub use chain::{Chain, ChainMarker, ChainContext};
+pub use const_text::{ConstText, AsciiClass};
+pub use versioned::Versioned;
+pub use stateful::Stateful;
+pub use lexed::{Lexed, Modal, Mode};
+pub use normalize_newlines::NormalizeNewlines;
+pub use interned::{Interned, Intern, Interner, StringInterner, Symbol};
+pub use tree_capture::{TreeCapture, SyntaxNode};
+pub use bits::Bits;
+pub use limited::Limited;
+pub use fueled::Fueled;
+pub use case_insensitive::AsciiCaseInsensitive;
+pub use slice_eq::{SliceEq, CaseFold};
+#[cfg(feature = "graphemes")] pub use graphemes::Graphemes;
+pub use lazy_context::LazyContext;
+pub use remapped::{Remapped, SourceMap, Location, RemappedSpan};
+pub use input_set::{InputSet, FileId, FileInput, FileContext};
+#[cfg(feature = "mmap")] pub use mapped::MappedFile;
+pub use fast::{FastPredicate, FastScan, Delimiters};
+pub use token_set::{TokenClass, TokenSet, SetMember};
+pub use checkpoint::Checkpoint;
+#[cfg(feature = "std")] pub use profiled::{Profiled, Stats, ParserStats};
 pub use length::Length;
 pub use show::Show;
+pub use describe::Describe;
+
+pub(crate) use text::line_col;
 
 use crate::error;
 
 pub type Expected<I> = error::Expected<<I as Input>::Token, <I as Input>::Slice>;
-pub type ParseError<I> = error::ParseError<<I as Input>::Context, Expected<I>>;
-pub type Result<T, I> = std::result::Result<T, ParseError<I>>;
+pub type ParseError<I, E = Expected<I>> = error::ParseError<<I as Input>::Context, E>;
+pub type Result<T, I, E = Expected<I>> = core::result::Result<T, ParseError<I, E>>;
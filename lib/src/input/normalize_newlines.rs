@@ -0,0 +1,157 @@
+use crate::input::{Input, Rewind, Position, Slice};
+
+/// An input that presents every line ending as a single `'\n'` token.
+///
+/// A `NormalizeNewlines` input wraps an inner input whose tokens are `char`s.
+/// A `\r\n` or lone `\r` in the inner input reads as one `'\n'` token, so a
+/// grammar that ends lines with `eat('\n')` or takes a line with
+/// `take_while(|&c| c != '\n')` accepts text written with any newline
+/// convention.
+///
+/// Only tokens are normalized. Slices, including those returned by `take()`,
+/// are of the inner input: a slice spanning a `\r\n` contains both
+/// characters, and `eat_slice("a\nb")` matches only `"a\nb"`. To report
+/// which line ending was used, parse the inner input with
+/// [`line_end()`](crate::parsers::lines::line_end()) instead.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, NormalizeNewlines, Result};
+/// use pear::{macros::*, parsers::*, combinators::*};
+///
+/// type Input<'a> = NormalizeNewlines<Text<'a>>;
+///
+/// #[parser]
+/// fn line<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+///     let text = take_while(|&c| c != '\n')?;
+///     eat('\n')?;
+///     text
+/// }
+///
+/// #[parser]
+/// fn lines<'a>(input: &mut Input<'a>) -> Result<Vec<&'a str>, Input<'a>> {
+///     let lines: Vec<_> = collect(line)?;
+///     lines
+/// }
+///
+/// let mut input = NormalizeNewlines::new(Text::from("a\r\nb\rc\n\r\n"));
+/// assert_eq!(parse!(lines: &mut input).unwrap(), ["a", "b", "c", ""]);
+/// ```
+#[derive(Debug)]
+pub struct NormalizeNewlines<I> {
+    input: I,
+}
+
+impl<I: Input<Token = char>> NormalizeNewlines<I> {
+    /// Wraps `input`, normalizing its line endings.
+    pub fn new(input: I) -> Self {
+        NormalizeNewlines { input }
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+/// Returns `c` with a `'\r'` normalized to a `'\n'`.
+#[inline(always)]
+fn normal(c: char) -> char {
+    match c {
+        '\r' => '\n',
+        c => c,
+    }
+}
+
+/// Returns a condition on tokens of the inner input that applies `cond` to
+/// normalized tokens. The `'\n'` of a `"\r\n"` whose `'\r'` was accepted is
+/// accepted as part of it and counted in `merged`.
+#[inline(always)]
+fn normalized<'m, F>(mut cond: F, merged: &'m mut usize) -> impl FnMut(&char) -> bool + 'm
+    where F: FnMut(&char) -> bool + 'm
+{
+    let mut after_cr = false;
+    move |c| {
+        if core::mem::take(&mut after_cr) && *c == '\n' {
+            *merged += 1;
+            return true;
+        }
+
+        after_cr = *c == '\r';
+        cond(&normal(*c))
+    }
+}
+
+impl<'a, I> Slice<NormalizeNewlines<I>> for &str where I: Input<Token = char, Slice = &'a str> { }
+
+impl<I: Rewind<Token = char>> Rewind for NormalizeNewlines<I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position<Token = char>> Position for NormalizeNewlines<I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input<Token = char>> Input for NormalizeNewlines<I> {
+    delegate_input! {
+        to input: I;
+        type Slice, Many, Marker, Context;
+        fn slice, peek_slice_at, peek_slice, eat_slice, has, mark, context, unmark, needed,
+           halted;
+    }
+
+    type Token = char;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.input.token().map(normal)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.input.peek(|c| cond(&normal(*c)))
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.input.eat(|c| cond(&normal(*c)))?;
+        if token == '\r' {
+            self.input.eat(|&c| c == '\n');
+        }
+
+        Some(normal(token))
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.input.take(normalized(cond, &mut 0))
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut merged = 0;
+        let skipped = self.input.skip(normalized(cond, &mut merged));
+        skipped - merged
+    }
+}
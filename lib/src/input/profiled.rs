@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::input::{Input, Show, Rewind, Position, Slice, ParserInfo};
+
+/// Statistics collected for a single parser by a [`Profiled`] input.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ParserStats {
+    /// The number of times the parser was called.
+    pub calls: u64,
+    /// The number of calls which failed, causing the caller to backtrack.
+    pub backtracks: u64,
+    /// The total time spent in the parser, including in the parsers it calls.
+    pub time: Duration,
+}
+
+/// Per-parser statistics collected by a [`Profiled`] input.
+///
+/// Parsers are identified by name, so distinct parsers of the same name share
+/// their statistics. The `Display` implementation prints a report with one
+/// row per parser, from the most to the least time spent.
+#[derive(Debug, Default, Clone)]
+pub struct Stats {
+    parsers: HashMap<&'static str, ParserStats>,
+}
+
+impl Stats {
+    /// Returns the statistics for the parser named `name`, if it was called.
+    pub fn get(&self, name: &str) -> Option<&ParserStats> {
+        self.parsers.get(name)
+    }
+
+    /// Returns the statistics for every parser that was called, from the most
+    /// to the least time spent.
+    pub fn sorted(&self) -> Vec<(&'static str, ParserStats)> {
+        let mut parsers: Vec<_> = self.parsers.iter().map(|(k, v)| (*k, *v)).collect();
+        parsers.sort_by(|a, b| b.1.time.cmp(&a.1.time).then(a.0.cmp(b.0)));
+        parsers
+    }
+
+    /// Discards all of the collected statistics.
+    pub fn clear(&mut self) {
+        self.parsers.clear();
+    }
+
+    fn record(&mut self, info: &ParserInfo, success: bool, time: Duration) {
+        let stats = self.parsers.entry(info.name).or_default();
+        stats.calls += 1;
+        stats.backtracks += !success as u64;
+        stats.time += time;
+    }
+}
+
+impl core::fmt::Display for Stats {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let parsers = self.sorted();
+        let width = parsers.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        let width = width.max("parser".len());
+        writeln!(f, "{:<width$} {:>10} {:>10} {:>12} {:>12}",
+            "parser", "calls", "backtracks", "total", "per call", width = width)?;
+
+        for (name, stats) in parsers {
+            let per_call = stats.time / stats.calls.max(1) as u32;
+            writeln!(f, "{:<width$} {:>10} {:>10} {:>12} {:>12}", name,
+                stats.calls, stats.backtracks, format!("{:.2?}", stats.time),
+                format!("{:.2?}", per_call), width = width)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An input which collects statistics about the parsers run on it.
+///
+/// For every parser, a `Profiled` input records the number of calls, the
+/// number of calls which failed and were backtracked from, and the time spent.
+/// Times are inclusive: they include the time spent in nested parsers, and
+/// the time of a recursive parser is counted once per level of recursion.
+/// Pathological rules typically stand out as those with many more calls or
+/// backtracks than their share of the input warrants. The statistics are
+/// available through [`Profiled::stats()`], whose `Display` implementation
+/// prints a report.
+///
+/// Apart from collecting statistics, a `Profiled` input behaves exactly like
+/// its inner input. This input requires the `std` feature.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Profiled, Result};
+/// use pear::macros::{parser, parse, switch};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = Profiled<Text<'a>>;
+///
+/// #[parser]
+/// fn value<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+///     switch! {
+///         eat_slice("true") => "true",
+///         eat_slice("false") => "false",
+///         _ => take_some_while(|c| c.is_ascii_digit())?,
+///     }
+/// }
+///
+/// let mut input = Profiled::new(Text::from("false"));
+/// parse!(value: &mut input).unwrap();
+///
+/// let eat_slice = input.stats().get("eat_slice").unwrap();
+/// assert_eq!((eat_slice.calls, eat_slice.backtracks), (2, 1));
+/// println!("{}", input.stats());
+/// ```
+#[derive(Debug)]
+pub struct Profiled<I> {
+    input: I,
+    stats: Stats,
+}
+
+impl<I: Input> Profiled<I> {
+    /// Wraps `input`, collecting statistics about the parsers run on it.
+    pub fn new(input: I) -> Self {
+        Profiled { input, stats: Stats::default() }
+    }
+
+    /// Returns the statistics collected so far.
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Returns a mutable reference to the statistics collected so far, for
+    /// instance, to clear them between runs.
+    pub fn stats_mut(&mut self) -> &mut Stats {
+        &mut self.stats
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input and the collected statistics.
+    pub fn into_inner(self) -> (I, Stats) {
+        (self.input, self.stats)
+    }
+}
+
+impl<'a, I> Slice<Profiled<I>> for &str where I: Input<Slice = &'a str> { }
+
+impl<'a, T, I> Slice<Profiled<I>> for &[T]
+    where T: PartialEq + Show + 'a, I: Input<Slice = &'a [T]> { }
+
+impl<I: Rewind> Rewind for Profiled<I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(&marker.0)
+    }
+}
+
+impl<I: Position> Position for Profiled<I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input> Input for Profiled<I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Context;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           needed, halted;
+    }
+
+    type Marker = (I::Marker, Instant);
+
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        (self.input.mark(info), Instant::now())
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        self.input.context(&mark.0)
+    }
+
+    fn unmark(&mut self, info: &ParserInfo, success: bool, mark: Self::Marker) {
+        self.stats.record(info, success, mark.1.elapsed());
+        self.input.unmark(info, success, mark.0)
+    }
+}
@@ -0,0 +1,307 @@
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::input::{Input, Show, Rewind, Position, Slice, line_col};
+
+/// A map from offsets in transformed text to locations in the original text it
+/// was produced from.
+///
+/// Text that is preprocessed before parsing, by expanding includes, say, or
+/// decoding escapes, no longer lines up with the files it came from. A
+/// `SourceMap` records where each part of the transformed text originated as a
+/// series of contiguous segments, pushed in order: text [copied](Self::copied)
+/// verbatim from an original file, text that [replaced](Self::replaced) some
+/// original text, and text that was [synthesized](Self::synthesized) and has no
+/// origin at all. A [`Remapped`] input uses a `SourceMap` to report contexts in
+/// terms of the original files.
+///
+/// ```rust
+/// use pear::input::SourceMap;
+///
+/// let main = "#include lib\nmain";
+/// let lib = "lib\n";
+///
+/// // The preprocessed text is "lib\nmain".
+/// let mut map = SourceMap::new();
+/// map.copied("lib.txt", lib, 0..4).copied("main.txt", main, 13..17);
+///
+/// let location = map.locate(5).unwrap();
+/// assert_eq!((location.file, location.offset), ("main.txt", 14));
+/// assert_eq!((location.line, location.column), (2, 2));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap<'s> {
+    segments: Vec<Segment<'s>>,
+}
+
+#[derive(Debug, Clone)]
+struct Segment<'s> {
+    start: usize,
+    len: usize,
+    origin: Option<Origin<'s>>,
+}
+
+#[derive(Debug, Clone)]
+struct Origin<'s> {
+    file: &'s str,
+    text: &'s str,
+    range: Range<usize>,
+}
+
+/// A location in an original file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Location<'s> {
+    /// The name of the file.
+    pub file: &'s str,
+    /// The 1-indexed line.
+    pub line: usize,
+    /// The 1-indexed column.
+    pub column: usize,
+    /// The byte offset into the file.
+    pub offset: usize,
+}
+
+impl Show for Location<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// The context type of a [`Remapped`] input: a span of original text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RemappedSpan<'s> {
+    /// Where the span starts.
+    pub start: Location<'s>,
+    /// Where the span ends. May lie in a different file than `start`.
+    pub end: Location<'s>,
+    /// The original text between start and end, if they lie in one file.
+    pub snippet: Option<&'s str>,
+}
+
+impl Show for RemappedSpan<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (start, end) = (&self.start, &self.end);
+        write!(f, "{}", start as &dyn Show)?;
+        if start.file != end.file {
+            write!(f, " to {}", end as &dyn Show)?;
+        } else if start.offset != end.offset {
+            write!(f, " to {}:{}", end.line, end.column)?;
+        }
+
+        if let Some(snippet) = self.snippet {
+            write!(f, " {:?}", snippet)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'s> Segment<'s> {
+    fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    fn original(&self, offset: usize) -> Option<(&Origin<'s>, usize)> {
+        let origin = self.origin.as_ref()?;
+        let delta = offset - self.start;
+        let original = if self.len == origin.range.len() {
+            origin.range.start + delta
+        } else if delta == self.len {
+            origin.range.end
+        } else {
+            origin.range.start
+        };
+
+        Some((origin, original))
+    }
+}
+
+impl<'s> SourceMap<'s> {
+    /// Returns a map with no segments.
+    pub fn new() -> Self {
+        SourceMap { segments: Vec::new() }
+    }
+
+    /// Returns the length of the transformed text covered by the map.
+    pub fn len(&self) -> usize {
+        self.segments.last().map_or(0, |s| s.end())
+    }
+
+    /// Returns `true` if the map covers no transformed text.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Records that the next transformed text is `text[range]`, copied
+    /// verbatim from the file named `file` whose contents are `text`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of `text`.
+    pub fn copied(&mut self, file: &'s str, text: &'s str, range: Range<usize>) -> &mut Self {
+        let len = range.len();
+        self.replaced(len, file, text, range)
+    }
+
+    /// Records that the next `len` bytes of transformed text replace
+    /// `text[range]` in the file named `file` whose contents are `text`. Offsets
+    /// within the replacement map to the start of `range` unless `len` is the
+    /// length of `range`, in which case they map one-to-one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of `text`.
+    pub fn replaced(&mut self, len: usize, file: &'s str, text: &'s str, range: Range<usize>) -> &mut Self {
+        assert!(range.start <= range.end && range.end <= text.len(), "range out of bounds");
+        let origin = Origin { file, text, range };
+        self.segments.push(Segment { start: self.len(), len, origin: Some(origin) });
+        self
+    }
+
+    /// Records that the next `len` bytes of transformed text have no origin.
+    pub fn synthesized(&mut self, len: usize) -> &mut Self {
+        self.segments.push(Segment { start: self.len(), len, origin: None });
+        self
+    }
+
+    /// Returns the segment containing `offset`. At a boundary between
+    /// segments, this is the one starting at `offset` unless `end` is set.
+    fn segment(&self, offset: usize, end: bool) -> Option<&Segment<'s>> {
+        let i = self.segments.partition_point(|s| match end {
+            true => s.end() < offset,
+            false => s.end() <= offset,
+        });
+
+        self.segments.get(i).or_else(|| self.segments.last().filter(|s| s.end() == offset))
+    }
+
+    fn location(origin: &Origin<'s>, offset: usize) -> Location<'s> {
+        let (line, column) = line_col(origin.text.get(..offset).unwrap_or(""));
+        Location { file: origin.file, line, column, offset }
+    }
+
+    /// Returns the original location of the transformed `offset`, or `None`
+    /// if `offset` is out of bounds or lies in synthesized text.
+    pub fn locate(&self, offset: usize) -> Option<Location<'s>> {
+        let (origin, offset) = self.segment(offset, false)?.original(offset)?;
+        Some(Self::location(origin, offset))
+    }
+
+    /// Returns the original span of the transformed `range`, or `None` if
+    /// either end is out of bounds or lies in synthesized text.
+    pub fn span(&self, range: Range<usize>) -> Option<RemappedSpan<'s>> {
+        let (start, a) = self.segment(range.start, false)?.original(range.start)?;
+        let (end, b) = match range.start == range.end {
+            true => (start, a),
+            false => self.segment(range.end, true)?.original(range.end)?,
+        };
+
+        let snippet = match core::ptr::eq(start.text, end.text) && a <= b {
+            true => start.text.get(a..b),
+            false => None,
+        };
+
+        let (start, end) = (Self::location(start, a), Self::location(end, b));
+        Some(RemappedSpan { start, end, snippet })
+    }
+}
+
+/// An input over transformed text that reports contexts in terms of the
+/// original text it was produced from.
+///
+/// Apart from its contexts, a `Remapped` input behaves exactly like its inner
+/// input, whose [`Position`]s must be offsets into the transformed text, as
+/// those of [`Text`](crate::input::Text) are. Contexts are computed by
+/// looking up the positions they span in a [`SourceMap`]. A context touching
+/// synthesized text, or text the map does not cover, is `None`.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Remapped, SourceMap, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = Remapped<'a, Text<'a>>;
+///
+/// #[parser]
+/// fn words<'a>(input: &mut Input<'a>) -> Result<(), Input<'a>> {
+///     skip_while(|c| c.is_alphabetic() || c.is_whitespace())?;
+///     eof()?;
+/// }
+///
+/// let main = "one\n#include lib\nthree";
+/// let lib = "two!\n";
+/// let expanded = "one\ntwo!\nthree";
+///
+/// let mut map = SourceMap::new();
+/// map.copied("main", main, 0..4).copied("lib", lib, 0..5).copied("main", main, 17..22);
+///
+/// let mut input = Remapped::new(Text::from(expanded), map);
+/// let error = parse!(words: &mut input).unwrap_err();
+/// let span = error.contexts.last().unwrap().context.unwrap();
+/// assert_eq!((span.start.file, span.start.line, span.start.column), ("main", 1, 1));
+/// assert_eq!((span.end.file, span.end.line, span.end.column), ("lib", 1, 4));
+/// ```
+#[derive(Debug)]
+pub struct Remapped<'s, I> {
+    input: I,
+    map: SourceMap<'s>,
+}
+
+impl<'s, I: Position<Marker = usize>> Remapped<'s, I> {
+    /// Wraps `input`, which was produced as described by `map`.
+    pub fn new(input: I, map: SourceMap<'s>) -> Self {
+        Remapped { input, map }
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a reference to the source map.
+    pub fn source_map(&self) -> &SourceMap<'s> {
+        &self.map
+    }
+
+    /// Returns the inner input and the source map.
+    pub fn into_inner(self) -> (I, SourceMap<'s>) {
+        (self.input, self.map)
+    }
+}
+
+impl<'a, I> Slice<Remapped<'_, I>> for &str where I: Position<Marker = usize, Slice = &'a str> { }
+
+impl<'a, T, I> Slice<Remapped<'_, I>> for &[T]
+    where T: PartialEq + Show + 'a, I: Position<Marker = usize, Slice = &'a [T]> { }
+
+impl<I: Rewind<Marker = usize> + Position> Rewind for Remapped<'_, I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position<Marker = usize>> Position for Remapped<'_, I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<'s, I: Position<Marker = usize>> Input for Remapped<'s, I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           mark, unmark, needed, halted;
+    }
+
+    type Context = RemappedSpan<'s>;
+
+    /// Returns the original span of the text from `mark` to the current
+    /// position, if it has one.
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        self.map.span(*mark..self.input.position())
+    }
+}
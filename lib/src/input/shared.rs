@@ -0,0 +1,497 @@
+use core::ops::{Deref, Range};
+
+use alloc::{string::String, sync::Arc};
+
+use crate::input::{Input, Rewind, Position, Slice, Show, Length, ParserInfo, Span};
+use crate::input::text::line_col;
+
+/// A reference-counted subrange of an `Arc<str>`: a slice of a
+/// [`SharedText`] that can outlive the input and be sent across threads
+/// without copying.
+#[derive(Clone)]
+pub struct SharedStr {
+    source: Arc<str>,
+    range: Range<usize>,
+}
+
+impl SharedStr {
+    /// Returns the slice as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.source[self.range.clone()]
+    }
+
+    /// Returns the text this is a slice of.
+    pub fn source(&self) -> &Arc<str> {
+        &self.source
+    }
+
+    /// Returns the byte range of the slice in its [source](SharedStr::source()).
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl From<&str> for SharedStr {
+    fn from(string: &str) -> SharedStr {
+        SharedStr { source: string.into(), range: 0..string.len() }
+    }
+}
+
+impl Deref for SharedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SharedStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl core::fmt::Debug for SharedStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl core::fmt::Display for SharedStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Show for SharedStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "`{}`", self.as_str())
+    }
+}
+
+impl Length for SharedStr {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+}
+
+impl PartialEq for SharedStr {
+    fn eq(&self, other: &SharedStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SharedStr { }
+
+impl core::hash::Hash for SharedStr {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialEq<str> for SharedStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for SharedStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<SharedStr> for &str {
+    fn eq(&self, other: &SharedStr) -> bool {
+        *self == other.as_str()
+    }
+}
+
+/// The context of a [`SharedText`]: like a [`Span`], but with a shared
+/// snippet, so errors can outlive the input too.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SharedSpan {
+    /// Start line/column/offset.
+    pub start: (usize, usize, usize),
+    /// End line/column/offset.
+    pub end: (usize, usize, usize),
+    /// Snippet between start and end.
+    pub snippet: Option<SharedStr>,
+}
+
+impl Show for SharedSpan {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let snippet = self.snippet.as_ref().map(|s| s.as_str());
+        Show::fmt(&Span { start: self.start, end: self.end, snippet }, f)
+    }
+}
+
+/// A text input backed by an `Arc<str>` whose slices, [`SharedStr`]s, are
+/// reference-counted subranges of it rather than borrows.
+///
+/// Parse results built from a `SharedText`, and errors from it, are `'static`
+/// and `Send`: they can outlive the input and be sent across threads without
+/// copying the text they refer to.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{SharedText, SharedStr, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// #[parser]
+/// fn word(input: &mut SharedText) -> Result<SharedStr, SharedText> {
+///     take_some_while(|c: &char| c.is_alphabetic())?
+/// }
+///
+/// let word = parse!(word: &mut SharedText::from("hello")).unwrap();
+/// let handle = std::thread::spawn(move || word.len());
+/// assert_eq!(handle.join().unwrap(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedText {
+    source: Arc<str>,
+    position: usize,
+}
+
+impl From<Arc<str>> for SharedText {
+    fn from(source: Arc<str>) -> SharedText {
+        SharedText { source, position: 0 }
+    }
+}
+
+impl From<&str> for SharedText {
+    fn from(source: &str) -> SharedText {
+        SharedText::from(Arc::<str>::from(source))
+    }
+}
+
+impl From<String> for SharedText {
+    fn from(source: String) -> SharedText {
+        SharedText::from(Arc::<str>::from(source))
+    }
+}
+
+impl SharedText {
+    /// Returns the entire text, parsed or not.
+    pub fn source(&self) -> &Arc<str> {
+        &self.source
+    }
+
+    /// Returns the remaining, unparsed text.
+    pub fn remaining(&self) -> SharedStr {
+        self.share(self.position..self.source.len())
+    }
+
+    fn rest(&self) -> &str {
+        &self.source[self.position..]
+    }
+
+    fn share(&self, range: Range<usize>) -> SharedStr {
+        SharedStr { source: self.source.clone(), range }
+    }
+}
+
+impl Slice<SharedText> for &str { }
+
+impl Rewind for SharedText {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.position = *marker;
+    }
+}
+
+impl Position for SharedText {
+    fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Input for SharedText {
+    type Token = char;
+    type Slice = SharedStr;
+    type Many = SharedStr;
+
+    type Marker = usize;
+    type Context = SharedSpan;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.rest().chars().next()
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.peek_slice_at(0, n)
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        let start = self.position.checked_add(offset)?;
+        let end = start.checked_add(len)?;
+        self.source.get(start..end)?;
+        Some(self.share(start..end))
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token().filter(|t| cond(t))?;
+        self.position += token.len_utf8();
+        Some(token)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n).filter(|s| cond(s))?;
+        self.position += n;
+        Some(slice)
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.position;
+        self.position += self.rest().take(cond).len();
+        self.share(start..self.position)
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let skipped = self.rest().take(cond).len();
+        self.position += skipped;
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.rest().len() >= n
+    }
+
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.position
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        if self.position == 0 {
+            return Some(SharedSpan { start: (1, 1, 0), end: (1, 1, 0), snippet: None });
+        }
+
+        let (start_line, start_col) = line_col(&self.source[..*mark]);
+        let (end_line, end_col) = line_col(&self.source[..self.position]);
+        Some(SharedSpan {
+            start: (start_line, start_col, *mark),
+            end: (end_line, end_col, self.position),
+            snippet: Some(self.share(*mark..self.position)),
+        })
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod bytes {
+    use ::bytes::Bytes;
+
+    use crate::input::{Input, Rewind, Position, Slice, Show, Length, ParserInfo};
+
+    impl Show for Bytes {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    impl Length for Bytes {
+        #[inline(always)]
+        fn len(&self) -> usize {
+            Bytes::len(self)
+        }
+    }
+
+    /// A binary input backed by [`Bytes`] whose slices are `Bytes` too:
+    /// reference-counted subranges of the input that can outlive it and be
+    /// sent across threads without copying.
+    ///
+    /// The context of a `SharedBytes` is the range of offsets spanned.
+    ///
+    /// ```rust
+    /// #![feature(proc_macro_hygiene)]
+    ///
+    /// use bytes::Bytes;
+    /// use pear::input::{SharedBytes, Result};
+    /// use pear::macros::{parser, parse};
+    /// use pear::parsers::*;
+    ///
+    /// #[parser]
+    /// fn method(input: &mut SharedBytes) -> Result<Bytes, SharedBytes> {
+    ///     take_some_while(|b: &u8| b.is_ascii_uppercase())?
+    /// }
+    ///
+    /// let mut input = SharedBytes::from(Bytes::from_static(b"GET"));
+    /// assert_eq!(parse!(method: &mut input).unwrap(), &b"GET"[..]);
+    /// ```
+    ///
+    /// [`Bytes`]: ::bytes::Bytes
+    #[derive(Debug, Clone)]
+    pub struct SharedBytes {
+        bytes: Bytes,
+        position: usize,
+    }
+
+    impl From<Bytes> for SharedBytes {
+        fn from(bytes: Bytes) -> SharedBytes {
+            SharedBytes { bytes, position: 0 }
+        }
+    }
+
+    impl SharedBytes {
+        /// Returns the entire input, parsed or not.
+        pub fn source(&self) -> &Bytes {
+            &self.bytes
+        }
+
+        /// Returns the remaining, unparsed input.
+        pub fn remaining(&self) -> Bytes {
+            self.bytes.slice(self.position..)
+        }
+    }
+
+    impl Slice<SharedBytes> for &[u8] { }
+
+    impl Rewind for SharedBytes {
+        fn rewind_to(&mut self, marker: &Self::Marker) {
+            self.position = *marker;
+        }
+    }
+
+    impl Position for SharedBytes {
+        fn position(&self) -> usize {
+            self.position
+        }
+    }
+
+    impl Input for SharedBytes {
+        type Token = u8;
+        type Slice = Bytes;
+        type Many = Bytes;
+
+        type Marker = usize;
+        type Context = core::ops::Range<usize>;
+
+        /// Returns a copy of the current token, if there is one.
+        fn token(&mut self) -> Option<Self::Token> {
+            self.bytes.get(self.position).copied()
+        }
+
+        /// Returns a copy of the current slice of size `n`, if there is one.
+        fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+            self.peek_slice_at(0, n)
+        }
+
+        /// Returns a copy of the slice of size `len` starting `offset` past the
+        /// current position, if there is one, without consuming anything.
+        fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+            let start = self.position.checked_add(offset)?;
+            let end = start.checked_add(len)?;
+            self.bytes.get(start..end)?;
+            Some(self.bytes.slice(start..end))
+        }
+
+        /// Checks if the current token fulfills `cond`.
+        fn peek<F>(&mut self, mut cond: F) -> bool
+            where F: FnMut(&Self::Token) -> bool
+        {
+            self.token().map(|t| cond(&t)).unwrap_or(false)
+        }
+
+        /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+        fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+            where F: FnMut(&Self::Slice) -> bool
+        {
+            self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+        }
+
+        /// Checks if the current token fulfills `cond`. If so, the token is
+        /// consumed and returned. Otherwise, returns `None`.
+        fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+            where F: FnMut(&Self::Token) -> bool
+        {
+            let token = self.token().filter(|t| cond(t))?;
+            self.position += 1;
+            Some(token)
+        }
+
+        /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+        /// the slice is consumed and returned. Otherwise, returns `None`.
+        fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+            where F: FnMut(&Self::Slice) -> bool
+        {
+            let slice = self.slice(n).filter(|s| cond(s))?;
+            self.position += n;
+            Some(slice)
+        }
+
+        /// Takes tokens while `cond` returns true, collecting them into a
+        /// `Self::Many` and returning it.
+        fn take<F>(&mut self, cond: F) -> Self::Many
+            where F: FnMut(&Self::Token) -> bool
+        {
+            let start = self.position;
+            self.skip(cond);
+            self.bytes.slice(start..self.position)
+        }
+
+        /// Skips tokens while `cond` returns true. Returns the number of skipped
+        /// tokens.
+        fn skip<F>(&mut self, mut cond: F) -> usize
+            where F: FnMut(&Self::Token) -> bool
+        {
+            let skipped = self.bytes[self.position..].iter()
+                .take_while(|b| cond(b))
+                .count();
+
+            self.position += skipped;
+            skipped
+        }
+
+        /// Returns `true` if there are at least `n` tokens remaining.
+        fn has(&mut self, n: usize) -> bool {
+            self.bytes.len() - self.position >= n
+        }
+
+        fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+            self.position
+        }
+
+        fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+            Some(*mark..self.position)
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+pub use self::bytes::SharedBytes;
@@ -32,58 +32,67 @@
 // * Print parser arguments in debug/error output.
 // * Bonus: Migrate to syn 1.0, also in devise.
 
+use alloc::{vec::Vec, string::String};
+
 pub trait Show {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;
 }
 
-impl std::fmt::Display for &dyn Show {
+impl core::fmt::Display for &dyn Show {
     #[inline(always)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         Show::fmt(*self, f)
     }
 }
 
 impl<T: Show + ?Sized> Show for &T {
     #[inline(always)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         <T as Show>::fmt(self, f)
     }
 }
 
 impl<T: Show> Show for [T] {
     #[inline(always)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for (i, value) in self.iter().enumerate() {
             if i > 0 { write!(f, " ")?; }
             write!(f, "{}", value as &dyn Show)?;
         }
 
-        write!(f, ")")
+        Ok(())
     }
 }
 
 impl<T: Show> Show for Vec<T> {
     #[inline(always)]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for (i, value) in self.iter().enumerate() {
             if i > 0 { write!(f, " ")?; }
             write!(f, "{}", value as &dyn Show)?;
         }
 
-        write!(f, ")")
+        Ok(())
+    }
+}
+
+impl<T: Show> Show for core::ops::Range<T> {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}..{}", &self.start as &dyn Show, &self.end as &dyn Show)
     }
 }
 
 impl_show_with! { Debug,
     u8, u16, u32, u64, u128, usize,
-    i8, i16, i32, i64, i128, isize
+    i8, i16, i32, i64, i128, isize, bool
 }
 
 macro_rules! impl_with_tick_display {
     ($($T:ty,)*) => ($(
         impl Show for $T {
             #[inline(always)]
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 write!(f, "`{}`", self)
             }
         }
@@ -91,5 +100,5 @@ macro_rules! impl_with_tick_display {
 }
 
 impl_with_tick_display! {
-    &str, String, char, std::borrow::Cow<'static, str>,
+    &str, String, char, alloc::borrow::Cow<'static, str>,
 }
@@ -0,0 +1,99 @@
+use core::ops::RangeInclusive;
+
+use crate::input::Length;
+
+/// An equivalence between slices, for matching slices that aren't equal.
+///
+/// [`eat_slice_eq()`](crate::parsers::eat_slice_eq()) and
+/// [`peek_slice_eq()`](crate::parsers::peek_slice_eq()) match the input
+/// against an expected slice using a `SliceEq` instead of `==`. A slice
+/// equivalent to the expected one may differ in length: `"STRASSE"` and
+/// `"straße"` are equivalent when case is folded, but differ in length by a
+/// byte. [`SliceEq::lengths()`] gives the lengths the parsers try, shortest
+/// first; by default, only that of the expected slice.
+///
+/// Any `Fn(&S, &S) -> bool` is a `SliceEq` that only matches slices of the
+/// expected length. [`CaseFold`] matches text regardless of Unicode case.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::{macros::*, parsers::*};
+///
+/// // Treats `-` and `_` as the same character.
+/// fn dash_eq(expected: &&str, found: &&str) -> bool {
+///     let normal = |c: char| if c == '_' { '-' } else { c };
+///     expected.chars().map(normal).eq(found.chars().map(normal))
+/// }
+///
+/// #[parser]
+/// fn option<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     eat_slice("--")?;
+///     eat_slice_eq("dry-run", dash_eq)?
+/// }
+///
+/// assert_eq!(parse!(option: &mut Text::from("--dry_run")).unwrap(), "dry_run");
+/// assert!(parse!(option: &mut Text::from("--dry.run")).is_err());
+/// ```
+pub trait SliceEq<S: Length> {
+    /// Returns `true` if `found` is equivalent to `expected`.
+    fn slice_eq(&self, expected: &S, found: &S) -> bool;
+
+    /// Returns the lengths, as measured by [`Length`], that a slice
+    /// equivalent to `expected` may have. Each is tried in turn, so the range
+    /// should be small.
+    fn lengths(&self, expected: &S) -> RangeInclusive<usize> {
+        expected.len()..=expected.len()
+    }
+}
+
+impl<S: Length, F: Fn(&S, &S) -> bool> SliceEq<S> for F {
+    fn slice_eq(&self, expected: &S, found: &S) -> bool {
+        self(expected, found)
+    }
+}
+
+/// A [`SliceEq`] that matches text regardless of case.
+///
+/// Text is compared after case folding, approximated as the lowercase of the
+/// uppercase of each character, so that `"straße"` matches `"STRASSE"` and
+/// `"Σ"` matches `"ς"`. Unlike [`AsciiCaseInsensitive`], this matches
+/// non-ASCII letters of any case, but does so more slowly.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, CaseFold, Result};
+/// use pear::{macros::*, parsers::*};
+///
+/// #[parser]
+/// fn street<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     eat_slice_eq("straße", CaseFold)?
+/// }
+///
+/// assert_eq!(parse!(street: &mut Text::from("STRASSE")).unwrap(), "STRASSE");
+/// assert_eq!(parse!(street: &mut Text::from("Straße")).unwrap(), "Straße");
+/// ```
+///
+/// [`AsciiCaseInsensitive`]: crate::input::AsciiCaseInsensitive
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CaseFold;
+
+impl CaseFold {
+    fn fold(string: &str) -> impl Iterator<Item = char> + '_ {
+        string.chars().flat_map(char::to_uppercase).flat_map(char::to_lowercase)
+    }
+}
+
+impl SliceEq<&str> for CaseFold {
+    fn slice_eq(&self, expected: &&str, found: &&str) -> bool {
+        CaseFold::fold(expected).eq(CaseFold::fold(found))
+    }
+
+    /// A character's folded form is between a third and three times as long
+    /// in UTF-8: `K` (Kelvin sign) is three bytes, its folded `k` one.
+    fn lengths(&self, expected: &&str) -> RangeInclusive<usize> {
+        expected.len().div_ceil(3)..=expected.len().saturating_mul(3)
+    }
+}
@@ -0,0 +1,169 @@
+use alloc::vec::Vec;
+
+use crate::input::{Input, Show, Rewind, Position, Slice, ParserInfo};
+
+/// A state which may be snapshotted: one that is `Clone`.
+trait Snapshot: Sized {
+    fn snapshot(&self) -> Option<Self>;
+}
+
+impl<S> Snapshot for S {
+    #[inline(always)]
+    default fn snapshot(&self) -> Option<S> {
+        None
+    }
+}
+
+impl<S: Clone> Snapshot for S {
+    #[inline(always)]
+    fn snapshot(&self) -> Option<S> {
+        Some(self.clone())
+    }
+}
+
+/// An input carrying user-defined parser state alongside an inner input.
+///
+/// Context-sensitive grammars need to remember things as they parse: the
+/// stack of indentation levels of a layout-sensitive language, the names of
+/// declared types, or the feature flags enabled by a pragma. Rather than
+/// keeping such state in globals or threading it through every parser as an
+/// argument, wrap the input in a `Stateful` and access the state from any
+/// parser with [`state!()`], which evaluates to a `&mut S`, or with
+/// [`Stateful::state()`] and [`Stateful::state_mut()`].
+///
+/// If `S` is `Clone`, the state is rewound along with the input: it's cloned
+/// whenever the input is marked, as when a parser begins, and restored when
+/// the input is rewound to the mark, so changes made by a parser that fails
+/// and is backtracked over are undone. As every parser marks its input, a
+/// `Clone` state should be cheap to clone. If `S` isn't `Clone`, changes made
+/// by a parser that later fails or is backtracked over persist. Apart from
+/// carrying the state, a `Stateful` input behaves exactly like its inner
+/// input.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Stateful, Result};
+/// use pear::macros::{parser, parse, parse_error, state};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = Stateful<Text<'a>, Vec<usize>>;
+///
+/// // Each line must be indented by exactly one more space than the last.
+/// #[parser]
+/// fn line<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+///     let indent = skip_while(|c: &char| *c == ' ')?;
+///     let expected = state!().last().map_or(0, |n| n + 1);
+///     if indent != expected {
+///         parse_error!("bad indentation")?;
+///     }
+///
+///     state!().push(indent);
+///     let text = take_some_while(|c: &char| *c != '\n')?;
+///     eat('\n')?;
+///     text
+/// }
+///
+/// #[parser]
+/// fn lines<'a>(input: &mut Input<'a>) -> Result<Vec<&'a str>, Input<'a>> {
+///     let mut lines = vec![];
+///     while eof(input).is_err() {
+///         let text = line()?;
+///         lines.push(text);
+///     }
+///
+///     lines
+/// }
+///
+/// let mut input = Stateful::new(Text::from("a\n b\n  c\n"), vec![]);
+/// assert_eq!(parse!(lines: &mut input).unwrap(), ["a", "b", "c"]);
+/// assert_eq!(input.state(), &[0, 1, 2]);
+///
+/// let mut input = Stateful::new(Text::from("a\n  b\n"), vec![]);
+/// assert!(parse!(lines: &mut input).is_err());
+/// ```
+///
+/// [`state!()`]: crate::macros#state
+#[derive(Debug)]
+pub struct Stateful<I, S> {
+    input: I,
+    state: S,
+    snapshots: Vec<Option<S>>,
+}
+
+impl<I: Input, S> Stateful<I, S> {
+    /// Wraps `input`, carrying the initial parser state `state`.
+    pub fn new(input: I, state: S) -> Self {
+        Stateful { input, state, snapshots: Vec::new() }
+    }
+
+    /// Returns a reference to the current state.
+    #[inline(always)]
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns a mutable reference to the current state.
+    #[inline(always)]
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input and the current state.
+    pub fn into_inner(self) -> (I, S) {
+        (self.input, self.state)
+    }
+}
+
+impl<'a, I, S> Slice<Stateful<I, S>> for &str where I: Input<Slice = &'a str> { }
+
+impl<'a, T, I, S> Slice<Stateful<I, S>> for &[T]
+    where T: PartialEq + Show + 'a, I: Input<Slice = &'a [T]> { }
+
+impl<I: Rewind, S> Rewind for Stateful<I, S> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(&marker.0);
+        if let Some(Some(snapshot)) = self.snapshots.get(marker.1) {
+            if let Some(state) = snapshot.snapshot() {
+                self.state = state;
+            }
+        }
+    }
+}
+
+impl<I: Position, S> Position for Stateful<I, S> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input, S> Input for Stateful<I, S> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Context;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           needed, halted;
+    }
+
+    /// The inner marker and the index of the state's snapshot.
+    type Marker = (I::Marker, usize);
+
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        self.snapshots.push(self.state.snapshot());
+        (self.input.mark(info), self.snapshots.len() - 1)
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        self.input.context(&mark.0)
+    }
+
+    fn unmark(&mut self, info: &ParserInfo, success: bool, mark: Self::Marker) {
+        self.snapshots.truncate(mark.1);
+        self.input.unmark(info, success, mark.0)
+    }
+}
@@ -1,7 +1,10 @@
 pub use crate::input::{Input, Token, Slice, ParserInfo};
+use crate::input::{FastScan, FastPredicate};
 
 impl<'a, 'b: 'a> Slice<&'a str> for &'b str { }
 
+impl Slice<&str> for alloc::string::String { }
+
 // ident_impl_token!(&str);
 
 impl<'a> Input for &'a str {
@@ -22,6 +25,12 @@ impl<'a> Input for &'a str {
         self.get(..n)
     }
 
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        self.get(offset..offset.checked_add(len)?)
+    }
+
     /// Checks if the current token fulfills `cond`.
     fn peek<F>(&mut self, mut cond: F) -> bool
         where F: FnMut(&Self::Token) -> bool
@@ -104,3 +113,16 @@ impl<'a> Input for &'a str {
         Some(&mark[..consumed])
     }
 }
+
+impl FastScan for &str {
+    fn scan<P: FastPredicate + ?Sized>(&mut self, pred: &P) -> Self::Many {
+        let mut n = pred.prefix_len(self.as_bytes());
+        while !self.is_char_boundary(n) {
+            n -= 1;
+        }
+
+        let value = &self[..n];
+        *self = &self[n..];
+        value
+    }
+}
@@ -1,4 +1,5 @@
-pub use crate::input::{Input, Rewind, Token, Slice, Show, ParserInfo};
+pub use crate::input::{Input, Rewind, Position, Token, Slice, Show, ParserInfo};
+use crate::input::{FastScan, FastPredicate};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Span<'a> {
@@ -11,7 +12,7 @@ pub struct Span<'a> {
 }
 
 impl<'a> Show for Span<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let (a, b, _) = self.start;
         let (c, d, _) = self.end;
 
@@ -42,16 +43,39 @@ impl<'a> From<&'a str> for Text<'a> {
     }
 }
 
+impl<'a> Text<'a> {
+    /// Returns the entire text, including what's been parsed.
+    #[inline(always)]
+    pub(crate) fn source(&self) -> &'a str {
+        self.start
+    }
+}
+
 impl<'a, 'b> Slice<Text<'a>> for &'b str { }
 
+impl Slice<Text<'_>> for alloc::string::String { }
+
 // ident_impl_token!(Text<'_>);
 
+#[cfg(feature = "ffi")]
+impl<'a> crate::ffi::FfiInput<'a> for Text<'a> {
+    fn remaining(&self) -> usize {
+        self.current.len()
+    }
+}
+
 impl Rewind for Text<'_> {
     fn rewind_to(&mut self, marker: &Self::Marker) {
         self.current = &self.start[*marker..];
     }
 }
 
+impl Position for Text<'_> {
+    fn position(&self) -> usize {
+        self.start.len() - self.current.len()
+    }
+}
+
 impl<'a> Input for Text<'a> {
     type Token = char;
     type Slice = &'a str;
@@ -70,6 +94,12 @@ impl<'a> Input for Text<'a> {
         self.current.slice(n)
     }
 
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        self.current.peek_slice_at(offset, len)
+    }
+
     /// Checks if the current token fulfills `cond`.
     fn peek<F>(&mut self, cond: F) -> bool
         where F: FnMut(&Self::Token) -> bool
@@ -149,7 +179,13 @@ impl<'a> Input for Text<'a> {
     }
 }
 
-fn line_col(string: &str) -> (usize, usize) {
+impl FastScan for Text<'_> {
+    fn scan<P: FastPredicate + ?Sized>(&mut self, pred: &P) -> Self::Many {
+        self.current.scan(pred)
+    }
+}
+
+pub(crate) fn line_col(string: &str) -> (usize, usize) {
     if string.is_empty() {
         return (1, 1);
     }
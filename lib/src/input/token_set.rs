@@ -0,0 +1,239 @@
+use core::borrow::Borrow;
+use core::ops::{Range, RangeInclusive};
+
+use alloc::{vec, vec::Vec, string::String, format};
+
+use crate::input::{Input, Token, Show, FastPredicate};
+
+/// A class of tokens, as matched by [`eat_any_of()`] and [`none_of()`].
+///
+/// Token classes are arrays and slices of tokens, ranges of tokens, closures
+/// over tokens, and [`TokenSet`]s. A class can describe its members for error
+/// messages with [`TokenClass::members()`].
+///
+/// [`eat_any_of()`]: crate::parsers::eat_any_of()
+/// [`none_of()`]: crate::parsers::none_of()
+pub trait TokenClass<I: Input> {
+    /// Returns `true` if `token` is a member of this class.
+    fn contains(&self, token: &I::Token) -> bool;
+
+    /// Returns the rendered members of this class, if there are few enough to
+    /// list in an error message, or an empty vector otherwise.
+    fn members(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+fn show<T: Show>(token: &T) -> String {
+    use alloc::string::ToString;
+    (token as &dyn Show).to_string()
+}
+
+impl<I: Input, T: Token<I>, const N: usize> TokenClass<I> for [T; N] {
+    fn contains(&self, token: &I::Token) -> bool {
+        self.iter().any(|t| t == token)
+    }
+
+    fn members(&self) -> Vec<String> {
+        self.iter().map(show).collect()
+    }
+}
+
+impl<I: Input, T: Token<I>> TokenClass<I> for &[T] {
+    fn contains(&self, token: &I::Token) -> bool {
+        self.iter().any(|t| t == token)
+    }
+
+    fn members(&self) -> Vec<String> {
+        self.iter().map(show).collect()
+    }
+}
+
+impl<I: Input, T: PartialOrd + Show> TokenClass<I> for Range<T>
+    where I::Token: Borrow<T>
+{
+    fn contains(&self, token: &I::Token) -> bool {
+        Range::contains(self, token.borrow())
+    }
+
+    fn members(&self) -> Vec<String> {
+        vec![format!("{}..{}", show(&self.start), show(&self.end))]
+    }
+}
+
+impl<I: Input, T: PartialOrd + Show> TokenClass<I> for RangeInclusive<T>
+    where I::Token: Borrow<T>
+{
+    fn contains(&self, token: &I::Token) -> bool {
+        RangeInclusive::contains(self, token.borrow())
+    }
+
+    fn members(&self) -> Vec<String> {
+        vec![format!("{}..={}", show(self.start()), show(self.end()))]
+    }
+}
+
+impl<I: Input, F: Fn(&I::Token) -> bool> TokenClass<I> for F {
+    fn contains(&self, token: &I::Token) -> bool {
+        self(token)
+    }
+}
+
+/// A token which can be a member of a [`TokenSet`]: a byte or a `char`.
+pub trait SetMember {
+    /// Returns the byte this token corresponds to, if any. Only ASCII `char`s
+    /// correspond to bytes.
+    fn as_byte(&self) -> Option<u8>;
+}
+
+impl SetMember for u8 {
+    #[inline(always)]
+    fn as_byte(&self) -> Option<u8> {
+        Some(*self)
+    }
+}
+
+impl SetMember for &u8 {
+    #[inline(always)]
+    fn as_byte(&self) -> Option<u8> {
+        Some(**self)
+    }
+}
+
+impl SetMember for char {
+    #[inline(always)]
+    fn as_byte(&self) -> Option<u8> {
+        match self.is_ascii() {
+            true => Some(*self as u8),
+            false => None,
+        }
+    }
+}
+
+/// A set of bytes stored as a 256-bit bitmap.
+///
+/// Testing membership in a `TokenSet` is a single bit lookup, regardless of
+/// the size of the set, making it a faster replacement for chains of
+/// comparisons or long arrays of alternatives. A `TokenSet` matches byte
+/// tokens of inputs like [`Cursor<u8>`](crate::input::Cursor) and ASCII
+/// `char` tokens of text inputs; non-ASCII characters are never members. Sets
+/// are built in `const` contexts with [`TokenSet::new()`],
+/// [`TokenSet::range()`], and [`TokenSet::union()`]. A `TokenSet` is also a
+/// [`FastPredicate`].
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, TokenSet, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// const HEX: TokenSet = TokenSet::range(b'0', b'9')
+///     .union(TokenSet::range(b'a', b'f'))
+///     .union(TokenSet::new(b"ABCDEF"));
+///
+/// #[parser]
+/// fn hex_pair<'a>(input: &mut Text<'a>) -> Result<(char, char), Text<'a>> {
+///     (eat_any_of(HEX)?, eat_any_of(HEX)?)
+/// }
+///
+/// assert_eq!(parse!(hex_pair: &mut Text::from("aF")).unwrap(), ('a', 'F'));
+/// assert!(parse!(hex_pair: &mut Text::from("ag")).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TokenSet {
+    bits: [u64; 4],
+}
+
+impl TokenSet {
+    /// The empty set.
+    pub const EMPTY: TokenSet = TokenSet { bits: [0; 4] };
+
+    /// Returns the set of the bytes in `bytes`.
+    pub const fn new(bytes: &[u8]) -> TokenSet {
+        let mut set = TokenSet::EMPTY;
+        let mut i = 0;
+        while i < bytes.len() {
+            set = set.with(bytes[i]);
+            i += 1;
+        }
+
+        set
+    }
+
+    /// Returns the set of the bytes in the inclusive range `start..=end`.
+    pub const fn range(start: u8, end: u8) -> TokenSet {
+        let mut set = TokenSet::EMPTY;
+        let mut byte = start as u16;
+        while byte <= end as u16 {
+            set = set.with(byte as u8);
+            byte += 1;
+        }
+
+        set
+    }
+
+    /// Returns this set with `byte` added.
+    pub const fn with(mut self, byte: u8) -> TokenSet {
+        self.bits[(byte >> 6) as usize] |= 1 << (byte & 63);
+        self
+    }
+
+    /// Returns the union of this set and `other`.
+    pub const fn union(self, other: TokenSet) -> TokenSet {
+        let [a, b, c, d] = self.bits;
+        let [e, f, g, h] = other.bits;
+        TokenSet { bits: [a | e, b | f, c | g, d | h] }
+    }
+
+    /// Returns the set of bytes not in this set.
+    pub const fn complement(self) -> TokenSet {
+        let [a, b, c, d] = self.bits;
+        TokenSet { bits: [!a, !b, !c, !d] }
+    }
+
+    /// Returns `true` if `byte` is in this set.
+    #[inline(always)]
+    pub const fn contains_byte(&self, byte: u8) -> bool {
+        self.bits[(byte >> 6) as usize] & (1 << (byte & 63)) != 0
+    }
+
+    /// Returns the number of bytes in this set.
+    pub const fn len(&self) -> usize {
+        let [a, b, c, d] = self.bits;
+        (a.count_ones() + b.count_ones() + c.count_ones() + d.count_ones()) as usize
+    }
+
+    /// Returns `true` if this set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<I: Input> TokenClass<I> for TokenSet where I::Token: SetMember {
+    #[inline(always)]
+    fn contains(&self, token: &I::Token) -> bool {
+        token.as_byte().is_some_and(|b| self.contains_byte(b))
+    }
+
+    /// Lists the members of sets of at most eight bytes.
+    fn members(&self) -> Vec<String> {
+        if self.len() > 8 {
+            return vec![];
+        }
+
+        (0..=255u8).filter(|&b| self.contains_byte(b))
+            .map(|b| match b.is_ascii() {
+                true => show(&(b as char)),
+                false => show(&b),
+            })
+            .collect()
+    }
+}
+
+impl FastPredicate for TokenSet {
+    #[inline(always)]
+    fn matches(&self, byte: u8) -> bool {
+        self.contains_byte(byte)
+    }
+}
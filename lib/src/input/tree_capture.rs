@@ -0,0 +1,169 @@
+use core::ops::Range;
+use alloc::{vec, vec::Vec};
+
+use crate::input::{Input, Rewind, Position, Slice, ParserInfo};
+
+/// A node in a concrete syntax tree: a successful invocation of a `#[parser]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxNode {
+    /// The name of the parser.
+    pub name: &'static str,
+    /// The span of input the parser consumed.
+    pub span: Range<usize>,
+    /// The nodes of the parsers it invoked, in order.
+    pub children: Vec<SyntaxNode>,
+}
+
+impl SyntaxNode {
+    /// Returns the text this node was parsed from given the `source` text that
+    /// was parsed.
+    pub fn text<'s>(&self, source: &'s str) -> &'s str {
+        &source[self.span.clone()]
+    }
+
+    /// Returns an iterator over this node and its descendants, in pre-order.
+    pub fn descendants(&self) -> impl Iterator<Item = &SyntaxNode> {
+        let mut stack = vec![self];
+        core::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// A parser invocation in progress.
+#[derive(Debug)]
+struct Frame {
+    name: &'static str,
+    start: usize,
+    children: Vec<SyntaxNode>,
+}
+
+/// An input which records a concrete syntax tree of the parsers run on it.
+///
+/// Every non-raw `#[parser]` that succeeds on a `TreeCapture` input records a
+/// [`SyntaxNode`] with its name, the span of input it consumed, and the nodes
+/// of the parsers it invoked. Failed invocations, and those undone by a rewind,
+/// are discarded. The tree is lossless: the input between a node's children
+/// was consumed by raw parsers, like `eat()`, and is recoverable from the
+/// node's span. Retrieve the tree with [`TreeCapture::roots()`] or
+/// [`TreeCapture::into_roots()`]; the parsed values are returned as usual.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, TreeCapture, Result};
+/// use pear::{macros::*, parsers::*};
+///
+/// type Input<'a> = TreeCapture<Text<'a>>;
+///
+/// #[parser]
+/// fn number<'a>(input: &mut Input<'a>) -> Result<u32, Input<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>().unwrap()
+/// }
+///
+/// #[parser]
+/// fn sum<'a>(input: &mut Input<'a>) -> Result<u32, Input<'a>> {
+///     let a = number()?;
+///     eat_slice(" + ")?;
+///     a + number()?
+/// }
+///
+/// let source = "1 + 23";
+/// let mut input = TreeCapture::new(Text::from(source));
+/// assert_eq!(parse!(sum: &mut input).unwrap(), 24);
+///
+/// let sum = &input.roots()[0];
+/// assert_eq!((sum.name, sum.text(source)), ("sum", "1 + 23"));
+/// assert_eq!(sum.children[1].text(source), "23");
+/// ```
+///
+/// The body of a `#[parser(left_recursive)]` parser is rerun as its seed
+/// grows; only the final run is recorded, and the recursive invocations that
+/// reuse the seed or a memoized result are recorded without children.
+#[derive(Debug)]
+pub struct TreeCapture<I> {
+    input: I,
+    stack: Vec<Frame>,
+    roots: Vec<SyntaxNode>,
+}
+
+impl<I: Input + Position> TreeCapture<I> {
+    /// Wraps `input`, recording a syntax tree as it is parsed.
+    pub fn new(input: I) -> Self {
+        TreeCapture { input, stack: vec![], roots: vec![] }
+    }
+
+    /// Returns the roots of the syntax tree recorded so far: the nodes of the
+    /// outermost parsers that have completed successfully.
+    pub fn roots(&self) -> &[SyntaxNode] {
+        &self.roots
+    }
+
+    /// Returns the inner input and the roots of the recorded syntax tree.
+    pub fn into_roots(self) -> (I, Vec<SyntaxNode>) {
+        (self.input, self.roots)
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// The nodes of the innermost parser in progress, or the roots.
+    fn children(&mut self) -> &mut Vec<SyntaxNode> {
+        match self.stack.last_mut() {
+            Some(frame) => &mut frame.children,
+            None => &mut self.roots,
+        }
+    }
+}
+
+impl<'a, I> Slice<TreeCapture<I>> for &str where I: Input<Slice = &'a str> + Position { }
+
+impl<I: Rewind + Position> Rewind for TreeCapture<I> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker);
+        let position = self.input.position();
+        self.children().retain(|node| node.span.start < position && node.span.end <= position);
+    }
+}
+
+impl<I: Input + Position> Position for TreeCapture<I> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input + Position> Input for TreeCapture<I> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker, Context;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           context, needed, halted;
+    }
+
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        if !info.raw {
+            let start = self.input.position();
+            self.stack.push(Frame { name: info.name, start, children: vec![] });
+        }
+
+        self.input.mark(info)
+    }
+
+    fn unmark(&mut self, info: &ParserInfo, success: bool, mark: Self::Marker) {
+        if !info.raw {
+            if let Some(frame) = self.stack.pop() {
+                if success {
+                    let span = frame.start..self.input.position();
+                    let node = SyntaxNode { name: frame.name, span, children: frame.children };
+                    self.children().push(node);
+                }
+            }
+        }
+
+        self.input.unmark(info, success, mark)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::input::{Input, Show, Rewind, Position, Slice};
+
+/// An input carrying a grammar version, or dialect, alongside an inner input.
+///
+/// Formats with several dialects often differ in only a handful of
+/// productions. Rather than threading a flag through every parser, wrap the
+/// input in a `Versioned` and branch on the version where the dialects differ,
+/// either by inspecting [`Versioned::version()`] directly or, more concisely,
+/// with [`when_version!`]. The version can be changed mid-parse, for instance,
+/// after reading a version header, with [`Versioned::set_version()`].
+///
+/// Apart from carrying the version, a `Versioned` input behaves exactly like
+/// its inner input.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Versioned, Result};
+/// use pear::macros::{parser, parse, when_version};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = Versioned<Text<'a>>;
+///
+/// // v1 comments start with `#`; v2 comments start with `//`.
+/// #[parser]
+/// fn comment_start<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+///     when_version!(input, >= 2, eat_slice("//"), eat_slice("#"))?
+/// }
+///
+/// let result = parse!(comment_start: &mut Versioned::new(Text::from("#"), 1));
+/// assert_eq!(result.unwrap(), "#");
+///
+/// let result = parse!(comment_start: &mut Versioned::new(Text::from("#"), 2));
+/// assert!(result.is_err());
+/// ```
+///
+/// [`when_version!`]: crate::macros#when_version
+#[derive(Debug)]
+pub struct Versioned<I, V = u32> {
+    input: I,
+    version: V,
+}
+
+impl<I: Input, V> Versioned<I, V> {
+    /// Wraps `input`, parsing it according to the grammar version `version`.
+    pub fn new(input: I, version: V) -> Self {
+        Versioned { input, version }
+    }
+
+    /// Returns the current grammar version.
+    #[inline(always)]
+    pub fn version(&self) -> &V {
+        &self.version
+    }
+
+    /// Sets the grammar version used by subsequent parsers.
+    pub fn set_version(&mut self, version: V) {
+        self.version = version;
+    }
+
+    /// Returns a reference to the inner input.
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns the inner input and the current version.
+    pub fn into_inner(self) -> (I, V) {
+        (self.input, self.version)
+    }
+}
+
+impl<'a, I, V> Slice<Versioned<I, V>> for &str where I: Input<Slice = &'a str> { }
+
+impl<'a, T, I, V> Slice<Versioned<I, V>> for &[T]
+    where T: PartialEq + Show + 'a, I: Input<Slice = &'a [T]> { }
+
+impl<I: Rewind, V> Rewind for Versioned<I, V> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
+
+impl<I: Position, V> Position for Versioned<I, V> {
+    fn position(&self) -> usize {
+        self.input.position()
+    }
+}
+
+impl<I: Input, V> Input for Versioned<I, V> {
+    delegate_input! {
+        to input: I;
+        type Token, Slice, Many, Marker, Context;
+        fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice, take, skip, has,
+           mark, context, unmark, needed, halted;
+    }
+}
@@ -3,13 +3,50 @@
 
 #![warn(rust_2018_idioms)]
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 #[macro_use] pub mod macros;
 pub mod input;
 pub mod result;
 pub mod error;
 pub mod parsers;
 pub mod combinators;
+pub mod provenance;
+pub mod export;
+pub mod incremental;
+pub mod session;
+pub mod grammar;
+pub mod formats;
+pub mod wrap;
+pub mod pipeline;
+pub mod fuzz;
+pub mod emit;
+#[cfg(feature = "std")] pub mod recursion;
+#[cfg(feature = "std")] pub mod trace;
+#[cfg(feature = "std")] pub mod replay;
+#[cfg(feature = "rayon")] pub mod parallel;
+#[cfg(feature = "ffi")] pub mod ffi;
+#[cfg(any(feature = "miette", feature = "ariadne"))] pub mod report;
+#[cfg(feature = "serde")] pub mod serde;
+#[cfg(feature = "proptest")] pub mod proptest;
 
 mod expected;
+mod parseable;
+
+pub use parseable::{FromPearStr, parse_str};
+pub use emit::Emit;
+pub use error::Error;
+
+#[doc(hidden)] pub use pear_codegen::parse_declare;
+#[doc(hidden)] pub use pear_codegen::parser_closure as parser;
 
 #[doc(hidden)] pub mod debug;
+
+/// Items used by macro and derive expansions. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::format;
+    pub use crate::parseable::separated;
+}
@@ -4,14 +4,16 @@
 //!
 //!   * [`#[parser]`](#parser)
 //!
-//!     The core attribute macro. Can only be applied to free functions with at
-//!     least one parameter and a return value. To typecheck, the free function
-//!     must meet the following typing requirements:
+//!     The core attribute macro. Can only be applied to free functions and
+//!     methods with at least one parameter and a return value. To typecheck,
+//!     the function must meet the following typing requirements:
 //!
 //!     - The _first_ parameter's type `&mut I` must be a mutable reference to a
-//!       type that implements [`Input`]. This is the _input_ parameter.
+//!       type that implements [`Input`]. This is the _input_ parameter. For
+//!       methods, this is the first parameter after `self`.
 //!     - The return type must be [`Result<O, I>`] where `I` is the inner type
-//!       of the input parameter and `O` can be any type.
+//!       of the input parameter and `O` can be any type, including an `impl
+//!       Trait`.
 //!
 //!     The following transformations are applied to the _contents_ of the
 //!     attributed function:
@@ -20,7 +22,14 @@
 //!       first parameter to every function call in the function with a posfix
 //!       `?`. That is, every function call of the form `foo(a, b, c, ...)?` is
 //!       converted to `foo(input, a, b, c, ...)?` where `input` is the input
-//!       parameter.
+//!       parameter. The same goes for calls to methods on `self`: in a method,
+//!       `self.foo(a, b)?` is converted to `self.foo(input, a, b)?`, as are
+//!       the conditions of `switch!`.
+//!     - The error of every other expression with a postfix `?` is converted
+//!       into a [`ParseError`] via [`IntoParseError`], so that `?` can be
+//!       applied to results from outside of Pear, such as those of
+//!       `str::parse()`. A foreign error becomes an error, with its `Display`
+//!       as the message, about the input consumed by the function so far.
 //!     - The inputs to every macro whose name starts with `parse_` are prefixed
 //!       with `[PARSER_NAME, INPUT, MARKER, OUTPUT]` where `PARSER_NAME` is the
 //!       raw string literal of the functon's name, `INPUT` is the input
@@ -85,6 +94,130 @@
 //!     assert!(x.is_err());
 //!     ```
 //!
+//!     Attribute arguments may each be given once. Combinations that
+//!     contradict one another or have no effect are rejected at compile time:
+//!     `rewind` or `peek` with `raw`, `rewind` with `ffi`, whose errors report
+//!     where parsing failed, and `peek` with `ffi`, whose input is discarded.
+//!
+//!     ```rust,compile_fail
+//!     # #![feature(proc_macro_hygiene)]
+//!     # use pear::input::{Text, Result};
+//!     # use pear::macros::parser;
+//!     # use pear::parsers::*;
+//!     #[parser(ffi, rewind)]
+//!     fn digits<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+//!         take_some_while(|c: &char| c.is_ascii_digit())?
+//!     }
+//!     ```
+//!
+//!   * [`#[grammar]`](#grammar)
+//!
+//!     Applied to an inline module, appends a "Grammar" section to its
+//!     documentation listing an EBNF production for every `#[parser]` function
+//!     and method in it, and adds a `GRAMMAR` constant with the same listing.
+//!     A production is derived from the parsers a function's body calls: calls
+//!     followed by `?` in sequence, `switch!` cases, `if`s and `match`es as
+//!     alternatives, loops as repetitions, and `parse_try!` as options. Calls to
+//!     `eat()` and `eat_slice()` with a literal are shown as the literal, and
+//!     calls like `take_while()` as repetitions of the token their predicate
+//!     describes. A loop guarded by `while let .. = parse_try!(item)` that
+//!     breaks when `parse_try!(separator)` fails is shown as a list of `item`s
+//!     separated by `separator`.
+//!
+//!     `#[parser(doc_grammar)]` similarly appends a "Grammar" section with its
+//!     production to a single parser's documentation.
+//!
+//!     ```rust
+//!     #![feature(proc_macro_hygiene)]
+//!
+//!     #[pear::macros::grammar]
+//!     mod sign {
+//!         use pear::input::{Text, Result};
+//!         use pear::{macros::*, parsers::*};
+//!
+//!         #[parser]
+//!         pub fn signed<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+//!             let sign = switch! { eat('-') => -1, _ => 1 };
+//!             sign * digits()?
+//!         }
+//!
+//!         #[parser]
+//!         fn digits<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+//!             take_some_while(|c: &char| c.is_ascii_digit())?.parse::<i64>().unwrap()
+//!         }
+//!     }
+//!
+//!     assert_eq!(sign::GRAMMAR, "signed = [ '-' ] digits ;\n\
+//!         digits = ? c.is_ascii_digit() ? { ? c.is_ascii_digit() ? } ;");
+//!     ```
+//!
+//! Derive Macros:
+//!
+//!   * [`#[derive(Describe)]`](#derivedescribe)
+//!
+//!     Derives [`Describe`] and [`Show`] for an enum of tokens, so that errors
+//!     name tokens by human-readable descriptions instead of their `Debug`
+//!     representation. Each variant is described by its `#[describe("...")]`
+//!     attribute, or if it has none, by its name in lowercase words: `a
+//!     string literal` and `left paren` below.
+//!
+//!     ```rust
+//!     use pear::macros::Describe;
+//!
+//!     #[derive(Debug, PartialEq, Describe)]
+//!     enum Token<'a> {
+//!         #[describe("a string literal")]
+//!         Str(&'a str),
+//!         LeftParen,
+//!     }
+//!     ```
+//!
+//!   * [`#[derive(Emit)]`](#deriveemit)
+//!
+//!     Derives [`Emit`] for an AST type, so that values can be written back
+//!     to the text they were parsed from. A struct or variant emits its fields
+//!     in order, or as laid out by its `#[emit("...")]` template, in which
+//!     `{field}` emits a field. A field marked `#[emit(separator = "...")]` is
+//!     a sequence whose items are emitted with the separator between them.
+//!
+//!     ```rust
+//!     use pear::macros::Emit;
+//!
+//!     #[derive(Emit)]
+//!     #[emit("{name}({args})")]
+//!     struct Call {
+//!         name: String,
+//!         #[emit(separator = ", ")]
+//!         args: Vec<u32>,
+//!     }
+//!
+//!     let call = Call { name: "f".into(), args: vec![1, 2] };
+//!     assert_eq!(pear::emit::to_string(&call), "f(1, 2)");
+//!     ```
+//!
+//!   * [`#[derive(FromPearStr)]`](#derivefrompearstr)
+//!
+//!     Derives [`FromPearStr`] for an AST type. A struct or variant parses its
+//!     fields in order, or as laid out by its `#[parse("...")]` template, in
+//!     which `{field}` parses a field. A field marked
+//!     `#[parse(separator = "...")]` is a sequence of items separated by the
+//!     separator. An enum parses as the first variant that parses.
+//!
+//!     ```rust
+//!     use pear::macros::FromPearStr;
+//!
+//!     #[derive(Debug, PartialEq, FromPearStr)]
+//!     #[parse("{name}:{args}")]
+//!     struct Call {
+//!         name: u32,
+//!         #[parse(separator = ", ")]
+//!         args: Vec<u32>,
+//!     }
+//!
+//!     let call = pear::parse_str::<Call>("7:1, 2").unwrap();
+//!     assert_eq!(call, Call { name: 7, args: vec![1, 2] });
+//!     ```
+//!
 //! Bang Macros:
 //!
 //!   * [`parse!`](#parse)
@@ -95,9 +228,10 @@
 //!     Syntax:
 //!
 //!     ```text
-//!     parse := PARSER_NAME ':' INPUT_EXPR
+//!     parse := (RECEIVER '.')? PARSER_NAME ':' INPUT_EXPR
 //!
-//!     PARSER_NAME := rust identifier to parser function
+//!     RECEIVER := rust identifier to a value with parser methods
+//!     PARSER_NAME := rust identifier to parser function or method
 //!     INPUT_EXPR := any valid rust expression which resolves to a mutable
 //!                   reference to type that implements `Input`
 //!     ```
@@ -107,10 +241,42 @@
 //!     Invoked with no arguments: `parse_context!()`. Returns the current
 //!     context given the current mark.
 //!
+//!     Outside of a function attributed with `#[parser]`, the input and a
+//!     marker must be passed explicitly: `parse_context!(input, &marker)`.
+//!
 //!   * [`parse_marker!`](#parse_marker)
 //!
 //!     Invoked with no arguments: `parse_marker!()`. Returns the current mark.
 //!
+//!     Outside of a function attributed with `#[parser]`, the input must be
+//!     passed explicitly: `parse_marker!(input)`. The input is then marked
+//!     with [`Input::mark()`], and the resulting marker is returned. The
+//!     marker is outstanding until it's passed to [`Input::unmark()`], with
+//!     the info `ParserInfo::new("parse_marker", true)`, which must be done
+//!     once it's no longer needed: an input like
+//!     [`IterInput`](crate::input::IterInput) retains everything read while a
+//!     marker is outstanding.
+//!
+//!     ```rust
+//!     use pear::input::{Text, Input, ParserInfo, Result};
+//!     use pear::macros::{parse_marker, parse_context};
+//!     use pear::parsers::*;
+//!
+//!     fn word<'a>(input: &mut Text<'a>) -> Result<(&'a str, usize), Text<'a>> {
+//!         let start = parse_marker!(input);
+//!         let result = take_some_while(input, |c: &char| c.is_alphabetic())
+//!             .map(|word| (word, parse_context!(input, &start).unwrap().start.2));
+//!
+//!         input.unmark(&ParserInfo::new("parse_marker", true), result.is_ok(), start);
+//!         result
+//!     }
+//!
+//!     let mut input = Text::from("to be");
+//!     assert_eq!(word(&mut input).unwrap(), ("to", 0));
+//!     assert_eq!(eat(&mut input, ' ').unwrap(), ' ');
+//!     assert_eq!(word(&mut input).unwrap(), ("be", 3));
+//!     ```
+//!
 //!   * [`switch!`](#switch)
 //!
 //!     Invoked much like match, except each condition must be a parser, which is
@@ -125,14 +291,162 @@
 //!     }
 //!     ```
 //!
+//!     A condition's output can be destructured by any irrefutable pattern in
+//!     place of a name: `(key, value)@pair() => ..`.
+//!
+//!     The alternatives of a case, separated by `|`, may bind different names
+//!     or none at all, as long as every name the arm uses is bound by every
+//!     alternative.
+//!
+//!     A switch without a final `_` case evaluates to `()` when no condition
+//!     succeeds, so it fails to compile, suggesting a `_` case, unless its
+//!     arms are `()` too. A call to a parser that consumes no input when it
+//!     fails, like `eat('a')`, identical to one in an earlier case is reported
+//!     with a warning: it only runs once the earlier one has failed, and so
+//!     fails too.
+//!
+//!     ```rust,compile_fail
+//!     # #![feature(proc_macro_hygiene)]
+//!     # use pear::input::{Text, Result};
+//!     # use pear::macros::{parser, switch};
+//!     # use pear::parsers::*;
+//!     #[parser]
+//!     fn sign<'a>(input: &mut Text<'a>) -> Result<i8, Text<'a>> {
+//!         switch! {
+//!             eat('+') => 1,
+//!             eat('-') => -1,
+//!         }
+//!     }
+//!     ```
+//!
 //!   * [`parse_try!`](#parse_try)
 //!
 //!     Take a single parser expression as input. Runs the parser. If the parser
 //!     succeeds, returns `Some` of the result. If the parser fails, returns
 //!     `None`.
 //!
+//!   * [`when_version!`](#when_version)
+//!
+//!     Runs one of two parsers depending on the grammar version of a
+//!     [`Versioned`] input. The first argument is the input, the second is a
+//!     comparison operator and a version, and the remaining two are parser
+//!     calls, written without their input, as in the conditions of `switch!`.
+//!     Evaluates to the result of the first parser if the input's version
+//!     satisfies the comparison and to the result of the second otherwise.
+//!
+//!     ```rust,ignore
+//!     when_version!(input, >= 2, v2_header(), v1_header())?
+//!     when_version!(input, == Dialect::Strict, strict_value(x), value(x))?
+//!     ```
+//!
+//!   * [`state!`](#state)
+//!
+//!     Invoked with no arguments: `state!()`. Returns a mutable reference to
+//!     the user state of a [`Stateful`] input. Only valid in a function
+//!     attributed with `#[parser]` whose input is a `Stateful`.
+//!
+//!     ```rust,ignore
+//!     state!().indents.push(column);
+//!     if state!().strict { .. }
+//!     ```
+//!
 //!   * [`parse_declare!`](#parse_declare)
+//!
+//!     Declares a trait, implemented for every input it describes, to use as
+//!     an alias for an [`Input`] with the given associated types. Each
+//!     associated type is either fixed, as in `Slice = &'a str`, or bounded,
+//!     as in `Many: Into<String>`; the rest are left free. The trait may be
+//!     generic, as long as its generics are used by the associated types, and
+//!     may require further traits, like [`Rewind`], of the input.
+//!
+//!     ```rust
+//!     # #![feature(proc_macro_hygiene)]
+//!     # use pear::input::{Rewind, Length, Show};
+//!     # use pear::macros::parse_declare;
+//!     parse_declare!(pub Input<'a>(Token = char, Slice = &'a str, Many = &'a str));
+//!     parse_declare!(Tokens<'a, T: PartialEq + Show + 'a>: Rewind (
+//!         Token = &'a T,
+//!         Slice = &'a [T],
+//!         Many: Length + Copy,
+//!     ));
+//!     ```
+//!
+//!   * [`delegate_input!`](#delegate_input)
+//!
+//!     Used inside of an `impl Input` block for an input that wraps another,
+//!     forwards the listed associated types and methods of [`Input`] to the
+//!     wrapped input, a field of `self`, so that only the methods the wrapper
+//!     changes need be written by hand. Methods are forwarded as is: `mark()`
+//!     and `context()` assume the wrapper's `Marker` and `Context` are the
+//!     wrapped input's.
+//!
+//!     ```rust
+//!     # use pear::input::{Input, ParserInfo, Text};
+//!     # use pear::macros::delegate_input;
+//!     /// An input which counts the parsers that fail on it.
+//!     struct Failures<I> {
+//!         input: I,
+//!         failures: usize,
+//!     }
+//!
+//!     impl<I: Input> Input for Failures<I> {
+//!         delegate_input! {
+//!             to input: I;
+//!             type Token, Slice, Many, Marker, Context;
+//!             fn token, slice, peek_slice_at, peek, peek_slice, eat, eat_slice,
+//!                take, skip, has, mark, context, needed, halted;
+//!         }
+//!
+//!         fn unmark(&mut self, info: &ParserInfo, success: bool, mark: Self::Marker) {
+//!             self.failures += !success as usize;
+//!             self.input.unmark(info, success, mark)
+//!         }
+//!     }
+//!     # let _ = Failures { input: Text::from(""), failures: 0 };
+//!     ```
+//!
 //!   * [`parse_error!`](#parse_error)
+//!
+//!     Returns an `Err` of a [`ParseError`] built from its argument, which is
+//!     either an error or a format string and arguments:
+//!     `parse_error!("bad digit {}", d)`. Unlike the other macros, usable in
+//!     any function.
+//!
+//!   * `pear::parser!`
+//!
+//!     Defines an anonymous parser inline, as a closure that behaves like a
+//!     function attributed with `#[parser]`: calls to parsers in its body take
+//!     its input implicitly, the other macros are usable in it, and when it
+//!     fails, its context is pushed into the error. The closure may be
+//!     preceded by a name for its context, `closure` by default, and by any
+//!     of the attribute's `rewind`, `peek`, `commit`, `label`, `map_err`,
+//!     `spanned`, and `complete` arguments:
+//!
+//!     ```rust
+//!     #![feature(proc_macro_hygiene)]
+//!
+//!     use pear::input::{Text, Result};
+//!     use pear::macros::{parser, parse};
+//!     use pear::{parsers::*, combinators::*};
+//!
+//!     #[parser]
+//!     fn list<'a>(input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+//!         let item = pear::parser!("item", rewind, |input: &mut Text<'a>| {
+//!             skip_while(|c: &char| c.is_whitespace())?;
+//!             take_some_while(|c: &char| c.is_alphabetic())?
+//!         });
+//!
+//!         let items: Vec<_> = series(item, ',')?;
+//!         items
+//!     }
+//!
+//!     let items = parse!(list: &mut Text::from("a, b,c")).unwrap();
+//!     assert_eq!(items, ["a", "b", "c"]);
+//!     ```
+//!
+//!     The input's type may be omitted, `|input|`, where it can be inferred:
+//!     when the closure is passed directly to a combinator.
+//!
 //!   * [`impl_show_with!`](#impl_show_with)
 //!
 //! [`Input`]: crate::input::Input
@@ -140,52 +454,82 @@
 //! [`Input::mark()`]: crate::input::Input::mark()
 //! [`Input::unmark()`]: crate::input::Input::unmark()
 //! [`Input::context()`]: crate::input::Input::context()
+//! [`ParseError`]: crate::error::ParseError
+//! [`IntoParseError`]: crate::error::IntoParseError
 //! [`ParseError::push_context()`]: crate::error::ParseError::push_context()
 //! [`eof()`]: crate::parsers::eof()
+//! [`Versioned`]: crate::input::Versioned
+//! [`Stateful`]: crate::input::Stateful
+//! [`Describe`]: crate::input::Describe
+//! [`Show`]: crate::input::Show
+//! [`Emit`]: crate::emit::Emit
+//! [`FromPearStr`]: crate::FromPearStr
+//! [`Rewind`]: crate::input::Rewind
 
-#[doc(hidden)] pub use pear_codegen::{parser, switch};
-#[doc(hidden)] pub use crate::{parse, parse_declare, parse_error, parse_try, is_parse_debug};
+#[doc(hidden)] pub use pear_codegen::{parser, switch, grammar, parse_declare, Describe, Emit, FromPearStr};
+#[doc(hidden)] pub use crate::{parse, parse_error, parse_try, is_parse_debug};
 #[doc(hidden)] pub use crate::{parse_marker, parse_mark, parse_context};
-#[doc(hidden)] pub use crate::impl_show_with;
+#[doc(hidden)] pub use crate::{impl_show_with, when_version, state, delegate_input};
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! parse {
     ($parser:ident : $e:expr) => ({
         let input = $e;
-        (move || {
+        #[allow(clippy::result_large_err)]
+        let result = (move || {
             let result = $parser(input)?;
             $crate::parsers::eof(input).map_err(|e| e.into())?;
             $crate::result::AsResult::as_result(result)
-        })()
+        })();
+
+        result
     });
     ($parser:ident ($($x:expr),*) : $e:expr) => ({
         let input = $e;
-        (move || {
+        #[allow(clippy::result_large_err)]
+        let result = (move || {
             let result = $parser(input, $($x),*)?;
             $crate::parsers::eof(input).map_err(|e| e.into())?;
             $crate::result::AsResult::as_result(result)
-        })()
+        })();
+
+        result
+    });
+    ($receiver:ident . $parser:ident $(($($x:expr),*))? : $e:expr) => ({
+        let input = $e;
+        #[allow(clippy::result_large_err)]
+        let result = (|| {
+            let result = $receiver.$parser(input $($(, $x)*)?)?;
+            $crate::parsers::eof(input).map_err(|e| e.into())?;
+            $crate::result::AsResult::as_result(result)
+        })();
+
+        result
     })
 }
 
+/// The types that a `switch!` without a `_` case can evaluate to: when no
+/// case matches, such a switch evaluates to `()`.
 #[doc(hidden)]
-#[macro_export(local_inner_macros)]
-macro_rules! parse_declare {
-    (pub($($inner:tt)+) $($rest:tt)*) => { $crate::_parse_declare!([pub($($inner)+)] $($rest)*); };
-    (pub $($rest:tt)*) => { $crate::_parse_declare!([pub] $($rest)*); };
-    ($($rest:tt)*) => { $crate::_parse_declare!([] $($rest)*); }
+#[diagnostic::on_unimplemented(
+    message = "`switch!` has no `_` case, so it evaluates to `()` if no case matches",
+    label = "expected `()`, found `{Self}`",
+    note = "add an irrefutable `_ => ...` case as the last case of the switch"
+)]
+pub trait SwitchFallthrough {
+    fn fallthrough() -> Self;
 }
 
-#[doc(hidden)]
-#[macro_export(local_inner_macros)]
-macro_rules! _parse_declare {
-    ([$($vis:tt)*] $input:ident $(<$($gen:tt),+>)* ($($T:ident = $t:ty),*)) => {
-        $($vis)* trait $input $(<$($gen),+>)*: $crate::input::Input<$($T = $t),*> {  }
+impl SwitchFallthrough for () {
+    fn fallthrough() { }
+}
 
-        impl<$($($gen,)+)* T> $input $(<$($gen)+>)* for T
-            where T: $crate::input::Input<$($T = $t),*> + $($($gen),+)* {  }
-    }
+/// Generated by `switch!` as the result of a switch without a `_` case when
+/// no case matches; not intended to be called directly.
+#[doc(hidden)]
+pub fn switch_fallthrough<T: SwitchFallthrough>() -> T {
+    T::fallthrough()
 }
 
 #[doc(hidden)]
@@ -195,7 +539,13 @@ macro_rules! parse_error {
         Err($crate::error::ParseError::new($err))
     };
     ([$n:expr; $i:expr; $m:expr; $T:ty] $fmt:expr, $($arg:tt)*) => {
-        parse_error!([$n; $i; $m; $T] format!($fmt, $($arg)*))
+        parse_error!([$n; $i; $m; $T] $crate::__private::format!($fmt, $($arg)*))
+    };
+    ($err:expr) => {
+        Err($crate::error::ParseError::new(::core::convert::Into::into($err)))
+    };
+    ($fmt:expr, $($arg:tt)*) => {
+        parse_error!($crate::__private::format!($fmt, $($arg)*))
     };
 }
 
@@ -203,6 +553,9 @@ macro_rules! parse_error {
 #[macro_export]
 macro_rules! parse_marker {
     ([$n:expr; $i:expr; $marker:expr; $T:ty]) => (*$marker);
+    ($input:expr) => (
+        $crate::input::Input::mark($input, &$crate::input::ParserInfo::new("parse_marker", true))
+    );
 }
 
 #[doc(hidden)]
@@ -219,6 +572,9 @@ macro_rules! parse_context {
     ([$n:expr; $i:expr; $marker:expr; $T:ty]) => (
         $crate::input::Input::context($i, $marker)
     );
+    ($input:expr, $marker:expr) => (
+        $crate::input::Input::context($input, $marker)
+    );
 }
 
 /// FIXME: This is an issue with rustc here where if `$input` is `expr`
@@ -227,16 +583,38 @@ macro_rules! parse_context {
 #[macro_export]
 macro_rules! parse_try {
     ([$n:expr; $input:ident; $m:expr; $T:ty] $e:expr) => {{
-        $crate::macros::switch! { [$n;$input;$m;$T] result@$e => { Some(result) }, _ => { None } }
+        $crate::macros::switch! { [$n;$input;$m;$T;$crate] result@$e => { Some(result) }, _ => { None } }
     }};
     ([$n:expr; $input:ident; $m:expr; $T:ty] $e:expr => $r:expr) => {{
-        $crate::macros::switch! { [$n;$input;$m;$T] $e => { Some($r) }, _ => { None } }
+        $crate::macros::switch! { [$n;$input;$m;$T;$crate] $e => { Some($r) }, _ => { None } }
     }};
     ([$n:expr; $input:ident; $m:expr; $T:ty] $pat:ident@$e:expr => $r:expr) => {{
-        $crate::macros::switch! { [$n;$input;$m;$T] $pat@$e => { Some($r) }, _ => { None } }
+        $crate::macros::switch! { [$n;$input;$m;$T;$crate] $pat@$e => { Some($r) }, _ => { None } }
     }}
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! when_version {
+    ($input:expr, $op:tt $version:expr,
+        $($p:ident)::+ $(($($a:expr),* $(,)?))?,
+        $($q:ident)::+ $(($($b:expr),* $(,)?))? $(,)?) => {{
+        let input = &mut *$input;
+        if *$crate::input::Versioned::version(input) $op $version {
+            $($p)::+(input $($(, $a)*)?)
+        } else {
+            $($q)::+(input $($(, $b)*)?)
+        }
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! state {
+    ([$n:expr; $i:expr; $m:expr; $T:ty]) => ($crate::input::Stateful::state_mut(&mut *$i));
+}
+
+#[cfg(feature = "std")]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! is_parse_debug {
@@ -257,19 +635,149 @@ macro_rules! is_parse_debug {
     })
 }
 
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! is_parse_debug {
+    () => (false);
+    ($kind:expr) => (false);
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! impl_show_with {
+    (Describe, $($T:ty),+) => (
+        $(impl $crate::input::Show for $T {
+            #[inline(always)]
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                $crate::input::Describe::describe(self, f)
+            }
+        })+
+    );
     ($trait:ident, $($T:ty),+) => (
         $(impl $crate::input::Show for $T {
             #[inline(always)]
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                std::fmt::$trait::fmt(self, f)
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::$trait::fmt(self, f)
             }
         })+
     )
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! delegate_input {
+    (to $field:ident: $I:ty; $(type $($ty:ident),+;)? $(fn $($method:ident),+;)?) => (
+        $($($crate::delegate_input!(@type $I, $ty);)+)?
+        $($($crate::delegate_input!(@fn $field, $I, $method);)+)?
+    );
+    (@type $I:ty, $ty:ident) => (
+        type $ty = <$I as $crate::input::Input>::$ty;
+    );
+    (@fn $f:ident, $I:ty, token) => (
+        #[inline(always)]
+        fn token(&mut self) -> Option<Self::Token> {
+            <$I as $crate::input::Input>::token(&mut self.$f)
+        }
+    );
+    (@fn $f:ident, $I:ty, slice) => (
+        #[inline(always)]
+        fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+            <$I as $crate::input::Input>::slice(&mut self.$f, n)
+        }
+    );
+    (@fn $f:ident, $I:ty, peek_slice_at) => (
+        #[inline(always)]
+        fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+            <$I as $crate::input::Input>::peek_slice_at(&mut self.$f, offset, len)
+        }
+    );
+    (@fn $f:ident, $I:ty, peek) => (
+        #[inline(always)]
+        fn peek<F>(&mut self, cond: F) -> bool
+            where F: FnMut(&Self::Token) -> bool
+        {
+            <$I as $crate::input::Input>::peek(&mut self.$f, cond)
+        }
+    );
+    (@fn $f:ident, $I:ty, peek_slice) => (
+        #[inline(always)]
+        fn peek_slice<F>(&mut self, n: usize, cond: F) -> bool
+            where F: FnMut(&Self::Slice) -> bool
+        {
+            <$I as $crate::input::Input>::peek_slice(&mut self.$f, n, cond)
+        }
+    );
+    (@fn $f:ident, $I:ty, eat) => (
+        #[inline(always)]
+        fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+            where F: FnMut(&Self::Token) -> bool
+        {
+            <$I as $crate::input::Input>::eat(&mut self.$f, cond)
+        }
+    );
+    (@fn $f:ident, $I:ty, eat_slice) => (
+        #[inline(always)]
+        fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
+            where F: FnMut(&Self::Slice) -> bool
+        {
+            <$I as $crate::input::Input>::eat_slice(&mut self.$f, n, cond)
+        }
+    );
+    (@fn $f:ident, $I:ty, take) => (
+        #[inline(always)]
+        fn take<F>(&mut self, cond: F) -> Self::Many
+            where F: FnMut(&Self::Token) -> bool
+        {
+            <$I as $crate::input::Input>::take(&mut self.$f, cond)
+        }
+    );
+    (@fn $f:ident, $I:ty, skip) => (
+        #[inline(always)]
+        fn skip<F>(&mut self, cond: F) -> usize
+            where F: FnMut(&Self::Token) -> bool
+        {
+            <$I as $crate::input::Input>::skip(&mut self.$f, cond)
+        }
+    );
+    (@fn $f:ident, $I:ty, has) => (
+        #[inline(always)]
+        fn has(&mut self, n: usize) -> bool {
+            <$I as $crate::input::Input>::has(&mut self.$f, n)
+        }
+    );
+    (@fn $f:ident, $I:ty, mark) => (
+        #[inline(always)]
+        fn mark(&mut self, info: &$crate::input::ParserInfo) -> Self::Marker {
+            <$I as $crate::input::Input>::mark(&mut self.$f, info)
+        }
+    );
+    (@fn $f:ident, $I:ty, context) => (
+        #[inline(always)]
+        fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+            <$I as $crate::input::Input>::context(&mut self.$f, mark)
+        }
+    );
+    (@fn $f:ident, $I:ty, unmark) => (
+        #[inline(always)]
+        fn unmark(&mut self, info: &$crate::input::ParserInfo, success: bool, mark: Self::Marker) {
+            <$I as $crate::input::Input>::unmark(&mut self.$f, info, success, mark)
+        }
+    );
+    (@fn $f:ident, $I:ty, needed) => (
+        #[inline(always)]
+        fn needed(&self) -> Option<$crate::error::Needed> {
+            <$I as $crate::input::Input>::needed(&self.$f)
+        }
+    );
+    (@fn $f:ident, $I:ty, halted) => (
+        #[inline(always)]
+        fn halted(&self) -> Option<$crate::error::ErrorCode> {
+            <$I as $crate::input::Input>::halted(&self.$f)
+        }
+    );
+}
+
 // #[doc(hidden)]
 // #[macro_export]
 // macro_rules! ident_impl_token {
@@ -0,0 +1,197 @@
+//! Parsing record-oriented inputs, like CSV files and logs, in parallel.
+//!
+//! A large input made of independent records is first [`split()`] into
+//! records by a _boundary scanner_: a function which, given the rest of the
+//! input, returns the range of the separator ending the next record, if
+//! there is one. The scan is sequential, so a scanner may track state such
+//! as whether it's inside of a quoted field. The records are then parsed, each
+//! as a complete input, by a Pear parser running on rayon's thread pool.
+//!
+//! [`parse()`] does both, returning the outputs of every record in input
+//! order, or, if any record failed to parse, the errors of every failed record
+//! in input order, each tagged with the record's index and offset.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::{macros::*, parsers::*};
+//! use pear::parallel::{self, delimiter};
+//!
+//! #[parser]
+//! fn entry<'a>(input: &mut Text<'a>) -> Result<(&'a str, u32), Text<'a>> {
+//!     let level = take_some_while(|c: &char| c.is_alphabetic())?;
+//!     eat(' ')?;
+//!     (level, take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>().unwrap())
+//! }
+//!
+//! let log = "INFO 200\nWARN 404\nINFO 200\n";
+//! let entries = parallel::parse(log, delimiter(b'\n'), entry).unwrap();
+//! assert_eq!(entries, [("INFO", 200), ("WARN", 404), ("INFO", 200)]);
+//!
+//! let errors = parallel::parse("INFO 200\nWARN\nINFO\n", delimiter(b'\n'), entry).unwrap_err();
+//! assert_eq!(errors.iter().map(|e| e.offset).collect::<Vec<_>>(), [9, 14]);
+//! ```
+//!
+//! This module is only available with the `rayon` feature enabled.
+
+use core::ops::Range;
+
+use rayon::prelude::*;
+
+use crate::error::Error;
+use crate::input::{Input, Result, ParseError};
+use crate::parsers::eof;
+
+/// An input that can be split into records: `str` and `[u8]`.
+pub trait Source: Sync {
+    /// Returns the length of the input in bytes.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the input is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the input's bytes.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Returns the subrange `range` of the input.
+    fn slice(&self, range: Range<usize>) -> &Self;
+}
+
+impl Source for str {
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+}
+
+impl Source for [u8] {
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+}
+
+/// A record of an input, as split by [`split()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Record<'a, T: ?Sized> {
+    /// The index of the record among all records.
+    pub index: usize,
+    /// The offset of the record from the start of the input, in bytes.
+    pub offset: usize,
+    /// The record itself, without its separator.
+    pub data: &'a T,
+}
+
+/// The error a record failed to parse with in [`parse()`].
+#[derive(Debug, Clone)]
+pub struct RecordError<E> {
+    /// The index of the record among all records.
+    pub index: usize,
+    /// The offset of the record from the start of the input, in bytes.
+    pub offset: usize,
+    /// The error the record failed to parse with. Its contexts are relative to
+    /// the record, not to the whole input.
+    pub error: E,
+}
+
+/// Returns a boundary scanner that ends records at every `byte`, as in
+/// `delimiter(b'\n')` for line-oriented inputs.
+///
+/// For `str` inputs, `byte` must be ASCII.
+pub fn delimiter<T: Source + ?Sized>(byte: u8) -> impl FnMut(&T) -> Option<Range<usize>> {
+    move |rest: &T| {
+        let i = rest.as_bytes().iter().position(|&b| b == byte)?;
+        Some(i..i + 1)
+    }
+}
+
+/// Splits `source` into records using the boundary scanner `boundary`.
+///
+/// `boundary` is called with the rest of the input, starting at the next
+/// record, and returns the range of the separator ending it, relative to the
+/// rest, or `None` if the rest is the last record. The separator isn't part
+/// of any record. A final, empty record, as after a trailing separator, is
+/// left out. If the separator returned is empty and at the start of the rest,
+/// the rest is taken as the last record instead.
+pub fn split<'a, T, B>(source: &'a T, mut boundary: B) -> Vec<Record<'a, T>>
+    where T: Source + ?Sized, B: FnMut(&T) -> Option<Range<usize>>
+{
+    let (mut records, mut offset) = (vec![], 0);
+    while offset < source.len() {
+        let rest = source.slice(offset..source.len());
+        let (end, next) = match boundary(rest) {
+            Some(separator) if separator.end > 0 => (separator.start, separator.end),
+            _ => (rest.len(), rest.len()),
+        };
+
+        records.push(Record { index: records.len(), offset, data: rest.slice(0..end) });
+        offset += next;
+    }
+
+    records
+}
+
+/// Splits `source` into records with `boundary`, as by [`split()`], and
+/// parses every record, in parallel, with `parser`.
+///
+/// Each record is parsed as a complete input: `parser` must consume all of
+/// it. Returns the outputs of every record in input order if all of them
+/// parsed, and the errors of the records that failed, in input order,
+/// otherwise.
+pub fn parse<'a, T, B, I, P, O, E>(
+    source: &'a T,
+    boundary: B,
+    parser: P,
+) -> core::result::Result<Vec<O>, Vec<RecordError<ParseError<I, E>>>>
+    where T: Source + ?Sized,
+          B: FnMut(&T) -> Option<Range<usize>>,
+          I: Input + From<&'a T>,
+          P: Fn(&mut I) -> Result<O, I, E> + Sync,
+          O: Send,
+          E: Error<I>,
+          ParseError<I, E>: Send,
+{
+    let results: Vec<_> = split(source, boundary)
+        .into_par_iter()
+        .map(|record| {
+            let mut input = I::from(record.data);
+            let result = parser(&mut input).and_then(|output| {
+                eof(&mut input).map_err(|e| e.into())?;
+                Ok(output)
+            });
+
+            result.map_err(|error| RecordError { index: record.index, offset: record.offset, error })
+        })
+        .collect();
+
+    let mut errors = vec![];
+    let mut outputs = Vec::with_capacity(results.len());
+    for result in results {
+        match result {
+            Ok(output) => outputs.push(output),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(outputs),
+        false => Err(errors),
+    }
+}
@@ -0,0 +1,146 @@
+use core::str::FromStr;
+use alloc::{vec, string::ToString};
+
+use crate::error::{ParseError, Expected};
+use crate::input::{Input, Text, Result};
+use crate::combinators::{Collection, opt};
+use crate::macros::parser;
+use crate::parsers::{eof, eat_slice, take_some_while};
+
+/// A type with a canonical parser over text.
+///
+/// Implementing `FromPearStr` declares, once, how a type is parsed. The type
+/// can then be parsed from a string with [`parse_str()`], much like
+/// [`str::parse()`], or from within another parser with
+/// `T::from_pear_str()?`:
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::macros::parser;
+/// use pear::parsers::*;
+/// use pear::FromPearStr;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Version(u32, u32);
+///
+/// impl FromPearStr for Version {
+///     #[parser]
+///     fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>> {
+///         let major = u32::from_pear_str()?;
+///         eat('.')?;
+///         Version(major, u32::from_pear_str()?)
+///     }
+/// }
+///
+/// assert_eq!(pear::parse_str::<Version>("1.12").unwrap(), Version(1, 12));
+/// assert!(pear::parse_str::<Version>("1.12.3").is_err());
+/// ```
+///
+/// `FromPearStr` can be derived with
+/// [`#[derive(FromPearStr)]`](crate::macros#derivefrompearstr): a struct or
+/// variant parses its fields in order, or the text of its `#[parse("...")]`
+/// template, where `{name}` (or `{0}` for a tuple field) parses a field with
+/// its `FromPearStr` implementation. A field marked
+/// `#[parse(separator = "...")]` is a [`Collection`] of zero or more items
+/// separated by the separator, and a field absent from a template is
+/// defaulted. An enum parses as the first of its variants to parse, which are
+/// tried in order.
+///
+/// ```rust
+/// use pear::macros::FromPearStr;
+///
+/// #[derive(Debug, PartialEq, FromPearStr)]
+/// enum Value {
+///     #[parse("nil")]
+///     Nil,
+///     #[parse("[{0}]")]
+///     List(#[parse(separator = ",")] Vec<Value>),
+///     Int(u32),
+/// }
+///
+/// let value = pear::parse_str::<Value>("[1,nil,[]]").unwrap();
+/// assert_eq!(value, Value::List(vec![Value::Int(1), Value::Nil, Value::List(vec![])]));
+/// ```
+///
+/// Templates are written as those of [`#[derive(Emit)]`](crate::macros#deriveemit),
+/// so a type deriving both with the same templates parses what it emits.
+///
+/// `FromPearStr` is implemented for `bool` and the primitive integer types.
+pub trait FromPearStr: Sized {
+    /// Parses a `Self` from the start of `input`.
+    fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>>;
+}
+
+/// Parses a `T` from all of `string` using its canonical parser.
+///
+/// Fails if `T`'s parser fails or if it does not consume all of `string`.
+pub fn parse_str<T: FromPearStr>(string: &str) -> Result<T, Text<'_>> {
+    let mut input = Text::from(string);
+    let value = T::from_pear_str(&mut input)?;
+    eof(&mut input)?;
+    Ok(value)
+}
+
+/// Parses zero or more `C::Item`s separated by `separator`.
+///
+/// Generated by `#[derive(FromPearStr)]`; not intended to be called directly.
+#[doc(hidden)]
+#[parser(raw)]
+pub fn separated<'a, C>(input: &mut Text<'a>, separator: &str) -> Result<C, Text<'a>>
+    where C: Collection, C::Item: FromPearStr
+{
+    let mut items = C::new();
+    if let Some(item) = opt(input, C::Item::from_pear_str)? {
+        items.add(item);
+        while eat_slice(input, separator).is_ok() {
+            items.add(C::Item::from_pear_str(input)?);
+        }
+    }
+
+    Ok(items)
+}
+
+#[parser(raw)]
+fn integer<'a, T>(input: &mut Text<'a>, signed: bool) -> Result<T, Text<'a>>
+    where T: FromStr, T::Err: ToString
+{
+    let mut sign = false;
+    let digits = take_some_while(input, |&c| {
+        let is_sign = signed && !sign && (c == '-' || c == '+');
+        sign = true;
+        is_sign || c.is_ascii_digit()
+    })?;
+
+    digits.parse().map_err(|e: T::Err| {
+        let expected = Expected::Other(e.to_string().into());
+        ParseError::new(expected)
+    })
+}
+
+macro_rules! impl_integer {
+    ($signed:expr => $($T:ty),*) => ($(
+        impl FromPearStr for $T {
+            fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>> {
+                integer(input, $signed)
+            }
+        }
+    )*)
+}
+
+impl_integer!(false => u8, u16, u32, u64, u128, usize);
+impl_integer!(true => i8, i16, i32, i64, i128, isize);
+
+impl FromPearStr for bool {
+    fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>> {
+        if input.eat_slice(4, |s| *s == "true").is_some() {
+            Ok(true)
+        } else if input.eat_slice(5, |s| *s == "false").is_some() {
+            Ok(false)
+        } else {
+            let expected = Expected::OneOf(vec!["`true`".into(), "`false`".into()], input.token());
+            Err(ParseError::new(expected))
+        }
+    }
+}
@@ -1,13 +1,38 @@
-use crate::error::{ParseError, Expected};
-use crate::input::{Input, Length, Token, Slice, Show, Result, Rewind};
+use alloc::{vec::Vec, format};
+use alloc::string::{String, ToString};
+use alloc::borrow::Cow;
+
+use crate::error::{ParseError, Expected, is_quiet};
+use crate::input::{Input, Length, Token, Slice, Show, Result, Rewind, ParserInfo, AsciiCaseInsensitive};
+use crate::input::{FastScan, FastPredicate, TokenClass, SliceEq};
 use crate::macros::parser;
 
+pub mod binary;
+pub mod datetime;
+pub mod lines;
+pub mod net;
+#[cfg(feature = "unicode")] pub mod unicode;
+#[cfg(feature = "regex")] mod regex;
+
+#[cfg(feature = "regex")] pub use self::regex::{re, Regex, Captures};
+
 // // TODO:
 // // * provide basic parsers in pear
 // //   - [f32, f64, i8, i32, ..., bool, etc.]: one for all reasonable built-ins
 // //   - quoted_string(allowed): '"' allowed* '"'
 // //   - escaped string, with some way to configure escapes
 
+/// Renders `value`, as what was expected, for an error. Errors are not
+/// rendered while they're being suppressed by
+/// [`opt()`](crate::combinators::opt()).
+#[inline(always)]
+fn render(value: &dyn Show) -> Option<String> {
+    match is_quiet() {
+        true => None,
+        false => Some(value.to_string()),
+    }
+}
+
 #[inline(always)]
 fn expected_token<T, A, I>(
     input: &mut I,
@@ -19,7 +44,7 @@ fn expected_token<T, A, I>(
     // let expected = Expected::Token(token.map(|t| t.into_token()), input.token());
     // Err(ParseError::expected(expected))
 
-    let string = token.map(|t| (&t as &dyn Show).to_string());
+    let string = token.and_then(|t| render(&t));
     let expected = Expected::Token(string, input.token());
     Err(ParseError::new(expected))
 }
@@ -36,11 +61,17 @@ fn expected_slice<S, A, I>(
     // let expected = Expected::Slice(Some(slice.into_slice()), input.slice(len));
     // Err(ParseError::expected(expected))
 
-    let string = (&slice as &dyn Show).to_string();
-    let expected = Expected::Slice(Some(string), input.slice(slice.len()));
+    let expected = Expected::Slice(render(&slice), input.slice(slice.len()));
     Err(ParseError::new(expected))
 }
 
+/// Fails with a message saying what valid input was expected: for a value
+/// which is well-formed but out of range, say.
+#[inline(always)]
+pub(crate) fn invalid<T, I: Input>(_: &mut I, message: &'static str) -> Result<T, I> {
+    Err(ParseError::new(Expected::Other(message.into())))
+}
+
 /// Eats the current token if it is `token`.
 #[parser(raw)]
 pub fn eat<I, T>(input: &mut I, token: T) -> Result<I::Token, I>
@@ -63,6 +94,41 @@ pub fn eat_if<I, F>(input: &mut I, cond: F) -> Result<I::Token, I>
     }
 }
 
+/// Eats the current token if it is a member of the token class `class`: an
+/// array, slice, or range of tokens, a closure, or a
+/// [`TokenSet`](crate::input::TokenSet).
+#[parser(raw)]
+pub fn eat_any_of<I, C>(input: &mut I, class: C) -> Result<I::Token, I>
+    where I: Input, C: TokenClass<I>
+{
+    match input.eat(|t| class.contains(t)) {
+        Some(token) => Ok(token),
+        None => {
+            let expected = match is_quiet() {
+                true => Expected::Token(None, input.token()),
+                false => match class.members() {
+                    members if members.is_empty() => Expected::Token(None, input.token()),
+                    members => Expected::OneOf(members, input.token()),
+                }
+            };
+
+            Err(ParseError::new(expected))
+        }
+    }
+}
+
+/// Eats the current token if it is _not_ a member of the token class `class`.
+/// Fails if there are no tokens.
+#[parser(raw)]
+pub fn none_of<I, C>(input: &mut I, class: C) -> Result<I::Token, I>
+    where I: Input, C: TokenClass<I>
+{
+    match input.eat(|t| !class.contains(t)) {
+        Some(token) => Ok(token),
+        None => expected_token::<I::Token, _, _>(input, None)
+    }
+}
+
 /// Eats the current token unconditionally. Fails if there are no tokens.
 #[parser(raw)]
 pub fn eat_any<I: Input>(input: &mut I) -> Result<I::Token, I> {
@@ -146,6 +212,90 @@ pub fn peek_slice<I, S>(input: &mut I, slice: S) -> Result<(), I>
     }
 }
 
+/// Succeeds if the slice `offset` past the current position is `slice`,
+/// consuming nothing. Allows deciding between alternatives which share a
+/// prefix by looking past it. See [`Input::peek_slice_at()`].
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::macros::{parser, parse, switch};
+/// use pear::parsers::*;
+///
+/// // `a=` starts an assignment; any other `a` starts an expression.
+/// #[parser]
+/// fn statement<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     switch! {
+///         peek_slice_at(1, "=") => { eat_any()?; eat('=')?; "assignment" },
+///         _ => "expression"
+///     }
+/// }
+///
+/// assert_eq!(parse!(statement: &mut Text::from("a=")).unwrap(), "assignment");
+/// assert_eq!(parse!(statement: &mut Text::from("")).unwrap(), "expression");
+/// ```
+#[parser(raw)]
+pub fn peek_slice_at<I, S>(input: &mut I, offset: usize, slice: S) -> Result<(), I>
+    where I: Input, S: Slice<I>
+{
+    match input.peek_slice_at(offset, slice.len()) {
+        Some(ref s) if &slice == s => Ok(()),
+        found => {
+            Err(ParseError::new(Expected::Slice(render(&slice), found)))
+        }
+    }
+}
+
+/// Eats the current slice if it is `slice`, ignoring ASCII case. See
+/// [`AsciiCaseInsensitive`].
+#[parser(raw)]
+pub fn eat_slice_ci<I, S>(input: &mut I, slice: S) -> Result<I::Slice, I>
+    where I: Input, AsciiCaseInsensitive<S>: Slice<I>
+{
+    eat_slice(input, AsciiCaseInsensitive(slice))
+}
+
+/// Succeeds if the current slice is `slice`, ignoring ASCII case. See
+/// [`AsciiCaseInsensitive`].
+#[parser(raw)]
+pub fn peek_slice_ci<I, S>(input: &mut I, slice: S) -> Result<(), I>
+    where I: Input, AsciiCaseInsensitive<S>: Slice<I>
+{
+    peek_slice(input, AsciiCaseInsensitive(slice))
+}
+
+/// Eats the current slice if it is equivalent to `slice` by `eq`. The slices
+/// of the lengths given by [`SliceEq::lengths()`] are tried in turn; the
+/// first that's equivalent is eaten.
+#[parser(raw)]
+pub fn eat_slice_eq<I, Q>(input: &mut I, slice: I::Slice, eq: Q) -> Result<I::Slice, I>
+    where I: Input, Q: SliceEq<I::Slice>
+{
+    for n in eq.lengths(&slice) {
+        if let Some(found) = input.eat_slice(n, |s| eq.slice_eq(&slice, s)) {
+            return Ok(found);
+        }
+    }
+
+    expected_slice(input, slice)
+}
+
+/// Succeeds if the current slice is equivalent to `slice` by `eq`. See
+/// [`eat_slice_eq()`].
+#[parser(raw)]
+pub fn peek_slice_eq<I, Q>(input: &mut I, slice: I::Slice, eq: Q) -> Result<(), I>
+    where I: Input, Q: SliceEq<I::Slice>
+{
+    for n in eq.lengths(&slice) {
+        if input.peek_slice(n, |s| eq.slice_eq(&slice, s)) {
+            return Ok(());
+        }
+    }
+
+    expected_slice(input, slice)
+}
+
 /// Returns the current token.
 #[parser(raw)]
 pub fn peek_any<I: Input>(input: &mut I) -> Result<I::Token, I> {
@@ -172,6 +322,25 @@ pub fn take_while<I, F>(input: &mut I, cond: F) -> Result<I::Many, I>
     Ok(input.take(cond))
 }
 
+/// Skips bytes while `pred` matches, scanning with the accelerated
+/// [`FastPredicate::prefix_len()`]. Returns the number of skipped bytes.
+#[parser(raw)]
+pub fn skip_while_fast<I, P>(input: &mut I, pred: P) -> Result<usize, I>
+    where I: FastScan, P: FastPredicate
+{
+    Ok(input.scan(&pred).len())
+}
+
+/// Consumes bytes while `pred` matches and returns them, scanning with the
+/// accelerated [`FastPredicate::prefix_len()`]. Succeeds even if no bytes
+/// match.
+#[parser(raw)]
+pub fn take_while_fast<I, P>(input: &mut I, pred: P) -> Result<I::Many, I>
+    where I: FastScan, P: FastPredicate
+{
+    Ok(input.scan(&pred))
+}
+
 /// Consumes tokens while `cond` matches on a continously growing slice
 /// beginning at a length of `0` and ending when `cond` fails. Returns the slice
 /// between `0` and `cond` failing. Errors if no such slice exists.
@@ -217,11 +386,7 @@ pub fn take_while_window<I, F>(input: &mut I, n: usize, mut f: F) -> Result<I::M
         return Ok(input.take(|_| true));
     }
 
-    // FIXME: We should be able to call `parse_marker!` here.
-    let start = input.mark(&crate::input::ParserInfo {
-        name: "take_while_window",
-        raw: true
-    });
+    let start = parse_marker!();
 
     let mut tokens = 0;
     loop {
@@ -306,7 +471,7 @@ pub fn take_some_while<I, F>(input: &mut I, cond: F) -> Result<I::Many, I>
 {
     let value = input.take(cond);
     if value.len() == 0 {
-        return Err(ParseError::new(Expected::Token(None, None)));
+        return Err(ParseError::new(Expected::Token(None, input.token())));
     }
 
     Ok(value)
@@ -342,6 +507,420 @@ pub fn take_some_while_until<I, T, F>(
     take_some_while(input, |t| cond(t) && (&until != t))
 }
 
+/// Consumes characters while `cond` matches, returning them with each
+/// replaced by `map`. Succeeds even if no characters match.
+///
+/// The input is borrowed when `map` leaves every character unchanged, so
+/// that, say, lowercasing identifiers only allocates for those that weren't
+/// already lowercase.
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use pear::input::Text;
+/// use pear::parsers::take_while_transform;
+///
+/// let lower = |c: char| c.to_ascii_lowercase();
+/// let ident = take_while_transform(&mut Text::from("name"), char::is_ascii_alphabetic, lower);
+/// assert!(matches!(ident.unwrap(), Cow::Borrowed("name")));
+///
+/// let ident = take_while_transform(&mut Text::from("Name"), char::is_ascii_alphabetic, lower);
+/// assert!(matches!(ident.unwrap(), Cow::Owned(s) if s == "name"));
+/// ```
+#[parser(raw)]
+pub fn take_while_transform<'a, I, F, M>(input: &mut I, cond: F, mut map: M) -> Result<Cow<'a, str>, I>
+    where I: Input<Token = char, Many = &'a str>,
+          F: FnMut(&char) -> bool,
+          M: FnMut(char) -> char
+{
+    let slice = input.take(cond);
+    let mut owned: Option<String> = None;
+    for (i, c) in slice.char_indices() {
+        let mapped = map(c);
+        match owned {
+            Some(ref mut string) => string.push(mapped),
+            None if mapped != c => {
+                let mut string = String::with_capacity(slice.len());
+                string.push_str(&slice[..i]);
+                string.push(mapped);
+                owned = Some(string);
+            }
+            None => {}
+        }
+    }
+
+    Ok(owned.map_or(Cow::Borrowed(slice), Cow::Owned))
+}
+
+/// Consumes characters while `normal` matches, decoding escape sequences: an
+/// `escape` character is consumed and `transform` is run to parse the rest of
+/// the sequence, returning the character it decodes to. Succeeds even if no
+/// characters match.
+///
+/// The input is borrowed when it contains no escape sequences, and only copied
+/// into an allocated string once the first one is decoded.
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use pear::input::{Text, Result};
+/// use pear::parsers::*;
+///
+/// fn escape<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+///     match eat_any(input)? {
+///         'n' => Ok('\n'),
+///         c => Ok(c),
+///     }
+/// }
+///
+/// fn string<'a>(input: &mut Text<'a>) -> Result<Cow<'a, str>, Text<'a>> {
+///     escaped_transform(input, |c| *c != '"', '\\', escape)
+/// }
+///
+/// assert!(matches!(string(&mut Text::from("plain")).unwrap(), Cow::Borrowed("plain")));
+/// assert_eq!(string(&mut Text::from(r#"a\"b\nc"#)).unwrap(), "a\"b\nc");
+/// ```
+#[parser(raw)]
+pub fn escaped_transform<'a, I, F, T>(
+    input: &mut I,
+    mut normal: F,
+    escape: char,
+    mut transform: T
+) -> Result<Cow<'a, str>, I>
+    where I: Input<Token = char, Many = &'a str>,
+          F: FnMut(&char) -> bool,
+          T: FnMut(&mut I) -> Result<char, I>
+{
+    let mut owned: Option<String> = None;
+    loop {
+        let chunk = input.take(|c| *c != escape && normal(c));
+        if input.eat(|c| *c == escape).is_none() {
+            return Ok(match owned {
+                Some(mut string) => { string.push_str(chunk); Cow::Owned(string) }
+                None => Cow::Borrowed(chunk),
+            });
+        }
+
+        let decoded = transform(input)?;
+        let string = owned.get_or_insert_with(String::new);
+        string.push_str(chunk);
+        string.push(decoded);
+    }
+}
+
+/// Parses a backslash escape sequence, the backslash already consumed: one of
+/// `\n`, `\r`, `\t`, `\0`, `\\`, `\"`, `\'`, or `\u{...}` with one to six
+/// hex digits naming a Unicode scalar value.
+#[parser(raw)]
+fn escape<I: Input<Token = char>>(input: &mut I) -> Result<char, I> {
+    let escaped = match eat_any(input)? {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '0' => '\0',
+        c @ ('\\' | '"' | '\'') => c,
+        'u' => {
+            eat(input, '{')?;
+            let mut value = eat_if(input, char::is_ascii_hexdigit)?.to_digit(16).unwrap();
+            for _ in 1..6 {
+                match eat_if(input, char::is_ascii_hexdigit) {
+                    Ok(digit) => value = value * 16 + digit.to_digit(16).unwrap(),
+                    Err(_) => break,
+                }
+            }
+
+            eat(input, '}')?;
+            match char::from_u32(value) {
+                Some(c) => c,
+                None => return invalid(input, "a Unicode scalar value"),
+            }
+        }
+        _ => return invalid(input, "a valid escape sequence"),
+    };
+
+    Ok(escaped)
+}
+
+/// Parses a string between two `quote`s with backslash escapes decoded.
+#[parser(raw)]
+fn quoted<'a, I>(input: &mut I, quote: char) -> Result<Cow<'a, str>, I>
+    where I: Input<Token = char, Many = &'a str>
+{
+    eat(input, quote)?;
+    let string = escaped_transform(input, |&c| c != quote, '\\', escape)?;
+    eat(input, quote)?;
+    Ok(string)
+}
+
+/// Parses a double-quoted string, as in `"a \"quoted\" string"`, returning
+/// its contents with escape sequences decoded. The escape sequences are `\n`,
+/// `\r`, `\t`, `\0`, `\\`, `\"`, `\'`, and `\u{...}`, as in Rust. The
+/// contents are borrowed unless they contain escapes.
+///
+/// ```rust
+/// use pear::input::Text;
+/// use pear::parsers::*;
+///
+/// let string = double_quoted(&mut Text::from(r#""say \"hi\"\u{21}""#));
+/// assert_eq!(string.unwrap(), "say \"hi\"!");
+///
+/// assert!(double_quoted(&mut Text::from(r#""unterminated"#)).is_err());
+/// assert!(double_quoted(&mut Text::from(r#""\q""#)).is_err());
+/// ```
+#[parser(raw)]
+pub fn double_quoted<'a, I>(input: &mut I) -> Result<Cow<'a, str>, I>
+    where I: Input<Token = char, Many = &'a str>
+{
+    quoted(input, '"')
+}
+
+/// Parses a single-quoted string, as in `'it\'s'`, returning its contents
+/// with escape sequences decoded as by [`double_quoted()`].
+#[parser(raw)]
+pub fn single_quoted<'a, I>(input: &mut I) -> Result<Cow<'a, str>, I>
+    where I: Input<Token = char, Many = &'a str>
+{
+    quoted(input, '\'')
+}
+
+/// Returns the Levenshtein edit distance between `a` and `b`: the number of
+/// character insertions, deletions, and substitutions turning one into the
+/// other.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let mut row: Vec<usize> = (0..=b.chars().count()).collect();
+    for (i, x) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, y) in b.chars().enumerate() {
+            let substitution = diagonal + (x != y) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[row.len() - 1]
+}
+
+/// Eats an identifier, a nonempty run of alphanumerics and underscores, if it
+/// is one of `keywords`, returning the keyword.
+///
+/// Otherwise, fails without consuming input, expecting one of `keywords`. If
+/// the identifier is a likely misspelling of a keyword, one within an edit
+/// distance of a third of its length, rounded up, the error suggests
+/// replacing it with the closest such keyword. When the input has no context,
+/// the suggestion is a help message instead. See
+/// [`ParseError::suggestions()`].
+///
+/// ```rust
+/// use pear::input::Text;
+/// use pear::parsers::*;
+///
+/// const LITERALS: &[&str] = &["true", "false", "null"];
+///
+/// assert_eq!(keyword_of(&mut Text::from("null"), LITERALS).unwrap(), "null");
+/// assert!(keyword_of(&mut Text::from("nullable"), LITERALS).is_err());
+///
+/// let error = keyword_of(&mut Text::from("flase"), LITERALS).unwrap_err();
+/// let suggestion = error.suggestions().next().unwrap();
+/// assert_eq!(suggestion.replacement, "false");
+/// assert_eq!(suggestion.context.snippet, Some("flase"));
+/// ```
+#[parser(raw)]
+pub fn keyword_of<'a, 'k, I>(input: &mut I, keywords: &[&'k str]) -> Result<&'k str, I>
+    where I: Rewind<Token = char, Many = &'a str>
+{
+    let info = ParserInfo::new("keyword_of", true);
+    let mark = input.mark(&info);
+    let word = input.take(|&c| c.is_alphanumeric() || c == '_');
+    if let Some(&keyword) = keywords.iter().find(|k| **k == word) {
+        input.unmark(&info, true, mark);
+        return Ok(keyword);
+    }
+
+    let quiet = is_quiet();
+    let context = match quiet || word.is_empty() {
+        true => None,
+        false => input.context(&mark),
+    };
+
+    input.rewind_to(&mark);
+    input.unmark(&info, false, mark);
+    let expected = match quiet {
+        true => Expected::Token(None, input.token()),
+        false => Expected::OneOf(keywords.iter().filter_map(|k| render(k)).collect(), input.token()),
+    };
+
+    let mut error = ParseError::new(expected);
+    if quiet || word.is_empty() {
+        return Err(error);
+    }
+
+    let limit = word.chars().count().div_ceil(3);
+    let closest = keywords.iter()
+        .map(|k| (levenshtein(word, k), *k))
+        .filter(|&(distance, _)| distance <= limit)
+        .min_by_key(|&(distance, _)| distance);
+
+    if let Some((_, keyword)) = closest {
+        error = match context {
+            Some(context) => error.with_suggestion(context, keyword.to_string()),
+            None => error.with_help(format!("did you mean `{}`?", keyword)),
+        };
+    }
+
+    Err(error)
+}
+
+/// Skips a comment starting with `prefix` and running to the end of the
+/// line, as in `// ...` or `# ...`. The newline ending the comment, if any, is
+/// not consumed.
+#[parser(raw)]
+pub fn line_comment<I, S>(input: &mut I, prefix: S) -> Result<(), I>
+    where I: Input<Token = char>, S: Slice<I>
+{
+    eat_slice(input, prefix)?;
+    input.skip(|&c| c != '\n');
+    Ok(())
+}
+
+/// Skips a comment between `open` and `close`, as in `/* ... */`. If
+/// `nested`, comments may nest: every `open` inside of the comment must be
+/// matched by a `close`, as in Rust. An unterminated comment fails with a
+/// [committed](crate::combinators::cut()) error.
+///
+/// ```rust
+/// use pear::input::Text;
+/// use pear::parsers::*;
+///
+/// let input = &mut Text::from("/* a /* b */ c */ d");
+/// assert!(block_comment(input, "/*", "*/", true).is_ok());
+/// assert_eq!(take_while(input, |_| true).unwrap(), " d");
+///
+/// let input = &mut Text::from("/* a /* b */ c */ d");
+/// assert!(block_comment(input, "/*", "*/", false).is_ok());
+/// assert_eq!(take_while(input, |_| true).unwrap(), " c */ d");
+///
+/// assert!(block_comment(&mut Text::from("/* a"), "/*", "*/", true).is_err());
+/// ```
+#[parser(raw)]
+pub fn block_comment<I, S>(input: &mut I, open: S, close: S, nested: bool) -> Result<(), I>
+    where I: Input, S: Slice<I> + Clone
+{
+    eat_slice(input, open.clone())?;
+    let mut depth = 1usize;
+    loop {
+        if eat_slice(input, close.clone()).is_ok() {
+            depth -= 1;
+            if depth == 0 {
+                return Ok(());
+            }
+        } else if nested && eat_slice(input, open.clone()).is_ok() {
+            depth += 1;
+        } else if skip_any(input).is_err() {
+            return eat_slice(input, close).map(|_| ()).map_err(ParseError::commit);
+        }
+    }
+}
+
+/// Scans past tokens inside of `open` and `close`, the opening `open` having
+/// been eaten, stopping before the matching `close`. Returns the number of
+/// tokens scanned, or `None` if there's no matching `close`.
+fn scan_balanced<I, T, Q>(input: &mut I, open: &T, close: &T, quotes: Q, escape: Option<&T>) -> Option<usize>
+    where I: Input, T: Token<I>, Q: Fn(&I::Token) -> bool
+{
+    let (mut depth, mut tokens, mut quote) = (1usize, 0, None);
+    loop {
+        let token = input.eat(|_| true)?;
+        if escape.is_some_and(|escape| escape == &token) {
+            input.eat(|_| true)?;
+            tokens += 1;
+        } else if let Some(ref q) = quote {
+            if q == &token {
+                quote = None;
+            }
+        } else if close == &token {
+            depth -= 1;
+            if depth == 0 {
+                return Some(tokens);
+            }
+        } else if open == &token {
+            depth += 1;
+        } else if quotes(&token) {
+            quote = Some(token);
+        }
+
+        tokens += 1;
+    }
+}
+
+/// Takes the tokens between `open` and the `close` that matches it, eating
+/// both. `open`s and `close`s between them nest and must be balanced. An
+/// unbalanced region fails with a [committed](crate::combinators::cut())
+/// error.
+///
+/// ```rust
+/// use pear::input::Text;
+/// use pear::parsers::*;
+///
+/// let input = &mut Text::from("{ a { b } c } d");
+/// assert_eq!(balanced(input, '{', '}').unwrap(), " a { b } c ");
+/// assert_eq!(take_while(input, |_| true).unwrap(), " d");
+///
+/// assert!(balanced(&mut Text::from("{ a { b }"), '{', '}').is_err());
+/// ```
+#[parser(raw)]
+pub fn balanced<I, T>(input: &mut I, open: T, close: T) -> Result<I::Many, I>
+    where I: Input + Rewind, T: Token<I> + Clone
+{
+    take_balanced(input, &parse_marker!(), open, close, |_| false, None)
+}
+
+/// Like [`balanced()`], but aware of quoted strings: delimiters between a
+/// token in the class `quotes` and the next occurrence of the same token are
+/// ignored, as is any token following `escape`.
+///
+/// ```rust
+/// use pear::input::Text;
+/// use pear::parsers::*;
+///
+/// let input = &mut Text::from(r#"{{ "}" '{' \} }} rest"#);
+/// let inner = balanced_quoted(input, '{', '}', ['"', '\''], '\\').unwrap();
+/// assert_eq!(inner, r#"{ "}" '{' \} }"#);
+/// ```
+#[parser(raw)]
+pub fn balanced_quoted<I, T, C>(input: &mut I, open: T, close: T, quotes: C, escape: T) -> Result<I::Many, I>
+    where I: Input + Rewind, T: Token<I> + Clone, C: TokenClass<I>
+{
+    take_balanced(input, &parse_marker!(), open, close, |t| quotes.contains(t), Some(escape))
+}
+
+/// Takes the balanced region that `start`, which marks an `open`, begins.
+#[inline(always)]
+fn take_balanced<I, T, Q>(
+    input: &mut I,
+    start: &I::Marker,
+    open: T,
+    close: T,
+    quotes: Q,
+    escape: Option<T>
+) -> Result<I::Many, I>
+    where I: Input + Rewind, T: Token<I> + Clone, Q: Fn(&I::Token) -> bool
+{
+    eat(input, open.clone())?;
+    let mut tokens = match scan_balanced(input, &open, &close, quotes, escape.as_ref()) {
+        Some(tokens) => tokens,
+        None => return expected_token(input, Some(close)).map_err(ParseError::commit),
+    };
+
+    input.rewind_to(start);
+    input.eat(|_| true);
+    let inner = input.take(|_| match tokens > 0 {
+        true => { tokens -= 1; true },
+        false => false
+    });
+
+    eat(input, close)?;
+    Ok(inner)
+}
+
 /// Takes at most `n` tokens.
 #[parser(raw)]
 pub fn take_n<I: Input>(input: &mut I, n: usize) -> Result<I::Many, I> {
@@ -366,7 +945,7 @@ pub fn take_n_if<I, F>(input: &mut I, n: usize, mut cond: F) -> Result<I::Many,
     let mut i = 0;
     let v = input.take(|c| { cond(c) && { let ok = i < n; i += 1; ok } });
     if v.len() != n {
-        return Err(ParseError::new(Expected::Token(None, None)));
+        return Err(ParseError::new(Expected::Token(None, input.token())));
     }
 
     Ok(v)
@@ -412,10 +991,11 @@ pub fn delimited_some<I, T, F>(
     Ok(output)
 }
 
-/// Succeeds only if the input has reached EOF.
+/// Succeeds only if the input has reached EOF. Fails if the input is
+/// [halted](Input::halted()), as it could have continued.
 #[parser(raw)]
 pub fn eof<I: Input>(input: &mut I) -> Result<(), I> {
-    if input.has(1) {
+    if input.has(1) || input.halted().is_some() {
         let next = input.token();
         Err(ParseError::new(Expected::Eof(next)))
     } else {
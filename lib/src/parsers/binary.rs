@@ -0,0 +1,239 @@
+//! Parsers for binary data: fixed-width integers and floats in either byte
+//! order, LEB128 variable-length integers, and bit fields via [`Bits`].
+//!
+//! The parsers operate on any input whose tokens are bytes, that is, whose
+//! token type implements [`Byte`], such as a [`Cursor`] over a `&[u8]` or an
+//! [`IterInput`] over an iterator of `u8`s.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Cursor, Result};
+//! use pear::macros::{parser, parse};
+//! use pear::parsers::binary::*;
+//!
+//! type Input<'a> = Cursor<'a, u8>;
+//!
+//! // A record: a big-endian `u16` tag, a LEB128 length, and a little-endian
+//! // `f32` value.
+//! #[parser]
+//! fn record<'a>(input: &mut Input<'a>) -> Result<(u16, u64, f32), Input<'a>> {
+//!     (be_u16()?, uleb128()?, le_f32()?)
+//! }
+//!
+//! let bytes = [0x01, 0x02, 0xE5, 0x8E, 0x26, 0x00, 0x00, 0x80, 0x3F];
+//! let input = &mut Cursor::from(&bytes[..]);
+//! assert_eq!(parse!(record: input).unwrap(), (0x0102, 624485, 1.0));
+//! ```
+//!
+//! [`Cursor`]: crate::input::Cursor
+//! [`Bits`]: crate::input::Bits
+//! [`IterInput`]: crate::input::IterInput
+
+use alloc::{vec, format};
+
+use crate::error::{ParseError, Expected};
+use crate::input::{Input, Rewind, Bits, ParserInfo, Result};
+use crate::macros::parser;
+
+/// A token which is a byte.
+pub trait Byte {
+    /// Returns the byte.
+    fn byte(&self) -> u8;
+}
+
+impl Byte for u8 {
+    #[inline(always)]
+    fn byte(&self) -> u8 {
+        *self
+    }
+}
+
+impl Byte for &u8 {
+    #[inline(always)]
+    fn byte(&self) -> u8 {
+        **self
+    }
+}
+
+/// Eats exactly `N` bytes, failing without consuming any if fewer remain.
+#[parser(raw)]
+pub fn bytes<I, const N: usize>(input: &mut I) -> Result<[u8; N], I>
+    where I: Input, I::Token: Byte
+{
+    if !input.has(N) {
+        let expected = match N {
+            1 => "a byte".into(),
+            n => format!("{} bytes", n),
+        };
+
+        return Err(ParseError::new(Expected::OneOf(vec![expected], None)));
+    }
+
+    let mut bytes = [0; N];
+    for byte in bytes.iter_mut() {
+        *byte = input.eat(|_| true).expect("has(N)").byte();
+    }
+
+    Ok(bytes)
+}
+
+/// Eats a single byte.
+#[parser(raw)]
+pub fn u8<I>(input: &mut I) -> Result<u8, I>
+    where I: Input, I::Token: Byte
+{
+    Ok(bytes::<I, 1>(input)?[0])
+}
+
+/// Eats a single byte as a signed integer.
+#[parser(raw)]
+pub fn i8<I>(input: &mut I) -> Result<i8, I>
+    where I: Input, I::Token: Byte
+{
+    Ok(bytes::<I, 1>(input)?[0] as i8)
+}
+
+macro_rules! impl_fixed {
+    ($($le:ident, $be:ident: $T:ty),*) => ($(
+        #[doc = concat!("Eats a little-endian `", stringify!($T), "`.")]
+        #[parser(raw)]
+        pub fn $le<I>(input: &mut I) -> Result<$T, I>
+            where I: Input, I::Token: Byte
+        {
+            Ok(<$T>::from_le_bytes(bytes(input)?))
+        }
+
+        #[doc = concat!("Eats a big-endian `", stringify!($T), "`.")]
+        #[parser(raw)]
+        pub fn $be<I>(input: &mut I) -> Result<$T, I>
+            where I: Input, I::Token: Byte
+        {
+            Ok(<$T>::from_be_bytes(bytes(input)?))
+        }
+    )*)
+}
+
+impl_fixed! {
+    le_u16, be_u16: u16,
+    le_u32, be_u32: u32,
+    le_u64, be_u64: u64,
+    le_u128, be_u128: u128,
+    le_i16, be_i16: i16,
+    le_i32, be_i32: i32,
+    le_i64, be_i64: i64,
+    le_i128, be_i128: i128,
+    le_f32, be_f32: f32,
+    le_f64, be_f64: f64
+}
+
+/// Eats the bytes of a LEB128 value, accumulating its 7-bit groups, least
+/// significant first, into a `u64`. Returns the value and the number of bits
+/// read. Fails, rewinding to where the value began, if the value doesn't fit
+/// in 64 bits, signed if `signed`, or if the input ends before it does.
+fn leb128<I>(input: &mut I, signed: bool) -> Result<(u64, u32), I>
+    where I: Rewind, I::Token: Byte
+{
+    let info = ParserInfo::new("leb128", true);
+    let mark = input.mark(&info);
+    let result = leb128_groups(input, signed);
+    if result.is_err() {
+        input.rewind_to(&mark);
+    }
+
+    input.unmark(&info, result.is_ok(), mark);
+    result
+}
+
+/// Eats the groups of a LEB128 value for [`leb128()`], which see.
+fn leb128_groups<I>(input: &mut I, signed: bool) -> Result<(u64, u32), I>
+    where I: Input, I::Token: Byte
+{
+    let (mut value, mut shift) = (0u64, 0);
+    loop {
+        let byte = u8(input)?;
+        let group = byte & 0x7F;
+        let fits = match (shift, signed) {
+            (0..=56, _) => true,
+            (63, false) => group <= 1,
+            (63, true) => group == 0 || group == 0x7F,
+            _ => false,
+        };
+
+        if !fits {
+            let expected = Expected::Other("LEB128 value overflows 64 bits".into());
+            return Err(ParseError::new(expected));
+        }
+
+        value |= (group as u64) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            return Ok((value, shift));
+        }
+    }
+}
+
+/// Eats an unsigned LEB128 variable-length integer, as used by DWARF,
+/// WebAssembly, and, as "varints", Protocol Buffers. Fails, without consuming
+/// any input, if the value does not fit in a `u64` or the input ends first.
+#[parser(raw)]
+pub fn uleb128<I>(input: &mut I) -> Result<u64, I>
+    where I: Rewind, I::Token: Byte
+{
+    Ok(leb128(input, false)?.0)
+}
+
+/// Eats a signed LEB128 variable-length integer. Fails, without consuming any
+/// input, if the value does not fit in an `i64` or the input ends first.
+#[parser(raw)]
+pub fn sleb128<I>(input: &mut I) -> Result<i64, I>
+    where I: Rewind, I::Token: Byte
+{
+    let (mut value, bits) = leb128(input, true)?;
+    if bits < 64 && value & (1 << (bits - 1)) != 0 {
+        value |= !0 << bits;
+    }
+
+    Ok(value as i64)
+}
+
+/// Eats `n` bits as an unsigned integer, most significant bit first, failing
+/// without consuming any if fewer remain.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than 64.
+#[parser(raw)]
+pub fn bits<I>(input: &mut Bits<I>, n: u32) -> Result<u64, Bits<I>>
+    where I: Input, I::Token: Byte, I::Slice: AsRef<[u8]>
+{
+    match input.take_bits(n) {
+        Some(value) => Ok(value),
+        None => {
+            let expected = match n {
+                1 => "a bit".into(),
+                n => format!("{} bits", n),
+            };
+
+            Err(ParseError::new(Expected::OneOf(vec![expected], None)))
+        }
+    }
+}
+
+/// Eats a single bit as a flag.
+#[parser(raw)]
+pub fn flag<I>(input: &mut Bits<I>) -> Result<bool, Bits<I>>
+    where I: Input, I::Token: Byte, I::Slice: AsRef<[u8]>
+{
+    Ok(bits(input, 1)? == 1)
+}
+
+/// Skips the remaining bits of a partially read byte, if any, so that parsing
+/// resumes at a byte boundary. Returns the number of skipped bits. Never
+/// fails.
+#[parser(raw)]
+pub fn align<I>(input: &mut Bits<I>) -> Result<u8, Bits<I>>
+    where I: Input, I::Token: Byte, I::Slice: AsRef<[u8]>
+{
+    Ok(input.align())
+}
@@ -0,0 +1,448 @@
+//! Parsers for [RFC 3339] timestamps and [ISO 8601] durations.
+//!
+//! The parsers return plain component structs, [`Date`], [`Time`],
+//! [`Offset`], [`DateTime`], and [`Duration`], and operate on any input whose
+//! tokens are `char`s. Values are validated as they're parsed: a month is
+//! between 1 and 12, a day exists in its month, and so on.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::{parser, parse};
+//! use pear::parsers::*;
+//! use pear::parsers::datetime::*;
+//!
+//! // A log line: `[timestamp] message`.
+//! #[parser]
+//! fn line<'a>(input: &mut Text<'a>) -> Result<(DateTime, &'a str), Text<'a>> {
+//!     eat('[')?;
+//!     let timestamp = rfc3339()?;
+//!     eat_slice("] ")?;
+//!     (timestamp, take_while(|_| true)?)
+//! }
+//!
+//! let (timestamp, message) = parse!(line: &mut Text::from("[2024-02-29T12:30:00.5Z] up")).unwrap();
+//! assert_eq!(timestamp.date, Date { year: 2024, month: 2, day: 29 });
+//! assert_eq!(timestamp.time.nanosecond, 500_000_000);
+//! assert_eq!(timestamp.offset, Some(Offset::UTC));
+//! assert_eq!(message, "up");
+//!
+//! let ttl = parse!(duration: &mut Text::from("P1DT2H30M")).unwrap();
+//! assert_eq!((ttl.days, ttl.hours, ttl.minutes), (1, 2, 30));
+//! ```
+//!
+//! With the `chrono` or `time` features enabled, the component structs
+//! convert, via `TryFrom`, into the corresponding types of those crates.
+//!
+//! [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+//! [ISO 8601]: https://en.wikipedia.org/wiki/ISO_8601
+
+use core::fmt;
+use core::convert::{TryFrom, TryInto};
+
+use crate::input::{Input, Result};
+use crate::macros::parser;
+use crate::parsers::*;
+
+/// A calendar date.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year, from 0 to 9999.
+    pub year: u16,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1 to 31.
+    pub day: u8,
+}
+
+/// A time of day.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 60, where 60 is a leap second.
+    pub second: u8,
+    /// The fraction of the second in nanoseconds.
+    pub nanosecond: u32,
+}
+
+/// An offset from UTC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Offset {
+    /// The offset in minutes, east of UTC being positive.
+    pub minutes: i16,
+}
+
+impl Offset {
+    /// The offset of UTC itself, written `Z`.
+    pub const UTC: Offset = Offset { minutes: 0 };
+}
+
+/// A date and time of day, with an offset from UTC if known.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+    /// The offset from UTC, or `None` for a local time.
+    pub offset: Option<Offset>,
+}
+
+/// An ISO 8601 duration: an amount of each calendar and clock unit.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Duration {
+    pub years: u32,
+    pub months: u32,
+    pub weeks: u32,
+    pub days: u32,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    /// The fraction of the last second in nanoseconds.
+    pub nanoseconds: u32,
+}
+
+/// The error converting a component struct into a type which can't represent
+/// its value, such as a leap second or a year out of range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OutOfRange;
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value out of range")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfRange { }
+
+fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parses exactly `n` ASCII digits as a decimal number.
+#[parser(raw)]
+fn digits<I: Input<Token = char>>(input: &mut I, n: usize) -> Result<u32, I> {
+    let mut value = 0;
+    for _ in 0..n {
+        let digit = eat_if(input, char::is_ascii_digit)?;
+        value = value * 10 + digit.to_digit(10).unwrap();
+    }
+
+    Ok(value)
+}
+
+/// Parses one or more ASCII digits as a decimal number.
+#[parser(raw)]
+fn number<I: Input<Token = char>>(input: &mut I) -> Result<u32, I> {
+    let mut value: u32 = digits(input, 1)?;
+    while let Ok(digit) = eat_if(input, char::is_ascii_digit) {
+        value = match value.checked_mul(10).and_then(|v| v.checked_add(digit.to_digit(10).unwrap())) {
+            Some(value) => value,
+            None => return invalid(input, "a number that fits in 32 bits"),
+        };
+    }
+
+    Ok(value)
+}
+
+/// Parses a fraction of a second, `.` followed by one or more digits, into
+/// nanoseconds. Digits past the ninth are consumed but ignored.
+#[parser(raw)]
+fn fraction<I: Input<Token = char>>(input: &mut I) -> Result<u32, I> {
+    eat_if(input, |&c| c == '.' || c == ',')?;
+    let (mut nanos, mut scale) = (digits(input, 1)? * 100_000_000, 10_000_000);
+    while let Ok(digit) = eat_if(input, char::is_ascii_digit) {
+        nanos += digit.to_digit(10).unwrap() * scale;
+        scale /= 10;
+    }
+
+    Ok(nanos)
+}
+
+/// Parses a date: `YYYY-MM-DD`.
+#[parser(raw)]
+pub fn date<I: Input<Token = char>>(input: &mut I) -> Result<Date, I> {
+    let year = digits(input, 4)? as u16;
+    eat(input, '-')?;
+    let month = digits(input, 2)? as u8;
+    if !(1..=12).contains(&month) {
+        return invalid(input, "a month from 01 to 12");
+    }
+
+    eat(input, '-')?;
+    let day = digits(input, 2)? as u8;
+    if day < 1 || day > days_in_month(year, month) {
+        return invalid(input, "a day that exists in the month");
+    }
+
+    Ok(Date { year, month, day })
+}
+
+/// Parses a time of day: `hh:mm:ss`, optionally followed by a fraction of a
+/// second, as in `12:30:00.25`.
+#[parser(raw)]
+pub fn time<I: Input<Token = char>>(input: &mut I) -> Result<Time, I> {
+    let hour = digits(input, 2)? as u8;
+    eat(input, ':')?;
+    let minute = digits(input, 2)? as u8;
+    eat(input, ':')?;
+    let second = digits(input, 2)? as u8;
+    if hour > 23 || minute > 59 || second > 60 {
+        return invalid(input, "a time from 00:00:00 to 23:59:60");
+    }
+
+    let nanosecond = match peek_if(input, |&c| c == '.' || c == ',') {
+        Ok(()) => fraction(input)?,
+        Err(_) => 0,
+    };
+
+    Ok(Time { hour, minute, second, nanosecond })
+}
+
+/// Parses an offset from UTC: `Z` or `±hh:mm`.
+#[parser(raw)]
+pub fn offset<I: Input<Token = char>>(input: &mut I) -> Result<Offset, I> {
+    let sign = match eat_any_of(input, &['Z', 'z', '+', '-'][..])? {
+        '+' => 1,
+        '-' => -1,
+        _ => return Ok(Offset::UTC),
+    };
+
+    let hours = digits(input, 2)? as i16;
+    eat(input, ':')?;
+    let minutes = digits(input, 2)? as i16;
+    if hours > 23 || minutes > 59 {
+        return invalid(input, "an offset from -23:59 to +23:59");
+    }
+
+    Ok(Offset { minutes: sign * (hours * 60 + minutes) })
+}
+
+/// Parses an ISO 8601 date and time: a [`date()`], `T` or a space, a
+/// [`time()`], and an optional [`offset()`]. Without an offset, the time is
+/// local.
+#[parser(raw)]
+pub fn datetime<I: Input<Token = char>>(input: &mut I) -> Result<DateTime, I> {
+    let date = date(input)?;
+    eat_any_of(input, &['T', 't', ' '][..])?;
+    let time = time(input)?;
+    let offset = match peek_if(input, |&c| matches!(c, 'Z' | 'z' | '+' | '-')) {
+        Ok(()) => Some(offset(input)?),
+        Err(_) => None,
+    };
+
+    Ok(DateTime { date, time, offset })
+}
+
+/// Parses an RFC 3339 timestamp: a [`datetime()`] which must have an offset.
+#[parser(raw)]
+pub fn rfc3339<I: Input<Token = char>>(input: &mut I) -> Result<DateTime, I> {
+    let datetime = datetime(input)?;
+    match datetime.offset {
+        Some(_) => Ok(datetime),
+        None => invalid(input, "a timestamp with an offset from UTC"),
+    }
+}
+
+/// Parses an ISO 8601 duration: `P`, then any of `nY`, `nM`, `nW`, and `nD`,
+/// then, after a `T`, any of `nH`, `nM`, and `nS`, in that order. At least
+/// one component is required, and seconds may have a fraction, as in
+/// `PT0.5S`.
+#[parser(raw)]
+pub fn duration<I: Input<Token = char>>(input: &mut I) -> Result<Duration, I> {
+    eat(input, 'P')?;
+    let mut duration = Duration::default();
+    let (mut time, mut components, mut next) = (false, 0, 0);
+    loop {
+        if !time && eat(input, 'T').is_ok() {
+            time = true;
+            next = 0;
+            continue;
+        }
+
+        if peek_if(input, char::is_ascii_digit).is_err() {
+            break;
+        }
+
+        let value = number(input)?;
+        let nanoseconds = match peek_if(input, |&c| time && (c == '.' || c == ',')) {
+            Ok(()) => Some(fraction(input)?),
+            Err(_) => None,
+        };
+
+        let designators: &[char] = match (time, nanoseconds) {
+            (true, Some(_)) => &['S'],
+            (true, None) => &['H', 'M', 'S'],
+            (false, _) => &['Y', 'M', 'W', 'D'],
+        };
+
+        let designator = eat_any_of(input, designators)?;
+        let order: &[char] = if time { &['H', 'M', 'S'] } else { &['Y', 'M', 'W', 'D'] };
+        let index = order.iter().position(|&d| d == designator).unwrap();
+        if index < next {
+            return invalid(input, "duration components in order");
+        }
+
+        next = index + 1;
+        components += 1;
+        match (time, designator) {
+            (false, 'Y') => duration.years = value,
+            (false, 'M') => duration.months = value,
+            (false, 'W') => duration.weeks = value,
+            (false, _) => duration.days = value,
+            (true, 'H') => duration.hours = value,
+            (true, 'M') => duration.minutes = value,
+            (true, _) => {
+                duration.seconds = value;
+                duration.nanoseconds = nanoseconds.unwrap_or(0);
+            }
+        }
+    }
+
+    if components == 0 || (time && next == 0) {
+        return invalid(input, "at least one duration component");
+    }
+
+    Ok(duration)
+}
+
+impl TryFrom<Duration> for core::time::Duration {
+    type Error = OutOfRange;
+
+    /// Converts a duration with no years or months, whose lengths vary, into
+    /// a `core::time::Duration`, taking days to be 24 hours long.
+    fn try_from(d: Duration) -> core::result::Result<Self, OutOfRange> {
+        if d.years != 0 || d.months != 0 {
+            return Err(OutOfRange);
+        }
+
+        let days = u64::from(d.weeks) * 7 + u64::from(d.days);
+        let minutes = (days * 24 + u64::from(d.hours)) * 60 + u64::from(d.minutes);
+        let seconds = minutes * 60 + u64::from(d.seconds);
+        Ok(core::time::Duration::new(seconds, d.nanoseconds))
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+
+    impl TryFrom<Date> for chrono::NaiveDate {
+        type Error = OutOfRange;
+
+        fn try_from(d: Date) -> core::result::Result<Self, OutOfRange> {
+            let (month, day) = (u32::from(d.month), u32::from(d.day));
+            chrono::NaiveDate::from_ymd_opt(i32::from(d.year), month, day).ok_or(OutOfRange)
+        }
+    }
+
+    impl TryFrom<Time> for chrono::NaiveTime {
+        type Error = OutOfRange;
+
+        /// Converts a time, representing a leap second as chrono does: as
+        /// second 59 with over a billion nanoseconds.
+        fn try_from(t: Time) -> core::result::Result<Self, OutOfRange> {
+            let (hour, minute) = (u32::from(t.hour), u32::from(t.minute));
+            let (second, nanos) = match t.second {
+                60 => (59, t.nanosecond + 1_000_000_000),
+                second => (u32::from(second), t.nanosecond),
+            };
+
+            chrono::NaiveTime::from_hms_nano_opt(hour, minute, second, nanos).ok_or(OutOfRange)
+        }
+    }
+
+    impl TryFrom<Offset> for chrono::FixedOffset {
+        type Error = OutOfRange;
+
+        fn try_from(o: Offset) -> core::result::Result<Self, OutOfRange> {
+            chrono::FixedOffset::east_opt(i32::from(o.minutes) * 60).ok_or(OutOfRange)
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::NaiveDateTime {
+        type Error = OutOfRange;
+
+        /// Converts the date and time, ignoring the offset, if any.
+        fn try_from(dt: DateTime) -> core::result::Result<Self, OutOfRange> {
+            Ok(chrono::NaiveDateTime::new(dt.date.try_into()?, dt.time.try_into()?))
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::DateTime<chrono::FixedOffset> {
+        type Error = OutOfRange;
+
+        /// Converts a date and time with an offset. Fails for a local time.
+        fn try_from(dt: DateTime) -> core::result::Result<Self, OutOfRange> {
+            let offset: chrono::FixedOffset = dt.offset.ok_or(OutOfRange)?.try_into()?;
+            let naive: chrono::NaiveDateTime = dt.try_into()?;
+            naive.and_local_timezone(offset).single().ok_or(OutOfRange)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::*;
+
+    impl TryFrom<Date> for time::Date {
+        type Error = OutOfRange;
+
+        fn try_from(d: Date) -> core::result::Result<Self, OutOfRange> {
+            let month = time::Month::try_from(d.month).map_err(|_| OutOfRange)?;
+            time::Date::from_calendar_date(i32::from(d.year), month, d.day).map_err(|_| OutOfRange)
+        }
+    }
+
+    impl TryFrom<Time> for time::Time {
+        type Error = OutOfRange;
+
+        /// Converts a time. Fails for a leap second, which `time` can't
+        /// represent.
+        fn try_from(t: Time) -> core::result::Result<Self, OutOfRange> {
+            time::Time::from_hms_nano(t.hour, t.minute, t.second, t.nanosecond)
+                .map_err(|_| OutOfRange)
+        }
+    }
+
+    impl TryFrom<Offset> for time::UtcOffset {
+        type Error = OutOfRange;
+
+        fn try_from(o: Offset) -> core::result::Result<Self, OutOfRange> {
+            let (hours, minutes) = (o.minutes / 60, o.minutes % 60);
+            time::UtcOffset::from_hms(hours as i8, minutes as i8, 0).map_err(|_| OutOfRange)
+        }
+    }
+
+    impl TryFrom<DateTime> for time::PrimitiveDateTime {
+        type Error = OutOfRange;
+
+        /// Converts the date and time, ignoring the offset, if any.
+        fn try_from(dt: DateTime) -> core::result::Result<Self, OutOfRange> {
+            Ok(time::PrimitiveDateTime::new(dt.date.try_into()?, dt.time.try_into()?))
+        }
+    }
+
+    impl TryFrom<DateTime> for time::OffsetDateTime {
+        type Error = OutOfRange;
+
+        /// Converts a date and time with an offset. Fails for a local time.
+        fn try_from(dt: DateTime) -> core::result::Result<Self, OutOfRange> {
+            let offset: time::UtcOffset = dt.offset.ok_or(OutOfRange)?.try_into()?;
+            let primitive: time::PrimitiveDateTime = dt.try_into()?;
+            Ok(primitive.assume_offset(offset))
+        }
+    }
+}
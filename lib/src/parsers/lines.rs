@@ -0,0 +1,93 @@
+//! Parsers for lines of text ended by any common newline convention.
+//!
+//! Text written on different platforms ends lines differently: with `\n` on
+//! Unix, `\r\n` on Windows and in many network protocols, and `\r` in some
+//! older formats. [`line_end()`] accepts any of the three and reports which it
+//! found as a [`LineEnding`], [`line()`] takes a line's text along with its
+//! ending, and [`lines()`] takes every line that remains. To treat line
+//! endings uniformly in every parser instead, wrap the input in a
+//! [`NormalizeNewlines`](crate::input::NormalizeNewlines). All of the parsers
+//! operate on any input whose tokens are `char`s.
+//!
+//! ```rust
+//! use pear::input::Text;
+//! use pear::parsers::lines::*;
+//!
+//! let mut input = Text::from("one\r\ntwo\nthree");
+//! let lines: Vec<_> = lines(&mut input).unwrap();
+//! assert_eq!(lines, [
+//!     ("one", Some(LineEnding::CrLf)),
+//!     ("two", Some(LineEnding::Lf)),
+//!     ("three", None),
+//! ]);
+//! ```
+
+use crate::error::{ParseError, Expected};
+use crate::input::{Input, Result};
+use crate::combinators::Collection;
+use crate::macros::parser;
+use crate::parsers::*;
+
+/// A line-ending convention.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`, as on Unix.
+    Lf,
+    /// `\r\n`, as on Windows.
+    CrLf,
+    /// `\r`, as on classic Mac OS.
+    Cr,
+}
+
+impl LineEnding {
+    /// Returns the characters that make up the line ending.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+}
+
+/// Eats a line ending: `\n`, `\r\n`, or `\r`. Returns which it was.
+#[parser(raw)]
+pub fn line_end<I>(input: &mut I) -> Result<LineEnding, I>
+    where I: Input<Token = char>
+{
+    match eat_any_of(input, ['\n', '\r'])? {
+        '\r' if input.eat(|&c| c == '\n').is_some() => Ok(LineEnding::CrLf),
+        '\r' => Ok(LineEnding::Cr),
+        _ => Ok(LineEnding::Lf),
+    }
+}
+
+/// Takes the text of a line, up to but excluding its ending, then eats the
+/// ending. Returns the text and the ending, which is `None` if the line ends
+/// at the end of the input. Fails if there's no input left.
+#[parser(raw)]
+pub fn line<I>(input: &mut I) -> Result<(I::Many, Option<LineEnding>), I>
+    where I: Input<Token = char>
+{
+    if input.token().is_none() {
+        return Err(ParseError::new(Expected::Token(None, None)));
+    }
+
+    let text = input.take(|&c| c != '\n' && c != '\r');
+    Ok((text, line_end(input).ok()))
+}
+
+/// Takes every remaining [`line()`], collecting the text and ending of each. A
+/// line ending at the end of the input ends the last line; it doesn't begin
+/// an empty one. Succeeds with no lines on empty input.
+#[parser(raw)]
+pub fn lines<C, I>(input: &mut I) -> Result<C, I>
+    where I: Input<Token = char>, C: Collection<Item = (I::Many, Option<LineEnding>)>
+{
+    let mut lines = C::new();
+    while input.token().is_some() {
+        lines.add(line(input)?);
+    }
+
+    Ok(lines)
+}
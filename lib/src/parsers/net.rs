@@ -0,0 +1,315 @@
+//! Parsers for IP addresses, socket addresses, and [RFC 3986] URI components.
+//!
+//! The address parsers return the standard library's [`Ipv4Addr`],
+//! [`Ipv6Addr`], and [`SocketAddr`]. Unlike parsing with `str::parse()`, an
+//! invalid address fails at the offending character, so errors point at, say,
+//! the octet that's out of range. The URI component parsers validate and
+//! return slices of the input, leaving percent-encoded octets encoded. All of
+//! the parsers operate on any input whose tokens are `char`s.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use std::net::Ipv6Addr;
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::{parser, parse};
+//! use pear::parsers::*;
+//! use pear::parsers::net::*;
+//!
+//! // `scheme://authority/path`, as in an HTTP URL without a query.
+//! #[parser]
+//! fn url<'a>(input: &mut Text<'a>) -> Result<(&'a str, Authority<&'a str>, &'a str), Text<'a>> {
+//!     let scheme = scheme()?;
+//!     eat_slice("://")?;
+//!     (scheme, authority()?, path()?)
+//! }
+//!
+//! let (scheme, authority, path) = parse!(url: &mut Text::from("https://me@[::1]:8080/a%20b")).unwrap();
+//! assert_eq!((scheme, authority.userinfo, path), ("https", Some("me"), "/a%20b"));
+//! assert_eq!((authority.host, authority.port), (Host::Ipv6(Ipv6Addr::LOCALHOST), Some(8080)));
+//!
+//! let error = parse!(ipv4: &mut Text::from("10.0.256.1")).unwrap_err();
+//! assert_eq!(error.contexts[0].context.as_ref().unwrap().start.2, 5);
+//! ```
+//!
+//! [RFC 3986]: https://www.rfc-editor.org/rfc/rfc3986
+
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use crate::input::{Input, Rewind, Result, Checkpoint};
+use crate::macros::parser;
+use crate::parsers::*;
+
+/// The host of a URI authority.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Host<S> {
+    /// An IPv4 address, as in `127.0.0.1`.
+    Ipv4(Ipv4Addr),
+    /// An IPv6 address, as in `[::1]`.
+    Ipv6(Ipv6Addr),
+    /// A registered name, as in `example.com`. May be empty.
+    Name(S),
+}
+
+/// The authority of a URI: `[userinfo@]host[:port]`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Authority<S> {
+    pub userinfo: Option<S>,
+    pub host: Host<S>,
+    /// The port, if one is given. An empty port, as in `host:`, is `None`.
+    pub port: Option<u16>,
+}
+
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+fn is_sub_delim(c: char) -> bool {
+    matches!(c, '!' | '$' | '&' | '\'' | '(' | ')' | '*' | '+' | ',' | ';' | '=')
+}
+
+fn is_pchar(c: char) -> bool {
+    is_unreserved(c) || is_sub_delim(c) || c == ':' || c == '@'
+}
+
+/// Takes characters which are `allowed` or are part of a percent-encoded
+/// octet, `%` and two hex digits, failing if an octet is cut short.
+#[parser(raw)]
+fn take_encoded<I, F>(input: &mut I, mut allowed: F) -> Result<I::Many, I>
+    where I: Input<Token = char>, F: FnMut(char) -> bool
+{
+    let mut pending = 0;
+    let encoded = input.take(|&c| match pending {
+        0 if c == '%' => { pending = 2; true }
+        0 => allowed(c),
+        _ if c.is_ascii_hexdigit() => { pending -= 1; true }
+        _ => false,
+    });
+
+    if pending > 0 {
+        return invalid(input, "a percent-encoded octet: `%` and two hex digits");
+    }
+
+    Ok(encoded)
+}
+
+/// Parses a decimal octet: `0` to `255` without leading zeros.
+#[parser(raw)]
+fn dec_octet<I: Input<Token = char>>(input: &mut I) -> Result<u8, I> {
+    let first = eat_if(input, char::is_ascii_digit)?;
+    let mut value = first.to_digit(10).unwrap();
+    if first != '0' {
+        for _ in 0..2 {
+            match eat_if(input, char::is_ascii_digit) {
+                Ok(digit) => value = value * 10 + digit.to_digit(10).unwrap(),
+                Err(_) => break,
+            }
+        }
+    }
+
+    if value > 255 {
+        return invalid(input, "an octet from 0 to 255");
+    }
+
+    Ok(value as u8)
+}
+
+/// Parses the last three octets of an IPv4 address whose first is `first`.
+#[parser(raw)]
+fn ipv4_rest<I: Input<Token = char>>(input: &mut I, first: u8) -> Result<Ipv4Addr, I> {
+    let mut octets = [first, 0, 0, 0];
+    for octet in octets.iter_mut().skip(1) {
+        eat(input, '.')?;
+        *octet = dec_octet(input)?;
+    }
+
+    Ok(Ipv4Addr::from(octets))
+}
+
+/// Parses an IPv4 address in dotted-decimal form, as in `192.168.0.1`.
+#[parser(raw)]
+pub fn ipv4<I: Input<Token = char>>(input: &mut I) -> Result<Ipv4Addr, I> {
+    let first = dec_octet(input)?;
+    ipv4_rest(input, first)
+}
+
+/// Parses one to four hex digits as a group of an IPv6 address. Returns the
+/// group's value and, if its digits also form a decimal octet, that octet.
+#[parser(raw)]
+fn h16<I: Input<Token = char>>(input: &mut I) -> Result<(u16, Option<u8>), I> {
+    let first = eat_if(input, char::is_ascii_hexdigit)?;
+    let (mut hex, mut decimal) = (first.to_digit(16).unwrap(), first.to_digit(10));
+    for i in 1..4 {
+        let digit = match eat_if(input, char::is_ascii_hexdigit) {
+            Ok(digit) => digit,
+            Err(_) => break,
+        };
+
+        hex = hex * 16 + digit.to_digit(16).unwrap();
+        decimal = match (decimal, digit.to_digit(10)) {
+            (Some(value), Some(d)) if first != '0' && i < 3 => Some(value * 10 + d),
+            _ => None,
+        };
+    }
+
+    Ok((hex as u16, decimal.filter(|&d| d <= 255).map(|d| d as u8)))
+}
+
+/// Parses an IPv6 address in any of the text forms of [RFC 4291]: eight
+/// groups of hex digits separated by `:`, with one run of zero groups
+/// optionally elided as `::`, and optionally ending in an IPv4 address, as in
+/// `::ffff:192.0.2.1`.
+///
+/// [RFC 4291]: https://www.rfc-editor.org/rfc/rfc4291#section-2.2
+#[parser(raw)]
+pub fn ipv6<I: Input<Token = char>>(input: &mut I) -> Result<Ipv6Addr, I> {
+    let mut groups = [0u16; 8];
+    let (mut len, mut gap) = (0, None);
+    if eat(input, ':').is_ok() {
+        eat(input, ':')?;
+        gap = Some(0);
+    }
+
+    loop {
+        if gap == Some(len) && peek_if(input, char::is_ascii_hexdigit).is_err() {
+            break;
+        }
+
+        let (group, octet) = h16(input)?;
+        if let (Some(octet), true) = (octet, len <= 6 && peek(input, '.').is_ok()) {
+            let [a, b, c, d] = ipv4_rest(input, octet)?.octets();
+            groups[len] = u16::from_be_bytes([a, b]);
+            groups[len + 1] = u16::from_be_bytes([c, d]);
+            len += 2;
+            break;
+        }
+
+        groups[len] = group;
+        len += 1;
+        if len == 8 || eat(input, ':').is_err() {
+            break;
+        }
+
+        if eat(input, ':').is_ok() {
+            if gap.is_some() {
+                return invalid(input, "at most one `::` in an IPv6 address");
+            }
+
+            gap = Some(len);
+        }
+    }
+
+    match gap {
+        None if len < 8 => invalid(input, "eight groups or a `::` in an IPv6 address"),
+        Some(_) if len == 8 => invalid(input, "at most seven groups around a `::`"),
+        Some(at) => {
+            let tail = len - at;
+            groups.copy_within(at..len, 8 - tail);
+            groups[at..8 - tail].iter_mut().for_each(|g| *g = 0);
+            Ok(Ipv6Addr::from(groups))
+        }
+        None => Ok(Ipv6Addr::from(groups)),
+    }
+}
+
+/// Parses a port number: one or more digits forming a number up to `65535`.
+#[parser(raw)]
+pub fn port<I: Input<Token = char>>(input: &mut I) -> Result<u16, I> {
+    let mut value = eat_if(input, char::is_ascii_digit)?.to_digit(10).unwrap();
+    while let Ok(digit) = eat_if(input, char::is_ascii_digit) {
+        value = value * 10 + digit.to_digit(10).unwrap();
+        if value > u32::from(u16::MAX) {
+            return invalid(input, "a port from 0 to 65535");
+        }
+    }
+
+    Ok(value as u16)
+}
+
+/// Parses a socket address: an IPv4 address and port, as in `127.0.0.1:80`,
+/// or a bracketed IPv6 address and port, as in `[::1]:80`.
+#[parser(raw)]
+pub fn socket_addr<I: Input<Token = char>>(input: &mut I) -> Result<SocketAddr, I> {
+    if eat(input, '[').is_ok() {
+        let ip = ipv6(input)?;
+        eat(input, ']')?;
+        eat(input, ':')?;
+        return Ok(SocketAddr::V6(SocketAddrV6::new(ip, port(input)?, 0, 0)));
+    }
+
+    let ip = ipv4(input)?;
+    eat(input, ':')?;
+    Ok(SocketAddr::V4(SocketAddrV4::new(ip, port(input)?)))
+}
+
+/// Parses a URI scheme, as in `https`: a letter followed by any number of
+/// letters, digits, `+`, `-`, and `.`.
+#[parser(raw)]
+pub fn scheme<I: Input<Token = char>>(input: &mut I) -> Result<I::Many, I> {
+    let mut first = true;
+    take_some_while(input, |&c| match core::mem::replace(&mut first, false) {
+        true => c.is_ascii_alphabetic(),
+        false => c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'),
+    })
+}
+
+/// Parses the host of a URI authority: a bracketed IPv6 address, an IPv4
+/// address, or, failing those, a registered name.
+#[parser(raw)]
+pub fn host<I: Rewind<Token = char>>(input: &mut I) -> Result<Host<I::Many>, I> {
+    if eat(input, '[').is_ok() {
+        let ip = ipv6(input)?;
+        eat(input, ']')?;
+        return Ok(Host::Ipv6(ip));
+    }
+
+    let mut checkpoint = Checkpoint::named(input, "host");
+    if let Ok(ip) = ipv4(&mut *checkpoint) {
+        if checkpoint.token().is_none_or(|c| !is_unreserved(c) && c != '%') {
+            checkpoint.commit();
+            return Ok(Host::Ipv4(ip));
+        }
+    }
+
+    checkpoint.rollback();
+    Ok(Host::Name(take_encoded(input, |c| is_unreserved(c) || is_sub_delim(c))?))
+}
+
+/// Parses the authority of a URI: `[userinfo@]host[:port]`.
+#[parser(raw)]
+pub fn authority<I: Rewind<Token = char>>(input: &mut I) -> Result<Authority<I::Many>, I> {
+    let mut checkpoint = Checkpoint::named(input, "userinfo");
+    let userinfo = take_encoded(&mut *checkpoint, |c| is_unreserved(c) || is_sub_delim(c) || c == ':')?;
+    let userinfo = match eat(&mut *checkpoint, '@') {
+        Ok(_) => { checkpoint.commit(); Some(userinfo) }
+        Err(_) => { checkpoint.rollback(); None }
+    };
+
+    let host = host(input)?;
+    let port = match eat(input, ':') {
+        Ok(_) if peek_if(input, char::is_ascii_digit).is_ok() => Some(port(input)?),
+        _ => None,
+    };
+
+    Ok(Authority { userinfo, host, port })
+}
+
+/// Parses a URI path: any number of segments of path characters separated by
+/// `/`. May be empty.
+#[parser(raw)]
+pub fn path<I: Input<Token = char>>(input: &mut I) -> Result<I::Many, I> {
+    take_encoded(input, |c| is_pchar(c) || c == '/')
+}
+
+/// Parses a URI query, as after a `?`. May be empty.
+#[parser(raw)]
+pub fn query<I: Input<Token = char>>(input: &mut I) -> Result<I::Many, I> {
+    take_encoded(input, |c| is_pchar(c) || c == '/' || c == '?')
+}
+
+/// Parses a URI fragment, as after a `#`. May be empty.
+#[parser(raw)]
+pub fn fragment<I: Input<Token = char>>(input: &mut I) -> Result<I::Many, I> {
+    take_encoded(input, |c| is_pchar(c) || c == '/' || c == '?')
+}
@@ -0,0 +1,145 @@
+use core::fmt;
+use core::ops::{Index, Range};
+
+use alloc::{vec, format, boxed::Box, string::String};
+
+use regex_automata::{Anchored, meta, util::captures};
+
+use crate::error::{ParseError, Expected, is_quiet};
+use crate::input::{Input, Position, Text, Result};
+use crate::macros::parser;
+
+/// A compiled regular expression, for matching with [`re()`].
+///
+/// The syntax is that of the [`regex`](https://docs.rs/regex) crate.
+#[derive(Clone)]
+pub struct Regex {
+    pattern: String,
+    regex: meta::Regex,
+}
+
+impl Regex {
+    /// Compiles `pattern`. The error, which is large, is boxed.
+    pub fn new(pattern: &str) -> core::result::Result<Regex, Box<meta::BuildError>> {
+        let regex = meta::Regex::new(pattern).map_err(Box::new)?;
+        Ok(Regex { pattern: pattern.into(), regex })
+    }
+
+    /// Returns the pattern this regex was compiled from.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl fmt::Debug for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Regex").field(&self.pattern).finish()
+    }
+}
+
+/// The capture groups of a match by [`re()`]. Group `0` is the entire match.
+///
+/// Groups are indexed by number or, if they're named, by name. Indexing
+/// panics if the group doesn't exist or didn't participate in the match.
+#[derive(Debug, Clone)]
+pub struct Captures<'a> {
+    text: &'a str,
+    start: usize,
+    captures: captures::Captures,
+}
+
+impl<'a> Captures<'a> {
+    /// Returns the text of group `i`, if it participated in the match.
+    pub fn get(&self, i: usize) -> Option<&'a str> {
+        self.captures.get_group(i).map(|span| &self.text[span.range()])
+    }
+
+    /// Returns the text of the group named `name`, if it participated in the
+    /// match.
+    pub fn name(&self, name: &str) -> Option<&'a str> {
+        self.captures.get_group_by_name(name).map(|span| &self.text[span.range()])
+    }
+
+    /// Returns the range of group `i`, if it participated in the match,
+    /// relative to the position the match began at.
+    pub fn range(&self, i: usize) -> Option<Range<usize>> {
+        let span = self.captures.get_group(i)?;
+        Some((span.start - self.start)..(span.end - self.start))
+    }
+
+    /// Returns the number of groups in the regex, including group `0`.
+    pub fn len(&self) -> usize {
+        self.captures.group_len()
+    }
+
+    /// Returns `false`: a regex always has group `0`.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl Index<usize> for Captures<'_> {
+    type Output = str;
+
+    fn index(&self, i: usize) -> &str {
+        self.get(i).unwrap_or_else(|| panic!("no group at index `{}`", i))
+    }
+}
+
+impl Index<&str> for Captures<'_> {
+    type Output = str;
+
+    fn index(&self, name: &str) -> &str {
+        self.name(name).unwrap_or_else(|| panic!("no group named `{}`", name))
+    }
+}
+
+/// Eats the text matched by `regex` at the current position, returning the
+/// captures of the match. Group `0` is the matched text.
+///
+/// The match must begin at the current position: `regex` is anchored there,
+/// so a failing match doesn't search the rest of the input. The text before
+/// the current position is visible to the regex, so `\b` sees the preceding
+/// character and `^` matches only at the start of the text. Capture ranges,
+/// as returned by [`Captures::range()`], are relative to the current position.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// #[parser]
+/// fn version<'a>(input: &mut Text<'a>) -> Result<(u32, u32), Text<'a>> {
+///     let regex = Regex::new(r"(\d+)\.(\d+)").unwrap();
+///     let captures = re(&regex)?;
+///     (captures[1].parse::<u32>().unwrap(), captures[2].parse::<u32>().unwrap())
+/// }
+///
+/// assert_eq!(parse!(version: &mut Text::from("1.42")).unwrap(), (1, 42));
+/// assert!(parse!(version: &mut Text::from("v1.42")).is_err());
+/// ```
+///
+/// This parser requires the `regex` feature.
+#[parser(raw)]
+pub fn re<'a>(input: &mut Text<'a>, regex: &Regex) -> Result<Captures<'a>, Text<'a>> {
+    let (text, start) = (input.source(), input.position());
+    let search = regex_automata::Input::new(text)
+        .span(start..text.len())
+        .anchored(Anchored::Yes);
+
+    let mut captures = regex.regex.create_captures();
+    regex.regex.search_captures(&search, &mut captures);
+    match captures.get_match() {
+        Some(found) => {
+            input.eat_slice(found.len(), |_| true);
+            Ok(Captures { text, start, captures })
+        }
+        None if is_quiet() => Err(ParseError::new(Expected::Token(None, input.token()))),
+        None => {
+            let expected = format!("text matching `{}`", regex.as_str());
+            Err(ParseError::new(Expected::OneOf(vec![expected], input.token())))
+        }
+    }
+}
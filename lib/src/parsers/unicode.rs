@@ -0,0 +1,124 @@
+//! Parsers for Unicode character classes.
+//!
+//! The parsers in this module classify characters by their Unicode
+//! properties rather than by ASCII ranges. In particular, [`identifier()`]
+//! follows the default identifier syntax of [UAX #31], `XID_Start
+//! XID_Continue*`, as used by Rust, Python, and JavaScript, among others:
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::{parser, parse};
+//! use pear::parsers::*;
+//! use pear::parsers::unicode::*;
+//!
+//! #[parser]
+//! fn assignment<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+//!     let name = identifier()?;
+//!     skip_while(is_whitespace)?;
+//!     eat('=')?;
+//!     skip_while(is_whitespace)?;
+//!     (name, take_some_while(is_alphanumeric)?)
+//! }
+//!
+//! let result = parse!(assignment: &mut Text::from("größe\u{3000}= 四二"));
+//! assert_eq!(result.unwrap(), ("größe", "四二"));
+//! ```
+//!
+//! This module requires the `unicode` feature.
+//!
+//! [UAX #31]: https://www.unicode.org/reports/tr31/
+
+use alloc::vec;
+
+use crate::error::{ParseError, Expected};
+use crate::input::{Input, Result};
+use crate::macros::parser;
+
+/// Returns `true` if `c` has the Unicode `Alphabetic` or `Numeric` property.
+pub fn is_alphanumeric(c: &char) -> bool {
+    c.is_alphanumeric()
+}
+
+/// Returns `true` if `c` has the Unicode `White_Space` property.
+pub fn is_whitespace(c: &char) -> bool {
+    c.is_whitespace()
+}
+
+/// Returns `true` if `c` has the Unicode `XID_Start` property: it may begin
+/// an identifier.
+pub fn is_xid_start(c: &char) -> bool {
+    unicode_ident::is_xid_start(*c)
+}
+
+/// Returns `true` if `c` has the Unicode `XID_Continue` property: it may
+/// continue an identifier.
+pub fn is_xid_continue(c: &char) -> bool {
+    unicode_ident::is_xid_continue(*c)
+}
+
+/// Eats the current character if `cond` holds for it, otherwise failing with
+/// an error expecting `class`.
+fn eat_class<I, F>(input: &mut I, class: &str, cond: F) -> Result<char, I>
+    where I: Input<Token = char>, F: FnMut(&char) -> bool
+{
+    match input.eat(cond) {
+        Some(c) => Ok(c),
+        None => {
+            let expected = Expected::OneOf(vec![class.into()], input.token());
+            Err(ParseError::new(expected))
+        }
+    }
+}
+
+/// Eats an alphabetic or numeric character.
+#[parser(raw)]
+pub fn unicode_alphanumeric<I>(input: &mut I) -> Result<char, I>
+    where I: Input<Token = char>
+{
+    eat_class(input, "an alphanumeric character", is_alphanumeric)
+}
+
+/// Eats a whitespace character.
+#[parser(raw)]
+pub fn unicode_whitespace<I>(input: &mut I) -> Result<char, I>
+    where I: Input<Token = char>
+{
+    eat_class(input, "a whitespace character", is_whitespace)
+}
+
+/// Eats a character which may begin an identifier.
+#[parser(raw)]
+pub fn xid_start<I>(input: &mut I) -> Result<char, I>
+    where I: Input<Token = char>
+{
+    eat_class(input, "an identifier", is_xid_start)
+}
+
+/// Eats a character which may continue an identifier.
+#[parser(raw)]
+pub fn xid_continue<I>(input: &mut I) -> Result<char, I>
+    where I: Input<Token = char>
+{
+    eat_class(input, "an identifier character", is_xid_continue)
+}
+
+/// Eats an identifier: an `XID_Start` character followed by any number of
+/// `XID_Continue` characters. Note that `_` is not `XID_Start`; grammars
+/// which allow identifiers to begin with `_` should check for it first.
+#[parser(raw)]
+pub fn identifier<I>(input: &mut I) -> Result<I::Many, I>
+    where I: Input<Token = char>
+{
+    if !input.peek(is_xid_start) {
+        let expected = Expected::OneOf(vec!["an identifier".into()], input.token());
+        return Err(ParseError::new(expected));
+    }
+
+    let mut first = true;
+    Ok(input.take(|c| match core::mem::replace(&mut first, false) {
+        true => is_xid_start(c),
+        false => is_xid_continue(c),
+    }))
+}
@@ -0,0 +1,239 @@
+//! Two-phase parsing: a lexer producing tokens, then a parser over them.
+//!
+//! A lexer is any parser over text that returns one token at a time. [`lex()`]
+//! runs it until the text is exhausted, collecting each token along with the
+//! span of source bytes it was lexed from, as a [`Spanned`]. A
+//! [`TokenStreamInput`] then presents the tokens as an input to a parser.
+//! Its contexts are the [`Span`]s of the source text covered by the tokens
+//! they span, so parser errors point at the original source, by line,
+//! column, and byte offset, just as lexer errors do.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::{parser, switch};
+//! use pear::parsers::*;
+//! use pear::pipeline::{lex, TokenStreamInput};
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Tok { Num(i64), Plus }
+//!
+//! pear::impl_show_with!(Debug, Tok);
+//!
+//! type Tokens<'a> = TokenStreamInput<'a, Tok>;
+//!
+//! // The lexer skips whitespace, returning `None` for it.
+//! #[parser]
+//! fn token<'a>(input: &mut Text<'a>) -> Result<Option<Tok>, Text<'a>> {
+//!     switch! {
+//!         eat('+') => Some(Tok::Plus),
+//!         take_some_while(|c: &char| c.is_whitespace()) => None,
+//!         _ => Some(Tok::Num(take_some_while(|c: &char| c.is_ascii_digit())?.parse().unwrap())),
+//!     }
+//! }
+//!
+//! #[parser]
+//! fn num<'a>(input: &mut Tokens<'a>) -> Result<i64, Tokens<'a>> {
+//!     match eat_if(|t: &&Tok| matches!(t, Tok::Num(_)))? {
+//!         Tok::Num(n) => *n,
+//!         _ => unreachable!(),
+//!     }
+//! }
+//!
+//! #[parser]
+//! fn sum<'a>(input: &mut Tokens<'a>) -> Result<i64, Tokens<'a>> {
+//!     let mut sum = num()?;
+//!     while eat(input, &Tok::Plus).is_ok() {
+//!         sum += num()?;
+//!     }
+//!
+//!     eof()?;
+//!     sum
+//! }
+//!
+//! let source = "1 + 2 +\n  30";
+//! let tokens = lex(&mut Text::from(source), token).unwrap();
+//! assert_eq!(sum(&mut TokenStreamInput::new(source, &tokens)).unwrap(), 33);
+//!
+//! let source = "1 + 2\n  30";
+//! let tokens = lex(&mut Text::from(source), token).unwrap();
+//! let error = sum(&mut TokenStreamInput::new(source, &tokens)).unwrap_err();
+//! assert_eq!(error.contexts[0].context.unwrap().start, (2, 3, 8));
+//! ```
+
+use core::ops::Range;
+use alloc::vec::Vec;
+
+use crate::error::{Error, ParseError};
+use crate::input::{Input, Rewind, Position, Show, Span, Spanned, ParserInfo, Result, line_col};
+
+/// Lexes all of `input` with `lexer`, which lexes one token or, returning
+/// `None`, skips trivia like whitespace and comments. Returns the tokens, each
+/// with the span of [`Position`]s it was lexed from.
+///
+/// Fails if `lexer` fails or if it succeeds without consuming any input.
+pub fn lex<I, T, P, E>(input: &mut I, mut lexer: P) -> Result<Vec<Spanned<T>>, I, E>
+    where I: Position, E: Error<I>, P: FnMut(&mut I) -> Result<Option<T>, I, E>
+{
+    let mut tokens = Vec::new();
+    while input.token().is_some() {
+        let start = input.position();
+        let token = lexer(input)?;
+        let end = input.position();
+        if start == end {
+            let message = "a lexer that consumes input";
+            return Err(ParseError::new(E::from_context(message.into(), None)));
+        }
+
+        if let Some(token) = token {
+            tokens.push(Spanned::new(token, start..end));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// An input over tokens lexed from `source`, each spanning a range of bytes in
+/// `source`, as returned by [`lex()`]. See the [module documentation](self).
+///
+/// Tokens are `&T`, and slices are of the `Spanned<T>`s themselves. Positions
+/// are token indices. Contexts are the [`Span`]s of source text from the start
+/// of the first token spanned to the end of the last. A context which spans no
+/// tokens is the empty span at the start of the next token, or at the end of
+/// `source` if there are no more tokens.
+#[derive(Debug)]
+pub struct TokenStreamInput<'a, T> {
+    source: &'a str,
+    start: &'a [Spanned<T>],
+    items: &'a [Spanned<T>],
+}
+
+impl<'a, T> TokenStreamInput<'a, T> {
+    /// Returns an input over `tokens` lexed from `source`.
+    pub fn new(source: &'a str, tokens: &'a [Spanned<T>]) -> Self {
+        TokenStreamInput { source, start: tokens, items: tokens }
+    }
+
+    /// Returns the source text the tokens were lexed from.
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Returns the byte offset in the source of the token at index `index`, or
+    /// of the end of the source if there is no such token.
+    fn offset(&self, index: usize) -> usize {
+        self.start.get(index).map_or(self.source.len(), |t| t.span.start)
+    }
+
+    /// Returns the span of source bytes covered by tokens `start..end`.
+    pub fn source_span(&self, tokens: Range<usize>) -> Range<usize> {
+        match tokens.is_empty() {
+            true => self.offset(tokens.start)..self.offset(tokens.start),
+            false => self.start[tokens.start].span.start..self.start[tokens.end - 1].span.end,
+        }
+    }
+}
+
+impl<T: PartialEq + Show> Rewind for TokenStreamInput<'_, T> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.items = &self.start[*marker..];
+    }
+}
+
+impl<T: PartialEq + Show> Position for TokenStreamInput<'_, T> {
+    fn position(&self) -> usize {
+        self.start.len() - self.items.len()
+    }
+}
+
+impl<'a, T: PartialEq + Show> Input for TokenStreamInput<'a, T> {
+    type Token = &'a T;
+    type Slice = &'a [Spanned<T>];
+    type Many = &'a [Spanned<T>];
+
+    type Marker = usize;
+    type Context = Span<'a>;
+
+    fn token(&mut self) -> Option<Self::Token> {
+        self.items.first().map(|t| &t.value)
+    }
+
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.items.get(..n)
+    }
+
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        self.items.get(offset..offset.checked_add(len)?)
+    }
+
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().is_some_and(|t| cond(&t))
+    }
+
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).is_some_and(|s| cond(&s))
+    }
+
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.items = &self.items[1..];
+            Some(token)
+        } else {
+            None
+        }
+    }
+
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.items = &self.items[n..];
+            Some(slice)
+        } else {
+            None
+        }
+    }
+
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let matches = self.items.iter().take_while(|t| cond(&&t.value)).count();
+        let value = &self.items[..matches];
+        self.items = &self.items[matches..];
+        value
+    }
+
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.take(cond).len()
+    }
+
+    fn has(&mut self, n: usize) -> bool {
+        self.items.len() >= n
+    }
+
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.position()
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        let span = self.source_span(*mark..self.position());
+        let (start_line, start_col) = line_col(&self.source[..span.start]);
+        let (end_line, end_col) = line_col(&self.source[..span.end]);
+        Some(Span {
+            start: (start_line, start_col, span.start),
+            end: (end_line, end_col, span.end),
+            snippet: Some(&self.source[span]),
+        })
+    }
+}
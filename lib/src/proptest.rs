@@ -0,0 +1,662 @@
+//! Property-testing input generators derived from grammars, for `proptest`.
+//!
+//! A module attributed with [`#[grammar]`](crate::macros::grammar) lists the
+//! EBNF productions of its parsers in its `GRAMMAR` constant. A [`Generator`]
+//! reads that listing and derives, for any rule, a [`GrammarStrategy`]: a
+//! `proptest` strategy generating strings the rule's production describes.
+//! Parsing the generated strings with the rule's parser and comparing the
+//! result against expectations then tests the parser against its own grammar:
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::macros::grammar;
+//! use pear::proptest::Generator;
+//! use proptest::prelude::*;
+//!
+//! #[grammar]
+//! mod list {
+//!     use pear::input::{Text, Result};
+//!     use pear::{macros::*, parsers::*};
+//!
+//!     // list = '[' number [ ',' numbers ] ']' ;
+//!     #[parser]
+//!     pub fn list<'a>(input: &mut Text<'a>) -> Result<Vec<u32>, Text<'a>> {
+//!         eat('[')?;
+//!         let first = number()?;
+//!         let mut items = vec![first];
+//!         switch! {
+//!             eat(',') => items.extend(numbers()?),
+//!             _ => ()
+//!         }
+//!
+//!         eat(']')?;
+//!         items
+//!     }
+//!
+//!     // numbers = number [ ',' numbers ] ;
+//!     #[parser]
+//!     pub fn numbers<'a>(input: &mut Text<'a>) -> Result<Vec<u32>, Text<'a>> {
+//!         let first = number()?;
+//!         let mut items = vec![first];
+//!         switch! {
+//!             eat(',') => items.extend(numbers()?),
+//!             _ => ()
+//!         }
+//!
+//!         items
+//!     }
+//!
+//!     // number = ? c.is_ascii_digit() ? { ? c.is_ascii_digit() ? } ;
+//!     #[parser]
+//!     pub fn number<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+//!         take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>().unwrap()
+//!     }
+//! }
+//!
+//! // `number` describes its digits only informally, so replace it.
+//! let generator = Generator::new(list::GRAMMAR).unwrap()
+//!     .rule("number", "[1-9][0-9]{0,3}");
+//!
+//! proptest!(|(string in generator.strategy("list").unwrap())| {
+//!     let items = list::list(&mut pear::input::Text::from(&*string));
+//!     prop_assert_eq!(items.unwrap().len(), string.matches(',').count() + 1);
+//! });
+//! ```
+//!
+//! Literals, like `'['` and `"nil"`, generate themselves. A symbol that
+//! isn't a rule or a literal, like the token a `take_some_while()` predicate
+//! accepts, `? c.is_ascii_digit() ?`, or a rule defined outside of the
+//! module, is opaque: it must be given a strategy with
+//! [`Generator::rule()`], which can also replace the production of a rule.
+//! Strategies are validated when they're derived: [`Generator::strategy()`]
+//! fails if an opaque symbol the rule may reach has no strategy.
+//!
+//! Productions don't record whitespace a parser skips, nor constraints a
+//! parser checks in code; strategies for opaque symbols, or for the rules
+//! that check, fill those gaps. Generated strings shrink toward the simplest
+//! derivation: the first alternative of every choice, no repetitions, and no
+//! optional terms.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+use ::proptest::prelude::Rng;
+use ::proptest::strategy::{BoxedStrategy, NewTree, Strategy, ValueTree};
+use ::proptest::test_runner::TestRunner;
+
+use crate::input::{Input, Text, Result};
+use crate::macros::parser;
+use crate::parsers::{eat, eat_any, take_while, take_some_while, invalid};
+
+/// A term in a production.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    /// A literal, like `'a'` or `"ab"`.
+    Literal(String),
+    /// A rule or an opaque symbol, like `value`, `take_n(2)`, or a token
+    /// described by a special sequence, like `? c.is_ascii_digit() ?`.
+    Symbol(String),
+    /// One of several sequences: `( a | b )`.
+    Choice(Vec<Vec<Term>>),
+    /// One of several sequences, or nothing: `[ a | b ]`.
+    Optional(Vec<Vec<Term>>),
+    /// Zero or more repetitions of a sequence: `{ a }`.
+    Repeat(Vec<Term>),
+}
+
+/// Why a [`Generator`] couldn't derive a strategy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrammarError {
+    /// The rule isn't in the grammar and has no strategy.
+    UnknownRule(String),
+    /// An opaque symbol reachable from the rule has no strategy.
+    Opaque {
+        /// The rule whose production refers to the symbol.
+        rule: String,
+        /// The symbol.
+        symbol: String,
+    },
+    /// The rule only has derivations that never end.
+    Unbounded(String),
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrammarError::UnknownRule(rule) => write!(f, "unknown rule `{}`", rule),
+            GrammarError::Opaque { rule, symbol } => {
+                write!(f, "`{}` in rule `{}` needs a strategy", symbol, rule)
+            }
+            GrammarError::Unbounded(rule) => write!(f, "rule `{}` has no finite derivation", rule),
+        }
+    }
+}
+
+impl std::error::Error for GrammarError { }
+
+/// Derives `proptest` strategies from a grammar. See the [module
+/// documentation](self).
+#[derive(Debug, Clone)]
+pub struct Generator {
+    productions: BTreeMap<String, Vec<Term>>,
+    overrides: BTreeMap<String, BoxedStrategy<String>>,
+    max_depth: usize,
+    max_repeat: u32,
+}
+
+impl Generator {
+    /// Reads the productions in `grammar`, one per line, as listed in the
+    /// `GRAMMAR` constant of a [`#[grammar]`](crate::macros::grammar) module.
+    pub fn new(grammar: &str) -> Result<Generator, Text<'_>> {
+        let productions = crate::parse!(productions: &mut Text::from(grammar))?;
+        Ok(Generator {
+            productions: productions.into_iter().collect(),
+            overrides: BTreeMap::new(),
+            max_depth: 8,
+            max_repeat: 4,
+        })
+    }
+
+    /// Generates `symbol`, a rule or an opaque symbol like
+    /// `? c.is_ascii_digit() ?`, with `strategy` instead of from the grammar.
+    pub fn rule<S>(mut self, symbol: &str, strategy: S) -> Self
+        where S: Strategy<Value = String> + 'static
+    {
+        self.overrides.insert(symbol.into(), strategy.boxed());
+        self
+    }
+
+    /// Sets the depth of nested rules past which generation takes the
+    /// shortest way out: choices pick their shallowest alternative, and
+    /// repetitions and optional terms are left out. Defaults to `8`.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets the maximum number of times a repetition repeats. Defaults to `4`.
+    pub fn max_repeat(mut self, n: u32) -> Self {
+        self.max_repeat = n;
+        self
+    }
+
+    /// Returns a strategy generating strings described by `rule`.
+    pub fn strategy(&self, rule: &str) -> std::result::Result<GrammarStrategy, GrammarError> {
+        if !self.overrides.contains_key(rule) && !self.productions.contains_key(rule) {
+            return Err(GrammarError::UnknownRule(rule.into()));
+        }
+
+        let depths = self.min_depths();
+        let mut pending = vec![rule.to_string()];
+        let mut seen = vec![];
+        while let Some(rule) = pending.pop() {
+            if seen.contains(&rule) || self.overrides.contains_key(&rule) {
+                continue;
+            }
+
+            let terms = &self.productions[&rule];
+            if depths[&rule] == usize::MAX {
+                return Err(GrammarError::Unbounded(rule));
+            }
+
+            let mut symbols = vec![];
+            collect_symbols(terms, &mut symbols);
+            for symbol in symbols {
+                if !self.productions.contains_key(symbol) && !self.overrides.contains_key(symbol) {
+                    let symbol = symbol.into();
+                    return Err(GrammarError::Opaque { rule, symbol });
+                }
+
+                pending.push(symbol.into());
+            }
+
+            seen.push(rule);
+        }
+
+        let generator = Rc::new(self.clone());
+        Ok(GrammarStrategy { generator, depths: Rc::new(depths), rule: rule.into() })
+    }
+
+    /// Returns the least depth of nested rules a derivation of each rule
+    /// needs, or `usize::MAX` if no derivation ends.
+    fn min_depths(&self) -> BTreeMap<String, usize> {
+        let mut depths: BTreeMap<_, _> = self.productions.keys()
+            .map(|rule| (rule.clone(), usize::MAX))
+            .collect();
+
+        loop {
+            let mut changed = false;
+            for (rule, terms) in &self.productions {
+                let depth = seq_depth(terms, &depths, &self.overrides);
+                if depth < depths[rule] {
+                    depths.insert(rule.clone(), depth);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return depths;
+            }
+        }
+    }
+}
+
+fn collect_symbols<'t>(terms: &'t [Term], symbols: &mut Vec<&'t str>) {
+    for term in terms {
+        match term {
+            Term::Literal(_) => {}
+            Term::Symbol(symbol) => symbols.push(symbol),
+            Term::Choice(alts) | Term::Optional(alts) => {
+                alts.iter().for_each(|alt| collect_symbols(alt, symbols))
+            }
+            Term::Repeat(terms) => collect_symbols(terms, symbols),
+        }
+    }
+}
+
+type Overrides = BTreeMap<String, BoxedStrategy<String>>;
+
+fn seq_depth(terms: &[Term], depths: &BTreeMap<String, usize>, overrides: &Overrides) -> usize {
+    terms.iter().map(|term| term_depth(term, depths, overrides)).max().unwrap_or(0)
+}
+
+fn term_depth(term: &Term, depths: &BTreeMap<String, usize>, overrides: &Overrides) -> usize {
+    match term {
+        Term::Literal(_) | Term::Optional(_) | Term::Repeat(_) => 0,
+        Term::Symbol(symbol) if overrides.contains_key(symbol) => 0,
+        Term::Symbol(symbol) => depths.get(symbol).map_or(0, |d| d.saturating_add(1)),
+        Term::Choice(alts) => alts.iter()
+            .map(|alt| seq_depth(alt, depths, overrides))
+            .min()
+            .unwrap_or(0),
+    }
+}
+
+/// A strategy generating strings described by a rule of a grammar, derived
+/// by [`Generator::strategy()`].
+#[derive(Debug, Clone)]
+pub struct GrammarStrategy {
+    generator: Rc<Generator>,
+    depths: Rc<BTreeMap<String, usize>>,
+    rule: String,
+}
+
+/// The number of values drawn from the strategy of each opaque symbol for
+/// each generated string.
+const POOL_SIZE: usize = 8;
+
+impl Strategy for GrammarStrategy {
+    type Tree = GrammarValueTree;
+    type Value = String;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let mut pools = BTreeMap::new();
+        for (symbol, strategy) in &self.generator.overrides {
+            let mut pool = Vec::with_capacity(POOL_SIZE);
+            for _ in 0..POOL_SIZE {
+                pool.push(strategy.new_tree(runner)?.current());
+            }
+
+            pools.insert(symbol.clone(), pool);
+        }
+
+        let mut tree = GrammarValueTree {
+            strategy: self.clone(),
+            pools,
+            decisions: vec![],
+            current: String::new(),
+            pass: 0,
+            index: 0,
+            last: None,
+        };
+
+        tree.generate(Some(runner));
+        Ok(tree)
+    }
+}
+
+/// The value tree of a [`GrammarStrategy`].
+///
+/// A string is generated by a series of decisions: which alternative of a
+/// choice to take, whether to include an optional term, and how many times to
+/// repeat a repetition, where `0` is always the simplest decision. The tree
+/// shrinks a string by shrinking its decisions, in passes: deleting runs of
+/// decisions, which tends to delete parts of the string, then making each
+/// decision `0`, then decrementing each. A change is only kept if it leaves
+/// fewer decisions, or as many but lesser ones.
+#[derive(Debug, Clone)]
+pub struct GrammarValueTree {
+    strategy: GrammarStrategy,
+    pools: BTreeMap<String, Vec<String>>,
+    decisions: Vec<u32>,
+    current: String,
+    /// The current shrinking pass, an index into `PASSES`.
+    pass: usize,
+    /// The index of the next decision to shrink in the current pass.
+    index: usize,
+    /// The decisions and string before the last change, if it may be undone.
+    last: Option<(Vec<u32>, String)>,
+}
+
+/// A way of shrinking the decisions of a [`GrammarValueTree`].
+#[derive(Debug, Clone, Copy)]
+enum Pass {
+    Delete(usize),
+    Zero,
+    Decrement,
+}
+
+const PASSES: [Pass; 6] = [
+    Pass::Delete(8), Pass::Delete(4), Pass::Delete(2), Pass::Delete(1),
+    Pass::Zero, Pass::Decrement
+];
+
+/// Generates a string, recording each decision or, when replaying, following
+/// the recorded decisions and taking the simplest ones past their end.
+struct Derivation<'t, 'r> {
+    tree: &'t mut GrammarValueTree,
+    runner: Option<&'r mut TestRunner>,
+    position: usize,
+    uses: BTreeMap<String, usize>,
+}
+
+impl GrammarValueTree {
+    fn generate(&mut self, runner: Option<&mut TestRunner>) {
+        let strategy = self.strategy.clone();
+        let mut output = String::new();
+        let mut derivation = Derivation { tree: self, runner, position: 0, uses: BTreeMap::new() };
+        derivation.symbol(&strategy, &strategy.rule, 0, &mut output);
+        let used = derivation.position;
+        self.decisions.truncate(used);
+        self.current = output;
+    }
+
+    /// Returns the decisions the current pass proposes at the current index,
+    /// advancing to the next pass when this one is done, or `None` when all
+    /// passes are done.
+    fn candidate(&mut self) -> Option<Vec<u32>> {
+        loop {
+            let (index, mut decisions) = (self.index, self.decisions.clone());
+            let proposed = match PASSES.get(self.pass)? {
+                Pass::Delete(n) if index + n <= decisions.len() => {
+                    decisions.drain(index..index + n);
+                    true
+                }
+                Pass::Zero | Pass::Decrement if index < decisions.len() => {
+                    let decision = &mut decisions[index];
+                    let zero = matches!(PASSES[self.pass], Pass::Zero);
+                    *decision = if zero { 0 } else { decision.saturating_sub(1) };
+                    *decision != self.decisions[index]
+                }
+                _ => {
+                    self.pass += 1;
+                    self.index = 0;
+                    continue;
+                }
+            };
+
+            match proposed {
+                true => return Some(decisions),
+                false => self.index += 1,
+            }
+        }
+    }
+}
+
+impl Derivation<'_, '_> {
+    /// Returns the next decision, between `0` and `n - 1`.
+    fn decide(&mut self, n: u32) -> u32 {
+        let position = self.position;
+        self.position += 1;
+        if let Some(decision) = self.tree.decisions.get_mut(position) {
+            *decision %= n;
+            return *decision;
+        }
+
+        let decision = self.runner.as_mut().map_or(0, |r| r.rng().next_u32() % n);
+        self.tree.decisions.push(decision);
+        decision
+    }
+
+    fn symbol(&mut self, strategy: &GrammarStrategy, symbol: &str, depth: usize, out: &mut String) {
+        if let Some(pool) = self.tree.pools.get(symbol) {
+            let uses = self.uses.entry(symbol.into()).or_insert(0);
+            out.push_str(&pool[*uses % pool.len()]);
+            *uses += 1;
+            return;
+        }
+
+        let terms = &strategy.generator.productions[symbol];
+        self.sequence(strategy, terms, depth + 1, out);
+    }
+
+    fn sequence(&mut self, strategy: &GrammarStrategy, terms: &[Term], depth: usize, out: &mut String) {
+        for term in terms {
+            self.term(strategy, term, depth, out);
+        }
+    }
+
+    fn term(&mut self, strategy: &GrammarStrategy, term: &Term, depth: usize, out: &mut String) {
+        let generator = &strategy.generator;
+        let exhausted = depth >= generator.max_depth;
+        match term {
+            Term::Literal(literal) => out.push_str(literal),
+            Term::Symbol(symbol) => self.symbol(strategy, symbol, depth, out),
+            Term::Choice(alts) => {
+                let alt = match exhausted {
+                    false => self.decide(alts.len() as u32) as usize,
+                    true => (0..alts.len())
+                        .min_by_key(|&i| seq_depth(&alts[i], &strategy.depths, &generator.overrides))
+                        .unwrap_or(0),
+                };
+
+                self.sequence(strategy, &alts[alt], depth, out);
+            }
+            Term::Optional(alts) if !exhausted => {
+                let alt = self.decide(alts.len() as u32 + 1) as usize;
+                if alt != 0 {
+                    self.sequence(strategy, &alts[alt - 1], depth, out);
+                }
+            }
+            Term::Repeat(terms) if !exhausted => {
+                for _ in 0..self.decide(generator.max_repeat + 1) {
+                    self.sequence(strategy, terms, depth, out);
+                }
+            }
+            Term::Optional(_) | Term::Repeat(_) => {}
+        }
+    }
+}
+
+impl ValueTree for GrammarValueTree {
+    type Value = String;
+
+    fn current(&self) -> String {
+        self.current.clone()
+    }
+
+    fn simplify(&mut self) -> bool {
+        while let Some(candidate) = self.candidate() {
+            let last = (std::mem::replace(&mut self.decisions, candidate), self.current.clone());
+            self.generate(None);
+
+            // Only keep changes that make the decisions smaller, so that
+            // shrinking ends.
+            let smaller = |a: &[u32], b: &[u32]| (a.len(), a) < (b.len(), b);
+            if smaller(&self.decisions, &last.0) {
+                self.last = Some(last);
+                return true;
+            }
+
+            (self.decisions, self.current) = last;
+            self.index += 1;
+        }
+
+        false
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.last.take() {
+            Some((decisions, current)) => {
+                (self.decisions, self.current) = (decisions, current);
+                self.index += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn skip_spaces(input: &mut Text<'_>) {
+    let _ = take_while(input, |&c| c == ' ');
+}
+
+/// Parses a quoted literal, optionally prefixed with `b`, decoding escapes.
+#[parser(raw)]
+fn literal<'a>(input: &mut Text<'a>) -> Result<String, Text<'a>> {
+    let _ = eat(input, 'b');
+    let quote = match input.token() {
+        Some(quote @ ('\'' | '"')) => { eat_any(input)?; quote }
+        _ => return invalid(input, "a quoted literal"),
+    };
+
+    let mut literal = String::new();
+    loop {
+        match eat_any(input)? {
+            c if c == quote => return Ok(literal),
+            '\\' => match eat_any(input)? {
+                'n' => literal.push('\n'),
+                'r' => literal.push('\r'),
+                't' => literal.push('\t'),
+                '0' => literal.push('\0'),
+                'x' => {
+                    let hex = take_some_while(input, |c| c.is_ascii_hexdigit())?;
+                    match u8::from_str_radix(hex, 16) {
+                        Ok(byte) => literal.push(byte as char),
+                        Err(_) => return invalid(input, "a byte escape"),
+                    }
+                }
+                'u' => {
+                    eat(input, '{')?;
+                    let hex = take_some_while(input, |c| c.is_ascii_hexdigit())?;
+                    eat(input, '}')?;
+                    match u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) {
+                        Some(c) => literal.push(c),
+                        None => return invalid(input, "a unicode escape"),
+                    }
+                }
+                c => literal.push(c),
+            },
+            c => literal.push(c),
+        }
+    }
+}
+
+/// Parses a rule name or a call, like `take_while(..)`, verbatim.
+#[parser(raw)]
+fn symbol<'a>(input: &mut Text<'a>) -> Result<String, Text<'a>> {
+    let mut symbol: String = take_some_while(input, |&c| c.is_alphanumeric() || c == '_' || c == ':')?.into();
+    if eat(input, '(').is_ok() {
+        let (mut depth, mut quote, mut escaped) = (1, None, false);
+        let args = take_while(input, |&c| {
+            match (quote, c) {
+                (Some(_), _) if escaped => escaped = false,
+                (Some(_), '\\') => escaped = true,
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => depth -= 1,
+                (None, _) => {}
+            }
+
+            depth > 0
+        })?;
+
+        eat(input, ')')?;
+        symbol = format!("{}({})", symbol, args);
+    }
+
+    Ok(symbol)
+}
+
+/// Parses a special sequence, like `? c.is_ascii_digit() ?`, verbatim.
+#[parser(raw)]
+fn special<'a>(input: &mut Text<'a>) -> Result<String, Text<'a>> {
+    eat(input, '?')?;
+    let mut last = '?';
+    let description = take_while(input, |&c| !(last == ' ' && c == '?') && { last = c; true })?;
+    eat(input, '?')?;
+    Ok(format!("?{}?", description))
+}
+
+#[parser(raw)]
+fn term<'a>(input: &mut Text<'a>) -> Result<Term, Text<'a>> {
+    let term = match input.token() {
+        Some('(') => { eat_any(input)?; Term::Choice(alternatives(input)?) }
+        Some('[') => { eat_any(input)?; Term::Optional(alternatives(input)?) }
+        Some('{') => { eat_any(input)?; Term::Repeat(sequence(input)?) }
+        Some('\'' | '"') => Term::Literal(literal(input)?),
+        Some('b') if input.peek_slice(2, |s| *s == "b'" || *s == "b\"") => Term::Literal(literal(input)?),
+        Some('?') => return Ok(Term::Symbol(special(input)?)),
+        _ => return Ok(Term::Symbol(symbol(input)?)),
+    };
+
+    skip_spaces(input);
+    match term {
+        Term::Choice(_) => eat(input, ')')?,
+        Term::Optional(_) => eat(input, ']')?,
+        Term::Repeat(_) => eat(input, '}')?,
+        _ => return Ok(term),
+    };
+
+    Ok(term)
+}
+
+#[parser(raw)]
+fn sequence<'a>(input: &mut Text<'a>) -> Result<Vec<Term>, Text<'a>> {
+    let mut terms = vec![];
+    loop {
+        skip_spaces(input);
+        match input.token() {
+            None | Some('|' | ')' | ']' | '}' | ';') => return Ok(terms),
+            Some(_) => terms.push(term(input)?),
+        }
+    }
+}
+
+#[parser(raw)]
+fn alternatives<'a>(input: &mut Text<'a>) -> Result<Vec<Vec<Term>>, Text<'a>> {
+    let mut alts = vec![sequence(input)?];
+    while eat(input, '|').is_ok() {
+        alts.push(sequence(input)?);
+    }
+
+    Ok(alts)
+}
+
+#[parser(raw)]
+fn production<'a>(input: &mut Text<'a>) -> Result<(String, Vec<Term>), Text<'a>> {
+    skip_spaces(input);
+    let name = symbol(input)?;
+    skip_spaces(input);
+    eat(input, '=')?;
+    let terms = sequence(input)?;
+    eat(input, ';')?;
+    skip_spaces(input);
+    Ok((name, terms))
+}
+
+#[parser(raw)]
+fn productions<'a>(input: &mut Text<'a>) -> Result<Vec<(String, Vec<Term>)>, Text<'a>> {
+    let mut productions = vec![];
+    while input.token().is_some() {
+        if eat(input, '\n').is_err() {
+            productions.push(production(input)?);
+        }
+    }
+
+    Ok(productions)
+}
@@ -0,0 +1,112 @@
+//! Records which alternative of a [`switch!`] produced each value.
+//!
+//! When debugging an ambiguous grammar, it's often unclear which path through
+//! a `switch!` produced a surprising result. While recording is enabled via
+//! [`record()`], every `switch!` that selects a case appends a [`Choice`] to a
+//! thread-local log identifying the enclosing parser, the selected case, and
+//! the input consumed by the enclosing parser up to that point.
+//!
+//! Recording is off by default and costs a single thread-local check per
+//! selected case when disabled. Recording requires the `std` feature; without
+//! it, no choices are ever recorded.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::{parser, parse, switch};
+//! use pear::parsers::*;
+//!
+//! #[parser]
+//! fn sign<'a>(input: &mut Text<'a>) -> Result<i8, Text<'a>> {
+//!     switch! {
+//!         eat('+') => 1,
+//!         eat('-') => -1,
+//!         _ => 1
+//!     }
+//! }
+//!
+//! let (result, choices) = pear::provenance::record(|| {
+//!     parse!(sign: &mut Text::from("-"))
+//! });
+//!
+//! assert_eq!(result.unwrap(), -1);
+//! assert_eq!(choices[0].parser, "sign");
+//! assert_eq!(choices[0].case, 1);
+//! assert_eq!(choices[0].pattern, "eat('-')");
+//! ```
+//!
+//! [`switch!`]: crate::macros#switch
+
+#[cfg(feature = "std")] use std::cell::RefCell;
+use alloc::string::String;
+
+use crate::input::{Show, ParserInfo};
+
+/// A record of a single case selection by a `switch!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Choice {
+    /// The name of the parser containing the `switch!`.
+    pub parser: &'static str,
+    /// The zero-based index of the selected case.
+    pub case: usize,
+    /// The source text of the pattern that matched, `_` for a wildcard.
+    pub pattern: &'static str,
+    /// The rendered context of the enclosing parser when the case was chosen.
+    pub context: Option<String>,
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static LOG: RefCell<Option<Vec<Choice>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with provenance recording enabled, returning the result of `f`
+/// and the choices made, in order, while it ran.
+///
+/// Calls may be nested; an inner call receives only the choices made while
+/// it was running.
+#[cfg(feature = "std")]
+pub fn record<R, F: FnOnce() -> R>(f: F) -> (R, Vec<Choice>) {
+    let outer = LOG.with(|log| log.borrow_mut().replace(vec![]));
+    let result = f();
+    let choices = LOG.with(|log| std::mem::replace(&mut *log.borrow_mut(), outer));
+    (result, choices.unwrap_or_default())
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn is_recording() -> bool {
+    #[cfg(feature = "std")]
+    return LOG.with(|log| log.borrow().is_some());
+
+    #[cfg(not(feature = "std"))]
+    return false;
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn push(
+    parser: &ParserInfo,
+    case: usize,
+    pattern: &'static str,
+    context: Option<&dyn Show>
+) {
+    let choice = Choice {
+        parser: parser.name,
+        case,
+        pattern,
+        context: context.map(|c| c.to_string()),
+    };
+
+    LOG.with(|log| {
+        if let Some(ref mut choices) = *log.borrow_mut() {
+            choices.push(choice);
+        }
+    });
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[inline(always)]
+pub fn push(_: &ParserInfo, _: usize, _: &'static str, _: Option<&dyn Show>) { }
@@ -0,0 +1,243 @@
+//! Support for left-recursive parsers.
+//!
+//! A parser that calls itself before consuming any input, such as `expr` in
+//! `expr = expr '-' number | number`, would ordinarily recurse forever. Marking
+//! such a parser `#[parser(left_recursive)]` makes it terminate by growing a
+//! _seed_, in the style of Warth et al.'s "Packrat Parsers Can Support Left
+//! Recursion":
+//!
+//!   1. The seed starts out as a failure, memoized for the parser at the
+//!      current position.
+//!   2. The parser's body is run. A left-recursive call at the same position
+//!      returns the memoized seed instead of recursing, so the first run
+//!      parses via a non-left-recursive alternative.
+//!   3. If the body succeeds and consumes more input than the seed, its result
+//!      becomes the new seed and the body is run again. Otherwise, the seed is
+//!      final and is returned.
+//!
+//! The result is the longest parse, grouped to the left:
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::{macros::*, parsers::*};
+//!
+//! #[parser]
+//! fn number<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+//!     take_some_while(|c: &char| c.is_ascii_digit())?.parse::<i64>().unwrap()
+//! }
+//!
+//! #[parser(left_recursive)]
+//! fn expr<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+//!     switch! {
+//!         lhs@expr() => { eat('-')?; lhs - number()? },
+//!         _ => number()?
+//!     }
+//! }
+//!
+//! assert_eq!(parse!(expr: &mut Text::from("10-3-2")).unwrap(), 5);
+//! ```
+//!
+//! Grown results are memoized: while a left-recursive parser is running on an
+//! input, invoking any left-recursive parser again at a position where it has
+//! already grown a seed reuses the result instead of growing it anew, as
+//! happens when an alternative that begins with the parser fails and the next
+//! alternative begins with it too. Only results that didn't depend on a seed
+//! still being grown are memoized, and the memo is discarded once the
+//! outermost left-recursive parser on the input returns. As with
+//! [`memo()`](crate::incremental::memo()), a reused result skips the parser's
+//! body, along with any effect the body has on the input.
+//!
+//! The input must implement [`Rewind`] and its markers must be ordered by
+//! position, as those of [`Text`](crate::input::Text) are. The parser's output
+//! must implement `Clone`, its output and the input's markers must be
+//! `'static`, and the parser may not be generic over types. This module
+//! requires the `std` feature.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::error::{ParseError, Expected};
+use crate::input::{Rewind, ParserInfo, Result};
+
+/// A seed being grown for a parser at the position `start`.
+struct Seed<O, M> {
+    start: M,
+    value: Option<(O, M)>,
+}
+
+/// A seed on the stack: the parser's key, the input's address, the
+/// `Seed<O, M>`, and whether a seed deeper in the stack was reused while
+/// growing it, making its result provisional.
+struct Frame {
+    key: usize,
+    addr: usize,
+    seed: Box<dyn Any>,
+    tainted: bool,
+}
+
+thread_local! {
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+
+    /// Grown results keyed by the parser's key and the input's address, each
+    /// a `Vec<(M, O, M)>` of start, output, and end, sorted by start.
+    static GROWN: RefCell<HashMap<(usize, usize), Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+/// Pops the topmost seed, even if the parser panics, discarding the memoized
+/// results for its input if no other seed is being grown on it.
+struct PopGuard(usize);
+
+impl Drop for PopGuard {
+    fn drop(&mut self) {
+        let addr = self.0;
+        let done = FRAMES.with(|frames| {
+            let mut frames = frames.borrow_mut();
+            frames.pop();
+            frames.iter().all(|frame| frame.addr != addr)
+        });
+
+        if done {
+            GROWN.with(|grown| grown.borrow_mut().retain(|&(_, a), _| a != addr));
+        }
+    }
+}
+
+/// Returns the result of the parser `key` at `start` on the input at `addr`:
+/// a memoized result, or the value of the seed being grown, if there is
+/// either. Reusing a seed marks the seeds above it as provisional.
+fn recall<O, M>(key: usize, addr: usize, start: &M) -> Option<Option<(O, M)>>
+    where O: Clone + 'static, M: Copy + PartialOrd + 'static
+{
+    let memoized = GROWN.with(|grown| {
+        let grown = grown.borrow();
+        let results = grown.get(&(key, addr))?.downcast_ref::<Vec<(M, O, M)>>()?;
+        let i = results.binary_search_by(|r| cmp(&r.0, start)).ok()?;
+        let (_, output, end) = &results[i];
+        Some(Some((output.clone(), *end)))
+    });
+
+    memoized.or_else(|| FRAMES.with(|frames| {
+        let mut frames = frames.borrow_mut();
+        let (i, value) = frames.iter().enumerate().rev()
+            .filter(|(_, frame)| frame.key == key && frame.addr == addr)
+            .filter_map(|(i, frame)| Some((i, frame.seed.downcast_ref::<Seed<O, M>>()?)))
+            .find(|(_, seed)| seed.start == *start)
+            .map(|(i, seed)| (i, seed.value.clone()))?;
+
+        frames[i + 1..].iter_mut().for_each(|frame| frame.tainted = true);
+        Some(value)
+    }))
+}
+
+/// Memoizes `output`, the final result of the parser `key` between `start`
+/// and `end` on the input at `addr`.
+fn memoize<O, M>(key: usize, addr: usize, start: M, output: O, end: M)
+    where O: 'static, M: PartialOrd + 'static
+{
+    GROWN.with(|grown| {
+        let mut grown = grown.borrow_mut();
+        let results = grown.entry((key, addr))
+            .or_insert_with(|| Box::new(Vec::<(M, O, M)>::new()));
+
+        if let Some(results) = results.downcast_mut::<Vec<(M, O, M)>>() {
+            if let Err(i) = results.binary_search_by(|r| cmp(&r.0, &start)) {
+                results.insert(i, (start, output, end));
+            }
+        }
+    })
+}
+
+/// Calls `f` with the seed at `index` in the stack and whether it's
+/// provisional.
+fn with_seed<O: 'static, M: 'static, R>(index: usize, f: impl FnOnce(&mut Seed<O, M>, bool) -> R) -> R {
+    FRAMES.with(|frames| {
+        let frame = &mut frames.borrow_mut()[index];
+        let seed = frame.seed.downcast_mut().expect("a seed has its parser's types");
+        f(seed, frame.tainted)
+    })
+}
+
+/// Compares markers, which are ordered by position.
+fn cmp<M: PartialOrd>(a: &M, b: &M) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Less)
+}
+
+/// Runs `body`, a left-recursive parser, by growing a seed. `key` uniquely
+/// identifies the parser and `start` is a marker to the current position.
+///
+/// Generated by `#[parser(left_recursive)]`; not intended to be called
+/// directly.
+#[doc(hidden)]
+pub fn grow_seed<I, O, F>(
+    key: &'static u8,
+    input: &mut I,
+    start: I::Marker,
+    mut body: F
+) -> Result<O, I>
+    where I: Rewind, I::Marker: PartialOrd + 'static, O: Clone + 'static,
+          F: FnMut(&mut I) -> Result<O, I>
+{
+    let (key, addr) = (key as *const u8 as usize, input as *const I as usize);
+    if let Some(value) = recall::<O, I::Marker>(key, addr, &start) {
+        return match value {
+            Some((output, end)) => {
+                input.rewind_to(&end);
+                Ok(output)
+            }
+            None => Err(ParseError::new(Expected::Token(None, input.token()))),
+        };
+    }
+
+    let index = FRAMES.with(|frames| {
+        let mut frames = frames.borrow_mut();
+        let seed = Box::new(Seed::<O, I::Marker> { start, value: None });
+        frames.push(Frame { key, addr, seed, tainted: false });
+        frames.len() - 1
+    });
+
+    let _guard = PopGuard(addr);
+
+    let info = ParserInfo::new("left_recursive", true);
+    loop {
+        input.rewind_to(&start);
+        let error = match body(input) {
+            Ok(output) => {
+                let end = input.mark(&info);
+                input.unmark(&info, true, end);
+
+                let grew = with_seed(index, |seed: &mut Seed<O, I::Marker>, _| match seed.value {
+                    Some((_, last)) if end <= last => false,
+                    _ => {
+                        seed.value = Some((output, end));
+                        true
+                    }
+                });
+
+                match grew {
+                    true => continue,
+                    false => None,
+                }
+            }
+            Err(e) => Some(e),
+        };
+
+        let take = |seed: &mut Seed<O, I::Marker>, tainted| (seed.value.take(), tainted);
+        let (value, tainted) = with_seed(index, take);
+        return match (value, error) {
+            (Some((output, end)), _) => {
+                if !tainted {
+                    memoize(key, addr, start, output.clone(), end);
+                }
+
+                input.rewind_to(&end);
+                Ok(output)
+            }
+            (None, Some(error)) => Err(error),
+            (None, None) => unreachable!("a seed is only final after an iteration"),
+        };
+    }
+}
@@ -0,0 +1,202 @@
+//! Deterministic replay of failed parses of text.
+//!
+//! A parse that fails rarely, on input that isn't at hand, is hard to debug. A
+//! [`Replay`] snapshots what's needed to reproduce a failed parse of [`Text`]:
+//! the input from where the outermost failing parser began, the offset at
+//! which the parse failed, the stack of parsers that were running, and the
+//! error. Its `Display` is a compact text artifact, to be logged or attached
+//! to a bug report, which is read back with [`Replay::parse()`].
+//! [`Replay::run()`] then re-runs a parser on the snapshotted input while
+//! [capturing](crate::trace::capture()) its activity.
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::{parser, parse};
+//! use pear::parsers::*;
+//! use pear::replay::Replay;
+//!
+//! #[parser]
+//! fn digits<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+//!     take_some_while(|c: &char| c.is_ascii_digit())?
+//! }
+//!
+//! #[parser]
+//! fn pair<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+//!     let a = digits()?;
+//!     eat(',')?;
+//!     (a, digits()?)
+//! }
+//!
+//! // In production: snapshot the failure.
+//! let source = "12,x4";
+//! let error = parse!(pair: &mut Text::from(source)).unwrap_err();
+//! let artifact = Replay::new(source, &error).to_string();
+//!
+//! // Later: read the artifact back and re-run the parse.
+//! let replay = Replay::parse(&artifact).unwrap();
+//! assert_eq!(replay.parser, "pair");
+//! assert_eq!(replay.offset, 3);
+//!
+//! let (result, trace) = replay.run(|input| pair(input));
+//! assert!(result.is_err());
+//! assert!(trace.to_string().contains("< digits ✗ (1:4 \"\")"));
+//! ```
+
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::input::{Input, Text, Span, Result};
+use crate::macros::parser;
+use crate::parsers::{eat, eat_slice, take_while, invalid};
+#[cfg(feature = "instrument")] use crate::trace::{self, Trace};
+use crate::FromPearStr;
+
+/// The first line of a replay artifact, identifying its format.
+const HEADER: &str = "pear-replay 1";
+
+/// A snapshot of a failed parse of text. See the [module
+/// documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    /// The name of the outermost parser that failed.
+    pub parser: String,
+    /// The offset into the original input at which the outermost parser that
+    /// failed began, and at which `window` begins.
+    pub start: usize,
+    /// The offset into the original input at which the parse failed.
+    pub offset: usize,
+    /// The parsers that were running when the parse failed, outermost first,
+    /// with the offsets into the original input at which they began.
+    pub stack: Vec<(String, usize)>,
+    /// The error, as displayed.
+    pub error: String,
+    /// The input from `start`.
+    pub window: String,
+}
+
+impl Replay {
+    /// Snapshots the failed parse of `source` that failed with `error`.
+    /// `source` must be the text that was parsed.
+    ///
+    /// The window extends to the end of `source`. Use
+    /// [`with_lookahead()`](Self::with_lookahead()) to shorten it.
+    pub fn new<E: fmt::Display>(source: &str, error: &ParseError<Span<'_>, E>) -> Replay {
+        let contexts = || error.contexts.iter().filter_map(|c| c.context.as_ref());
+        let start = contexts().next_back().map_or(0, |span| span.start.2);
+        let offset = contexts().next().map_or(start, |span| span.end.2);
+        let stack: Vec<_> = error.contexts.iter().rev()
+            .map(|c| (c.parser.name.into(), c.context.map_or(start, |span| span.start.2)))
+            .collect();
+
+        Replay {
+            parser: stack.first().map(|(name, _)| String::clone(name)).unwrap_or_default(),
+            start,
+            offset,
+            stack,
+            error: error.to_string(),
+            window: source.get(start..).unwrap_or_default().into(),
+        }
+    }
+
+    /// Truncates the window to end `n` bytes past the offset of the failure,
+    /// or at the next character boundary after. A parser that looks further
+    /// ahead than `n` bytes may fail differently on the truncated window.
+    pub fn with_lookahead(mut self, n: usize) -> Self {
+        let mut end = (self.offset - self.start).saturating_add(n).min(self.window.len());
+        while !self.window.is_char_boundary(end) {
+            end += 1;
+        }
+
+        self.window.truncate(end);
+        self
+    }
+
+    /// Reads a replay from an artifact written by its `Display`.
+    pub fn parse(artifact: &str) -> Result<Replay, Text<'_>> {
+        crate::parse_str(artifact)
+    }
+
+    /// Runs `parser` on the window while capturing its activity, returning its
+    /// result and the [`Trace`] of the parsers it ran. Contexts in the result
+    /// and trace are relative to the start of the window.
+    ///
+    /// This method requires the `instrument` feature.
+    #[cfg(feature = "instrument")]
+    pub fn run<'r, O, E, P>(&'r self, parser: P) -> (Result<O, Text<'r>, E>, Trace)
+        where P: FnOnce(&mut Text<'r>) -> Result<O, Text<'r>, E>
+    {
+        trace::capture(|| parser(&mut Text::from(self.window.as_str())))
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "parser {}", self.parser)?;
+        writeln!(f, "start {}", self.start)?;
+        writeln!(f, "offset {}", self.offset)?;
+        write!(f, "stack")?;
+        for (parser, start) in &self.stack {
+            write!(f, " {}@{}", parser, start)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "error {}\n{}", self.error.len(), self.error)?;
+        writeln!(f, "window {}\n{}", self.window.len(), self.window)
+    }
+}
+
+fn is_name(c: &char) -> bool {
+    c.is_alphanumeric() || *c == '_'
+}
+
+/// Parses `key value\n`, returning `value`.
+#[parser(raw)]
+fn field<'a, T: FromPearStr>(input: &mut Text<'a>, key: &str) -> Result<T, Text<'a>> {
+    eat_slice(input, key)?;
+    eat(input, ' ')?;
+    let value = T::from_pear_str(input)?;
+    eat(input, '\n')?;
+    Ok(value)
+}
+
+/// Parses `key length\n` followed by `length` bytes and a newline.
+#[parser(raw)]
+fn text<'a>(input: &mut Text<'a>, key: &str) -> Result<&'a str, Text<'a>> {
+    let len: usize = field(input, key)?;
+    let text = match input.eat_slice(len, |_| true) {
+        Some(text) => text,
+        None => return invalid(input, "text of the given length"),
+    };
+
+    eat(input, '\n')?;
+    Ok(text)
+}
+
+impl FromPearStr for Replay {
+    #[parser(raw)]
+    fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>> {
+        eat_slice(input, HEADER)?;
+        eat(input, '\n')?;
+        eat_slice(input, "parser ")?;
+        let parser = take_while(input, is_name)?.into();
+        eat(input, '\n')?;
+        let start = field(input, "start")?;
+        let offset = field(input, "offset")?;
+
+        let mut stack = Vec::new();
+        eat_slice(input, "stack")?;
+        while eat(input, ' ').is_ok() {
+            let name = take_while(input, is_name)?;
+            eat(input, '@')?;
+            stack.push((name.into(), usize::from_pear_str(input)?));
+        }
+
+        eat(input, '\n')?;
+        let error = text(input, "error")?.into();
+        let window = text(input, "window")?.into();
+        Ok(Replay { parser, start, offset, stack, error, window })
+    }
+}
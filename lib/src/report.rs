@@ -0,0 +1,254 @@
+//! Rich, source-annotated error reports via `miette` and `ariadne`.
+//!
+//! A [`Report`] is an owned snapshot of a [`ParseError`] together with the
+//! source text it was produced from. The parser context stack is rendered as
+//! labeled spans: the primary label points to where parsing stopped and
+//! carries the error message, while each enclosing non-raw parser contributes
+//! a secondary label spanning the input it consumed. The error's help
+//! messages and notes are carried over, and each of its suggestions becomes a
+//! label spanning the input it replaces.
+//!
+//! With the `miette` feature, `Report` implements [`miette::Diagnostic`] and
+//! can be returned directly from a `miette::Result`. With the `ariadne`
+//! feature, [`Report::to_ariadne()`] converts it into an [`ariadne::Report`].
+//!
+//! Contexts must implement [`SourceRange`] to contribute labels. It is
+//! implemented for the contexts of [`Text`], whose ranges are of bytes, and
+//! of [`IterInput`], whose ranges are of item indices. Labels index the
+//! source text by byte, so an `IterInput`'s labels are only accurate if each
+//! of its items is one byte of the source, as are the `char`s of ASCII text.
+//!
+//! [`Text`]: crate::input::Text
+//! [`IterInput`]: crate::input::IterInput
+
+use core::ops::Range;
+
+use crate::error::{ParseError, ErrorCode, Expected, Suggestion};
+use crate::input::{Show, Span};
+
+/// A context which corresponds to a range of the source.
+pub trait SourceRange {
+    /// Returns the range of the source covered by this context, if known, in
+    /// the units of the input's positions: bytes for [`Text`], or item
+    /// indices for [`IterInput`].
+    ///
+    /// [`Text`]: crate::input::Text
+    /// [`IterInput`]: crate::input::IterInput
+    fn source_range(&self) -> Option<Range<usize>>;
+}
+
+impl SourceRange for Span<'_> {
+    fn source_range(&self) -> Option<Range<usize>> {
+        Some(self.start.2..self.end.2)
+    }
+}
+
+impl SourceRange for Range<usize> {
+    fn source_range(&self) -> Option<Range<usize>> {
+        Some(self.clone())
+    }
+}
+
+impl<C: SourceRange> Suggestion<C> {
+    /// Returns `source` with the suggestion applied, or `None` if the context
+    /// has no range or its range is out of bounds of `source`.
+    ///
+    /// ```rust
+    /// use pear::input::{Text, Span, ParseError};
+    /// use pear::error::Expected;
+    ///
+    /// let source = "colour = red";
+    /// let span = Span { start: (1, 1, 0), end: (1, 7, 6), snippet: None };
+    /// let error = ParseError::<Text<'_>>::new(Expected::Other("unknown key".into()))
+    ///     .with_suggestion(span, "color");
+    ///
+    /// let suggestion = error.suggestions().next().unwrap();
+    /// assert_eq!(suggestion.apply(source).unwrap(), "color = red");
+    /// ```
+    pub fn apply(&self, source: &str) -> Option<String> {
+        let range = self.context.source_range()?;
+        let (before, after) = (source.get(..range.start)?, source.get(range.end..)?);
+        Some(format!("{}{}{}", before, self.replacement, after))
+    }
+}
+
+/// An owned, source-annotated parse error report. See the [module
+/// docs](self) for details.
+#[derive(Debug, Clone)]
+pub struct Report {
+    message: String,
+    code: ErrorCode,
+    location: Range<usize>,
+    labels: Vec<(Range<usize>, String)>,
+    help: Vec<String>,
+    notes: Vec<String>,
+    source: String,
+}
+
+impl Report {
+    /// Creates a report for `error`, which was produced by parsing `source`.
+    pub fn new<C, T, S, I>(error: &ParseError<C, Expected<T, S>>, source: I) -> Report
+        where C: SourceRange, T: Show, S: Show, I: Into<String>
+    {
+        let source = source.into();
+        let ranges = error.contexts.iter()
+            .filter_map(|c| Some((c.context.as_ref()?.source_range()?, c.parser)))
+            .map(|(range, parser)| {
+                let end = range.end.min(source.len());
+                (range.start.min(end)..end, parser)
+            });
+
+        let mut location = 0..0;
+        let mut labels = vec![];
+        for (i, (range, parser)) in ranges.enumerate() {
+            if i == 0 {
+                location = range.end..range.end;
+            }
+
+            if !parser.raw && !range.is_empty() {
+                labels.push((range, format!("while parsing {}", parser.name)));
+            }
+        }
+
+        for suggestion in error.suggestions() {
+            if let Some(range) = suggestion.context.source_range() {
+                let end = range.end.min(source.len());
+                let label = format!("replace with `{}`", suggestion.replacement);
+                labels.push((range.start.min(end)..end, label));
+            }
+        }
+
+        let help = error.help().map(String::from).collect();
+        let notes = error.notes().map(String::from).collect();
+        let message = error.error.to_string();
+        Report { message, code: error.code(), location, labels, help, notes, source }
+    }
+
+    /// The error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The error code.
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// The byte range in the source where parsing stopped.
+    pub fn location(&self) -> Range<usize> {
+        self.location.clone()
+    }
+
+    /// The secondary labels: the range consumed by each enclosing parser, from
+    /// innermost to outermost, and a description, followed by the range and
+    /// replacement of each suggestion.
+    pub fn labels(&self) -> &[(Range<usize>, String)] {
+        &self.labels
+    }
+
+    /// The help messages.
+    pub fn help(&self) -> &[String] {
+        &self.help
+    }
+
+    /// The notes.
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// The source text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl<C: SourceRange, T: Show, S: Show> ParseError<C, Expected<T, S>> {
+    /// Creates a [`Report`] for this error, which was produced by parsing
+    /// `source`.
+    pub fn report<I: Into<String>>(&self, source: I) -> Report {
+        Report::new(self, source)
+    }
+}
+
+impl core::fmt::Display for Report {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Report { }
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for Report {
+    fn code<'a>(&'a self) -> Option<Box<dyn core::fmt::Display + 'a>> {
+        Some(Box::new(self.code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn core::fmt::Display + 'a>> {
+        let notes = self.notes.iter().map(|note| format!("note: {}", note));
+        let lines: Vec<_> = self.help.iter().cloned().chain(notes).collect();
+        match lines.is_empty() {
+            true => None,
+            false => Some(Box::new(lines.join("\n"))),
+        }
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let primary = miette::LabeledSpan::new_primary_with_span(
+            Some(self.message.clone()),
+            self.location.clone(),
+        );
+
+        let secondary = self.labels.iter().map(|(range, label)| {
+            miette::LabeledSpan::new_with_span(Some(label.clone()), range.clone())
+        });
+
+        Some(Box::new(Some(primary).into_iter().chain(secondary)))
+    }
+}
+
+#[cfg(feature = "ariadne")]
+impl Report {
+    /// Converts this report into an [`ariadne::Report`]. Print it with the
+    /// source, for instance, with [`Report::eprint()`] or
+    /// `report.to_ariadne().eprint(ariadne::Source::from(report.source()))`.
+    pub fn to_ariadne(&self) -> ariadne::Report<'static, Range<usize>> {
+        use ariadne::{ReportKind, Label};
+
+        let primary = Label::new(self.location.clone())
+            .with_message(&self.message)
+            .with_order(-1);
+
+        let secondary = self.labels.iter().enumerate().map(|(i, (range, label))| {
+            Label::new(range.clone()).with_message(label).with_order(i as i32)
+        });
+
+        // Report ranges are of bytes; ariadne's default is of `char`s.
+        let config = ariadne::Config::default().with_index_type(ariadne::IndexType::Byte);
+        let mut report = ariadne::Report::build(ReportKind::Error, self.location.clone())
+            .with_config(config)
+            .with_code(self.code)
+            .with_message(&self.message)
+            .with_label(primary)
+            .with_labels(secondary);
+
+        for help in &self.help {
+            report = report.with_help(help);
+        }
+
+        for note in &self.notes {
+            report = report.with_note(note);
+        }
+
+        report.finish()
+    }
+
+    /// Prints this report to `stderr` using `ariadne`.
+    pub fn eprint(&self) -> std::io::Result<()> {
+        self.to_ariadne().eprint(ariadne::Source::from(&self.source))
+    }
+}
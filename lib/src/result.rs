@@ -1,4 +1,8 @@
-use crate::error::ParseError;
+use alloc::string::String;
+
+use crate::error::{ParseError, Error, Label};
+use crate::input::{Input, Rewind};
+use crate::combinators::merge;
 
 /// An alias to a Result where:
 ///
@@ -7,7 +11,92 @@ use crate::error::ParseError;
 ///
 /// For a `Result` that is parameterized only by the input type, see
 /// [`input::Result`](crate::input::Result).
-pub type Result<T, C, E> = std::result::Result<T, ParseError<C, E>>;
+pub type Result<T, C, E> = core::result::Result<T, ParseError<C, E>>;
+
+/// Extension methods on parser results, so that sequencing and alternation
+/// can be written fluently in raw parsers and closures, where `switch!` and
+/// `?` rewriting aren't available.
+///
+/// ```rust
+/// use pear::input::{Text, Input, ParserInfo, Result};
+/// use pear::result::ResultExt;
+/// use pear::macros::parse_marker;
+/// use pear::parsers::*;
+///
+/// fn boolean<'a>(input: &mut Text<'a>) -> Result<bool, Text<'a>> {
+///     let start = parse_marker!(input);
+///     let result = eat_slice(input, "true").map(|_| true)
+///         .or_rewind(input, &start, |i| eat_slice(i, "false").map(|_| false))
+///         .expected("a boolean");
+///
+///     input.unmark(&ParserInfo::new("parse_marker", true), result.is_ok(), start);
+///     result
+/// }
+///
+/// fn negation<'a>(input: &mut Text<'a>) -> Result<bool, Text<'a>> {
+///     eat(input, '!').and_then_parse(input, |i, _| boolean(i).map(|b| !b))
+/// }
+///
+/// assert_eq!(boolean(&mut Text::from("false")).unwrap(), false);
+/// assert_eq!(negation(&mut Text::from("!true")).unwrap(), false);
+///
+/// let error = boolean(&mut Text::from("yes")).unwrap_err();
+/// assert!(error.to_string().starts_with("expected a boolean"));
+/// ```
+pub trait ResultExt<T, C, E>: Sized {
+    /// If `self` is an error that isn't committed, runs `p` instead. If `p`
+    /// fails too, returns both errors merged, as
+    /// [`alt()`](crate::combinators::alt()) does. Like `switch!`, doesn't
+    /// rewind: the failed parser should have consumed nothing.
+    fn or_parse<I, P>(self, input: &mut I, p: P) -> Result<T, C, E>
+        where I: Input<Context = C>, E: Error<I>, P: FnOnce(&mut I) -> Result<T, C, E>;
+
+    /// Like [`or_parse()`](ResultExt::or_parse()), but rewinds `input` to
+    /// `marker`, where the failed parser started, before running `p`.
+    fn or_rewind<I, P>(self, input: &mut I, marker: &I::Marker, p: P) -> Result<T, C, E>
+        where I: Rewind<Context = C>, E: Error<I>, P: FnOnce(&mut I) -> Result<T, C, E>;
+
+    /// If `self` is `Ok`, runs `p` with its output, returning the result.
+    fn and_then_parse<I, U, P>(self, input: &mut I, p: P) -> Result<U, C, E>
+        where P: FnOnce(&mut I, T) -> Result<U, C, E>;
+
+    /// Replaces what the error, if any, expected with `label`, as
+    /// [`label()`](crate::combinators::label()) does.
+    fn expected<L: Into<String>>(self, label: L) -> Self where E: Label;
+}
+
+impl<T, C, E> ResultExt<T, C, E> for Result<T, C, E> {
+    fn or_parse<I, P>(self, input: &mut I, p: P) -> Result<T, C, E>
+        where I: Input<Context = C>, E: Error<I>, P: FnOnce(&mut I) -> Result<T, C, E>
+    {
+        match self {
+            Err(e) if !e.committed => p(input).map_err(|other| merge(e, other)),
+            result => result,
+        }
+    }
+
+    fn or_rewind<I, P>(self, input: &mut I, marker: &I::Marker, p: P) -> Result<T, C, E>
+        where I: Rewind<Context = C>, E: Error<I>, P: FnOnce(&mut I) -> Result<T, C, E>
+    {
+        match self {
+            Err(e) if !e.committed => {
+                input.rewind_to(marker);
+                p(input).map_err(|other| merge(e, other))
+            }
+            result => result,
+        }
+    }
+
+    fn and_then_parse<I, U, P>(self, input: &mut I, p: P) -> Result<U, C, E>
+        where P: FnOnce(&mut I, T) -> Result<U, C, E>
+    {
+        self.and_then(|output| p(input, output))
+    }
+
+    fn expected<L: Into<String>>(self, label: L) -> Self where E: Label {
+        self.map_err(|e| ParseError { error: e.error.label(label.into()), ..e })
+    }
+}
 
 #[doc(hidden)]
 pub trait AsResult<T, C, E> {
@@ -0,0 +1,249 @@
+//! Deserialization of `serde` types driven by Pear parsers.
+//!
+//! A Pear grammar for a custom format can back `#[derive(Deserialize)]` types
+//! by parsing into the self-describing [`Value`] tree. A [`ParserDeserializer`]
+//! runs such a parser and then feeds the resulting `Value` to the type's
+//! `Deserialize` implementation:
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::{Text, Result};
+//! use pear::macros::parser;
+//! use pear::parsers::*;
+//! use pear::combinators::*;
+//! use pear::serde::{Value, from_input};
+//!
+//! // A tiny format: `key=value` pairs separated by `;`.
+//! #[parser]
+//! fn pair<'a>(input: &mut Text<'a>) -> Result<(Value, Value), Text<'a>> {
+//!     let key = take_some_while(|c: &char| c.is_alphabetic())?;
+//!     eat('=')?;
+//!     let value = take_some_while(|c: &char| c.is_alphanumeric())?;
+//!     let value = match value.parse::<u64>() {
+//!         Ok(n) => Value::U64(n),
+//!         Err(_) => Value::String(value.into()),
+//!     };
+//!
+//!     (Value::String(key.into()), value)
+//! }
+//!
+//! #[parser]
+//! fn pairs<'a>(input: &mut Text<'a>) -> Result<Value, Text<'a>> {
+//!     let pairs: Vec<_> = series(pair, ';')?;
+//!     Value::Map(pairs)
+//! }
+//!
+//! #[derive(Debug, PartialEq, serde::Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     port: u16,
+//!     debug: Option<u8>,
+//! }
+//!
+//! let config: Config = from_input(&mut Text::from("name=web;port=80"), pairs).unwrap();
+//! assert_eq!(config, Config { name: "web".into(), port: 80, debug: None });
+//! ```
+//!
+//! This module requires the `serde` feature.
+
+use core::fmt;
+
+use ::serde::de::{self, Deserializer, Visitor, IntoDeserializer};
+use ::serde::de::value::{SeqDeserializer, MapDeserializer, MapAccessDeserializer};
+
+use crate::error::ParseError;
+use crate::input::{Input, Show};
+use crate::parsers::eof;
+
+/// A self-describing value produced by a parser for deserialization.
+///
+/// Integers deserialize into any integer type they fit in. A `String`
+/// deserializes into a unit enum variant of the same name, while a `Map` with
+/// a single entry deserializes into a non-unit variant named by its key. Any
+/// value other than `Option(None)` and `Unit` deserializes into `Some` when an
+/// `Option` is expected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// The unit value, `()`.
+    Unit,
+    /// A boolean.
+    Bool(bool),
+    /// A signed integer.
+    I64(i64),
+    /// An unsigned integer.
+    U64(u64),
+    /// A floating point number.
+    F64(f64),
+    /// A character.
+    Char(char),
+    /// A string.
+    String(String),
+    /// An optional value.
+    Option(Option<Box<Value>>),
+    /// A sequence of values.
+    Seq(Vec<Value>),
+    /// A sequence of key-value pairs.
+    Map(Vec<(Value, Value)>),
+}
+
+/// An error that occurred while parsing or deserializing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+}
+
+impl Error {
+    /// The error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error { }
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error { message: message.to_string() }
+    }
+}
+
+impl<C: Show, E: fmt::Display> From<ParseError<C, E>> for Error {
+    fn from(error: ParseError<C, E>) -> Self {
+        Error { message: error.to_string() }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Unit => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::I64(v) => visitor.visit_i64(v),
+            Value::U64(v) => visitor.visit_u64(v),
+            Value::F64(v) => visitor.visit_f64(v),
+            Value::Char(v) => visitor.visit_char(v),
+            Value::String(v) => visitor.visit_string(v),
+            Value::Option(None) => visitor.visit_none(),
+            Value::Option(Some(v)) => visitor.visit_some(*v),
+            Value::Seq(v) => visitor.visit_seq(SeqDeserializer::new(v.into_iter())),
+            Value::Map(v) => visitor.visit_map(MapDeserializer::new(v.into_iter())),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Unit | Value::Option(None) => visitor.visit_none(),
+            Value::Option(Some(v)) => visitor.visit_some(*v),
+            v => visitor.visit_some(v),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Error> {
+        match self {
+            Value::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Value::Map(entries) if entries.len() == 1 => {
+                let map = MapDeserializer::new(entries.into_iter());
+                visitor.visit_enum(MapAccessDeserializer::new(map))
+            }
+            v => Err(de::Error::custom(format_args!("expected an enum, found {:?}", v))),
+        }
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// A `serde::Deserializer` that runs a parser producing a [`Value`] over an
+/// input, requiring the parser to consume the entire input, and deserializes
+/// from the result.
+pub struct ParserDeserializer<'i, I, P> {
+    input: &'i mut I,
+    parser: P,
+}
+
+impl<'i, I, P> ParserDeserializer<'i, I, P>
+    where I: Input, P: FnOnce(&mut I) -> crate::input::Result<Value, I>
+{
+    /// Creates a deserializer that parses `input` with `parser`.
+    pub fn new(input: &'i mut I, parser: P) -> Self {
+        ParserDeserializer { input, parser }
+    }
+
+    /// Runs the parser, returning the parsed value.
+    pub fn parse(self) -> Result<Value, Error>
+        where I::Context: Show, crate::input::Expected<I>: fmt::Display
+    {
+        let value = (self.parser)(self.input)?;
+        eof(self.input)?;
+        Ok(value)
+    }
+}
+
+impl<'de, 'i, I, P> Deserializer<'de> for ParserDeserializer<'i, I, P>
+    where I: Input,
+          P: FnOnce(&mut I) -> crate::input::Result<Value, I>,
+          I::Context: Show,
+          crate::input::Expected<I>: fmt::Display
+{
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.parse()?.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.parse()?.deserialize_option(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Error> {
+        self.parse()?.deserialize_enum(name, variants, visitor)
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Parses `input` with `parser`, which must consume all of `input`, and
+/// deserializes a `T` from the resulting [`Value`].
+pub fn from_input<T, I, P>(input: &mut I, parser: P) -> Result<T, Error>
+    where T: de::DeserializeOwned,
+          I: Input,
+          P: FnOnce(&mut I) -> crate::input::Result<Value, I>,
+          I::Context: Show,
+          crate::input::Expected<I>: fmt::Display
+{
+    T::deserialize(ParserDeserializer::new(input, parser))
+}
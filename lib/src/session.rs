@@ -0,0 +1,347 @@
+//! Push-based parsing of byte streams, as for network protocols.
+//!
+//! A [`Session`] drives a parser over a stream of bytes that arrives in
+//! chunks of any size. Each chunk is [fed](Session::feed()) to the session,
+//! which buffers it and runs the parser over the buffered bytes as many times
+//! as it can, returning an [`Event`] for every message parsed and dropping the
+//! bytes each consumed. Only the bytes of the message in flight are buffered.
+//!
+//! The parser sees the buffered bytes as a [`Partial`] input, which tracks
+//! whether the parser looked past the end of the bytes received so far. If it
+//! did, the parse is _incomplete_: whether it failed or succeeded, it could
+//! have gone differently with more data. A parser that fails this way fails
+//! with an [incomplete](crate::error::ParseError::incomplete) error. The session then suspends, keeping
+//! the bytes, and resumes by parsing them again once more data arrives.
+//! Parsers need no special support to be resumable; they must only not
+//! depend on state other than their input.
+//!
+//! Resuming restarts the parse from the beginning of the message in flight:
+//! nothing of a suspended parse is kept but its bytes. If the parser looked
+//! for a known number of bytes past the end, as [`eat_slice()`] does, the
+//! session waits until that many have arrived before parsing again.
+//! Otherwise, as when [`take_while()`] scans for a delimiter, every chunk fed
+//! reparses the message, so a message of `n` bytes fed in `k` chunks costs
+//! `O(n * k)`: feeding a long message a byte at a time takes time quadratic in
+//! its length. Feed chunks as they're read, not byte by byte.
+//!
+//! [`eat_slice()`]: crate::parsers::eat_slice()
+//! [`take_while()`]: crate::parsers::take_while()
+//!
+//! ```rust
+//! #![feature(proc_macro_hygiene)]
+//!
+//! use pear::input::Result;
+//! use pear::session::{Session, Partial, Event};
+//! use pear::{macros::*, parsers::*};
+//!
+//! // A line of text terminated by `\r\n`.
+//! #[parser]
+//! fn line<'a>(input: &mut Partial<'a>) -> Result<String, Partial<'a>> {
+//!     let line = take_while(|b: &u8| *b != b'\r')?;
+//!     eat_slice(&b"\r\n"[..])?;
+//!     String::from_utf8_lossy(line).into_owned()
+//! }
+//!
+//! let mut session = Session::new(line);
+//! assert!(session.feed(b"HELLO\r").is_empty());
+//! assert_eq!(session.buffered(), b"HELLO\r");
+//!
+//! let events = session.feed(b"\nPING\r\nPO");
+//! assert!(matches!(&events[..], [Event::Message(a), Event::Message(b)] if a == "HELLO" && b == "PING"));
+//! assert_eq!(session.buffered(), b"PO");
+//! ```
+//!
+//! Errors are reported with contexts whose ranges are offsets into the whole
+//! stream. A parse error ends the session: it can't know where the next
+//! message begins, so it discards the data buffered and any data fed to it
+//! after.
+
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::error::{ParseError, Expected, Needed};
+use crate::input::{Input, Rewind, Position, Slice, ParserInfo, Result};
+
+/// The error a [`Session`] fails with: a parse error with owned values and
+/// with contexts spanning offsets into the stream.
+pub type SessionError = ParseError<Range<usize>, Expected<u8, Vec<u8>>>;
+
+/// An event produced by [`Session::feed()`].
+#[derive(Debug)]
+pub enum Event<O> {
+    /// A message was parsed.
+    Message(O),
+    /// Parsing failed. The session is over.
+    Error(SessionError),
+}
+
+/// The bytes of a stream received so far, as seen by a [`Session`]'s parser.
+///
+/// Whenever a parser looks at or past the end of the bytes received, the input
+/// [needs](Input::needed()) more: more data might have changed the outcome of
+/// the parse.
+#[derive(Debug)]
+pub struct Partial<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    offset: usize,
+    closed: bool,
+    needed: Option<Needed>,
+}
+
+impl<'a> Partial<'a> {
+    /// Returns an input over `bytes`, which start at `offset` in the stream.
+    /// If `closed`, `bytes` are the last of the stream, and the input never
+    /// needs more.
+    pub fn new(bytes: &'a [u8], offset: usize, closed: bool) -> Self {
+        Partial { bytes, position: 0, offset, closed, needed: None }
+    }
+
+    /// Returns the bytes not yet consumed.
+    fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.position..]
+    }
+
+    /// Records that more input is needed unless there are at least `n` bytes
+    /// left or the stream is closed. The least amount needed is kept.
+    fn want(&mut self, n: usize) -> bool {
+        let left = self.rest().len();
+        if left < n && !self.closed {
+            self.needed = match self.needed {
+                Some(Needed::Size(m)) if m <= n - left => self.needed,
+                _ => Some(Needed::Size(n - left)),
+            };
+        }
+
+        left >= n
+    }
+}
+
+impl<'a, 'b> Slice<Partial<'a>> for &'b [u8] { }
+
+impl Rewind for Partial<'_> {
+    fn rewind_to(&mut self, marker: &Self::Marker) {
+        self.position = *marker;
+    }
+}
+
+impl Position for Partial<'_> {
+    fn position(&self) -> usize {
+        self.offset + self.position
+    }
+}
+
+impl<'a> Input for Partial<'a> {
+    type Token = u8;
+    type Slice = &'a [u8];
+    type Many = &'a [u8];
+
+    type Marker = usize;
+    type Context = Range<usize>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        match self.want(1) {
+            true => Some(self.rest()[0]),
+            false => None,
+        }
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.peek_slice_at(0, n)
+    }
+
+    /// Returns a copy of the slice of size `len` starting `offset` past the
+    /// current position, if there is one, without consuming anything.
+    fn peek_slice_at(&mut self, offset: usize, len: usize) -> Option<Self::Slice> {
+        let end = offset.checked_add(len)?;
+        match self.want(end) {
+            true => Some(&self.rest()[offset..end]),
+            false => None,
+        }
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token().filter(|t| cond(t))?;
+        self.position += 1;
+        Some(token)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n).filter(|s| cond(s))?;
+        self.position += n;
+        Some(slice)
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.position;
+        self.skip(cond);
+        &self.bytes[start..self.position]
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let skipped = self.rest().iter().take_while(|b| cond(b)).count();
+        self.position += skipped;
+        self.want(1);
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.want(n)
+    }
+
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.position
+    }
+
+    fn context(&mut self, mark: &Self::Marker) -> Option<Self::Context> {
+        Some(self.offset + *mark..self.offset + self.position)
+    }
+
+    fn needed(&self) -> Option<Needed> {
+        self.needed
+    }
+}
+
+/// A resumable driver of the parser `P` over a stream of bytes. See the
+/// [module documentation](self).
+pub struct Session<P> {
+    parser: P,
+    buffer: Vec<u8>,
+    offset: usize,
+    wanted: usize,
+    failed: bool,
+}
+
+impl<P, O> Session<P>
+    where P: for<'a> FnMut(&mut Partial<'a>) -> Result<O, Partial<'a>>
+{
+    /// Returns a session parsing messages with `parser`.
+    pub fn new(parser: P) -> Self {
+        Session { parser, buffer: Vec::new(), offset: 0, wanted: 0, failed: false }
+    }
+
+    /// Returns the bytes received but not yet consumed by a parse.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns `true` if the session ended with an error.
+    pub fn is_failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Feeds `bytes` to the session and parses as many messages as possible,
+    /// returning them, or an error, as events in stream order.
+    ///
+    /// A suspended message is parsed again from its first byte, unless fewer
+    /// bytes than its parser asked for have been buffered. See the
+    /// [module documentation](self) for the cost of doing so.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Event<O>> {
+        if self.failed {
+            return Vec::new();
+        }
+
+        self.buffer.extend_from_slice(bytes);
+        if self.buffer.len() < self.wanted {
+            return Vec::new();
+        }
+
+        self.run(false)
+    }
+
+    /// Ends the stream, parsing the remaining buffered bytes, if any, knowing
+    /// that no more will arrive. A message left incomplete is an error.
+    pub fn finish(mut self) -> Vec<Event<O>> {
+        match self.failed {
+            true => Vec::new(),
+            false => self.run(true),
+        }
+    }
+
+    /// Parses messages from the buffered bytes until one is incomplete or
+    /// fails. An incomplete message is parsed again from its first byte by
+    /// the next run, which waits for the bytes it was known to need, if any.
+    fn run(&mut self, closed: bool) -> Vec<Event<O>> {
+        let mut events = Vec::new();
+        let mut consumed = 0;
+        self.wanted = 0;
+        while consumed < self.buffer.len() {
+            let mut input = Partial::new(&self.buffer[consumed..], self.offset + consumed, closed);
+            let result = (self.parser)(&mut input);
+            if let Some(needed) = input.needed() {
+                if let Needed::Size(n) = needed {
+                    self.wanted = self.buffer.len() - consumed + n;
+                }
+
+                break;
+            }
+
+            match result {
+                Ok(message) if input.position > 0 => {
+                    consumed += input.position;
+                    events.push(Event::Message(message));
+                }
+                Ok(_) => {
+                    let error = Expected::Other("parser succeeded without consuming input".into());
+                    events.push(Event::Error(ParseError::new(error)));
+                    break;
+                }
+                Err(e) => {
+                    events.push(Event::Error(ParseError {
+                        error: e.error.into_owned(),
+                        code: e.code,
+                        committed: e.committed,
+                        incomplete: e.incomplete,
+                        contexts: e.contexts,
+                        hints: e.hints,
+                    }));
+
+                    break;
+                }
+            }
+        }
+
+        self.buffer.drain(..consumed);
+        self.offset += consumed;
+        if let Some(Event::Error(_)) = events.last() {
+            self.failed = true;
+            self.buffer.clear();
+        }
+
+        events
+    }
+}
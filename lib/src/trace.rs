@@ -0,0 +1,395 @@
+//! Limiting `PARSE_DEBUG` traces to particular parsers.
+//!
+//! The parse tree traced when the `PARSE_DEBUG` environment variable is set
+//! includes every parser that runs, which for a large grammar is too much to
+//! read. A [`TraceFilter`] limits the trace to the parsers it matches: it is
+//! read from the `PEAR_TRACE` environment variable or set for the current
+//! thread with [`set_filter()`]. Parsers that don't match are omitted from the
+//! trace, and the parsers they call are shown in their place.
+//!
+//! A filter is a comma-separated list of patterns. A parser matches a pattern
+//! if its name or the end of its path, `module::name`, does: `header` and
+//! `http::header` both match a parser `header` in a module `my_crate::http`. A
+//! pattern ending in `*` matches any name or path suffix it prefixes:
+//! `json::*` matches every parser in a module named `json`, and `*` matches
+//! everything. For instance:
+//!
+//! ```sh
+//! PARSE_DEBUG=1 PEAR_TRACE='json::*,http::header' cargo run
+//! ```
+//!
+//! Parsers built at runtime, with [`wrap::parser()`](crate::wrap::parser())
+//! for example, have no module and are matched by name alone.
+//!
+//! ```rust
+//! use pear::input::{ParserInfo, ParserSource};
+//! use pear::trace::TraceFilter;
+//!
+//! static SOURCE: ParserSource = ParserSource::new("app::http", "header", "", 0, 0);
+//! let header = ParserInfo::new("header", false).with_source(&SOURCE);
+//!
+//! assert!(TraceFilter::parse("http::header").matches(&header));
+//! assert!(TraceFilter::parse("json::*, app::*").matches(&header));
+//! assert!(TraceFilter::parse("head*").matches(&header));
+//! assert!(!TraceFilter::parse("json::*").matches(&header));
+//! assert!(!TraceFilter::parse("p::header").matches(&header));
+//! ```
+//!
+//! # Logging
+//!
+//! With the `log` or `tracing` feature enabled, parsers also report their
+//! activity to the respective crate, with the target `pear`, whenever a logger
+//! or subscriber accepts it: `#[parser]` functions at the `DEBUG` level and raw
+//! parsers, like those in [`parsers`](crate::parsers), at the `TRACE` level.
+//! Parsers matching the filter, if any, are reported whether or not
+//! `PARSE_DEBUG` is set:
+//!
+//!   * With `log`, a record is logged when a parser is entered (`enter
+//!     path`), when it succeeds (`exit path (context)`), and when it fails and
+//!     its caller backtracks (`backtrack path (context)`).
+//!
+//!   * With `tracing`, each parser runs in a span named `parser` with the
+//!     fields `name`, `module`, `raw`, and `success`, recorded on exit. An
+//!     `exit` or `backtrack` event, with a `context` field, is emitted in the
+//!     span before it closes.
+//!
+//! # Capturing
+//!
+//! [`capture()`] collects the same activity into a [`Trace`] instead, for
+//! inspection by the program itself. Raw parsers are captured too, and the
+//! filter applies. Capturing requires the `instrument` feature, which `log`
+//! and `tracing` enable.
+
+use std::cell::RefCell;
+use std::fmt;
+#[cfg(any(feature = "log", feature = "tracing"))]
+use std::fmt::Write;
+
+use crate::input::{ParserInfo, Show};
+
+/// A set of patterns selecting the parsers to trace. See the [module
+/// documentation](self) for the syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceFilter {
+    patterns: Vec<Pattern>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    Exact(String),
+    Prefix(String),
+}
+
+impl TraceFilter {
+    /// Parses a comma-separated list of patterns. Whitespace around patterns
+    /// and empty patterns are ignored, so an empty `spec` matches nothing.
+    pub fn parse(spec: &str) -> TraceFilter {
+        let patterns = spec.split(',')
+            .map(|pattern| pattern.trim())
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => Pattern::Prefix(prefix.into()),
+                None => Pattern::Exact(pattern.into()),
+            })
+            .collect();
+
+        TraceFilter { patterns }
+    }
+
+    /// Returns `true` if `parser` matches any of the patterns.
+    pub fn matches(&self, parser: &ParserInfo) -> bool {
+        let path = parser.source.map(|source| format!("{}::{}", source.module, parser.name));
+        let path = path.as_deref().unwrap_or(parser.name);
+
+        // The name and each suffix of the path following a `::`.
+        let mut suffixes = path.match_indices("::")
+            .map(|(i, _)| &path[i + 2..])
+            .chain(Some(path));
+
+        suffixes.any(|suffix| self.patterns.iter().any(|pattern| match pattern {
+            Pattern::Exact(exact) => suffix == exact,
+            Pattern::Prefix(prefix) => suffix.starts_with(&**prefix),
+        }))
+    }
+}
+
+#[derive(Default)]
+struct Filters {
+    set: Option<TraceFilter>,
+    env: Option<(String, TraceFilter)>,
+}
+
+thread_local! {
+    static FILTERS: RefCell<Filters> = RefCell::new(Filters::default());
+}
+
+/// Sets the filter limiting traces on the current thread, overriding
+/// `PEAR_TRACE`. With `None`, `PEAR_TRACE` applies again.
+pub fn set_filter(filter: Option<TraceFilter>) {
+    FILTERS.with(|filters| filters.borrow_mut().set = filter);
+}
+
+/// Returns `true` if `parser` is to be traced: if no filter is set and
+/// `PEAR_TRACE` is unset, or if the filter matches it.
+pub(crate) fn is_traced(parser: &ParserInfo) -> bool {
+    FILTERS.with(|filters| {
+        let mut filters = filters.borrow_mut();
+        if let Some(filter) = &filters.set {
+            return filter.matches(parser);
+        }
+
+        let spec = match std::env::var("PEAR_TRACE") {
+            Ok(spec) => spec,
+            Err(_) => return true,
+        };
+
+        // Reparse the variable only if it changed.
+        match &filters.env {
+            Some((cached, _)) if *cached == spec => {}
+            _ => filters.env = Some((spec.clone(), TraceFilter::parse(&spec))),
+        }
+
+        filters.env.as_ref().is_none_or(|(_, filter)| filter.matches(parser))
+    })
+}
+
+/// What a parser did, as captured in a [`TraceEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceKind {
+    /// The parser was entered.
+    Enter,
+    /// The parser succeeded.
+    Exit,
+    /// The parser failed, and its caller backtracked.
+    Backtrack,
+}
+
+/// A parser's entry or exit, as captured by [`capture()`].
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    /// What the parser did.
+    pub kind: TraceKind,
+    /// The parser.
+    pub parser: ParserInfo,
+    /// The number of captured parsers the parser was called by.
+    pub depth: usize,
+    /// On exit, the context of the input the parser consumed, if any.
+    pub context: Option<String>,
+}
+
+/// The parser activity captured by [`capture()`], in order.
+///
+/// A trace displays as a tree, one event per line, indented by depth: an
+/// entered parser as `> name`, and an exited parser as `< name ✓ (context)`
+/// or, if it failed, `< name ✗ (context)`.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    events: Vec<TraceEvent>,
+}
+
+impl Trace {
+    /// Returns the captured events, in order.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Returns `true` if no parser ran while capturing.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, event) in self.events.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{:1$}", "", event.depth * 2)?;
+            match event.kind {
+                TraceKind::Enter => write!(f, "> {}", event.parser.name)?,
+                TraceKind::Exit => write!(f, "< {} ✓", event.parser.name)?,
+                TraceKind::Backtrack => write!(f, "< {} ✗", event.parser.name)?,
+            }
+
+            if let Some(context) = &event.context {
+                write!(f, " ({})", context)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Capture {
+    trace: Trace,
+    depth: usize,
+}
+
+thread_local! {
+    static CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` while capturing parser activity on the current thread, returning
+/// the result of `f` and the [`Trace`] captured while it ran.
+///
+/// Calls may be nested; an inner call receives only the activity that took
+/// place while it was running.
+///
+/// ```rust
+/// #![feature(proc_macro_hygiene)]
+///
+/// use pear::input::{Text, Result};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// #[parser]
+/// fn number<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+///     take_some_while(|c: &char| c.is_ascii_digit())?
+/// }
+///
+/// let (result, trace) = pear::trace::capture(|| parse!(number: &mut Text::from("x")));
+/// assert!(result.is_err());
+/// assert_eq!(trace.to_string(), "> number\n  > take_some_while\n  < take_some_while ✗ (1:1)\n< number ✗ (1:1)");
+/// ```
+#[cfg(feature = "instrument")]
+pub fn capture<R, F: FnOnce() -> R>(f: F) -> (R, Trace) {
+    let capture = Capture { trace: Trace::default(), depth: 0 };
+    let outer = CAPTURE.with(|c| c.borrow_mut().replace(capture));
+    let result = f();
+    let capture = CAPTURE.with(|c| std::mem::replace(&mut *c.borrow_mut(), outer));
+    (result, capture.map(|c| c.trace).unwrap_or_default())
+}
+
+fn capture_event(parser: &ParserInfo, kind: TraceKind, ctxt: Option<&dyn Show>) {
+    CAPTURE.with(|capture| {
+        let mut capture = capture.borrow_mut();
+        let capture = match capture.as_mut() {
+            Some(capture) if is_traced(parser) => capture,
+            _ => return,
+        };
+
+        let depth = match kind {
+            TraceKind::Enter => { capture.depth += 1; capture.depth - 1 }
+            _ => { capture.depth = capture.depth.saturating_sub(1); capture.depth }
+        };
+
+        let context = ctxt.map(|c| c.to_string());
+        capture.trace.events.push(TraceEvent { kind, parser: *parser, depth, context });
+    })
+}
+
+/// The target of log records and of `tracing` spans and events.
+#[cfg(any(feature = "log", feature = "tracing"))]
+const TARGET: &str = "pear";
+
+#[cfg(feature = "tracing")]
+thread_local! {
+    static SPANS: RefCell<Vec<Option<tracing::span::EnteredSpan>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns `true` if parser activity is being captured or if a logger or
+/// subscriber accepts it at any level.
+#[cfg(feature = "instrument")]
+#[inline(always)]
+pub(crate) fn is_tracing() -> bool {
+    if CAPTURE.with(|c| c.borrow().is_some()) {
+        return true;
+    }
+
+    #[cfg(feature = "log")]
+    if log::log_enabled!(target: TARGET, log::Level::Debug) {
+        return true;
+    }
+
+    #[cfg(feature = "tracing")]
+    if tracing::enabled!(target: TARGET, tracing::Level::DEBUG) {
+        return true;
+    }
+
+    false
+}
+
+/// The level at which `parser` is logged: `TRACE` if it's raw, else `DEBUG`.
+#[cfg(feature = "log")]
+fn log_level(parser: &ParserInfo) -> log::Level {
+    match parser.raw {
+        true => log::Level::Trace,
+        false => log::Level::Debug,
+    }
+}
+
+/// The parser's path, `module::name`, or its name if it has no module.
+#[cfg(any(feature = "log", feature = "tracing"))]
+fn path(parser: &ParserInfo) -> String {
+    let mut path = String::new();
+    if let Some(source) = parser.source {
+        let _ = write!(path, "{}::", source.module);
+    }
+
+    path.push_str(parser.name);
+    path
+}
+
+/// Reports that `parser` was entered.
+pub(crate) fn log_entry(parser: &ParserInfo) {
+    capture_event(parser, TraceKind::Enter, None);
+
+    #[cfg(feature = "log")]
+    if log::log_enabled!(target: TARGET, log_level(parser)) && is_traced(parser) {
+        log::log!(target: TARGET, log_level(parser), "enter {}", path(parser));
+    }
+
+    // `tracing` requires the level of a span to be a constant.
+    #[cfg(feature = "tracing")]
+    macro_rules! span {
+        ($level:ident) => (tracing::span!(target: TARGET, tracing::Level::$level, "parser",
+            name = parser.name,
+            module = parser.source.map(|s| s.module),
+            raw = parser.raw,
+            success = tracing::field::Empty,
+        ))
+    }
+
+    #[cfg(feature = "tracing")]
+    SPANS.with(|spans| {
+        let span = match (is_traced(parser), parser.raw) {
+            (true, true) => span!(TRACE),
+            (true, false) => span!(DEBUG),
+            (false, _) => tracing::Span::none(),
+        };
+
+        let span = (!span.is_disabled()).then(|| span.entered());
+        spans.borrow_mut().push(span);
+    });
+}
+
+/// Reports that `parser` exited, successfully or not, having consumed the
+/// input described by `ctxt`.
+pub(crate) fn log_exit(parser: &ParserInfo, success: bool, ctxt: Option<&dyn Show>) {
+    let kind = if success { TraceKind::Exit } else { TraceKind::Backtrack };
+    capture_event(parser, kind, ctxt);
+
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    let event = if success { "exit" } else { "backtrack" };
+
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    let context = || ctxt.map(|c| c.to_string()).unwrap_or_default();
+
+    #[cfg(feature = "log")]
+    if log::log_enabled!(target: TARGET, log_level(parser)) && is_traced(parser) {
+        log::log!(target: TARGET, log_level(parser), "{} {} ({})", event, path(parser), context());
+    }
+
+    #[cfg(feature = "tracing")]
+    SPANS.with(|spans| {
+        if let Some(Some(span)) = spans.borrow_mut().pop() {
+            span.record("success", success);
+            match parser.raw {
+                true => tracing::trace!(target: TARGET, context = %context(), "{}", event),
+                false => tracing::debug!(target: TARGET, context = %context(), "{}", event),
+            }
+        }
+    });
+}
@@ -0,0 +1,106 @@
+//! The `#[parser]` attribute as plain functions.
+//!
+//! The functions here wrap a parser, any `FnMut(&mut I) -> Result<O, I, E>`,
+//! in another that behaves like the parser would under the `#[parser]`
+//! attribute: [`parser()`] names it, capturing its context in errors and
+//! reporting it to debugging and [recording](crate::export), while
+//! [`rewinding()`] and [`peeking()`] correspond to the `rewind` and `peek`
+//! attribute arguments. They need no proc-macros and work with closures, so
+//! parsers can be built at runtime with the same diagnostics. The
+//! [`parser!`](crate::macros#pearparser) macro instead gives a closure the
+//! attribute's conveniences as well, such as implicit input.
+//!
+//! ```rust
+//! use pear::input::{Text, Position};
+//! use pear::parsers::*;
+//! use pear::wrap::{parser, rewinding};
+//!
+//! let mut number = parser("number", |input: &mut Text<'static>| {
+//!     take_some_while(input, |c: &char| c.is_ascii_digit())
+//! });
+//!
+//! let mut pair = rewinding(parser("pair", move |input: &mut Text<'static>| {
+//!     let a = number(input)?;
+//!     eat(input, ',')?;
+//!     Ok((a, number(input)?))
+//! }));
+//!
+//! let mut input = Text::from("1,2");
+//! assert_eq!(pair(&mut input).unwrap(), ("1", "2"));
+//!
+//! let mut input = Text::from("1,x");
+//! let error = pair(&mut input).unwrap_err();
+//! let names: Vec<_> = error.contexts.iter()
+//!     .filter(|c| !c.parser.raw)
+//!     .map(|c| c.parser.name)
+//!     .collect();
+//!
+//! assert_eq!(names, ["number", "pair"]);
+//! assert_eq!(input.position(), 0);
+//! ```
+
+use crate::error::push_failure;
+use crate::input::{Input, Rewind, Show, ParserInfo, Result};
+
+/// Returns a parser that runs `f` as a parser named `name`, as if `f` were a
+/// function attributed with `#[parser]`: when `f` fails, the context of the
+/// input since `f` began is pushed into its error along with `name`.
+pub fn parser<I, O, E, F>(name: &'static str, mut f: F) -> impl FnMut(&mut I) -> Result<O, I, E>
+    where I: Input, F: FnMut(&mut I) -> Result<O, I, E>
+{
+    move |input| {
+        let info = ParserInfo::new(name, false);
+        let debug = crate::debug::is_instrumented() || crate::is_parse_debug!();
+        if debug {
+            crate::debug::parser_entry(&info);
+        }
+
+        let mark = input.mark(&info);
+        let mut result = f(input);
+        if let Err(ref mut e) = result {
+            push_failure(input, e, &mark, info, false);
+        }
+
+        if debug {
+            let context = input.context(&mark);
+            let show = context.as_ref().map(|c| c as &dyn Show);
+            crate::debug::parser_exit(&info, result.is_ok(), show);
+        }
+
+        input.unmark(&info, result.is_ok(), mark);
+        result
+    }
+}
+
+/// Returns a parser that runs `f` and, if `f` fails, rewinds the input to
+/// where `f` began. Like `#[parser(rewind)]`.
+pub fn rewinding<I, O, E, F>(f: F) -> impl FnMut(&mut I) -> Result<O, I, E>
+    where I: Rewind, F: FnMut(&mut I) -> Result<O, I, E>
+{
+    rewind_if("rewinding", f, |result| result.is_err())
+}
+
+/// Returns a parser that runs `f` and, if `f` succeeds, rewinds the input to
+/// where `f` began, so that `f` only looks ahead. Like `#[parser(peek)]`;
+/// combine with [`rewinding()`] to never consume input.
+pub fn peeking<I, O, E, F>(f: F) -> impl FnMut(&mut I) -> Result<O, I, E>
+    where I: Rewind, F: FnMut(&mut I) -> Result<O, I, E>
+{
+    rewind_if("peeking", f, |result| result.is_ok())
+}
+
+fn rewind_if<I, O, E, F, C>(name: &'static str, mut f: F, cond: C) -> impl FnMut(&mut I) -> Result<O, I, E>
+    where I: Rewind, F: FnMut(&mut I) -> Result<O, I, E>, C: Fn(&Result<O, I, E>) -> bool
+{
+    move |input| {
+        let info = ParserInfo::new(name, true);
+        let mark = input.mark(&info);
+        let result = f(input);
+        if cond(&result) {
+            input.rewind_to(&mark);
+        }
+
+        input.unmark(&info, result.is_ok(), mark);
+        result
+    }
+}
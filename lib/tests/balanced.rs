@@ -0,0 +1,49 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn call<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+    let name = take_some_while(|c: &char| c.is_alphanumeric())?;
+    (name, balanced('(', ')')?)
+}
+
+#[parser]
+fn template<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    balanced_quoted('{', '}', ['"', '\''], '\\')?
+}
+
+#[test]
+fn test_balanced() {
+    let result = parse!(call: &mut Text::from("f(g(x), (y))"));
+    assert_eq!(result.unwrap(), ("f", "g(x), (y)"));
+
+    let result = parse!(call: &mut Text::from("f()"));
+    assert_eq!(result.unwrap(), ("f", ""));
+
+    let result = parse!(call: &mut Text::from("f(\")\")"));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_balanced_quoted() {
+    let result = parse!(template: &mut Text::from(r#"{ "}" '{' {x} }"#));
+    assert_eq!(result.unwrap(), r#" "}" '{' {x} "#);
+
+    let result = parse!(template: &mut Text::from(r#"{ "a \" }" \} }"#));
+    assert_eq!(result.unwrap(), r#" "a \" }" \} "#);
+}
+
+#[test]
+fn test_unbalanced() {
+    let error = parse!(call: &mut Text::from("f(g(x)")).unwrap_err();
+    assert!(error.committed);
+    assert!(error.to_string().contains("expected token `)`"), "{}", error);
+
+    let error = parse!(template: &mut Text::from(r#"{ "}"#)).unwrap_err();
+    assert!(error.committed);
+
+    let error = parse!(call: &mut Text::from("f)")).unwrap_err();
+    assert!(!error.committed);
+}
@@ -0,0 +1,100 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Cursor, IterInput, Result};
+use pear::macros::{parser, parse};
+use pear::parsers::binary::*;
+
+type Input<'a> = Cursor<'a, u8>;
+
+fn bytes(bytes: &[u8]) -> Input<'_> {
+    Cursor::from(bytes)
+}
+
+#[parser]
+fn header<'a>(input: &mut Input<'a>) -> Result<(u8, i8, u32, i16), Input<'a>> {
+    (u8()?, i8()?, be_u32()?, le_i16()?)
+}
+
+#[test]
+fn test_fixed_width() {
+    let data = [0xFF, 0xFF, 0x12, 0x34, 0x56, 0x78, 0xFE, 0xFF];
+    assert_eq!(parse!(header: &mut bytes(&data)).unwrap(), (255, -1, 0x12345678, -2));
+
+    assert_eq!(le_u16(&mut bytes(&[0x34, 0x12])).unwrap(), 0x1234);
+    assert_eq!(be_u64(&mut bytes(&[0, 0, 0, 0, 0, 0, 1, 0])).unwrap(), 256);
+    assert_eq!(le_i64(&mut bytes(&[0xFF; 8])).unwrap(), -1);
+    assert_eq!(be_u128(&mut bytes(&[0xAB; 16])).unwrap(), u128::from_be_bytes([0xAB; 16]));
+    assert_eq!(be_f64(&mut bytes(&2.5f64.to_be_bytes())).unwrap(), 2.5);
+    assert_eq!(le_f32(&mut bytes(&(-0.5f32).to_le_bytes())).unwrap(), -0.5);
+}
+
+#[test]
+fn test_short_input() {
+    let data = [1, 2, 3];
+    let mut input = bytes(&data);
+    let err = le_u32(&mut input).unwrap_err();
+    assert!(err.to_string().starts_with("expected 4 bytes but none was found"));
+
+    // Nothing was consumed.
+    assert_eq!(be_u16(&mut input).unwrap(), 0x0102);
+    assert!(u8(&mut input).is_ok());
+    assert!(u8(&mut input).unwrap_err().to_string().starts_with("expected a byte"));
+}
+
+#[test]
+fn test_leb128() {
+    let cases: &[(&[u8], u64)] = &[
+        (&[0x00], 0),
+        (&[0x7F], 127),
+        (&[0x80, 0x01], 128),
+        (&[0xE5, 0x8E, 0x26], 624485),
+        (&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01], u64::MAX),
+    ];
+
+    for &(data, value) in cases {
+        assert_eq!(uleb128(&mut bytes(data)).unwrap(), value);
+    }
+
+    let cases: &[(&[u8], i64)] = &[
+        (&[0x00], 0),
+        (&[0x02], 2),
+        (&[0x7E], -2),
+        (&[0xFF, 0x00], 127),
+        (&[0x81, 0x7F], -127),
+        (&[0xC0, 0xBB, 0x78], -123456),
+        (&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7F], i64::MIN),
+        (&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00], i64::MAX),
+    ];
+
+    for &(data, value) in cases {
+        assert_eq!(sleb128(&mut bytes(data)).unwrap(), value);
+    }
+}
+
+#[test]
+fn test_leb128_errors() {
+    let overflow = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02];
+    assert!(uleb128(&mut bytes(&overflow)).is_err());
+    assert!(sleb128(&mut bytes(&overflow)).is_err());
+    assert!(uleb128(&mut bytes(&[0x80, 0x80])).is_err());
+}
+
+#[test]
+fn test_leb128_errors_consume_nothing() {
+    let overflow = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02];
+    let mut input = bytes(&overflow);
+    assert!(uleb128(&mut input).is_err());
+    assert!(sleb128(&mut input).is_err());
+    assert_eq!(u8(&mut input).unwrap(), 0xFF);
+
+    let mut input = bytes(&[0x80, 0x80]);
+    assert!(uleb128(&mut input).is_err());
+    assert_eq!(be_u16(&mut input).unwrap(), 0x8080);
+}
+
+#[test]
+fn test_iter_input() {
+    let mut input = IterInput::new(vec![0xE5, 0x8E, 0x26, 0x00, 0x01].into_iter());
+    assert_eq!(uleb128(&mut input).unwrap(), 624485);
+    assert_eq!(be_u16(&mut input).unwrap(), 1);
+}
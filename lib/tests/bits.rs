@@ -0,0 +1,105 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Bits, Cursor, Input, Position, Result};
+use pear::macros::{parser, parse};
+use pear::parsers::{eat, take_while};
+use pear::parsers::binary::*;
+
+type BitInput<'a> = Bits<Cursor<'a, u8>>;
+
+fn bits_of(bytes: &[u8]) -> BitInput<'_> {
+    Bits::new(Cursor::from(bytes))
+}
+
+#[derive(Debug, PartialEq)]
+struct Header {
+    id: u16,
+    response: bool,
+    opcode: u8,
+    flags: [bool; 4],
+    rcode: u8,
+    questions: u16,
+}
+
+#[parser]
+fn header<'a>(input: &mut BitInput<'a>) -> Result<Header, BitInput<'a>> {
+    let id = bits(16)? as u16;
+    let response = flag()?;
+    let opcode = bits(4)? as u8;
+    let flags = [flag()?, flag()?, flag()?, flag()?];
+    bits(3)?;
+    let rcode = bits(4)? as u8;
+    let questions = bits(16)? as u16;
+    Header { id, response, opcode, flags, rcode, questions }
+}
+
+#[test]
+fn test_dns_header() {
+    let data = [0xAB, 0xCD, 0x81, 0x83, 0x00, 0x01];
+    let parsed = parse!(header: &mut bits_of(&data)).unwrap();
+    assert_eq!(parsed, Header {
+        id: 0xABCD,
+        response: true,
+        opcode: 0,
+        flags: [false, false, true, true],
+        rcode: 3,
+        questions: 1,
+    });
+
+    assert!(parse!(header: &mut bits_of(&data[..5])).is_err());
+}
+
+#[test]
+fn test_take_bits() {
+    let mut input = bits_of(&[0b1011_0010, 0b1111_0000]);
+    assert_eq!(input.take_bits(3), Some(0b101));
+    assert_eq!(input.bit_offset(), 3);
+    assert_eq!(input.take_bits(7), Some(0b100_1011));
+    assert_eq!(input.position(), 10);
+    assert_eq!(input.take_bits(7), None);
+    assert_eq!(input.take_bits(6), Some(0b11_0000));
+    assert_eq!(input.take_bits(0), Some(0));
+    assert_eq!(input.token(), None);
+}
+
+#[test]
+fn test_align() {
+    let mut input = bits_of(&[0b1110_0000, 0x42, 0x43]);
+    assert_eq!(input.take_bits(3), Some(0b111));
+    assert!(!input.is_aligned());
+    assert!(input.inner_mut().is_none());
+
+    assert_eq!(align(&mut input).unwrap(), 5);
+    assert!(input.is_aligned());
+    assert_eq!(input.align(), 0);
+
+    let inner = input.inner_mut().unwrap();
+    assert_eq!(u8(inner).unwrap(), 0x42);
+    assert_eq!(input.take_bits(8), Some(0x43));
+}
+
+#[test]
+fn test_bit_tokens() {
+    let mut input = bits_of(&[0b1110_0101]);
+    assert!(eat(&mut input, true).unwrap());
+    assert_eq!(take_while(&mut input, |&b| b).unwrap(), vec![true, true]);
+    assert_eq!(input.slice(3), Some(vec![false, false, true]));
+    assert!(eat(&mut input, true).is_err());
+    assert_eq!(input.take_bits(5), Some(0b00101));
+    assert!(bits(&mut input, 1).is_err());
+}
+
+#[test]
+fn test_rewind() {
+    #[parser(rewind)]
+    fn two_then_fail<'a>(input: &mut BitInput<'a>) -> Result<(), BitInput<'a>> {
+        bits(2)?;
+        eat(false)?;
+    }
+
+    let mut input = bits_of(&[0b1111_1111, 0xFF]);
+    input.take_bits(7);
+    assert!(two_then_fail(&mut input).is_err());
+    assert_eq!(input.position(), 7);
+    assert_eq!(input.take_bits(9), Some(0x1FF));
+}
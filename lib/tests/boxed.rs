@@ -0,0 +1,60 @@
+#![feature(proc_macro_hygiene)]
+
+use std::collections::HashMap;
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[derive(Debug, PartialEq)]
+enum Value<'a> {
+    Atom(&'a str),
+    List(Vec<Value<'a>>),
+}
+
+#[parser]
+fn atom<'a>(input: &mut Text<'a>) -> Result<Value<'a>, Text<'a>> {
+    Value::Atom(take_some_while(|c: &char| c.is_alphanumeric())?)
+}
+
+// A recursive grammar: every list boxes a fresh parser for its elements.
+fn value<'a>() -> BoxedParser<'a, Text<'a>, Value<'a>> {
+    boxed(|input: &mut Text<'a>| {
+        if peek(input, '(').is_err() {
+            return atom(input);
+        }
+
+        let items: Vec<_> = delimited_collect(input, '(', value(), ' ', ')')?;
+        Ok(Value::List(items))
+    })
+}
+
+#[test]
+fn test_recursive() {
+    use Value::*;
+
+    let mut parser = value();
+    let result = parser(&mut Text::from("(a (b c) ())")).unwrap();
+    assert_eq!(result, List(vec![Atom("a"), List(vec![Atom("b"), Atom("c")]), List(vec![])]));
+}
+
+#[test]
+fn test_rule_table() {
+    let mut rules: HashMap<&str, BoxedParser<'_, Text<'_>, String>> = HashMap::new();
+    rules.insert("upper", boxed(|input: &mut Text<'_>| {
+        let word = take_some_while(input, |c: &char| c.is_alphabetic())?;
+        Ok(word.to_uppercase())
+    }));
+
+    let suffix = String::from("!");
+    rules.insert("shout", boxed(move |input: &mut Text<'_>| {
+        let word = take_some_while(input, |c: &char| c.is_alphabetic())?;
+        Ok(format!("{}{}", word, suffix))
+    }));
+
+    let upper = rules.get_mut("upper").unwrap();
+    assert_eq!(upper(&mut Text::from("pear")).unwrap(), "PEAR");
+
+    let shout = rules.get_mut("shout").unwrap();
+    assert_eq!(shout(&mut Text::from("pear")).unwrap(), "pear!");
+    assert!(shout(&mut Text::from("42")).is_err());
+}
@@ -0,0 +1,66 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Cursor, AsciiCaseInsensitive, Result};
+use pear::macros::{parser, parse, switch};
+use pear::parsers::*;
+
+#[derive(Debug, PartialEq)]
+enum Header<'a> {
+    ContentLength(usize),
+    Other(&'a str, &'a str),
+}
+
+#[parser]
+fn header<'a>(input: &mut Text<'a>) -> Result<Header<'a>, Text<'a>> {
+    switch! {
+        eat_slice_ci("content-length:") => {
+            skip_while(|&c| c == ' ')?;
+            let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+            Header::ContentLength(digits.parse::<usize>().unwrap())
+        },
+        _ => {
+            let name = take_some_while(|&c| c != ':')?;
+            eat(':')?;
+            skip_while(|&c| c == ' ')?;
+            Header::Other(name, take_while(|_| true)?)
+        }
+    }
+}
+
+#[test]
+fn test_case_insensitive_slices() {
+    for name in ["content-length", "Content-Length", "CONTENT-LENGTH", "cOnTeNt-LeNgTh"] {
+        let text = format!("{}: 42", name);
+        assert_eq!(parse!(header: &mut Text::from(&*text)).unwrap(), Header::ContentLength(42));
+    }
+
+    let result = parse!(header: &mut Text::from("Content-Type: text/plain"));
+    assert_eq!(result.unwrap(), Header::Other("Content-Type", "text/plain"));
+
+    let mut input = Text::from("HeLLo");
+    assert!(peek_slice_ci(&mut input, "hello").is_ok());
+    assert_eq!(eat_slice_ci(&mut input, "hello").unwrap(), "HeLLo");
+}
+
+#[test]
+fn test_case_insensitive_tokens() {
+    let mut input = Text::from("Xy");
+    assert_eq!(eat(&mut input, AsciiCaseInsensitive('x')).unwrap(), 'X');
+    assert!(eat(&mut input, AsciiCaseInsensitive('z')).is_err());
+    assert_eq!(eat(&mut input, AsciiCaseInsensitive('Y')).unwrap(), 'y');
+
+    // Only ASCII letters are folded.
+    let mut input = Text::from("É");
+    assert!(eat(&mut input, AsciiCaseInsensitive('é')).is_err());
+    assert!(eat_slice_ci(&mut input, "é").is_err());
+}
+
+#[test]
+fn test_case_insensitive_bytes() {
+    let mut input = Cursor::from(&b"GET / HTTP/1.1"[..]);
+    assert_eq!(eat_slice_ci(&mut input, &b"get"[..]).unwrap(), b"GET");
+    assert!(eat(&mut input, AsciiCaseInsensitive(b' ')).is_ok());
+
+    assert!(eat_slice_ci(&mut input, &b"post"[..]).is_err());
+    assert!(eat_slice_ci(&mut input, &b"/ http"[..]).is_ok());
+}
@@ -0,0 +1,65 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Input as _, Rewind, Text, Span, Chain, ChainContext, IterInput, ParserInfo};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Chain<Text<'a>, Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Input<'a>>;
+
+#[parser]
+fn header<'a>(input: &mut Input<'a>) -> Result<'a, usize> {
+    eat_slice("v=")?;
+    let (first, second) = take_some_while(|c: &char| c.is_ascii_digit())?;
+    eat(';')?;
+    first.len() + second.len()
+}
+
+#[parser]
+fn document<'a>(input: &mut Input<'a>) -> Result<'a, (usize, &'a str)> {
+    let digits = header()?;
+    let (_, body) = take_while(|_| true)?;
+    (digits, body)
+}
+
+#[test]
+fn test_chain() {
+    let input = &mut Chain::new(Text::from("v=12"), Text::from("34;body"));
+    assert_eq!(parse!(document: input).unwrap(), (4, "body"));
+
+    let input = &mut Chain::new(Text::from(""), Text::from("v=1;"));
+    assert_eq!(parse!(document: input).unwrap(), (1, ""));
+
+    let input = &mut Chain::new(Text::from("v=1;"), Text::from(""));
+    assert_eq!(parse!(document: input).unwrap(), (1, ""));
+}
+
+#[test]
+fn test_chain_context() {
+    let input = &mut Chain::new(Text::from("v=12"), Text::from("34!"));
+    let error = parse!(document: input).unwrap_err();
+    let header = error.contexts.iter().find(|c| c.parser.name == "header").unwrap();
+    let first = Span { start: (1, 1, 0), end: (1, 5, 4), snippet: Some("v=12") };
+    let second = Span { start: (1, 1, 0), end: (1, 3, 2), snippet: Some("34") };
+    assert_eq!(header.context, Some(ChainContext::Both(first, second)));
+}
+
+#[test]
+fn test_chain_rewinds_iter_inputs() {
+    let info = ParserInfo::new("test", true);
+    let mut input = Chain::new(IterInput::new("ab".chars()), IterInput::new("cd".chars()));
+    let mark = input.mark(&info);
+    assert_eq!(input.take(|&c| c != 'd'), (vec!['a', 'b'], vec!['c']));
+    input.unmark(&info, true, mark);
+
+    let mark = input.mark(&info);
+    input.rewind_to(&mark);
+    assert_eq!(input.token(), Some('d'));
+    input.unmark(&info, true, mark);
+
+    let mut input = Chain::new(IterInput::new("ab".chars()), IterInput::new("cd".chars()));
+    let mark = input.mark(&info);
+    assert_eq!(input.skip(|&c| c != 'd'), 3);
+    input.rewind_to(&mark);
+    assert_eq!(input.take(|_| true), (vec!['a', 'b'], vec!['c', 'd']));
+    input.unmark(&info, true, mark);
+}
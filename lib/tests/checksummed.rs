@@ -0,0 +1,89 @@
+#![feature(proc_macro_hygiene)]
+
+use core::hash::Hasher;
+
+use pear::input::{Cursor, Result};
+use pear::{macros::*, combinators::*};
+use pear::parsers::binary::*;
+
+type Input<'a> = Cursor<'a, u8>;
+
+/// CRC-32 (IEEE), computed bitwise.
+struct Crc32(u32);
+
+impl Crc32 {
+    fn new() -> Self {
+        Crc32(!0)
+    }
+}
+
+impl Hasher for Crc32 {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u32;
+            for _ in 0..8 {
+                self.0 = (self.0 >> 1) ^ (0xEDB88320 & (self.0 & 1).wrapping_neg());
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        !self.0 as u64
+    }
+}
+
+/// A PNG-like chunk: a big-endian length, a 4-byte type, data, and the CRC-32
+/// of the type and data.
+#[parser]
+fn chunk<'a>(input: &mut Input<'a>) -> Result<(&'a [u8], &'a [u8]), Input<'a>> {
+    let len = be_u32()? as usize;
+    let body = checksummed(len + 4, be_u32, Crc32::new())?;
+    body.split_at(4)
+}
+
+fn encode(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut crc = Crc32::new();
+    crc.write(kind);
+    crc.write(data);
+
+    let mut bytes = (data.len() as u32).to_be_bytes().to_vec();
+    bytes.extend_from_slice(kind);
+    bytes.extend_from_slice(data);
+    bytes.extend_from_slice(&(crc.finish() as u32).to_be_bytes());
+    bytes
+}
+
+#[test]
+fn test_crc32() {
+    let mut crc = Crc32::new();
+    crc.write(b"123456789");
+    assert_eq!(crc.finish(), 0xCBF43926);
+}
+
+#[test]
+fn test_checksummed() {
+    let bytes = encode(b"IHDR", b"\x00\x01\x02");
+    let result = parse!(chunk: &mut Cursor::from(&bytes[..]));
+    assert_eq!(result.unwrap(), (&b"IHDR"[..], &b"\x00\x01\x02"[..]));
+
+    let bytes = encode(b"IEND", b"");
+    let result = parse!(chunk: &mut Cursor::from(&bytes[..]));
+    assert_eq!(result.unwrap(), (&b"IEND"[..], &b""[..]));
+}
+
+#[test]
+fn test_checksum_mismatch() {
+    let mut bytes = encode(b"tEXt", b"pear");
+    bytes[9] ^= 1;
+
+    let error = parse!(chunk: &mut Cursor::from(&bytes[..])).unwrap_err();
+    assert!(error.to_string().starts_with("expected checksum 0x"), "{}", error);
+}
+
+#[test]
+fn test_truncated() {
+    let bytes = encode(b"tEXt", b"pear");
+    for len in [6, 12, bytes.len() - 1] {
+        assert!(parse!(chunk: &mut Cursor::from(&bytes[..len])).is_err());
+    }
+}
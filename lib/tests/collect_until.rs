@@ -0,0 +1,73 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Input, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[parser]
+fn spaced_word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    skip_while(|c: &char| *c == ' ')?;
+    word()?
+}
+
+#[test]
+fn test_collect_until() {
+    let mut input = Text::from("a b c;d");
+    let words: Vec<_> = collect_until(&mut input, spaced_word, |i| eat(i, ';')).unwrap();
+    assert_eq!(words, ["a", "b", "c"]);
+    assert_eq!(input.token(), Some(';'));
+
+    let mut input = Text::from(";");
+    let words: Vec<_> = collect_until(&mut input, spaced_word, |i| eat(i, ';')).unwrap();
+    assert!(words.is_empty());
+
+    // Without the terminator, `p` eventually fails.
+    let mut input = Text::from("a b");
+    let result: Result<Vec<_>, Text> = collect_until(&mut input, spaced_word, |i| eat(i, ';'));
+    assert!(result.is_err());
+
+    // Multi-token terminators are rewound too.
+    let mut input = Text::from("a b end");
+    let words: Vec<_> = collect_until(&mut input, spaced_word, |i| eat_slice(i, " end")).unwrap();
+    assert_eq!(words, ["a", "b"]);
+    assert_eq!(input.slice(4), Some(" end"));
+}
+
+#[test]
+fn test_try_series() {
+    let mut input = Text::from("a,b,c,1");
+    let words: Vec<_> = try_series(&mut input, word, ',').unwrap();
+    assert_eq!(words, ["a", "b", "c"]);
+    assert_eq!(input.token(), Some(','));
+
+    let mut input = Text::from("1");
+    let words: Vec<_> = try_series(&mut input, word, ',').unwrap();
+    assert!(words.is_empty());
+    assert_eq!(input.token(), Some('1'));
+
+    let mut input = Text::from("a,b;c");
+    let words: Vec<_> = try_series(&mut input, word, ',').unwrap();
+    assert_eq!(words, ["a", "b"]);
+    assert_eq!(input.token(), Some(';'));
+}
+
+#[parser]
+fn committed<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    let w = word()?;
+    if w == "x" {
+        cut(|i| eat(i, '!'))?;
+    }
+
+    w
+}
+
+#[test]
+fn test_try_series_committed() {
+    let mut input = Text::from("a,x,b");
+    let result: Result<Vec<_>, Text> = try_series(&mut input, committed, ',');
+    assert!(result.unwrap_err().committed);
+}
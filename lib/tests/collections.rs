@@ -0,0 +1,100 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::Text;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn header<'a>(input: &mut Text<'a>) -> Result<'a, (&'a str, &'a str)> {
+    let name = take_some_while(|&c| c != ':')?;
+    eat(':')?;
+    let value = take_while(|&c| c != '\n')?;
+    eat('\n')?;
+    (name, value)
+}
+
+#[parser]
+fn headers<'a>(input: &mut Text<'a>) -> Result<'a, VecMap<&'a str, &'a str>> {
+    let headers: VecMap<_, _> = collect(header)?;
+    headers
+}
+
+#[test]
+fn test_vec_map() {
+    let text = "Accept:a\nVia:x\nAccept:b\nVia:y\nHost:h\n";
+    let map = parse!(headers: &mut Text::from(text)).unwrap();
+    assert_eq!(map.len(), 5);
+    assert_eq!(map.get("Accept"), Some(&"a"));
+    assert_eq!(map.get_all("Via").collect::<Vec<_>>(), vec![&"x", &"y"]);
+    assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"Accept", &"Via", &"Host"]);
+    assert!(!map.contains_key("Date"));
+
+    let pairs = map.into_vec();
+    assert_eq!(pairs[2], ("Accept", "b"));
+}
+
+#[parser]
+fn line<'a>(input: &mut Text<'a>) -> Result<'a, &'a str> {
+    let line = take_some_while(|&c| c != '\n')?;
+    eat('\n')?;
+    line
+}
+
+#[parser]
+fn batches<'a>(input: &mut Text<'a>, n: usize) -> Result<'a, (usize, Vec<Vec<&'a str>>)> {
+    let mut batches = vec![];
+    let count = chunked(n, line, |chunk| batches.push(chunk))?;
+    (count, batches)
+}
+
+#[test]
+fn test_chunked() {
+    let text = "a\nb\nc\nd\ne\n";
+    let (count, chunks) = parse!(batches(2): &mut Text::from(text)).unwrap();
+    assert_eq!(count, 5);
+    assert_eq!(chunks, vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+
+    let (count, chunks) = parse!(batches(5): &mut Text::from(text)).unwrap();
+    assert_eq!(count, 5);
+    assert_eq!(chunks, vec![vec!["a", "b", "c", "d", "e"]]);
+
+    let (count, chunks) = parse!(batches(2): &mut Text::from("")).unwrap();
+    assert_eq!(count, 0);
+    assert!(chunks.is_empty());
+
+    assert!(parse!(batches(2): &mut Text::from("a\nb\nc")).is_err());
+}
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<'a, u32> {
+    eat_if(|c: &char| c.is_ascii_digit())?.to_digit(10).unwrap()
+}
+
+#[parser]
+fn sum<'a>(input: &mut Text<'a>) -> Result<'a, u32> {
+    separated_fold(0, number, ',', |acc, n| acc + n)?
+}
+
+#[parser]
+fn difference<'a>(input: &mut Text<'a>) -> Result<'a, u32> {
+    eat('-')?;
+    number()?
+}
+
+#[parser]
+fn differences<'a>(input: &mut Text<'a>) -> Result<'a, String> {
+    let first = number()?;
+    fold_many(first.to_string(), difference, |acc, n| format!("({} - {})", acc, n))?
+}
+
+#[test]
+fn test_folds() {
+    assert_eq!(parse!(sum: &mut Text::from("1,2,3")).unwrap(), 6);
+    assert_eq!(parse!(sum: &mut Text::from("7")).unwrap(), 7);
+    assert!(parse!(sum: &mut Text::from("1,")).is_err());
+
+    assert_eq!(parse!(differences: &mut Text::from("1-2-3")).unwrap(), "((1 - 2) - 3)");
+    assert_eq!(parse!(differences: &mut Text::from("1")).unwrap(), "1");
+    assert!(parse!(differences: &mut Text::from("1-2-")).is_err());
+}
@@ -0,0 +1,58 @@
+#![feature(proc_macro_hygiene)]
+
+use std::borrow::Cow;
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn trivia<'a>(input: &mut Text<'a>) -> Result<(), Text<'a>> {
+    loop {
+        skip_while(|c: &char| c.is_whitespace())?;
+        switch! {
+            line_comment("//") => continue,
+            block_comment("/*", "*/", true) => continue,
+            _ => break,
+        }
+    }
+}
+
+#[parser]
+fn strings<'a>(input: &mut Text<'a>) -> Result<Vec<Cow<'a, str>>, Text<'a>> {
+    let strings: Vec<_> = try_collect(|i| {
+        trivia(i)?;
+        match peek(i, '\'') {
+            Ok(()) => single_quoted(i),
+            Err(_) => double_quoted(i),
+        }
+    })?;
+
+    trivia()?;
+    strings
+}
+
+#[test]
+fn test_strings_and_comments() {
+    let source = r#"
+        "a" // "not a string"
+        /* outer /* "nested" */ still a comment */ 'it\'s'
+        "tab\there" /**/
+    "#;
+
+    let strings = parse!(strings: &mut Text::from(source)).unwrap();
+    assert_eq!(strings, ["a", "it's", "tab\there"]);
+    assert!(matches!(strings[0], Cow::Borrowed("a")));
+    assert!(matches!(strings[1], Cow::Owned(_)));
+}
+
+#[test]
+fn test_errors() {
+    assert!(parse!(strings: &mut Text::from("/* /* */")).is_err());
+    assert!(single_quoted(&mut Text::from(r"'\u{d800}'")).is_err());
+    assert!(single_quoted(&mut Text::from(r"'\u{}'")).is_err());
+    assert_eq!(single_quoted(&mut Text::from(r"'\u{1F350}'")).unwrap(), "\u{1F350}");
+
+    let input = &mut Text::from("# comment\nnext");
+    line_comment(input, "#").unwrap();
+    assert_eq!(take_while(input, |_| true).unwrap(), "\nnext");
+}
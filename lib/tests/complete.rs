@@ -0,0 +1,40 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Input, Result, Spanned};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[parser(complete)]
+fn entry<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    word()?
+}
+
+#[parser(complete, spanned)]
+fn spanned_entry<'a>(input: &mut Text<'a>) -> Result<Spanned<&'a str>, Text<'a>> {
+    word()?
+}
+
+#[test]
+fn test_complete_attribute() {
+    assert_eq!(entry(&mut Text::from("hello")).unwrap(), "hello");
+
+    let mut input = Text::from("hello world");
+    let error = entry(&mut input).unwrap_err();
+    assert!(error.to_string().contains("expected EOF"));
+    assert_eq!(input.token(), Some(' '));
+
+    let value = spanned_entry(&mut Text::from("hi")).unwrap();
+    assert_eq!((value.value, value.span), ("hi", 0..2));
+    assert!(spanned_entry(&mut Text::from("hi!")).is_err());
+}
+
+#[test]
+fn test_complete_combinator() {
+    assert_eq!(complete(&mut Text::from("abc"), word).unwrap(), "abc");
+    assert!(complete(&mut Text::from("abc1"), word).is_err());
+    assert!(complete(&mut Text::from("1"), word).is_err());
+}
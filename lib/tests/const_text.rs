@@ -0,0 +1,66 @@
+use pear::input::{ConstText, AsciiClass};
+
+const fn number(input: &mut ConstText<'_>) -> Option<u64> {
+    let digits = input.take_while(AsciiClass::Digit).as_bytes();
+    if digits.is_empty() {
+        return None;
+    }
+
+    let (mut i, mut value) = (0, 0);
+    while i < digits.len() {
+        value = value * 10 + (digits[i] - b'0') as u64;
+        i += 1;
+    }
+
+    Some(value)
+}
+
+const fn version(string: &str) -> Option<(u64, u64, u64, &str)> {
+    let mut input = ConstText::new(string);
+    input.skip_while(AsciiClass::Whitespace);
+    input.eat(b'v');
+
+    let major = match number(&mut input) { Some(n) => n, None => return None };
+    if input.eat(b'.').is_none() { return None; }
+    let minor = match number(&mut input) { Some(n) => n, None => return None };
+    if input.eat(b'.').is_none() { return None; }
+    let patch = match number(&mut input) { Some(n) => n, None => return None };
+
+    let pre = match input.eat_slice("-") {
+        Some(_) => input.take_while(AsciiClass::OneOf(b"-.abcdefghijklmnopqrstuvwxyz0123456789")),
+        None => "",
+    };
+
+    match input.is_empty() {
+        true => Some((major, minor, patch, pre)),
+        false => None,
+    }
+}
+
+const VERSION: Option<(u64, u64, u64, &str)> = version("v1.20.3-rc.1");
+const INVALID: Option<(u64, u64, u64, &str)> = version("1.x.3");
+
+#[test]
+fn test_const_version() {
+    assert_eq!(VERSION, Some((1, 20, 3, "rc.1")));
+    assert_eq!(INVALID, None);
+    assert_eq!(version(" 0.1.0"), Some((0, 1, 0, "")));
+    assert_eq!(version("0.1.0 "), None);
+}
+
+#[test]
+fn test_const_text_primitives() {
+    const fn eat_all() -> (usize, Option<u8>, Option<&'static str>, usize, &'static str) {
+        let mut input = ConstText::new("ab  café");
+        let a = input.eat_if(AsciiClass::Range(b'a', b'b'));
+        let _ = input.eat(b'x');
+        let slice = input.eat_slice("b ");
+        let skipped = input.skip_while(AsciiClass::Whitespace);
+        let taken = input.take_while(AsciiClass::Alpha);
+        assert!(a.is_some() && taken.len() == 3);
+        (input.position(), input.eat(b'x'), slice, skipped, input.current())
+    }
+
+    const RESULT: (usize, Option<u8>, Option<&str>, usize, &str) = eat_all();
+    assert_eq!(RESULT, (7, None, Some("b "), 1, "é"));
+}
@@ -0,0 +1,63 @@
+#![feature(proc_macro_hygiene)]
+
+use std::borrow::Cow;
+
+use pear::input::{Text, Input, Result};
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn escape<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    switch! {
+        eat('n') => '\n',
+        eat('t') => '\t',
+        c@eat_any_of(['"', '\\']) => c,
+        _ => parse_error!("unknown escape")?
+    }
+}
+
+#[parser]
+fn string<'a>(input: &mut Text<'a>) -> Result<Cow<'a, str>, Text<'a>> {
+    eat('"')?;
+    let string = escaped_transform(|c: &char| *c != '"', '\\', escape)?;
+    eat('"')?;
+    string
+}
+
+#[test]
+fn test_escaped_transform_borrows() {
+    let value = parse!(string: &mut Text::from(r#""hello""#)).unwrap();
+    assert!(matches!(value, Cow::Borrowed("hello")));
+
+    let value = parse!(string: &mut Text::from(r#""""#)).unwrap();
+    assert!(matches!(value, Cow::Borrowed("")));
+}
+
+#[test]
+fn test_escaped_transform_decodes() {
+    let value = parse!(string: &mut Text::from(r#""a\tb\\c\"""#)).unwrap();
+    assert!(matches!(value, Cow::Owned(_)));
+    assert_eq!(value, "a\tb\\c\"");
+
+    let value = parse!(string: &mut Text::from(r#""\n""#)).unwrap();
+    assert_eq!(value, "\n");
+}
+
+#[test]
+fn test_escaped_transform_bad_escape() {
+    let mut input = Text::from(r#""a\qb""#);
+    let error = parse!(string: &mut input).unwrap_err();
+    assert!(error.to_string().contains("unknown escape"));
+}
+
+#[test]
+fn test_take_while_transform() {
+    let upper = |c: char| c.to_ascii_uppercase();
+    let mut input = Text::from("ABc1");
+    let value = take_while_transform(&mut input, char::is_ascii_alphabetic, upper).unwrap();
+    assert!(matches!(value, Cow::Owned(ref s) if s == "ABC"));
+    assert_eq!(input.token(), Some('1'));
+
+    let mut input = Text::from("1");
+    let value = take_while_transform(&mut input, char::is_ascii_alphabetic, upper).unwrap();
+    assert!(matches!(value, Cow::Borrowed("")));
+}
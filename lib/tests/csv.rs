@@ -0,0 +1,47 @@
+#![feature(proc_macro_hygiene)]
+
+use std::borrow::Cow;
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*};
+use pear::formats::csv::{self, Dialect};
+
+#[parser]
+fn document<'a>(input: &mut Text<'a>, dialect: Dialect) -> Result<Vec<Vec<Cow<'a, str>>>, Text<'a>> {
+    let records = csv::records(dialect)?;
+    eof()?;
+    records
+}
+
+#[test]
+fn test_records() {
+    let source = "a,b,c\n1,,\"x\ny\"\n\"\",2,3";
+    let records = document(&mut Text::from(source), Dialect::default()).unwrap();
+    assert_eq!(records, [["a", "b", "c"], ["1", "", "x\ny"], ["", "2", "3"]]);
+
+    let records = document(&mut Text::from("a\r\nb\r\n"), Dialect::default()).unwrap();
+    assert_eq!(records, [["a"], ["b"]]);
+
+    let records = document(&mut Text::from(""), Dialect::default()).unwrap();
+    assert!(records.is_empty());
+}
+
+#[test]
+fn test_fields() {
+    let field = csv::field(&mut Text::from("\"plain\""), Dialect::default()).unwrap();
+    assert!(matches!(field, Cow::Borrowed("plain")));
+
+    let field = csv::field(&mut Text::from("\"say \"\"hi\"\"\""), Dialect::default()).unwrap();
+    assert!(matches!(field, Cow::Owned(ref s) if s == "say \"hi\""));
+
+    assert!(csv::field(&mut Text::from("\"open"), Dialect::default()).is_err());
+}
+
+#[test]
+fn test_dialect() {
+    let dialect = Dialect { delimiter: ';', quote: '\'' };
+    let records = document(&mut Text::from("'a;b';'it''s'\nc;d"), dialect).unwrap();
+    assert_eq!(records, [["a;b", "it's"], ["c", "d"]]);
+
+    assert!(document(&mut Text::from("'a'b;c"), dialect).is_err());
+}
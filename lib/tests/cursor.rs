@@ -0,0 +1,14 @@
+use pear::input::{Cursor, Input};
+use pear::parsers::*;
+
+#[test]
+fn test_take_stops_at_first_failure() {
+    let mut input = Cursor::from(&[1, 2, 3, 10, 4, 5][..]);
+    assert_eq!(take_while(&mut input, |&&n| n < 5).unwrap(), [1, 2, 3]);
+    assert_eq!(input.token(), Some(&10));
+
+    assert!(take_while(&mut input, |&&n| n < 5).unwrap().is_empty());
+    assert_eq!(take_n(&mut input, 2).unwrap(), [10, 4]);
+    assert_eq!(take_while(&mut input, |_| true).unwrap(), [5]);
+    assert_eq!(input.token(), None);
+}
@@ -0,0 +1,113 @@
+#![feature(proc_macro_hygiene)]
+
+use std::borrow::Cow;
+
+use pear::input::{Input, Text, Result};
+use pear::error::{Expected, ParseError};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[derive(Debug, PartialEq)]
+enum ConfigError {
+    Syntax(Vec<String>),
+    Invalid(String),
+}
+
+type Error = ConfigError;
+
+impl<'a> From<Expected<char, &'a str>> for ConfigError {
+    fn from(expected: Expected<char, &'a str>) -> Self {
+        ConfigError::Syntax(vec![expected.to_string()])
+    }
+}
+
+impl<'a> pear::Error<Text<'a>> for ConfigError {
+    fn from_context(message: Cow<'static, str>, context: Option<<Text<'a> as Input>::Context>) -> Self {
+        let column = context.map(|span| span.start.1).unwrap_or(0);
+        ConfigError::Invalid(format!("{} at column {}", message, column))
+    }
+
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (ConfigError::Syntax(mut a), ConfigError::Syntax(b)) => {
+                a.extend(b);
+                ConfigError::Syntax(a)
+            }
+            (a, _) => a,
+        }
+    }
+}
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<u8, Text<'a>, Error> {
+    let start = input.mark(&pear::input::ParserInfo::new("number", true));
+    let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+    match digits.parse::<u8>() {
+        Ok(n) => n,
+        Err(_) => {
+            let context = input.context(&start);
+            let error = <Error as pear::Error<Text<'a>>>::from_context("too large".into(), context);
+            return Err(ParseError::new(error));
+        }
+    }
+}
+
+#[parser]
+fn boolean<'a>(input: &mut Text<'a>) -> Result<u8, Text<'a>, Error> {
+    switch! {
+        eat_slice("on") => 1,
+        _ => { eat_slice("off")?; 0 }
+    }
+}
+
+#[parser]
+fn value<'a>(input: &mut Text<'a>) -> Result<u8, Text<'a>, Error> {
+    alt(&[|i| number(i), |i| boolean(i)])?
+}
+
+#[parser]
+fn values<'a>(input: &mut Text<'a>) -> Result<Vec<u8>, Text<'a>, Error> {
+    let values: Vec<_> = delimited_collect('[', value, ',', ']')?;
+    values
+}
+
+#[test]
+fn test_custom_error_success() {
+    let result = parse!(values: &mut Text::from("[1,on,off,200]"));
+    assert_eq!(result.unwrap(), [1, 1, 0, 200]);
+}
+
+#[test]
+fn test_custom_error_merge() {
+    let error = parse!(values: &mut Text::from("[1,maybe]")).unwrap_err();
+    assert_eq!(error.error, ConfigError::Syntax(vec![
+        "unexpected token: `m`".into(),
+        "expected slice `off` but found `may`".into(),
+    ]));
+}
+
+#[test]
+fn test_custom_error_from_context() {
+    let error = parse!(values: &mut Text::from("[1,300]")).unwrap_err();
+    assert_eq!(error.error, ConfigError::Invalid("too large at column 4".into()));
+}
+
+#[test]
+fn test_custom_error_syntax() {
+    let error = parse!(values: &mut Text::from("1,2]")).unwrap_err();
+    assert_eq!(error.error, ConfigError::Syntax(vec!["expected token `[` but found `1`".into()]));
+}
+
+#[test]
+fn test_custom_error_none() {
+    let error = none::<_, (), Error>(&mut Text::from("x")).unwrap_err();
+    assert_eq!(error.error, ConfigError::Syntax(vec!["unexpected token: `x`".into()]));
+}
+
+#[test]
+fn test_take_failures_report_found_token() {
+    let error = take_some_while(&mut Text::from("x1"), |c: &char| c.is_ascii_digit()).unwrap_err();
+    assert!(matches!(error.error, Expected::Token(None, Some('x'))));
+
+    let error = take_n_if(&mut Text::from("1x"), 2, |c: &char| c.is_ascii_digit()).unwrap_err();
+    assert!(matches!(error.error, Expected::Token(None, Some('x'))));
+}
@@ -0,0 +1,88 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn digits<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?
+}
+
+#[parser]
+fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+// `#name` must be followed by a number once the `#` has been seen.
+#[parser]
+fn tag<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    eat('#')?;
+    cut(digits)?
+}
+
+#[parser(commit)]
+fn tag_number<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    digits()?
+}
+
+#[parser]
+fn committed_tag<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    eat('#')?;
+    tag_number()?
+}
+
+#[parser]
+fn token<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    switch! {
+        t@tag() => t,
+        _ => word()?
+    }
+}
+
+#[parser]
+fn committed_token<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    switch! {
+        t@committed_tag() => t,
+        _ => word()?
+    }
+}
+
+#[parser]
+fn tags<'a>(input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+    let tags: Vec<_> = try_collect(tag)?;
+    tags
+}
+
+#[test]
+fn test_cut_in_switch() {
+    assert_eq!(parse!(token: &mut Text::from("#12")).unwrap(), "12");
+    assert_eq!(parse!(token: &mut Text::from("abc")).unwrap(), "abc");
+
+    let err = parse!(token: &mut Text::from("#x")).unwrap_err();
+    assert!(err.committed);
+    assert_eq!(err.parsers().next().unwrap().name, "take_some_while");
+}
+
+#[test]
+fn test_commit_attribute() {
+    assert_eq!(parse!(committed_token: &mut Text::from("#12")).unwrap(), "12");
+    assert_eq!(parse!(committed_token: &mut Text::from("abc")).unwrap(), "abc");
+
+    let err = parse!(committed_token: &mut Text::from("#x")).unwrap_err();
+    assert!(err.committed);
+    assert!(err.parsers().any(|p| p.name == "tag_number"));
+}
+
+#[test]
+fn test_cut_in_try_collect() {
+    let mut input = Text::from("#1#2");
+    assert_eq!(tags(&mut input).unwrap(), vec!["1", "2"]);
+
+    let mut input = Text::from("#1#x");
+    assert!(tags(&mut input).unwrap_err().committed);
+
+    // Failing before the cut rewinds and stops, as usual.
+    let mut input = Text::from("#1!");
+    assert_eq!(tags(&mut input).unwrap(), vec!["1"]);
+    assert!(eat(&mut input, '!').is_ok());
+}
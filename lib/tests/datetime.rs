@@ -0,0 +1,71 @@
+use std::convert::TryFrom;
+
+use pear::input::Text;
+use pear::macros::parse;
+use pear::parsers::datetime::*;
+
+#[test]
+fn test_dates_and_times() {
+    assert_eq!(parse!(date: &mut Text::from("2000-02-29")).unwrap(), Date { year: 2000, month: 2, day: 29 });
+    assert!(parse!(date: &mut Text::from("1900-02-29")).is_err());
+    assert!(parse!(date: &mut Text::from("2023-13-01")).is_err());
+    assert!(parse!(date: &mut Text::from("2023-04-31")).is_err());
+
+    let t = parse!(time: &mut Text::from("23:59:60.123456789999")).unwrap();
+    assert_eq!((t.hour, t.minute, t.second, t.nanosecond), (23, 59, 60, 123_456_789));
+    assert!(parse!(time: &mut Text::from("24:00:00")).is_err());
+    assert!(parse!(time: &mut Text::from("12:00:00.")).is_err());
+}
+
+#[test]
+fn test_timestamps() {
+    let dt = parse!(rfc3339: &mut Text::from("1985-04-12t23:20:50.52-05:30")).unwrap();
+    assert_eq!(dt.offset, Some(Offset { minutes: -330 }));
+    assert_eq!(dt.time.nanosecond, 520_000_000);
+
+    let local = parse!(datetime: &mut Text::from("1985-04-12 23:20:50")).unwrap();
+    assert_eq!(local.offset, None);
+    assert!(parse!(rfc3339: &mut Text::from("1985-04-12 23:20:50")).is_err());
+    assert!(parse!(rfc3339: &mut Text::from("1985-04-12T23:20:50+24:00")).is_err());
+}
+
+#[test]
+fn test_durations() {
+    let d = parse!(duration: &mut Text::from("P1Y2M3W4DT5H6M7.5S")).unwrap();
+    assert_eq!((d.years, d.months, d.weeks, d.days), (1, 2, 3, 4));
+    assert_eq!((d.hours, d.minutes, d.seconds, d.nanoseconds), (5, 6, 7, 500_000_000));
+
+    let d = parse!(duration: &mut Text::from("PT36H")).unwrap();
+    let std = std::time::Duration::try_from(d).unwrap();
+    assert_eq!(std.as_secs(), 36 * 3600);
+    assert!(std::time::Duration::try_from(parse!(duration: &mut Text::from("P1M")).unwrap()).is_err());
+
+    for invalid in ["P", "PT", "P1D2Y", "PT1S2M", "P1DT", "P1H"] {
+        assert!(parse!(duration: &mut Text::from(invalid)).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_chrono() {
+    use chrono::Timelike;
+
+    let dt = parse!(rfc3339: &mut Text::from("2016-12-31T23:59:60+01:00")).unwrap();
+    let chrono = chrono::DateTime::<chrono::FixedOffset>::try_from(dt).unwrap();
+    assert_eq!((chrono.second(), chrono.nanosecond()), (59, 1_000_000_000));
+    assert_eq!(chrono.offset().local_minus_utc(), 3600);
+
+    let local = parse!(datetime: &mut Text::from("2016-12-31T12:00:00")).unwrap();
+    assert!(chrono::DateTime::<chrono::FixedOffset>::try_from(local).is_err());
+    assert!(chrono::NaiveDateTime::try_from(local).is_ok());
+}
+
+#[test]
+fn test_time() {
+    let dt = parse!(rfc3339: &mut Text::from("2016-12-31T23:59:59.25-08:00")).unwrap();
+    let time = time::OffsetDateTime::try_from(dt).unwrap();
+    assert_eq!(time.offset().whole_hours(), -8);
+    assert_eq!(time.millisecond(), 250);
+
+    let leap = parse!(rfc3339: &mut Text::from("2016-12-31T23:59:60Z")).unwrap();
+    assert!(time::OffsetDateTime::try_from(leap).is_err());
+}
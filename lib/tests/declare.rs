@@ -0,0 +1,53 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Cursor, Rewind, Position, Length, Show, Result};
+use pear::{macros::*, parsers::*};
+
+parse_declare!(pub(crate) Input<'a>: Rewind + Position (
+    Token = char,
+    Slice = &'a str,
+    Many: Into<String>,
+));
+
+parse_declare!(Tokens<'a, T: PartialEq + Show + 'a>(Token = &'a T, Slice = &'a [T], Many: Length));
+
+#[parser]
+fn word<'a, I: Input<'a>>(input: &mut I) -> Result<(usize, String), I> {
+    let start = input.position();
+    let word = take_some_while(|c: &char| c.is_alphabetic())?;
+    (start, word.into())
+}
+
+#[parser(rewind)]
+fn words<'a, I: Input<'a>>(input: &mut I) -> Result<Vec<(usize, String)>, I> {
+    let first = word()?;
+    eat(' ')?;
+    let second = word()?;
+    vec![first, second]
+}
+
+#[parser]
+fn run<'a, T, I>(input: &mut I, token: T) -> Result<usize, I>
+    where T: PartialEq + Show + 'a, I: Tokens<'a, T>
+{
+    take_while(|t: &&T| **t == token)?.len()
+}
+
+#[test]
+fn test_bounded_associated_types() {
+    let result = parse!(words: &mut Text::from("hi there")).unwrap();
+    assert_eq!(result, vec![(0, "hi".to_string()), (3, "there".to_string())]);
+
+    let mut input = Text::from("hi 42");
+    assert!(words(&mut input).is_err());
+    assert_eq!(input.position(), 0);
+}
+
+#[test]
+fn test_generic_declaration() {
+    let tokens = [1u8, 1, 1, 2];
+    let mut input = Cursor::from(&tokens[..]);
+    assert_eq!(run(&mut input, 1u8).unwrap(), 3);
+    assert_eq!(run(&mut input, 1u8).unwrap(), 0);
+    assert_eq!(run(&mut input, 2u8).unwrap(), 1);
+}
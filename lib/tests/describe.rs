@@ -0,0 +1,65 @@
+#![feature(proc_macro_hygiene)]
+
+use std::fmt;
+
+use pear::input::{Cursor, Describe, Result};
+use pear::{macros::*, parsers::*};
+
+#[derive(Debug, PartialEq, Describe)]
+enum Token<'a> {
+    #[describe("a string literal")]
+    Str(&'a str),
+    #[describe("an identifier")]
+    Ident { name: &'a str },
+    LeftParen,
+    RightParen,
+}
+
+type Tokens<'a> = Cursor<'a, Token<'a>>;
+
+#[parser]
+fn call<'a>(input: &mut Tokens<'a>) -> Result<&'a str, Tokens<'a>> {
+    let name = match eat_any()? {
+        Token::Ident { name } => *name,
+        _ => parse_error!("expected a function name")?,
+    };
+
+    eat_slice(&[Token::LeftParen, Token::RightParen][..])?;
+    name
+}
+
+#[test]
+fn test_derived_descriptions() {
+    let tokens = [Token::Ident { name: "f" }, Token::LeftParen, Token::RightParen];
+    assert_eq!(parse!(call: &mut Cursor::from(&tokens[..])).unwrap(), "f");
+
+    let tokens = [Token::Ident { name: "f" }, Token::LeftParen, Token::Str("x")];
+    let error = parse!(call: &mut Cursor::from(&tokens[..])).unwrap_err();
+    assert_eq!(error.error.to_string(),
+        "expected slice left paren right paren but found left paren a string literal");
+}
+
+#[test]
+fn test_eat_description() {
+    let tokens = [Token::Str("x")];
+    let error = eat(&mut Cursor::from(&tokens[..]), &Token::LeftParen).unwrap_err();
+    assert_eq!(error.error.to_string(), "expected token left paren but found a string literal");
+}
+
+#[derive(Debug, PartialEq)]
+struct Digit(u8);
+
+impl Describe for Digit {
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the digit {}", self.0)
+    }
+}
+
+impl_show_with!(Describe, Digit);
+
+#[test]
+fn test_impl_show_with_describe() {
+    let digits = [Digit(3)];
+    let error = eat(&mut Cursor::from(&digits[..]), &Digit(7)).unwrap_err();
+    assert_eq!(error.error.to_string(), "expected token the digit 7 but found the digit 3");
+}
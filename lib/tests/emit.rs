@@ -0,0 +1,126 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+use pear::emit::{to_string, round_trips, reformat};
+use pear::FromPearStr;
+
+#[derive(Debug, PartialEq, Emit)]
+enum Expr {
+    #[emit("true")]
+    True,
+    Num(u32),
+    #[emit("-{0}")]
+    Neg(Box<Expr>),
+    Call(Call),
+}
+
+#[derive(Debug, PartialEq, Emit)]
+#[emit("{name}({args})")]
+struct Call {
+    name: String,
+    #[emit(separator = ", ")]
+    args: Vec<Expr>,
+}
+
+#[parser]
+fn ws<'a>(input: &mut Text<'a>) -> Result<(), Text<'a>> {
+    skip_while(|c: &char| c.is_whitespace())?;
+}
+
+#[parser]
+fn call<'a>(input: &mut Text<'a>) -> Result<Call, Text<'a>> {
+    let name = take_some_while(|c: &char| c.is_ascii_alphabetic())?.to_string();
+    let args = delimited_collect('(', |i: &mut Text<'a>| {
+        ws(i)?;
+        let expr = Expr::from_pear_str(i)?;
+        ws(i)?;
+        Ok(expr)
+    }, ',', ')')?;
+
+    Call { name, args }
+}
+
+impl FromPearStr for Expr {
+    #[parser]
+    fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>> {
+        switch! {
+            eat_slice("true") => Expr::True,
+            eat('-') => Expr::Neg(Box::new(Expr::from_pear_str()?)),
+            peek_if_copy(|c: &char| c.is_ascii_digit()) => Expr::Num(u32::from_pear_str()?),
+            _ => Expr::Call(call()?)
+        }
+    }
+}
+
+#[derive(Emit)]
+struct Pair<T>(T, #[emit(separator = "+")] Vec<T>);
+
+#[derive(Emit)]
+#[emit("{{{key}: {value}}}")]
+struct Entry<'a> {
+    key: &'a str,
+    value: Option<bool>,
+}
+
+#[test]
+fn test_emit() {
+    let expr = Expr::Call(Call {
+        name: "max".into(),
+        args: vec![Expr::Num(1), Expr::Neg(Box::new(Expr::True)), Expr::Call(Call {
+            name: "f".into(),
+            args: vec![],
+        })],
+    });
+
+    assert_eq!(to_string(&expr), "max(1, -true, f())");
+    assert_eq!(to_string(&Pair(1u8, vec![2, 3])), "12+3");
+    assert_eq!(to_string(&Entry { key: "a", value: Some(true) }), "{a: true}");
+    assert_eq!(to_string(&Entry { key: "b", value: None }), "{b: }");
+}
+
+#[test]
+fn test_round_trip() {
+    let exprs = ["true", "42", "--7", "f()", "max(1, -true, f(g(2)))"];
+    for text in exprs {
+        let expr = pear::parse_str::<Expr>(text).unwrap();
+        assert!(round_trips(&expr), "{} didn't round-trip", text);
+        assert_eq!(reformat::<Expr>(text).unwrap(), text);
+    }
+
+    assert_eq!(reformat::<Expr>("max( 1,-true ,f(),)").unwrap(), "max(1, -true, f())");
+    assert!(reformat::<Expr>("max(1").is_err());
+}
+
+#[test]
+fn test_derived_round_trip() {
+    #[derive(Debug, PartialEq, Emit, FromPearStr)]
+    enum Value {
+        #[emit("nil")] #[parse("nil")]
+        Nil,
+        #[emit("[{0}]")] #[parse("[{0}]")]
+        List(#[emit(separator = ",")] #[parse(separator = ",")] Vec<Value>),
+        Int(u32),
+    }
+
+    let value = pear::parse_str::<Value>("[1,nil,[]]").unwrap();
+    assert_eq!(value, Value::List(vec![Value::Int(1), Value::Nil, Value::List(vec![])]));
+    assert!(round_trips(&value));
+}
+
+#[test]
+fn test_not_round_trip() {
+    // `Digits(1, 0)` emits `10`, which parses as `Digits(10, 0)`.
+    #[derive(Debug, PartialEq, Emit)]
+    #[emit("{0}{1}")]
+    struct Digits(u32, u32);
+
+    impl FromPearStr for Digits {
+        fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>> {
+            Ok(Digits(u32::from_pear_str(input)?, 0))
+        }
+    }
+
+    assert!(!round_trips(&Digits(1, 0)));
+    assert_eq!(to_string(&Digits(1, 0)), "10");
+}
@@ -0,0 +1,64 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Encoding, DecodeError, Result, decode};
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn words<'a>(input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+    let words: Vec<_> = pear::combinators::series(|i| take_some_while(i, |&c| c != ' '), ' ')?;
+    words
+}
+
+fn utf16(string: &str, le: bool) -> Vec<u8> {
+    let bom: &[u8] = if le { b"\xFF\xFE" } else { b"\xFE\xFF" };
+    let units = string.encode_utf16().flat_map(|u| if le { u.to_le_bytes() } else { u.to_be_bytes() });
+    bom.iter().copied().chain(units).collect()
+}
+
+#[test]
+fn test_from_bytes() {
+    for bytes in [&b"\xEF\xBB\xBFa b"[..], b"a b"] {
+        let result = parse!(words: &mut Text::from_bytes(bytes).unwrap());
+        assert_eq!(result.unwrap(), ["a", "b"]);
+    }
+
+    let error = Text::from_bytes(b"\xEF\xBB\xBFa\xFFb").unwrap_err();
+    assert_eq!(error, DecodeError::Invalid { encoding: Encoding::Utf8, offset: 4 });
+    assert_eq!(error.to_string(), "invalid UTF-8 at byte 4");
+
+    let error = Text::from_bytes(&utf16("a", false)).unwrap_err();
+    assert_eq!(error, DecodeError::Unsupported(Encoding::Utf16Be));
+}
+
+#[test]
+fn test_decode() {
+    for le in [true, false] {
+        let bytes = utf16("z̧ 𝄞", le);
+        let text = decode(&bytes).unwrap();
+        assert_eq!(parse!(words: &mut Text::from(&*text)).unwrap(), ["z̧", "𝄞"]);
+    }
+
+    let text = decode(b"\xEF\xBB\xBFplain").unwrap();
+    assert!(matches!(text, std::borrow::Cow::Borrowed("plain")));
+
+    let text = Encoding::Latin1.decode(b"\xA9 2024").unwrap();
+    assert_eq!(text, "© 2024");
+
+    let text = Encoding::Utf16Le.decode(b"h\0i\0").unwrap();
+    assert_eq!(text, "hi");
+}
+
+#[test]
+fn test_decode_errors() {
+    let mut odd = utf16("ab", true);
+    odd.pop();
+    let error = decode(&odd).unwrap_err();
+    assert_eq!(error, DecodeError::Invalid { encoding: Encoding::Utf16Le, offset: 4 });
+
+    // An unpaired high surrogate.
+    let error = decode(b"\xFE\xFF\0a\xD8\x00\0b").unwrap_err();
+    assert_eq!(error, DecodeError::Invalid { encoding: Encoding::Utf16Be, offset: 4 });
+
+    let error = decode(b"ok\xC3").unwrap_err();
+    assert_eq!(error, DecodeError::Invalid { encoding: Encoding::Utf8, offset: 2 });
+}
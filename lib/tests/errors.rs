@@ -0,0 +1,69 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::error::{ErrorBuilder, ErrorCode};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn sign<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    alt(&[|i| eat(i, '+'), |i| eat(i, '-')])?
+}
+
+#[parser]
+fn keyword<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    alt(&[|i| eat_slice(i, "let"), |i| eat_slice(i, "fn"), |i| eat_slice(i, "let")])?
+}
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<u8, Text<'a>> {
+    let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+    match digits.parse::<u8>() {
+        Ok(n) => n,
+        Err(_) => {
+            let error = ErrorBuilder::new().code("E0001").message("number too large").build();
+            return Err(error);
+        }
+    }
+}
+
+#[parser]
+fn signed<'a>(input: &mut Text<'a>) -> Result<i16, Text<'a>> {
+    let sign = sign()?;
+    let n = number()? as i16;
+    if sign == '-' { -n } else { n }
+}
+
+#[test]
+fn test_error_codes() {
+    let err = parse!(signed: &mut Text::from("")).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::UnexpectedEof);
+
+    let err = parse!(signed: &mut Text::from("*1")).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unexpected);
+
+    let err = parse!(signed: &mut Text::from("+1!")).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::TrailingInput);
+
+    let err = parse!(signed: &mut Text::from("+1000")).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Custom("E0001"));
+    assert!(err.to_string().starts_with("[E0001] number too large"));
+}
+
+#[test]
+fn test_merged_expected() {
+    let err = parse!(signed: &mut Text::from("*1")).unwrap_err();
+    assert_eq!(err.expected(), vec!["`+`", "`-`"]);
+    assert_eq!(err.found(), Some(&'*'));
+    assert!(err.to_string().starts_with("expected one of `+`, `-` but found `*`"));
+
+    let err = parse!(keyword: &mut Text::from("var")).unwrap_err();
+    assert_eq!(err.expected(), vec!["`let`", "`fn`"]);
+    assert_eq!(err.found(), None);
+}
+
+#[test]
+fn test_parser_stack() {
+    let err = parse!(signed: &mut Text::from("+x")).unwrap_err();
+    let stack: Vec<_> = err.parsers().map(|p| p.name).collect();
+    assert_eq!(stack, vec!["take_some_while", "number", "signed"]);
+}
@@ -0,0 +1,103 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::export::{record, Step};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Text<'a>;
+
+#[parser]
+fn value<'a>(input: &mut Input<'a>) -> Result<u32, Input<'a>> {
+    switch! {
+        eat('[') => {
+            let sum = list()?;
+            eat(']')?;
+            sum
+        },
+        _ => digit()?
+    }
+}
+
+#[parser]
+fn list<'a>(input: &mut Input<'a>) -> Result<u32, Input<'a>> {
+    let mut sum = 0;
+    while parse_try!(eat(',')).is_some() || sum == 0 {
+        sum += value()?;
+    }
+
+    sum
+}
+
+#[parser]
+fn digit<'a>(input: &mut Input<'a>) -> Result<u32, Input<'a>> {
+    eat_if(|c: &char| c.is_ascii_digit())?.to_digit(10).unwrap()
+}
+
+fn call(parser: &'static str, terminal: bool, repeated: bool) -> Step {
+    Step { parser, terminal, repeated }
+}
+
+#[test]
+fn test_record_call_graph() {
+    let (result, graph) = record(|| parse!(value: &mut Text::from("[1,[2,3]]")));
+    assert_eq!(result.unwrap(), 6);
+    assert_eq!(graph.entry(), Some("value"));
+
+    let names: Vec<_> = graph.rules().iter().map(|rule| rule.name).collect();
+    assert_eq!(names, ["value", "digit", "list"]);
+
+    let value = graph.rule("value").unwrap();
+    assert_eq!(value.alternatives, vec![
+        vec![call("digit", false, false)],
+        vec![call("eat", true, false), call("list", false, false), call("eat", true, false)],
+    ]);
+
+    let list = graph.rule("list").unwrap();
+    assert!(list.alternatives.contains(&vec![
+        call("value", false, false), call("eat", true, false), call("value", false, false)
+    ]));
+
+    assert!(graph.rule("eat").is_none());
+    assert!(!pear::export::is_recording());
+}
+
+#[test]
+fn test_nothing_recorded_outside() {
+    let (_, graph) = record(|| ());
+    assert!(graph.is_empty());
+    assert_eq!(graph.entry(), None);
+
+    let (_, graph) = record(|| parse!(digit: &mut Text::from("a")));
+    assert!(graph.is_empty());
+    assert_eq!(graph.entry(), Some("digit"));
+}
+
+#[test]
+fn test_merge() {
+    let (_, mut graph) = record(|| parse!(value: &mut Text::from("1")));
+    assert_eq!(graph.rules().len(), 2);
+
+    let (_, other) = record(|| parse!(value: &mut Text::from("[1]")));
+    graph.merge(other);
+    assert_eq!(graph.rule("value").unwrap().alternatives.len(), 2);
+    assert!(graph.rule("list").is_some());
+}
+
+#[test]
+fn test_render() {
+    let (_, graph) = record(|| parse!(value: &mut Text::from("[1,2]")));
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph grammar {\n"));
+    assert!(dot.contains("    \"value\" [shape=box, style=bold];\n"));
+    assert!(dot.contains("    \"eat\" [shape=ellipse];\n"));
+    assert!(dot.contains("    \"value\" -> \"list\";\n"));
+    assert_eq!(dot.matches("\"value\" -> \"eat\"").count(), 1);
+
+    let svg = graph.to_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    for name in ["value", "list", "digit"] {
+        assert!(svg.contains(&format!(">{}</text>", name)));
+    }
+}
@@ -0,0 +1,78 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Cursor, AsciiClass, Delimiters, FastPredicate, FastScan};
+use pear::macros::{parser, parse};
+use pear::parsers::*;
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn string<'a>(input: &mut Text<'a>) -> Result<'a, String> {
+    eat('"')?;
+    let mut string = String::new();
+    loop {
+        let chunk = take_while_fast(Delimiters(*b"\"\\"))?;
+        string.push_str(chunk);
+        if eat(input, '"').is_ok() {
+            break;
+        }
+
+        eat('\\')?;
+        let escaped = eat_any()?;
+        string.push(escaped);
+    }
+
+    string
+}
+
+#[test]
+fn test_json_string() {
+    let result = parse!(string: &mut Text::from(r#""a \"quoted\" \\ wört""#));
+    assert_eq!(result.unwrap(), r#"a "quoted" \ wört"#);
+
+    let result = parse!(string: &mut Text::from(r#""unterminated"#));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_delimiter_counts() {
+    let bytes = b"abcdef,ghi;jk\nlm|";
+    assert_eq!(Delimiters(*b",").prefix_len(bytes), 6);
+    assert_eq!(Delimiters(*b";,").prefix_len(bytes), 6);
+    assert_eq!(Delimiters(*b"\n;|").prefix_len(bytes), 10);
+    assert_eq!(Delimiters(*b"|\nxy").prefix_len(bytes), 13);
+    assert_eq!(Delimiters(*b"z").prefix_len(bytes), bytes.len());
+    assert_eq!(Delimiters([]).prefix_len(bytes), bytes.len());
+
+    // The accelerated scans agree with the byte-at-a-time default.
+    let long: Vec<u8> = (0..2000u32).map(|i| (i % 97) as u8 + 32).collect();
+    for delims in [*b"~a0", *b"!!!", [0, 1, 2]] {
+        let expected = long.iter().position(|b| delims.contains(b)).unwrap_or(long.len());
+        assert_eq!(Delimiters(delims).prefix_len(&long), expected);
+        assert_eq!(Delimiters([delims[0]]).prefix_len(&long),
+            long.iter().position(|b| *b == delims[0]).unwrap_or(long.len()));
+    }
+}
+
+#[test]
+fn test_byte_cursor() {
+    let mut input = Cursor::from(&b"  \t1234abc\x00rest"[..]);
+    assert_eq!(skip_while_fast(&mut input, AsciiClass::Whitespace).unwrap(), 3);
+    assert_eq!(take_while_fast(&mut input, AsciiClass::Digit).unwrap(), b"1234");
+    assert_eq!(take_while_fast(&mut input, |b: u8| b != 0).unwrap(), b"abc");
+    assert_eq!(take_while_fast(&mut input, AsciiClass::Alpha).unwrap(), b"");
+    assert_eq!(input.scan(&Delimiters(*b"e")), b"\x00r");
+}
+
+#[test]
+fn test_text_keeps_char_boundaries() {
+    // `é` is `0xC3 0xA9`: a scan stopping at `0xA9` would split it.
+    let mut input = Text::from("abé!");
+    assert_eq!(input.scan(&Delimiters([0xA9])), "ab");
+    assert_eq!(input.scan(&|b: u8| b != b'!'), "é");
+    assert_eq!(eat(&mut input, '!').unwrap(), '!');
+
+    let mut input = "größe = 1";
+    assert_eq!(input.scan(&Delimiters(*b" =")), "größe");
+    assert_eq!(input, " = 1");
+}
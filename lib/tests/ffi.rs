@@ -0,0 +1,127 @@
+#![feature(proc_macro_hygiene)]
+
+use std::ffi::CStr;
+
+use pear::input::{Input, Text, Result};
+use pear::macros::{parser, parse_error};
+use pear::parsers::*;
+use pear::ffi::{FfiResult, FfiStatus, pear_result_free};
+
+#[derive(Debug, PartialEq)]
+struct Version(u32, u32);
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+    let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+    match digits.parse::<u32>() {
+        Ok(n) => n,
+        Err(_) => parse_error!("number is too large")?,
+    }
+}
+
+#[parser(ffi)]
+fn version<'a>(input: &mut Text<'a>) -> Result<Version, Text<'a>> {
+    let major = number()?;
+    eat('.')?;
+    let minor = number()?;
+    eof()?;
+    Version(major, minor)
+}
+
+mod json {
+    use super::*;
+
+    #[parser(ffi = "json_version_parse")]
+    pub fn version<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+        number()?
+    }
+}
+
+#[parser(ffi)]
+fn explode<'a>(input: &mut Text<'a>) -> Result<(), Text<'a>> {
+    eat('!')?;
+    if !input.has(1) {
+        panic!("kaboom");
+    }
+}
+
+fn call(
+    f: unsafe extern "C" fn(*const u8, usize, *mut FfiResult) -> FfiStatus,
+    bytes: &[u8]
+) -> FfiResult {
+    let mut result = FfiResult::default();
+    let status = unsafe { f(bytes.as_ptr(), bytes.len(), &mut result) };
+    assert_eq!(status, result.status);
+    result
+}
+
+fn message(result: &FfiResult) -> String {
+    unsafe { CStr::from_ptr(result.error_message).to_string_lossy().into_owned() }
+}
+
+#[test]
+fn test_ffi_ok() {
+    let mut result = call(version_parse, b"1.23");
+    assert_eq!(result.status, FfiStatus::Ok);
+    assert!(result.error_message.is_null());
+    assert_eq!(unsafe { &*(result.value as *const Version) }, &Version(1, 23));
+
+    unsafe { pear_result_free(&mut result) };
+    assert!(result.value.is_null());
+}
+
+#[test]
+fn test_ffi_parse_error() {
+    let mut result = call(version_parse, b"1.x");
+    assert_eq!(result.status, FfiStatus::ParseError);
+    assert!(result.value.is_null());
+    assert_eq!(result.error_offset, 2);
+    assert!(message(&result).contains("version"));
+    unsafe { pear_result_free(&mut result) };
+}
+
+#[test]
+fn test_ffi_invalid_utf8() {
+    let mut result = call(version_parse, b"1.\xff");
+    assert_eq!(result.status, FfiStatus::InvalidUtf8);
+    assert_eq!(result.error_offset, 2);
+    unsafe { pear_result_free(&mut result) };
+}
+
+#[test]
+fn test_ffi_panic() {
+    let mut result = call(explode_parse, b"!");
+    assert_eq!(result.status, FfiStatus::Panic);
+    assert_eq!(message(&result), "kaboom");
+    unsafe { pear_result_free(&mut result) };
+}
+
+#[test]
+fn test_ffi_null_pointers() {
+    let status = unsafe { version_parse(std::ptr::null(), 1, &mut FfiResult::default()) };
+    assert_eq!(status, FfiStatus::NullPointer);
+
+    let status = unsafe { version_parse(b"1.2".as_ptr(), 3, std::ptr::null_mut()) };
+    assert_eq!(status, FfiStatus::NullPointer);
+
+    let mut result = FfiResult::default();
+    let status = unsafe { version_parse(std::ptr::null(), 0, &mut result) };
+    assert_eq!(status, FfiStatus::ParseError);
+    unsafe { pear_result_free(&mut result) };
+}
+
+#[test]
+fn test_ffi_symbol() {
+    extern "C" {
+        fn json_version_parse(ptr: *const u8, len: usize, out: *mut FfiResult) -> FfiStatus;
+    }
+
+    let mut result = call(json_version_parse, b"7");
+    assert_eq!(result.status, FfiStatus::Ok);
+    assert_eq!(unsafe { *(result.value as *const u32) }, 7);
+    unsafe { pear_result_free(&mut result) };
+
+    let mut result = call(json::version_parse, b"x");
+    assert_eq!(result.status, FfiStatus::ParseError);
+    unsafe { pear_result_free(&mut result) };
+}
@@ -0,0 +1,93 @@
+#![feature(proc_macro_hygiene)]
+
+use std::borrow::Cow;
+use std::num::ParseIntError;
+
+use pear::input::{Input, Text, Result};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn byte<'a>(input: &mut Text<'a>) -> Result<u8, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u8>()?
+}
+
+#[parser]
+fn bytes<'a>(input: &mut Text<'a>) -> Result<Vec<u8>, Text<'a>> {
+    let first = byte()?;
+    let mut bytes = vec![first];
+    while eat(input, ',').is_ok() {
+        bytes.push(byte()?);
+    }
+
+    bytes
+}
+
+#[test]
+fn test_foreign_error() {
+    assert_eq!(parse!(bytes: &mut Text::from("1,22,255")).unwrap(), [1, 22, 255]);
+
+    let error = parse!(bytes: &mut Text::from("1,256")).unwrap_err();
+    assert!(error.to_string().starts_with("number too large"));
+    assert_eq!(error.parsers().next().unwrap().name, "byte");
+
+    let span = error.contexts[0].context.as_ref().unwrap();
+    assert_eq!((span.start.2, span.end.2), (2, 5));
+}
+
+#[parser]
+fn checked<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+    let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+    let parsed: std::result::Result<u32, ParseIntError> = digits.parse();
+    let value = parsed?;
+    let result = eat(input, '!');
+    result?;
+    value
+}
+
+#[test]
+fn test_mixed_results() {
+    assert_eq!(parse!(checked: &mut Text::from("42!")).unwrap(), 42);
+
+    let error = parse!(checked: &mut Text::from("42?")).unwrap_err();
+    assert_eq!(error.expected(), ["`!`"]);
+
+    let error = parse!(checked: &mut Text::from("99999999999!")).unwrap_err();
+    assert!(error.to_string().starts_with("number too large"));
+}
+
+#[derive(Debug, PartialEq)]
+enum ConfigError {
+    Syntax(String),
+    Invalid(String, usize),
+}
+
+impl<'a> From<Expected<char, &'a str>> for ConfigError {
+    fn from(expected: Expected<char, &'a str>) -> Self {
+        ConfigError::Syntax(expected.to_string())
+    }
+}
+
+impl<'a> pear::Error<Text<'a>> for ConfigError {
+    fn from_context(message: Cow<'static, str>, context: Option<<Text<'a> as Input>::Context>) -> Self {
+        ConfigError::Invalid(message.into(), context.map(|span| span.start.1).unwrap_or(0))
+    }
+}
+
+#[parser]
+fn port<'a>(input: &mut Text<'a>) -> Result<u16, Text<'a>, ConfigError> {
+    eat_slice("port=")?;
+    take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u16>()?
+}
+
+#[test]
+fn test_custom_error_context() {
+    assert_eq!(parse!(port: &mut Text::from("port=8080")).unwrap(), 8080);
+
+    let error = parse!(port: &mut Text::from("port=65536")).unwrap_err();
+    let expected = "number too large to fit in target type";
+    assert_eq!(error.error, ConfigError::Invalid(expected.into(), 1));
+
+    let error = parse!(port: &mut Text::from("host=x")).unwrap_err();
+    assert!(matches!(error.error, ConfigError::Syntax(_)));
+}
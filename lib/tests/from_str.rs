@@ -0,0 +1,62 @@
+#![feature(proc_macro_hygiene)]
+
+use std::str::FromStr;
+
+use pear::input::{Text, Position, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[derive(Debug, PartialEq)]
+struct Hex(u32);
+
+impl FromStr for Hex {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        u32::from_str_radix(s, 16).map(Hex).map_err(|_| format!("`{}` isn't hex", s))
+    }
+}
+
+#[parser]
+fn color<'a>(input: &mut Text<'a>) -> Result<(Hex, Hex, Hex), Text<'a>> {
+    eat('#')?;
+    (from_str(2)?, from_str(2)?, from_str(2)?)
+}
+
+#[parser]
+fn word<'a>(input: &mut Text<'a>) -> Result<String, Text<'a>> {
+    let word: String = from_str(|c: &char| c.is_alphabetic())?;
+    word
+}
+
+#[test]
+fn test_from_str_by_length() {
+    let (r, g, b) = parse!(color: &mut Text::from("#ff8000")).unwrap();
+    assert_eq!((r, g, b), (Hex(0xff), Hex(0x80), Hex(0)));
+
+    let error = parse!(color: &mut Text::from("#ff80zz")).unwrap_err();
+    let span = error.contexts[0].context.as_ref().unwrap();
+    assert_eq!(error.error.to_string(), "`zz` isn't hex");
+    assert_eq!((span.start.2, span.end.2), (5, 7));
+
+    // There are too few characters left.
+    let error = parse!(color: &mut Text::from("#ff80f")).unwrap_err();
+    assert_eq!(error.error.to_string(), "expected any token but none was found");
+}
+
+#[test]
+fn test_from_str_counts_characters() {
+    let mut input = Text::from("héllo");
+    let value: String = from_str(&mut input, 3).unwrap();
+    assert_eq!(value, "hél");
+    assert_eq!(input.position(), 4);
+}
+
+#[test]
+fn test_from_str_by_predicate() {
+    assert_eq!(parse!(word: &mut Text::from("pear")).unwrap(), "pear");
+    assert!(parse!(word: &mut Text::from("1")).is_err());
+
+    let mut input = Text::from("42x");
+    let value: u8 = from_str(&mut input, |c: &char| c.is_ascii_digit()).unwrap();
+    assert_eq!((value, input.position()), (42, 2));
+}
@@ -0,0 +1,101 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Fueled, Input, Result};
+use pear::error::ErrorCode;
+use pear::macros::{parser, parse, switch};
+use pear::parsers::*;
+
+type Fuel<'a> = Fueled<Text<'a>>;
+
+// Exponential on inputs of `a`s that don't end with `b`: each level tries the
+// long alternative first and then reparses the same input with the short one.
+#[parser(rewind)]
+fn pathological<'a>(input: &mut Fuel<'a>) -> Result<usize, Fuel<'a>> {
+    switch! {
+        eat('a') => {
+            switch! {
+                n@pathological() => { eat('b')?; n + 1 },
+                n@pathological() => n + 1,
+                _ => 1
+            }
+        },
+        _ => { eat('b')?; 0 }
+    }
+}
+
+#[test]
+fn test_fuel_stops_backtracking() {
+    let source = "a".repeat(40);
+    let mut input = Fueled::new(Text::from(&*source), 10_000);
+    let result = parse!(pathological: &mut input);
+    assert!(input.is_exhausted());
+
+    let error = input.check(result).unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.code(), ErrorCode::FuelExhausted);
+    assert!(error.to_string().starts_with("fuel exhausted"));
+}
+
+#[test]
+fn test_fuel_not_refunded() {
+    let mut input = Fueled::new(Text::from("abc"), 4);
+    let mark = input.mark(&pear::input::ParserInfo::new("test", true));
+    assert_eq!(eat_slice(&mut input, "ab").unwrap(), "ab");
+    pear::input::Rewind::rewind_to(&mut input, &mark);
+    assert_eq!(input.remaining(), 2);
+
+    // Peeking is free, and refused consumptions don't burn anything.
+    assert_eq!(input.slice(3), Some("abc"));
+    assert!(eat_slice(&mut input, "abc").is_err());
+    assert!(input.is_exhausted());
+    assert_eq!(input.remaining(), 2);
+
+    // An exhausted input can't be read at all.
+    assert_eq!(input.token(), None);
+    assert_eq!(take_while(&mut input, |_| true).unwrap(), "");
+    assert_eq!(input.remaining(), 2);
+
+    input.refuel(1);
+    assert!(!input.is_exhausted());
+    assert_eq!(take_while(&mut input, |_| true).unwrap(), "abc");
+    assert_eq!(input.remaining(), 0);
+}
+
+#[parser]
+fn word<'a>(input: &mut Fuel<'a>) -> Result<&'a str, Fuel<'a>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[test]
+fn test_fuel_truncated_success_rejected() {
+    let mut input = Fueled::new(Text::from("hello"), 3);
+    let result = word(&mut input);
+    assert_eq!(result.as_ref().ok(), Some(&"hel"));
+    let error = input.check(result).unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.code(), ErrorCode::FuelExhausted);
+
+    let mut input = Fueled::new(Text::from("hello"), 3);
+    let error = parse!(word: &mut input).unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.code(), ErrorCode::FuelExhausted);
+}
+
+#[test]
+fn test_fuel_exhaustion_commits() {
+    // Without committing, `pathological` would try the `_` arm and succeed.
+    let source = "a".repeat(40);
+    let mut input = Fueled::new(Text::from(&*source), 10_000);
+    let error = pathological(&mut input).unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.code(), ErrorCode::FuelExhausted);
+}
+
+#[test]
+fn test_fuel_check_passthrough() {
+    let mut input = Fueled::new(Text::from("x"), 10);
+    let result = parse!(pathological: &mut input);
+    let error = input.check(result).unwrap_err();
+    assert_ne!(error.code(), ErrorCode::FuelExhausted);
+    assert!(!error.committed);
+}
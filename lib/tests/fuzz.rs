@@ -0,0 +1,69 @@
+#![feature(proc_macro_hygiene)]
+
+use std::cell::Cell;
+
+use pear::input::{Text, Cursor, Result};
+use pear::macros::parser;
+use pear::parsers::*;
+use pear::combinators::*;
+use pear::fuzz::{fuzz_str, fuzz_bytes};
+
+#[parser]
+fn pair<'a>(input: &mut Text<'a>) -> Result<(&'a str, u32), Text<'a>> {
+    let key = take_some_while(|c: &char| c.is_alphabetic())?;
+    eat('=')?;
+    let value = take_some_while(|c: &char| c.is_ascii_digit())?;
+    (key, value.parse().unwrap_or(0))
+}
+
+#[parser]
+fn pairs<'a>(input: &mut Text<'a>) -> Result<Vec<(&'a str, u32)>, Text<'a>> {
+    let pairs: Vec<_> = series(pair, ',')?;
+    pairs
+}
+
+#[parser]
+fn header<'a>(input: &mut Cursor<'a, u8>) -> Result<&'a [u8], Cursor<'a, u8>> {
+    eat_slice(&b"PEAR"[..])?;
+    let len = eat_any()?;
+    take_n(*len as usize)?
+}
+
+const CORPUS: &[&[u8]] = &[
+    b"", b"a=1", b"a=1,b=22", b"a=", b"=1", b"a=1,", "ä=1,é=x".as_bytes(),
+    b"PEAR\x02ab", b"PEAR\x09ab", b"PEA", b"\xff\xfe", b"a=\xc3",
+];
+
+#[test]
+fn test_corpus() {
+    for data in CORPUS {
+        fuzz_str(data, pairs);
+        fuzz_str(data, |i| opt(i, pair));
+        let _ = fuzz_bytes(data, header);
+    }
+
+    assert!(fuzz_str(b"a=\xc3", pairs).is_none());
+    assert_eq!(fuzz_str(b"a=1,b=2", pairs).unwrap().unwrap(), [("a", 1), ("b", 2)]);
+    assert_eq!(fuzz_bytes(b"PEAR\x02abc", header).unwrap(), b"ab");
+}
+
+#[test]
+#[should_panic(expected = "isn't deterministic")]
+fn test_nondeterministic() {
+    let calls = Cell::new(0);
+    fuzz_str(b"aaaa", |input| {
+        calls.set(calls.get() + 1);
+        take_n(input, calls.get())
+    });
+}
+
+#[test]
+#[should_panic(expected = "context of `bad` is out of bounds")]
+fn test_bad_context() {
+    fuzz_str(b"a", |input: &mut Text<'_>| -> Result<(), Text<'_>> {
+        let mut error = eat(input, 'b').map(|_| ()).unwrap_err();
+        let span = pear::input::Span { start: (1, 1, 0), end: (1, 3, 2), snippet: None };
+        error.push_context(Some(span), pear::input::ParserInfo::new("bad", false));
+        Err(error)
+    });
+}
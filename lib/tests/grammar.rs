@@ -0,0 +1,76 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::macros::grammar;
+
+#[grammar]
+mod list {
+    use pear::input::{Text, Result};
+    use pear::{macros::*, parsers::*};
+
+    type Input<'a> = Text<'a>;
+
+    #[parser(doc_grammar)]
+    pub fn list<'a>(input: &mut Input<'a>) -> Result<Vec<u32>, Input<'a>> {
+        eat('[')?;
+        let mut items = vec![];
+        while let Some(item) = parse_try!(item()) {
+            items.push(item);
+            if parse_try!(eat(',')).is_none() {
+                break;
+            }
+        }
+
+        eat(']')?;
+        items
+    }
+
+    #[parser]
+    pub fn items<'a>(input: &mut Input<'a>) -> Result<Vec<u32>, Input<'a>> {
+        let mut items = vec![];
+        loop {
+            items.push(item()?);
+            if parse_try!(eat(';')).is_none() {
+                break;
+            }
+        }
+
+        items
+    }
+
+    #[parser]
+    fn item<'a>(input: &mut Input<'a>) -> Result<u32, Input<'a>> {
+        switch! {
+            n@number() => n,
+            eat_slice("nil") => 0,
+            _ => parse_error!("expected an item")?
+        }
+    }
+
+    #[parser]
+    fn number<'a>(input: &mut Input<'a>) -> Result<u32, Input<'a>> {
+        let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+        digits.parse().unwrap_or(u32::MAX)
+    }
+
+    #[test]
+    fn test_grammar_parses() {
+        assert_eq!(parse!(list: &mut Text::from("[1,nil,3]")).unwrap(), vec![1, 0, 3]);
+        assert_eq!(parse!(list: &mut Text::from("[1,]")).unwrap(), vec![1]);
+        assert_eq!(parse!(list: &mut Text::from("[]")).unwrap(), vec![]);
+        assert!(parse!(list: &mut Text::from("[,]")).is_err());
+
+        assert_eq!(parse!(items: &mut Text::from("1;2")).unwrap(), vec![1, 2]);
+        assert!(parse!(items: &mut Text::from("1;")).is_err());
+    }
+}
+
+#[test]
+fn test_grammar_listing() {
+    let productions: Vec<_> = list::GRAMMAR.lines().collect();
+    assert_eq!(productions, [
+        "list = '[' [ item { ',' item } [ ',' ] ] ']' ;",
+        "items = item { ';' item } ;",
+        "item = ( number | \"nil\" ) ;",
+        "number = ? c.is_ascii_digit() ? { ? c.is_ascii_digit() ? } ;",
+    ]);
+}
@@ -0,0 +1,61 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Graphemes, Input, Result};
+use pear::macros::{parser, parse};
+use pear::parsers::*;
+
+#[parser]
+fn letter<'a>(input: &mut Graphemes<'a>) -> Result<&'a str, Graphemes<'a>> {
+    eat_if(|g: &&str| g.starts_with(char::is_alphabetic))?
+}
+
+#[parser]
+fn word<'a>(input: &mut Graphemes<'a>) -> Result<Vec<&'a str>, Graphemes<'a>> {
+    let mut letters = vec![];
+    while let Ok(l) = letter(input) {
+        letters.push(l);
+    }
+
+    letters
+}
+
+#[test]
+fn test_clusters_are_tokens() {
+    let result = parse!(word: &mut Graphemes::from("ne\u{301}e\u{308}"));
+    assert_eq!(result.unwrap(), ["n", "e\u{301}", "e\u{308}"]);
+
+    let mut input = Graphemes::from("🇯🇵🇺🇸a\r\nb");
+    assert_eq!(input.eat(|_| true), Some("🇯🇵"));
+    assert_eq!(input.eat(|_| true), Some("🇺🇸"));
+    assert_eq!(input.eat(|_| true), Some("a"));
+    assert_eq!(input.eat(|_| true), Some("\r\n"));
+    assert_eq!(input.eat(|_| true), Some("b"));
+    assert_eq!(input.token(), None);
+}
+
+#[test]
+fn test_emoji_sequences() {
+    let mut input = Graphemes::from("👍🏽👨‍💻✌\u{fe0f}x");
+    assert_eq!(input.skip(|g| g.chars().count() > 1), 3);
+    assert_eq!(eat(&mut input, "x").unwrap(), "x");
+}
+
+#[test]
+fn test_slices_respect_clusters() {
+    let mut input = Graphemes::from("e\u{301}x");
+    assert!(eat_slice(&mut input, "e").is_err());
+    assert!(eat(&mut input, "e").is_err());
+    assert_eq!(eat(&mut input, "e\u{301}").unwrap(), "e\u{301}");
+    assert_eq!(eat_slice(&mut input, "x").unwrap(), "x");
+
+    let mut input = Graphemes::from("ab\u{301}c");
+    assert_eq!(take_while(&mut input, |&g| g != "c").unwrap(), "ab\u{301}");
+    assert!(eof(&mut input).is_err());
+}
+
+#[test]
+fn test_context() {
+    let error = parse!(word: &mut Graphemes::from("ab\n!")).unwrap_err();
+    let context = error.contexts[0].context.unwrap();
+    assert_eq!(context.end, (1, 3, 2));
+}
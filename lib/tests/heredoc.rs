@@ -0,0 +1,67 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn here_doc<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    heredoc(|input| {
+        eat_slice(input, "<<")?;
+        let tag = take_some_while(input, |c: &char| c.is_ascii_alphabetic())?;
+        eat(input, '\n')?;
+        Ok(format!("\n{}", tag))
+    })?
+}
+
+#[parser]
+fn raw_string<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    heredoc(|input| {
+        eat(input, 'r')?;
+        let hashes = take_while(input, |&c| c == '#')?;
+        eat(input, '"')?;
+        Ok(format!("\"{}", hashes))
+    })?
+}
+
+#[parser]
+fn here_docs<'a>(input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+    let docs: Vec<_> = series(here_doc, ' ')?;
+    docs
+}
+
+#[test]
+fn test_heredoc() {
+    let result = parse!(here_doc: &mut Text::from("<<EOF\nline one\n  line two\nEOF"));
+    assert_eq!(result.unwrap(), "line one\n  line two");
+
+    let result = parse!(here_docs: &mut Text::from("<<A\nB\nA <<B\nA\nB"));
+    assert_eq!(result.unwrap(), vec!["B", "A"]);
+}
+
+#[test]
+fn test_raw_string() {
+    let result = parse!(raw_string: &mut Text::from(r#"r"plain""#));
+    assert_eq!(result.unwrap(), "plain");
+
+    let result = parse!(raw_string: &mut Text::from(r##"r#""#"##));
+    assert_eq!(result.unwrap(), "");
+
+    let result = parse!(raw_string: &mut Text::from(r####"r###"a "## b"###"####));
+    assert_eq!(result.unwrap(), r###"a "## b"###);
+}
+
+#[test]
+fn test_unterminated() {
+    let error = parse!(here_doc: &mut Text::from("<<EOF\nline\nEO")).unwrap_err();
+    assert!(error.committed);
+    assert!(error.to_string().contains("EOF"), "{}", error);
+
+    let error = parse!(raw_string: &mut Text::from(r##"r#"a""##)).unwrap_err();
+    assert!(error.committed);
+}
+
+#[test]
+fn test_open_fails() {
+    let error = parse!(here_doc: &mut Text::from("<EOF\nEOF")).unwrap_err();
+    assert!(!error.committed);
+}
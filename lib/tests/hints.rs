@@ -0,0 +1,78 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, ParseError, Result};
+use pear::error::{Expected, Hint};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn name<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_alphabetic())?
+}
+
+#[parser]
+fn call<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    let name = name()?;
+    with_help("arguments are enclosed in parentheses", |i| eat(i, '('))?;
+    eat(')')?;
+    name
+}
+
+#[parser]
+fn boolean<'a>(input: &mut Text<'a>) -> Result<bool, Text<'a>> {
+    let found = name()?;
+    match found {
+        "true" => true,
+        "false" => false,
+        _ => {
+            let span = parse_context!().unwrap();
+            let message = format!("`{}` is not a boolean", found);
+            return Err(ParseError::<Text<'a>>::new(Expected::Other(message.into()))
+                .with_suggestion(span, found.to_lowercase())
+                .with_note("booleans are lowercase"));
+        }
+    }
+}
+
+#[parser]
+fn a_or_b<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    alt(&[|i| a(i), |i| b(i)])?
+}
+
+fn a<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    eat(input, 'a').map_err(|e| e.with_help("try `a`"))
+}
+
+fn b<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    eat(input, 'b').map_err(|e| e.with_note("or `b`"))
+}
+
+#[test]
+fn test_with_help() {
+    assert_eq!(parse!(call: &mut Text::from("f()")).unwrap(), "f");
+
+    let error = parse!(call: &mut Text::from("f[]")).unwrap_err();
+    assert_eq!(error.help().collect::<Vec<_>>(), ["arguments are enclosed in parentheses"]);
+    assert_eq!(error.notes().count(), 0);
+    assert!(error.to_string().ends_with("\n = help: arguments are enclosed in parentheses"));
+
+    // Errors after `with_help` succeeds have no help.
+    let error = parse!(call: &mut Text::from("f(]")).unwrap_err();
+    assert!(error.hints.is_empty());
+}
+
+#[test]
+fn test_suggestion() {
+    let error = parse!(boolean: &mut Text::from("True")).unwrap_err();
+    let suggestion = error.suggestions().next().unwrap();
+    assert_eq!(suggestion.replacement, "true");
+    assert_eq!(suggestion.context.snippet, Some("True"));
+    assert_eq!(error.notes().collect::<Vec<_>>(), ["booleans are lowercase"]);
+    assert!(error.to_string().contains("= suggestion: replace 1:1 to 1:5 \"True\" with `true`"));
+    assert!(error.to_string().ends_with("= note: booleans are lowercase"));
+}
+
+#[test]
+fn test_merged_hints() {
+    let error = parse!(a_or_b: &mut Text::from("x")).unwrap_err();
+    assert_eq!(error.hints[..], [Hint::Help("try `a`".into()), Hint::Note("or `b`".into())]);
+}
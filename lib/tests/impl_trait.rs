@@ -0,0 +1,70 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result, Spanned};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Text<'a>;
+
+#[parser]
+fn word_with<'a>(
+    input: &mut Input<'a>,
+    mut cond: impl FnMut(&char) -> bool
+) -> Result<&'a str, Input<'a>> {
+    take_some_while(|c: &char| cond(c))?
+}
+
+#[parser]
+fn letters<'a>(input: &mut Input<'a>) -> Result<impl Iterator<Item = char> + 'a, Input<'a>> {
+    word_with(|c: &char| c.is_alphabetic())?.chars()
+}
+
+#[parser(spanned)]
+fn digits<'a>(input: &mut Input<'a>) -> Result<Spanned<impl Iterator<Item = u32> + 'a>, Input<'a>> {
+    word_with(|c: &char| c.is_ascii_digit())?.chars().filter_map(|c| c.to_digit(10))
+}
+
+#[parser]
+fn items<'a, O>(
+    input: &mut Input<'a>,
+    mut item: impl FnMut(&mut Input<'a>) -> Result<O, Input<'a>>
+) -> Result<impl Iterator<Item = O>, Input<'a>> {
+    let mut items = vec![];
+    loop {
+        items.push(item()?);
+        if parse_try!(eat(',')).is_none() {
+            break;
+        }
+    }
+
+    items.into_iter()
+}
+
+#[parser]
+fn counted<'a>(input: &mut Input<'a>) -> Result<usize, Input<'a>> {
+    switch! {
+        chars@letters() => chars.count(),
+        digits@digits() => digits.value.sum::<u32>() as usize,
+        _ => parse_error!("expected letters or digits")?
+    }
+}
+
+#[test]
+fn test_impl_trait_argument() {
+    let word = word_with(&mut Text::from("aab"), |c: &char| *c == 'a').unwrap();
+    assert_eq!(word, "aa");
+
+    let counts: Vec<_> = items(&mut Text::from("abc,12,d"), counted).unwrap().collect();
+    assert_eq!(counts, vec![3, 3, 1]);
+}
+
+#[test]
+fn test_impl_trait_return() {
+    let letters: String = letters(&mut Text::from("abc1")).unwrap().collect();
+    assert_eq!(letters, "abc");
+
+    let digits = digits(&mut Text::from("123a")).unwrap();
+    assert_eq!(digits.span, 0..3);
+    assert_eq!(digits.value.collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    assert!(parse!(counted: &mut Text::from("!")).is_err());
+}
@@ -0,0 +1,77 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Input, Text, Fueled, Result};
+use pear::error::{ParseError, ErrorCode, Expected, Needed};
+use pear::session::Partial;
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn method<'a>(input: &mut Partial<'a>) -> Result<&'a [u8], Partial<'a>> {
+    switch! {
+        m@eat_slice(&b"GET"[..]) | m@eat_slice(&b"PUT"[..]) => m,
+        _ => parse_error!("unknown method")?
+    }
+}
+
+#[parser]
+fn methods<'a>(input: &mut Partial<'a>) -> Result<usize, Partial<'a>> {
+    let methods: Vec<_> = try_collect(|i| { let m = method(i)?; eat(i, b' ')?; Ok(m) })?;
+    methods.len()
+}
+
+#[parser]
+fn fueled_method<'a>(input: &mut Fueled<Partial<'a>>) -> Result<u8, Fueled<Partial<'a>>> {
+    eat(b'G')?;
+    eat(b'E')?
+}
+
+#[test]
+fn test_incomplete_error() {
+    let mut input = Partial::new(b"GE", 0, false);
+    let error = method(&mut input).unwrap_err();
+    assert!(error.is_incomplete());
+    assert!(error.committed);
+    assert_eq!(error.incomplete, Some(Needed::Size(1)));
+    assert_eq!(error.code(), ErrorCode::Incomplete);
+
+    let mut input = Partial::new(b"GE", 0, true);
+    let error = method(&mut input).unwrap_err();
+    assert!(!error.is_incomplete());
+    assert_eq!(error.code(), ErrorCode::Other);
+}
+
+#[test]
+fn test_incomplete_propagates_through_combinators() {
+    // With the stream closed, `try_collect` stops at the failed method.
+    let mut input = Partial::new(b"GET PUT GE", 0, true);
+    assert_eq!(methods(&mut input).unwrap(), 2);
+
+    // With more to come, the last method may yet parse, so the whole parse is
+    // incomplete rather than failed or successful.
+    let mut input = Partial::new(b"GET PUT GE", 0, false);
+    assert!(methods(&mut input).unwrap_err().is_incomplete());
+
+    // A method that can't parse no matter what follows still ends the series.
+    let mut input = Partial::new(b"GET PUT POST ", 0, false);
+    assert_eq!(methods(&mut input).unwrap(), 2);
+}
+
+#[test]
+fn test_wrapped_partial_input() {
+    let mut input = Fueled::new(Partial::new(b"G", 0, false), 10);
+    let error = fueled_method(&mut input).unwrap_err();
+    assert_eq!(input.needed(), Some(Needed::Size(1)));
+    assert!(error.is_incomplete());
+}
+
+#[test]
+fn test_complete_inputs() {
+    let mut input = Text::from("GE");
+    assert_eq!(input.needed(), None);
+    assert!(eat_slice(&mut input, "GET").is_err());
+    assert_eq!(input.needed(), None);
+
+    let error: ParseError<(), Expected<char, &str>> =
+        ParseError::incomplete(Expected::Eof(None), Needed::Unknown);
+    assert!(error.is_incomplete() && error.committed);
+}
@@ -0,0 +1,102 @@
+#![feature(proc_macro_hygiene)]
+
+use std::cell::Cell;
+
+use pear::input::{Text, Result};
+use pear::incremental::{Document, Memoized, MemoTable, memo};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Memoized<'a, Text<'a>>;
+
+thread_local!(static CALLS: Cell<usize> = const { Cell::new(0) });
+
+fn calls() -> usize {
+    CALLS.with(|calls| calls.replace(0))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    Number(u32),
+    List(Vec<Item>),
+}
+
+#[parser]
+fn number<'a>(input: &mut Input<'a>) -> Result<Item, Input<'a>> {
+    CALLS.with(|calls| calls.set(calls.get() + 1));
+    Item::Number(take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>().unwrap())
+}
+
+#[parser]
+fn list<'a>(input: &mut Input<'a>) -> Result<Item, Input<'a>> {
+    CALLS.with(|calls| calls.set(calls.get() + 1));
+    Item::List(delimited_collect('[', item, ',', ']')?)
+}
+
+#[parser]
+fn item<'a>(input: &mut Input<'a>) -> Result<Item, Input<'a>> {
+    switch! {
+        peek('[') => memo("list", list)?,
+        _ => memo("number", number)?
+    }
+}
+
+fn numbers(numbers: &[u32]) -> Item {
+    Item::List(numbers.iter().map(|&n| Item::Number(n)).collect())
+}
+
+#[test]
+fn test_reuse_after_edit() {
+    let mut document = Document::new("[[1,2],[3,4],[5,6]]");
+    let expected = Item::List(vec![numbers(&[1, 2]), numbers(&[3, 4]), numbers(&[5, 6])]);
+    assert_eq!(document.parse(item).unwrap(), expected);
+    assert_eq!(calls(), 10);
+
+    // Reparsing without an edit reuses the outermost list.
+    assert_eq!(document.parse(item).unwrap(), expected);
+    assert_eq!(calls(), 0);
+
+    // Replace `3` with `30`: only the number, its list, and the outer list
+    // are reparsed.
+    document.edit(8..9, "30");
+    assert_eq!(document.source(), "[[1,2],[30,4],[5,6]]");
+    let expected = Item::List(vec![numbers(&[1, 2]), numbers(&[30, 4]), numbers(&[5, 6])]);
+    assert_eq!(document.parse(item).unwrap(), expected);
+    assert_eq!(calls(), 3);
+}
+
+#[test]
+fn test_insertion_and_deletion() {
+    let mut document = Document::new("[1,2,3]");
+    document.parse(item).unwrap();
+    calls();
+
+    document.edit(3..3, "7");
+    assert_eq!(document.parse(item).unwrap(), numbers(&[1, 72, 3]));
+    assert_eq!(calls(), 2);
+
+    // Remove `1,`: the shifted `72` and `3` are reused.
+    document.edit(1..3, "");
+    assert_eq!(document.source(), "[72,3]");
+    assert_eq!(document.parse(item).unwrap(), numbers(&[72, 3]));
+    assert_eq!(calls(), 1);
+
+    document.edit(4..6, "");
+    assert!(document.parse(item).is_err());
+}
+
+#[test]
+fn test_memo_table_edits() {
+    let mut table = MemoTable::new();
+    let mut input = Memoized::new(Text::from("12 34"), &mut table);
+    memo(&mut input, "number", number).unwrap();
+    eat(&mut input, ' ').unwrap();
+    memo(&mut input, "number", number).unwrap();
+
+    assert_eq!(table.get("number", 0), Some(0..2));
+    assert_eq!(table.get("number", 3), Some(3..5));
+
+    table.edit(2..3, "   ".len());
+    assert_eq!(table.get("number", 0), None);
+    assert_eq!(table.get("number", 5), Some(5..7));
+    assert_eq!(table.len(), 1);
+}
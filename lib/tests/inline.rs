@@ -0,0 +1,44 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::Text;
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser(inline)]
+fn digit<'a>(input: &mut Text<'a>) -> Result<'a, u32> {
+    eat_if(|c: &char| c.is_ascii_digit())?.to_digit(10).unwrap()
+}
+
+#[parser(inline, rewind, commit)]
+fn pair<'a>(input: &mut Text<'a>) -> Result<'a, (u32, u32)> {
+    let a = digit()?;
+    eat(',')?;
+    (a, digit()?)
+}
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<'a, u32> {
+    let mut value = 0;
+    while let Ok(d) = digit(input) {
+        value = value * 10 + d;
+    }
+
+    value
+}
+
+#[test]
+fn test_inline_parsers() {
+    assert_eq!(parse!(number: &mut Text::from("1234")).unwrap(), 1234);
+    assert_eq!(parse!(pair: &mut Text::from("4,2")).unwrap(), (4, 2));
+}
+
+#[test]
+fn test_inline_errors_keep_context() {
+    let mut input = Text::from("4;2");
+    let error = pair(&mut input).unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.parsers().map(|p| p.name).collect::<Vec<_>>(), ["eat", "pair"]);
+    assert_eq!(error.contexts[1].context.unwrap().snippet, Some("4"));
+    assert_eq!(eat_any(&mut input).unwrap(), '4');
+}
@@ -0,0 +1,98 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Input as _, InputSet, FileInput, FileId, Text, Show, Result};
+use pear::macros::{parser, parse};
+use pear::parsers::*;
+
+type Input<'a> = FileInput<'a, Text<'a>>;
+
+enum Line<'a> {
+    Include(&'a str),
+    Word(&'a str),
+}
+
+#[parser]
+fn line<'a>(input: &mut Input<'a>) -> Result<Line<'a>, Input<'a>> {
+    let line = match eat(input, '@') {
+        Ok(_) => Line::Include(take_some_while(|c: &char| c.is_ascii_alphanumeric() || *c == '.')?),
+        Err(_) => Line::Word(take_some_while(|c: &char| c.is_alphabetic())?),
+    };
+
+    eat('\n')?;
+    line
+}
+
+#[parser]
+fn lines<'a>(input: &mut Input<'a>) -> Result<Vec<Line<'a>>, Input<'a>> {
+    let mut lines = vec![];
+    while input.token().is_some() {
+        let line = line()?;
+        lines.push(line);
+    }
+
+    lines
+}
+
+// Parses the file `id` and, recursively, the files it includes, collecting
+// every word in order.
+fn expand<'a>(
+    files: &InputSet<'a>,
+    id: FileId,
+    words: &mut Vec<&'a str>
+) -> Result<(), Input<'a>> {
+    for line in parse!(lines: &mut files.input(id))? {
+        match line {
+            Line::Word(word) => words.push(word),
+            Line::Include(name) => expand(files, files.find(name).expect("file"), words)?,
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_registration() {
+    let mut files = InputSet::new();
+    assert!(files.is_empty());
+
+    let a = files.add("a.txt", "a\n");
+    let b = files.add("b.txt", "b\n");
+    let c = files.add("a.txt", "c\n");
+
+    assert_eq!(files.len(), 3);
+    assert_eq!((a.index(), b.index(), c.index()), (0, 1, 2));
+    assert_eq!(files.find("a.txt"), Some(a));
+    assert_eq!(files.find("c.txt"), None);
+    assert_eq!((files.name(b), files.text(b)), ("b.txt", "b\n"));
+    assert_eq!(files.ids().collect::<Vec<_>>(), [a, b, c]);
+
+    let input = files.input(c);
+    assert_eq!((input.file(), input.name()), (c, "a.txt"));
+}
+
+#[test]
+fn test_includes() {
+    let mut files = InputSet::new();
+    let main = files.add("main", "one\n@lib\nfour\n");
+    files.add("lib", "two\n@more\n");
+    files.add("more", "three\n");
+
+    let mut words = vec![];
+    expand(&files, main, &mut words).unwrap();
+    assert_eq!(words, ["one", "two", "three", "four"]);
+}
+
+#[test]
+fn test_error_file() {
+    let mut files = InputSet::new();
+    let main = files.add("main", "one\n@lib\n");
+    let lib = files.add("lib", "two\nthree!\n");
+
+    let mut words = vec![];
+    let error = expand(&files, main, &mut words).unwrap_err();
+    let context = error.contexts.last().unwrap().context.unwrap();
+    assert_eq!((context.file, context.name), (lib, "lib"));
+    assert_eq!(context.context.end, (2, 6, 9));
+    assert!(format!("{}", &context as &dyn Show).starts_with("lib:1:1 to 2:6"));
+    assert!(error.to_string().contains("lib:"));
+}
@@ -0,0 +1,67 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Interned, Intern, Interner, StringInterner, Symbol, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Interned<Text<'a>, StringInterner>;
+
+#[parser]
+fn word<'a, N: Interner<&'a str>>(input: &mut Interned<Text<'a>, N>) -> Result<&'a str, Interned<Text<'a>, N>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[parser(rewind)]
+fn assignment<'a>(input: &mut Input<'a>) -> Result<(Symbol, Symbol), Input<'a>> {
+    let name = interned(word)?;
+    eat('=')?;
+    (name, interned(word)?)
+}
+
+#[test]
+fn test_string_interner() {
+    let mut interner = StringInterner::new();
+    assert!(interner.is_empty());
+
+    let a = interner.intern("a");
+    let b = interner.intern(String::from("b"));
+    assert_eq!(interner.intern("a"), a);
+    assert_eq!((a.index(), b.index()), (0, 1));
+    assert_eq!(interner.resolve(b), Some("b"));
+    assert_eq!(interner.get("b"), Some(b));
+    assert_eq!(interner.get("c"), None);
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn test_interned_input() {
+    let mut input = Interned::new(Text::from("x=y"), StringInterner::new());
+    let (x, y) = parse!(assignment: &mut input).unwrap();
+    assert_eq!(input.interner().resolve(x), Some("x"));
+    assert_eq!(input.interner().resolve(y), Some("y"));
+
+    // Symbols interned by a parser that was rewound stay interned.
+    let mut input = Interned::new(Text::from("x!"), StringInterner::new());
+    assert!(parse!(assignment: &mut input).is_err());
+    assert_eq!(input.interner().get("x").map(Symbol::index), Some(0));
+    assert_eq!(input.intern("y").index(), 1);
+}
+
+/// Interns by length, to check that any `Interner` can be plugged in.
+struct ByLength(Vec<usize>);
+
+impl<'a> Interner<&'a str> for ByLength {
+    type Symbol = usize;
+
+    fn intern(&mut self, slice: &'a str) -> usize {
+        self.0.push(slice.len());
+        slice.len()
+    }
+}
+
+#[test]
+fn test_custom_interner() {
+    let mut input = Interned::new(Text::from("abc"), ByLength(vec![]));
+    let length = interned(&mut input, word).unwrap();
+    assert_eq!(length, 3);
+    assert_eq!(input.into_inner().1 .0, vec![3]);
+}
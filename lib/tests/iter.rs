@@ -0,0 +1,62 @@
+#![feature(proc_macro_hygiene)]
+
+use std::str::Chars;
+
+use pear::input::IterInput;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = IterInput<Chars<'a>>;
+type Result<'a, T> = pear::input::Result<T, Input<'a>>;
+
+#[parser]
+fn word<'a>(input: &mut Input<'a>) -> Result<'a, String> {
+    let chars = take_some_while(|c: &char| c.is_ascii_alphabetic())?;
+    chars.into_iter().collect::<String>()
+}
+
+#[parser]
+fn words<'a>(input: &mut Input<'a>) -> Result<'a, Vec<String>> {
+    let words: Vec<String> = series(word, ',')?;
+    words
+}
+
+#[parser(rewind)]
+fn abc<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    eat_slice(&['a', 'b', 'c'][..])?;
+}
+
+#[parser]
+fn abc_or_abd<'a>(input: &mut Input<'a>) -> Result<'a, &'static str> {
+    switch! {
+        abc() => "abc",
+        eat_slice(&['a', 'b', 'd'][..]) => "abd",
+        _ => parse_error!("expected abc or abd")?
+    }
+}
+
+#[test]
+fn test_iter_input() {
+    let result = parse!(words: &mut IterInput::new("a,bc,def".chars()));
+    assert_eq!(result.unwrap(), vec!["a", "bc", "def"]);
+
+    let result = parse!(words: &mut IterInput::new("a,bc,".chars()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_iter_input_rewind() {
+    let result = parse!(abc_or_abd: &mut IterInput::new("abc".chars()));
+    assert_eq!(result.unwrap(), "abc");
+
+    let result = parse!(abc_or_abd: &mut IterInput::new("abd".chars()));
+    assert_eq!(result.unwrap(), "abd");
+
+    let result = parse!(abc_or_abd: &mut IterInput::new("abe".chars()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_iter_input_context() {
+    let err = parse!(words: &mut IterInput::new("ab,cd,!".chars())).unwrap_err();
+    assert_eq!(err.contexts.last().and_then(|c| c.context.clone()), Some(0..6));
+}
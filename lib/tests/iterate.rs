@@ -0,0 +1,42 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn record<'a>(input: &mut Text<'a>) -> Result<(&'a str, u32), Text<'a>> {
+    let key = take_some_while(|c: &char| c.is_alphabetic())?;
+    eat('=')?;
+    let value = take_some_while(|c: &char| c.is_ascii_digit())?;
+    eat(';')?;
+    (key, value.parse::<u32>().unwrap())
+}
+
+#[test]
+fn test_iterate_to_eof() {
+    let mut input = Text::from("a=1;b=22;c=3;");
+    let mut records = iterate(&mut input, record);
+    assert_eq!(records.next(), Some(("a", 1)));
+    assert_eq!(records.by_ref().map(|(_, v)| v).sum::<u32>(), 25);
+    assert_eq!(records.next(), None);
+    assert!(records.finish().is_ok());
+    assert!(eof(&mut input).is_ok());
+}
+
+#[test]
+fn test_iterate_stops_at_error() {
+    let mut input = Text::from("a=1;b=;c=3;");
+    let mut records = iterate(&mut input, record);
+    assert_eq!(records.next(), Some(("a", 1)));
+    assert_eq!(records.next(), None);
+    assert_eq!(records.next(), None);
+
+    let error = records.finish().unwrap_err();
+    assert!(error.to_string().contains("expected"));
+}
+
+#[test]
+fn test_iterate_empty() {
+    let mut input = Text::from("");
+    assert_eq!(iterate(&mut input, record).count(), 0);
+}
@@ -0,0 +1,59 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Position, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+const LITERALS: &[&str] = &["true", "false", "null"];
+
+#[parser]
+fn entry<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'static str), Text<'a>> {
+    let key = take_some_while(|c: &char| c.is_alphabetic())?;
+    eat_slice(" = ")?;
+    (key, keyword_of(LITERALS)?)
+}
+
+#[test]
+fn test_keywords() {
+    assert_eq!(parse!(entry: &mut Text::from("a = true")).unwrap(), ("a", "true"));
+    assert_eq!(parse!(entry: &mut Text::from("b = null")).unwrap(), ("b", "null"));
+
+    // Keywords must be whole identifiers.
+    let mut input = Text::from("nullable");
+    assert!(keyword_of(&mut input, LITERALS).is_err());
+    assert_eq!(input.position(), 0);
+}
+
+#[test]
+fn test_suggestions() {
+    let cases = [("ture", "true"), ("fasle", "false"), ("nul", "null"), ("False", "false")];
+    for (typo, keyword) in cases.iter() {
+        let mut input = Text::from(*typo);
+        let error = keyword_of(&mut input, LITERALS).unwrap_err();
+        assert_eq!(input.position(), 0);
+
+        let suggestion = error.suggestions().next().unwrap();
+        assert_eq!(suggestion.replacement, *keyword);
+        assert_eq!(suggestion.context.snippet, Some(*typo));
+        assert_eq!(error.expected(), ["`true`", "`false`", "`null`"]);
+    }
+
+    let error = parse!(entry: &mut Text::from("key = flase")).unwrap_err();
+    let suggestion = error.suggestions().next().unwrap();
+    assert_eq!((suggestion.context.start.2, suggestion.context.end.2), (6, 11));
+    assert!(error.to_string().contains("with `false`"));
+}
+
+#[test]
+fn test_no_suggestion() {
+    for input in ["yes", "nothing", "", "1"].iter() {
+        let error = keyword_of(&mut Text::from(*input), LITERALS).unwrap_err();
+        assert!(error.hints.is_empty(), "{:?} has hints", input);
+    }
+}
+
+#[test]
+fn test_opt_keyword() {
+    let mut input = Text::from("nul");
+    assert_eq!(opt(&mut input, |i| keyword_of(i, LITERALS)).unwrap(), None);
+    assert_eq!(input.position(), 0);
+}
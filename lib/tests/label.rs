@@ -0,0 +1,56 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?
+}
+
+#[parser(label = "a boolean")]
+fn boolean<'a>(input: &mut Text<'a>) -> Result<bool, Text<'a>> {
+    match take_some_while(|c: &char| c.is_alphabetic())? {
+        "true" => true,
+        "false" => false,
+        _ => parse_error!("not a boolean")?
+    }
+}
+
+#[parser]
+fn value<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    switch! {
+        peek('t') | peek('f') => if boolean()? { "true" } else { "false" },
+        _ => label("a value", number)?
+    }
+}
+
+#[parser]
+fn list<'a>(input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+    let values: Vec<_> = delimited_collect('[', value, ',', ']')?;
+    values
+}
+
+#[test]
+fn test_label_combinator() {
+    assert_eq!(parse!(list: &mut Text::from("[1,true]")).unwrap(), vec!["1", "true"]);
+
+    let err = parse!(list: &mut Text::from("[1,x]")).unwrap_err();
+    assert_eq!(err.expected(), vec!["a value"]);
+    assert_eq!(err.found(), Some(&'x'));
+    assert!(err.to_string().starts_with("expected a value but found `x`"));
+
+    let err = parse!(list: &mut Text::from("[1,")).unwrap_err();
+    assert!(err.to_string().starts_with("expected a value but none was found"));
+}
+
+#[test]
+fn test_label_attribute() {
+    let err = parse!(value: &mut Text::from("tru")).unwrap_err();
+    assert_eq!(err.to_string().lines().next(), Some("not a boolean"));
+    assert_eq!(err.parsers().last().map(|p| p.name), Some("value"));
+
+    let err = parse!(boolean: &mut Text::from("1")).unwrap_err();
+    assert_eq!(err.expected(), vec!["a boolean"]);
+    assert!(err.to_string().starts_with("expected a boolean but found `1`"));
+}
@@ -0,0 +1,115 @@
+#![feature(proc_macro_hygiene)]
+
+mod common;
+
+use common::allocations;
+use pear::input::{Text, LazyContext, Position, Result};
+use pear::macros::{parser, parse, switch};
+use pear::combinators::opt;
+use pear::parsers::*;
+
+macro_rules! grammar {
+    ($I:ty) => {
+        #[parser]
+        fn word<'a>(input: &mut $I) -> Result<&'a str, $I> {
+            take_some_while(|c| c.is_ascii_alphabetic())?
+        }
+
+        #[parser]
+        pub fn item<'a>(input: &mut $I) -> Result<&'a str, $I> {
+            switch! {
+                eat('(') => (word()?, eat(')')?).0,
+                _ => word()?
+            }
+        }
+
+        #[parser]
+        pub fn list<'a>(input: &mut $I) -> Result<Vec<&'a str>, $I> {
+            let first = item()?;
+            let mut items = vec![first];
+            while eat(input, ',').is_ok() {
+                skip_while(|&c| c == ' ' || c == '\n')?;
+                let next = item()?;
+                items.push(next);
+            }
+
+            eof()?;
+            items
+        }
+    };
+}
+
+mod eager {
+    use super::*;
+    grammar!(Text<'a>);
+}
+
+mod lazy {
+    use super::*;
+    grammar!(LazyContext<Text<'a>>);
+}
+
+use eager::list as eager_list;
+use lazy::{list as lazy_list, item as lazy_item};
+
+#[test]
+fn test_success_is_unaffected() {
+    let source = "ab, (cd),\n ef";
+    let result = parse!(lazy_list: &mut LazyContext::new(Text::from(source)));
+    assert_eq!(result.unwrap(), ["ab", "cd", "ef"]);
+}
+
+#[test]
+fn test_contexts_are_ranges() {
+    let mut input = LazyContext::new(Text::from("ab,\n (cd"));
+    let error = parse!(lazy_list: &mut input).unwrap_err();
+    let contexts: Vec<_> = error.contexts.iter()
+        .map(|c| (c.parser.name, c.context.clone().unwrap()))
+        .collect();
+
+    assert_eq!(contexts, [("eat", 8..8), ("item", 5..8), ("list", 0..8)]);
+}
+
+#[test]
+fn test_resolve_matches_eager_contexts() {
+    for source in ["ab,\n (cd", "ab, cd,\n\n 1", "(ab) (cd)", ""] {
+        let expected = parse!(eager_list: &mut Text::from(source)).unwrap_err();
+
+        let mut input = LazyContext::new(Text::from(source));
+        let error = parse!(lazy_list: &mut input).unwrap_err();
+        let position = input.position();
+        let resolved = input.resolve(error);
+        assert_eq!(input.position(), position);
+
+        assert_eq!(resolved.to_string(), expected.to_string());
+        assert_eq!(resolved.contexts.len(), expected.contexts.len());
+        for (a, b) in resolved.contexts.iter().zip(expected.contexts.iter()) {
+            assert_eq!(a.parser.name, b.parser.name);
+            assert_eq!(a.context, b.context);
+        }
+    }
+}
+
+#[test]
+fn test_ranges_are_reportable() {
+    let source = "ab,\n (cd";
+    let error = parse!(lazy_list: &mut LazyContext::new(Text::from(source))).unwrap_err();
+    let report = error.report(source);
+    assert_eq!(report.location(), 8..8);
+    assert_eq!(report.labels()[0], (5..8, "while parsing item".to_string()));
+}
+
+#[test]
+fn test_speculative_failures_allocate_only_outside_opt() {
+    for source in ["(ab", "1", ""] {
+        let mut input = LazyContext::new(Text::from(source));
+        let (result, count) = allocations(|| opt(&mut input, lazy_item));
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(count, 0);
+
+        let mut input = LazyContext::new(Text::from(source));
+        let (result, count) = allocations(|| lazy_item(&mut input));
+        assert!(result.is_err());
+        assert!(count > 0);
+    }
+}
@@ -0,0 +1,129 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?.parse::<i64>().unwrap()
+}
+
+#[parser]
+fn atom<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+    switch! {
+        eat('(') => { let value = expr()?; eat(')')?; value },
+        _ => number()?
+    }
+}
+
+// term = term ('*' | '/') atom | atom
+#[parser(left_recursive)]
+fn term<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+    switch! {
+        lhs@term() => switch! {
+            eat('*') => lhs * atom()?,
+            _ => { eat('/')?; lhs / atom()? }
+        },
+        _ => atom()?
+    }
+}
+
+// expr = expr ('+' | '-') term | term
+#[parser(left_recursive)]
+fn expr<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+    switch! {
+        lhs@expr() => switch! {
+            eat('+') => lhs + term()?,
+            _ => { eat('-')?; lhs - term()? }
+        },
+        _ => term()?
+    }
+}
+
+// list = list ',' number | number, collecting in order.
+#[parser(left_recursive)]
+fn list<'a>(input: &mut Text<'a>) -> Result<Vec<i64>, Text<'a>> {
+    switch! {
+        items@list() => {
+            eat(',')?;
+            let mut items = items;
+            items.push(number()?);
+            items
+        },
+        _ => { let first = number()?; vec![first] }
+    }
+}
+
+fn eval(string: &str) -> Result<i64, Text<'_>> {
+    parse!(expr: &mut Text::from(string))
+}
+
+#[test]
+fn test_left_associativity() {
+    assert_eq!(eval("10-3-2").unwrap(), 5);
+    assert_eq!(eval("64/4/2").unwrap(), 8);
+    assert_eq!(eval("7").unwrap(), 7);
+}
+
+#[test]
+fn test_precedence_and_nesting() {
+    assert_eq!(eval("1+2*3+4").unwrap(), 11);
+    assert_eq!(eval("2*(10-3-2)-1").unwrap(), 9);
+    assert_eq!(eval("((1))-(2-(3-4))").unwrap(), -2);
+}
+
+#[test]
+fn test_left_recursive_collection() {
+    let list = parse!(list: &mut Text::from("1,2,3")).unwrap();
+    assert_eq!(list, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_left_recursive_errors() {
+    assert!(eval("").is_err());
+    assert!(eval("1+").is_err());
+    assert!(eval("(1+2").is_err());
+    assert!(eval("1+*2").is_err());
+}
+
+thread_local!(static PRODUCT_RUNS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) });
+
+// product = product '*' number | number, counting runs of its body.
+#[parser(left_recursive)]
+fn product<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+    PRODUCT_RUNS.with(|runs| runs.set(runs.get() + 1));
+    switch! {
+        lhs@product() => { eat('*')?; lhs * number()? },
+        _ => number()?
+    }
+}
+
+#[parser(rewind)]
+fn exclaimed<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+    let value = product()?;
+    eat('!')?;
+    value
+}
+
+// statement = product '!' | product '?'
+#[parser(left_recursive)]
+fn statement<'a>(input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+    switch! {
+        value@exclaimed() => value,
+        _ => { let value = product()?; eat('?')?; value }
+    }
+}
+
+#[test]
+fn test_grown_results_are_memoized() {
+    PRODUCT_RUNS.with(|runs| runs.set(0));
+    assert_eq!(parse!(statement: &mut Text::from("2*3?")).unwrap(), 6);
+
+    // The seed for `product` grows to `2`, then `2*3`, then stops growing: its
+    // body runs three times, and not again when `exclaimed` fails.
+    assert_eq!(PRODUCT_RUNS.with(|runs| runs.get()), 3);
+
+    // The memo is discarded once `statement` returns.
+    assert_eq!(parse!(product: &mut Text::from("4*5")).unwrap(), 20);
+    assert_eq!(PRODUCT_RUNS.with(|runs| runs.get()), 6);
+}
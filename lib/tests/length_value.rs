@@ -0,0 +1,79 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Cursor, Limited, Text, Result};
+use pear::macros::{parser, parse, switch, parse_error};
+use pear::parsers::*;
+use pear::parsers::binary::{u8, be_u16};
+use pear::combinators::*;
+
+type Input<'a> = Cursor<'a, u8>;
+type Sub<'l, 'a> = Limited<'l, Input<'a>>;
+
+#[derive(Debug, PartialEq)]
+enum Tlv {
+    Int(u16),
+    Bytes(Vec<u8>),
+}
+
+#[parser]
+fn bytes_value<'a, 'l>(input: &mut Sub<'l, 'a>) -> Result<Vec<u8>, Sub<'l, 'a>> {
+    take_while(|_| true)?.to_vec()
+}
+
+#[parser]
+fn tlv<'a>(input: &mut Input<'a>) -> Result<Tlv, Input<'a>> {
+    switch! {
+        eat(&1) => Tlv::Int(length_value(u8, |i| be_u16(i))?),
+        eat(&2) => Tlv::Bytes(length_value(u8, bytes_value)?),
+        _ => parse_error!("unknown tag")?
+    }
+}
+
+#[parser]
+fn tlvs<'a>(input: &mut Input<'a>) -> Result<Vec<Tlv>, Input<'a>> {
+    let values: Vec<_> = collect(tlv)?;
+    values
+}
+
+#[test]
+fn test_tlv() {
+    let data = [1, 2, 0x01, 0x02, 2, 3, 7, 8, 9, 2, 0];
+    let values = parse!(tlvs: &mut Cursor::from(&data[..])).unwrap();
+    assert_eq!(values, [Tlv::Int(0x0102), Tlv::Bytes(vec![7, 8, 9]), Tlv::Bytes(vec![])]);
+}
+
+#[test]
+fn test_length_mismatch() {
+    // Under-run: the value doesn't consume the declared length.
+    assert!(parse!(tlv: &mut Cursor::from(&[1, 3, 1, 2, 3][..])).is_err());
+
+    // Over-run: the value needs more than the declared length.
+    assert!(parse!(tlv: &mut Cursor::from(&[1, 1, 1, 2][..])).is_err());
+
+    // The declared length exceeds the input.
+    assert!(parse!(tlv: &mut Cursor::from(&[2, 5, 1, 2][..])).is_err());
+}
+
+#[parser]
+fn text_value<'a, 'l>(input: &mut Limited<'l, Text<'a>>) -> Result<&'a str, Limited<'l, Text<'a>>> {
+    take_while(|_| true)?
+}
+
+#[parser]
+fn text_record<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    let len = |i: &mut Text<'a>| take_some_while(i, |c| c.is_ascii_digit())
+        .map(|s| s.parse::<usize>().unwrap());
+
+    eat(':')?;
+    length_value(len, text_value)?
+}
+
+#[test]
+fn test_text_lengths_in_bytes() {
+    let mut input = Text::from(":3héllo");
+    assert_eq!(text_record(&mut input).unwrap(), "hé");
+    assert_eq!(take_while(&mut input, |_| true).unwrap(), "llo");
+
+    // The limit falls within the `é`: it is not taken.
+    assert!(parse!(text_record: &mut Text::from(":2hé")).is_err());
+}
@@ -0,0 +1,90 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Lexed, Modal, Mode, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Lexed<Text<'a>>;
+
+fn trivia(input: &mut Text<'_>) {
+    loop {
+        let _ = skip_while(input, |c: &char| c.is_whitespace());
+        if eat(input, '#').is_err() {
+            break;
+        }
+
+        let _ = skip_while(input, |&c| c != '\n');
+    }
+}
+
+#[parser]
+fn word<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    take_some_while(|c: &char| c.is_alphanumeric())?
+}
+
+#[parser]
+fn heredoc<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    eat_slice("<<")?;
+    let tag = word()?;
+    let body = with_mode(Mode::KeepWhitespace, |input| {
+        eat(input, '\n')?;
+        let body = take_while(input, |&c| c != '$')?;
+        eat(input, '$')?;
+        Ok(body)
+    })?;
+    let end = word()?;
+    if end != tag {
+        parse_error!("expected end tag `{}`", tag)?;
+    }
+
+    body
+}
+
+#[parser]
+fn words<'a>(input: &mut Input<'a>) -> Result<Vec<&'a str>, Input<'a>> {
+    let words: Vec<_> = collect(word)?;
+    words
+}
+
+#[test]
+fn test_skips_trivia() {
+    let input = "one  two # a comment\n\tthree # and another\n";
+    let result = parse!(words: &mut Lexed::new(Text::from(input), trivia));
+    assert_eq!(result.unwrap(), vec!["one", "two", "three"]);
+}
+
+#[test]
+fn test_keeps_whitespace_in_mode() {
+    let input = "<< EOF\n  # not a comment\n\tindented $ EOF  ";
+    let result = parse!(heredoc: &mut Lexed::new(Text::from(input), trivia));
+    assert_eq!(result.unwrap(), "  # not a comment\n\tindented ");
+
+    let input = "<< EOF\n body $ END";
+    let result = parse!(heredoc: &mut Lexed::new(Text::from(input), trivia));
+    assert!(result.unwrap_err().to_string().contains("expected end tag `EOF`"));
+}
+
+#[test]
+fn test_mode_stack() {
+    let mut input = Lexed::new(Text::from("  a b"), trivia);
+    assert_eq!(input.mode(), Mode::SkipWhitespace);
+
+    input.push_mode(Mode::KeepWhitespace);
+    input.push_mode(Mode::SkipWhitespace);
+    input.pop_mode();
+    assert_eq!(input.mode(), Mode::KeepWhitespace);
+    assert!(eat(&mut input, 'a').is_err());
+
+    input.pop_mode();
+    input.pop_mode();
+    assert_eq!(input.mode(), Mode::SkipWhitespace);
+    assert_eq!(eat(&mut input, 'a').unwrap(), 'a');
+}
+
+#[test]
+fn test_mode_restored_on_error() {
+    let mut input = Lexed::new(Text::from("  a"), trivia);
+    let result = with_mode(&mut input, Mode::KeepWhitespace, |input| eat(input, 'a'));
+    assert!(result.is_err());
+    assert_eq!(input.mode(), Mode::SkipWhitespace);
+    assert_eq!(eat(&mut input, 'a').unwrap(), 'a');
+}
@@ -0,0 +1,83 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Cursor, IterInput, Limited, Input, Result};
+use pear::macros::{parser, parse};
+use pear::parsers::*;
+use pear::combinators::*;
+
+type Sub<'l, 'a> = Limited<'l, Text<'a>>;
+
+#[parser]
+fn word<'a, 'l>(input: &mut Sub<'l, 'a>) -> Result<&'a str, Sub<'l, 'a>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[parser]
+fn field<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    limit(5, word)?
+}
+
+#[parser]
+fn fields<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+    let first = field()?;
+    eat(' ')?;
+    (first, field()?)
+}
+
+#[test]
+fn test_limit() {
+    assert_eq!(parse!(fields: &mut Text::from("hello world")).unwrap(), ("hello", "world"));
+    assert_eq!(parse!(fields: &mut Text::from("hi there")).unwrap(), ("hi", "there"));
+    assert!(parse!(fields: &mut Text::from("greetings world")).is_err());
+}
+
+#[parser]
+fn exact<'a, 'l>(input: &mut Sub<'l, 'a>) -> Result<&'a str, Sub<'l, 'a>> {
+    eat_slice("abc")?
+}
+
+#[test]
+fn test_limit_boundary_is_eof() {
+    let mut input = Text::from("abcdef");
+    assert!(limit(&mut input, 2, exact).is_err());
+    assert_eq!(limit(&mut input, 3, exact).unwrap(), "abc");
+
+    let mut limited = Limited::new(&mut input, 2);
+    assert_eq!(limited.remaining(), 2);
+    assert!(limited.has(2));
+    assert!(!limited.has(3));
+    assert_eq!(limited.slice(3), None);
+    assert_eq!(take_while(&mut limited, |_| true).unwrap(), "de");
+    assert_eq!(limited.token(), None);
+    assert!(eof(&mut limited).is_ok());
+    assert_eq!(input.token(), Some('f'));
+}
+
+#[test]
+fn test_limit_tokens() {
+    let data = [1u8, 2, 3, 4];
+    let mut input = Cursor::from(&data[..]);
+    let taken = limit(&mut input, 3, |i| take_while(i, |_| true)).unwrap();
+    assert_eq!(taken, &[1, 2, 3]);
+    assert_eq!(input.token(), Some(&4));
+}
+
+#[test]
+fn test_limit_multibyte() {
+    let mut input = Text::from("héllo");
+    let mut limited = Limited::new(&mut input, 2);
+    assert_eq!(limited.eat(|_| true), Some('h'));
+    assert_eq!(limited.token(), None);
+    assert_eq!(limited.skip(|_| true), 0);
+}
+
+#[test]
+fn test_limit_char_tokens() {
+    let mut input = IterInput::new("éab".chars());
+    let mut limited = Limited::new(&mut input, 2);
+    assert_eq!(limited.slice(2), Some(vec!['é', 'a']));
+    assert!(limited.has(2) && !limited.has(3));
+    assert_eq!(limited.take(|_| true), ['é', 'a']);
+    assert_eq!(limited.remaining(), 0);
+    assert_eq!(input.token(), Some('b'));
+}
@@ -0,0 +1,72 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, NormalizeNewlines, Result};
+use pear::{macros::*, parsers::*};
+use pear::parsers::lines::*;
+
+#[test]
+fn test_line_end() {
+    let input = &mut Text::from("\n\r\n\r\r");
+    assert_eq!(line_end(input).unwrap(), LineEnding::Lf);
+    assert_eq!(line_end(input).unwrap(), LineEnding::CrLf);
+    assert_eq!(line_end(input).unwrap(), LineEnding::Cr);
+    assert_eq!(line_end(input).unwrap(), LineEnding::Cr);
+    assert!(line_end(input).is_err());
+    assert!(line_end(&mut Text::from("a\n")).is_err());
+
+    assert_eq!(LineEnding::CrLf.as_str(), "\r\n");
+}
+
+#[test]
+fn test_line_and_lines() {
+    let input = &mut Text::from("a\r\n\rb");
+    assert_eq!(line(input).unwrap(), ("a", Some(LineEnding::CrLf)));
+    assert_eq!(line(input).unwrap(), ("", Some(LineEnding::Cr)));
+    assert_eq!(line(input).unwrap(), ("b", None));
+    assert!(line(input).is_err());
+
+    let all: Vec<_> = lines(&mut Text::from("x\ny\n")).unwrap();
+    assert_eq!(all, [("x", Some(LineEnding::Lf)), ("y", Some(LineEnding::Lf))]);
+
+    let none: Vec<_> = lines(&mut Text::from("")).unwrap();
+    assert!(none.is_empty());
+}
+
+type Input<'a> = NormalizeNewlines<Text<'a>>;
+
+#[parser]
+fn pair<'a>(input: &mut Input<'a>) -> Result<(&'a str, &'a str), Input<'a>> {
+    let key = take_some_while(|&c| c != '=' && c != '\n')?;
+    eat('=')?;
+    let value = take_while(|&c| c != '\n')?;
+    eat('\n')?;
+    (key, value)
+}
+
+#[parser]
+fn pairs<'a>(input: &mut Input<'a>) -> Result<Vec<(&'a str, &'a str)>, Input<'a>> {
+    let pairs: Vec<_> = pear::combinators::collect(pair)?;
+    pairs
+}
+
+#[test]
+fn test_normalize_newlines() {
+    let expected = [("a", "1"), ("b", ""), ("c", "3")];
+    for text in ["a=1\nb=\nc=3\n", "a=1\r\nb=\r\nc=3\r\n", "a=1\rb=\rc=3\r", "a=1\r\nb=\rc=3\n"] {
+        let mut input = NormalizeNewlines::new(Text::from(text));
+        assert_eq!(parse!(pairs: &mut input).unwrap(), expected, "{:?}", text);
+    }
+}
+
+#[test]
+fn test_normalized_tokens() {
+    let mut input = NormalizeNewlines::new(Text::from("a\r\n\r\nb"));
+    assert_eq!(eat_any(&mut input).unwrap(), 'a');
+    assert_eq!(peek_any(&mut input).unwrap(), '\n');
+    assert_eq!(skip_while(&mut input, |&c| c == '\n').unwrap(), 2);
+    assert_eq!(eat_any(&mut input).unwrap(), 'b');
+
+    let mut input = NormalizeNewlines::new(Text::from("a\r\nb\rc"));
+    assert_eq!(take_while(&mut input, |&c| c != 'c').unwrap(), "a\r\nb\r");
+    assert_eq!(eat_any(&mut input).unwrap(), 'c');
+}
@@ -0,0 +1,135 @@
+#![feature(proc_macro_hygiene)]
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use pear::input::{Text, Result};
+use pear::macros::{parser, parse, switch};
+use pear::parsers::*;
+
+type Input<'a> = Text<'a>;
+
+#[parser]
+fn digit<'a>(input: &mut Input<'a>) -> Result<char, Input<'a>> {
+    eat_if(|c| c.is_ascii_digit())?
+}
+
+#[parser]
+fn word<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    take_some_while(|c| c.is_ascii_alphabetic())?
+}
+
+#[parser]
+fn item<'a>(input: &mut Input<'a>) -> Result<String, Input<'a>> {
+    switch! {
+        c@digit() => c.to_string(),
+        _ => word()?.to_string()
+    }
+}
+
+thread_local! {
+    static RECORDS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn take_records() -> Vec<String> {
+    RECORDS.with(|records| records.take())
+}
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.target() == "pear" && metadata.level() <= log::Level::Debug
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if self.enabled(record.metadata()) {
+            let record = format!("log: {}", record.args());
+            RECORDS.with(|records| records.borrow_mut().push(record));
+        }
+    }
+
+    fn flush(&self) { }
+}
+
+struct Subscriber(AtomicU64);
+
+struct Fields(String);
+
+impl tracing::field::Visit for Fields {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+}
+
+impl tracing::Subscriber for Subscriber {
+    fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+        metadata.target() == "pear" && *metadata.level() <= tracing::Level::DEBUG
+    }
+
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let mut fields = Fields(format!("span: {}", span.metadata().name()));
+        span.record(&mut fields);
+        RECORDS.with(|records| records.borrow_mut().push(fields.0));
+        tracing::span::Id::from_u64(self.0.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+        let mut fields = Fields("record:".into());
+        values.record(&mut fields);
+        RECORDS.with(|records| records.borrow_mut().push(fields.0));
+    }
+
+    fn event(&self, event: &tracing::Event<'_>) {
+        let mut fields = Fields("event:".into());
+        event.record(&mut fields);
+        RECORDS.with(|records| records.borrow_mut().push(fields.0));
+    }
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) { }
+    fn enter(&self, _: &tracing::span::Id) { }
+    fn exit(&self, _: &tracing::span::Id) { }
+}
+
+#[test]
+fn test_log_records() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let result = parse!(item: &mut Text::from("ab"));
+    assert_eq!(result.unwrap(), "ab");
+    assert_eq!(take_records(), [
+        "log: enter logging::item",
+        "log: enter logging::digit",
+        "log: backtrack logging::digit (1:1)",
+        "log: enter logging::word",
+        "log: exit logging::word (1:1 to 1:3 \"ab\")",
+        "log: exit logging::item (1:1 to 1:3 \"ab\")",
+    ]);
+
+    pear::trace::set_filter(Some(pear::trace::TraceFilter::parse("digit")));
+    let result = parse!(item: &mut Text::from("7"));
+    pear::trace::set_filter(None);
+
+    assert_eq!(result.unwrap(), "7");
+    assert_eq!(take_records(), [
+        "log: enter logging::digit",
+        "log: exit logging::digit (1:1 to 1:2 \"7\")",
+    ]);
+}
+
+#[test]
+fn test_tracing_spans() {
+    let subscriber = Subscriber(AtomicU64::new(1));
+    let result = tracing::subscriber::with_default(subscriber, || parse!(word: &mut Text::from("1")));
+    assert!(result.is_err());
+
+    // The logger, if set, logs to the same thread's records.
+    let mut records = take_records();
+    records.retain(|record| !record.starts_with("log:"));
+    assert_eq!(records, [
+        "span: parser name=\"word\" module=\"logging\" raw=false",
+        "record: success=false",
+        "event: message=backtrack context=1:1",
+    ]);
+}
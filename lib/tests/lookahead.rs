@@ -0,0 +1,118 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Cursor, Graphemes, Limited, Versioned, IterInput, Chain, Bits, Input, Result};
+use pear::macros::{parser, parse, switch};
+use pear::parsers::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token { Ident, Colon, Eq, Num }
+
+pear::macros::impl_show_with!(Debug, Token);
+
+type Tokens<'a> = Cursor<'a, Token>;
+
+// An LL(2) decision: `x : ...` is a declaration, `x = ...` an assignment.
+#[parser]
+fn statement<'a>(input: &mut Tokens<'a>) -> Result<&'static str, Tokens<'a>> {
+    switch! {
+        peek_slice_at(1, &[Token::Colon][..]) => "declaration",
+        peek_slice_at(1, &[Token::Eq][..]) => "assignment",
+        _ => "expression"
+    }
+}
+
+#[test]
+fn test_ll2_decision() {
+    use Token::*;
+
+    let tokens = [Ident, Colon, Ident];
+    let mut input = Cursor::from(&tokens[..]);
+    assert_eq!(statement(&mut input).unwrap(), "declaration");
+    assert_eq!(input.items.len(), 3);
+
+    let tokens = [Ident, Eq, Num];
+    assert_eq!(statement(&mut Cursor::from(&tokens[..])).unwrap(), "assignment");
+
+    let tokens = [Num];
+    assert_eq!(statement(&mut Cursor::from(&tokens[..])).unwrap(), "expression");
+}
+
+#[test]
+fn test_peek_slice_at() {
+    let mut input = "größe";
+    assert_eq!(input.peek_slice_at(0, 2), Some("gr"));
+    assert_eq!(input.peek_slice_at(2, 2), Some("ö"));
+    assert_eq!(input.peek_slice_at(3, 1), None);
+    assert_eq!(input.peek_slice_at(5, 2), None);
+    assert_eq!(input.peek_slice_at(4, 3), Some("ße"));
+    assert_eq!(input.peek_slice_at(7, 1), None);
+    assert_eq!(input.peek_slice_at(usize::MAX, 2), None);
+    assert_eq!(input, "größe");
+
+    let mut text = Text::from("let x");
+    assert_eq!(text.peek_slice_at(4, 1), Some("x"));
+    assert!(peek_slice_at(&mut text, 4, "y").is_err());
+    assert_eq!(parse!(ident_after_let: &mut text).unwrap(), "x");
+}
+
+#[parser]
+fn ident_after_let<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    peek_slice_at(3, " ")?;
+    eat_slice("let ")?;
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[test]
+fn test_peek_slice_at_graphemes() {
+    let mut input = Graphemes::from("ae\u{301}b");
+    assert_eq!(input.peek_slice_at(1, 3), Some("e\u{301}"));
+    assert_eq!(input.peek_slice_at(1, 1), None);
+    assert_eq!(input.peek_slice_at(2, 3), None);
+}
+
+#[test]
+fn test_peek_slice_at_wrappers() {
+    let mut text = Text::from("abcdef");
+    let mut limited = Limited::new(&mut text, 4);
+    assert_eq!(limited.peek_slice_at(2, 2), Some("cd"));
+    assert_eq!(limited.peek_slice_at(3, 2), None);
+
+    let mut versioned = Versioned::new(Text::from("abc"), 1);
+    assert_eq!(versioned.peek_slice_at(1, 2), Some("bc"));
+}
+
+#[test]
+fn test_peek_slice_at_iter() {
+    let mut input = IterInput::new("abc".chars());
+    assert_eq!(input.peek_slice_at(0, 2).as_deref(), Some(&['a', 'b'][..]));
+    assert_eq!(input.peek_slice_at(1, 2).as_deref(), Some(&['b', 'c'][..]));
+    assert_eq!(input.peek_slice_at(2, 2), None);
+    assert_eq!(input.peek_slice_at(usize::MAX, 2), None);
+    assert_eq!(eat_any(&mut input).unwrap(), 'a');
+    assert_eq!(input.peek_slice_at(1, 1).as_deref(), Some(&['c'][..]));
+}
+
+#[test]
+fn test_peek_slice_at_chain() {
+    let mut input = Chain::new(Text::from("ab"), Text::from("cde"));
+    assert_eq!(input.peek_slice_at(0, 2), Some("ab"));
+    assert_eq!(input.peek_slice_at(1, 2), None);
+    assert_eq!(input.peek_slice_at(2, 2), Some("cd"));
+    assert_eq!(input.peek_slice_at(3, 2), Some("de"));
+    assert_eq!(input.peek_slice_at(4, 2), None);
+
+    assert_eq!(eat_slice(&mut input, "ab").unwrap(), "ab");
+    assert_eq!(input.peek_slice_at(1, 2), Some("de"));
+}
+
+#[test]
+fn test_peek_slice_at_bits() {
+    let bytes = [0b1010_0000, 0b1100_0000];
+    let mut input = Bits::new(Cursor::from(&bytes[..]));
+    assert_eq!(input.peek_slice_at(2, 2), Some(vec![true, false]));
+    assert_eq!(input.peek_slice_at(7, 3), Some(vec![false, true, true]));
+    assert_eq!(input.peek_slice_at(14, 3), None);
+
+    assert_eq!(input.take_bits(3), Some(0b101));
+    assert_eq!(input.peek_slice_at(5, 2), Some(vec![true, true]));
+}
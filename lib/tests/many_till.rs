@@ -0,0 +1,75 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Position, Result};
+use pear::result::ResultExt;
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn digit<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+    eat_if(|c: &char| c.is_ascii_digit())?.to_digit(10).unwrap()
+}
+
+fn terminator<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    eat_slice(input, ";;").or_parse(input, |i| eat_slice(i, "END"))
+}
+
+#[parser]
+fn digits<'a>(input: &mut Text<'a>) -> Result<(Vec<u32>, &'a str), Text<'a>> {
+    many_till(digit, terminator)?
+}
+
+#[parser]
+fn numbers<'a>(input: &mut Text<'a>) -> Result<(Vec<&'a str>, &'a str), Text<'a>> {
+    let number = |i: &mut Text<'a>| take_some_while(i, |c: &char| c.is_ascii_digit());
+    separated_until(number, ',', terminator)?
+}
+
+#[test]
+fn test_many_till() {
+    assert_eq!(parse!(digits: &mut Text::from("123;;")).unwrap(), (vec![1, 2, 3], ";;"));
+    assert_eq!(parse!(digits: &mut Text::from("END")).unwrap(), (vec![], "END"));
+
+    // `end` is consumed; what follows is left alone.
+    let mut input = Text::from("9END;;");
+    assert_eq!(digits(&mut input).unwrap(), (vec![9], "END"));
+    assert_eq!(input.position(), 4);
+}
+
+#[test]
+fn test_many_till_errors() {
+    let error = parse!(digits: &mut Text::from("12x")).unwrap_err();
+    assert_eq!(error.expected(), ["`;;`", "`END`"]);
+
+    // A partially matched `end` is rewound before trying `p`.
+    let error = parse!(digits: &mut Text::from("1;")).unwrap_err();
+    assert_eq!(error.contexts[0].context.as_ref().unwrap().start.2, 1);
+
+    assert!(parse!(digits: &mut Text::from("12")).is_err());
+}
+
+#[test]
+fn test_separated_until() {
+    let output = parse!(numbers: &mut Text::from("1,22,333END")).unwrap();
+    assert_eq!(output, (vec!["1", "22", "333"], "END"));
+    assert_eq!(parse!(numbers: &mut Text::from(";;")).unwrap(), (vec![], ";;"));
+
+    let error = parse!(numbers: &mut Text::from("1;2")).unwrap_err();
+    assert_eq!(error.expected(), ["`,`", "`;;`", "`END`"]);
+
+    // A trailing separator must be followed by an item.
+    assert!(parse!(numbers: &mut Text::from("1,;;")).is_err());
+}
+
+#[parser]
+fn strict_end<'a>(input: &mut Text<'a>) -> Result<(), Text<'a>> {
+    eat('.')?;
+    cut(|i| eat(i, '.'))?;
+}
+
+#[test]
+fn test_committed_end() {
+    let mut input = Text::from("12.x");
+    let error = many_till::<Vec<_>, _, _, _, _, _, _>(&mut input, digit, strict_end).unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.expected(), ["`.`"]);
+}
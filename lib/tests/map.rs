@@ -0,0 +1,75 @@
+#![feature(proc_macro_hygiene)]
+
+use std::fmt;
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[derive(Debug, PartialEq)]
+struct Rgb(u8, u8, u8);
+
+#[derive(Debug)]
+struct BadChannel(char);
+
+impl fmt::Display for BadChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "channel `{}` is not in `rgb`", self.0)
+    }
+}
+
+#[parser]
+fn hex<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_hexdigit())?
+}
+
+#[parser]
+fn color<'a>(input: &mut Text<'a>) -> Result<Rgb, Text<'a>> {
+    eat('#')?;
+    map_res(hex, |digits| {
+        let value = u32::from_str_radix(digits, 16).map_err(|e| e.to_string())?;
+        match digits.len() {
+            6 => Ok(Rgb((value >> 16) as u8, (value >> 8) as u8, value as u8)),
+            _ => Err(String::from("a color has six hex digits")),
+        }
+    })?
+}
+
+#[parser]
+fn channel<'a>(input: &mut Text<'a>) -> Result<usize, Text<'a>> {
+    map_res(eat_any, |c| "rgb".find(c).ok_or(BadChannel(c)))?
+}
+
+#[parser]
+fn lengths<'a>(input: &mut Text<'a>) -> Result<Vec<usize>, Text<'a>> {
+    let lengths: Vec<usize> = series(|i| map(i, hex, str::len), ',')?;
+    lengths
+}
+
+#[test]
+fn test_map() {
+    assert_eq!(parse!(lengths: &mut Text::from("a,bc,def")).unwrap(), [1, 2, 3]);
+    assert!(parse!(lengths: &mut Text::from("a,x")).is_err());
+}
+
+#[test]
+fn test_map_res() {
+    assert_eq!(parse!(color: &mut Text::from("#ff8000")).unwrap(), Rgb(255, 128, 0));
+    assert_eq!(parse!(channel: &mut Text::from("b")).unwrap(), 2);
+
+    let error = parse!(color: &mut Text::from("#fff")).unwrap_err();
+    assert!(error.to_string().starts_with("a color has six hex digits"));
+    assert_eq!(error.contexts[0].parser.name, "map_res");
+
+    let span = error.contexts[0].context.as_ref().unwrap();
+    assert_eq!((span.start.2, span.end.2), (1, 4));
+
+    let error = parse!(channel: &mut Text::from("x")).unwrap_err();
+    assert!(error.to_string().starts_with("channel `x` is not in `rgb`"));
+}
+
+#[test]
+fn test_map_res_parser_failure() {
+    // Errors from the parser itself are returned unchanged.
+    let error = parse!(color: &mut Text::from("#")).unwrap_err();
+    assert_eq!(error.parsers().next().unwrap().name, "take_some_while");
+}
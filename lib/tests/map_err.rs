@@ -0,0 +1,58 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, ParseError, Result};
+use pear::error::{ErrorCode, Expected};
+use pear::{macros::*, parsers::*};
+
+fn invalid_port(mut error: ParseError<Text<'_>>) -> ParseError<Text<'_>> {
+    if !error.committed {
+        error.error = Expected::Other("a port number between 1 and 65535".into());
+        error.code = Some(Box::new(ErrorCode::Other));
+    }
+
+    error
+}
+
+#[parser(map_err = invalid_port)]
+fn port<'a>(input: &mut Text<'a>) -> Result<u16, Text<'a>> {
+    let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+    match digits.parse::<u16>() {
+        Ok(0) | Err(_) => parse_error!("out of range")?,
+        Ok(port) => port,
+    }
+}
+
+#[parser(map_err = |e| e.label("a host"), rewind)]
+fn host<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    let host = take_some_while(|c: &char| c.is_ascii_alphanumeric() || *c == '.')?;
+    eat(':')?;
+    host
+}
+
+#[parser]
+fn address<'a>(input: &mut Text<'a>) -> Result<(&'a str, u16), Text<'a>> {
+    (host()?, port()?)
+}
+
+#[test]
+fn test_map_err() {
+    let result = parse!(address: &mut Text::from("localhost:8080"));
+    assert_eq!(result.unwrap(), ("localhost", 8080));
+
+    for source in ["localhost:", "localhost:0", "localhost:99999", "localhost:x"] {
+        let error = parse!(address: &mut Text::from(source)).unwrap_err();
+        assert_eq!(error.to_string().lines().next(),
+            Some("a port number between 1 and 65535"), "{}", source);
+
+        let parsers: Vec<_> = error.parsers().map(|p| p.name).collect();
+        assert_eq!(&parsers[parsers.len() - 2..], ["port", "address"]);
+    }
+}
+
+#[test]
+fn test_map_err_closure() {
+    let mut input = Text::from("localhost 80");
+    let error = parse!(address: &mut input).unwrap_err();
+    assert_eq!(error.expected(), ["a host"]);
+    assert_eq!(error.found(), Some(&' '));
+}
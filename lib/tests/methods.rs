@@ -0,0 +1,122 @@
+#![feature(proc_macro_hygiene)]
+
+use std::collections::HashMap;
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*};
+
+/// A calculator whose variables are kept in a symbol table.
+#[derive(Default)]
+struct Calc {
+    vars: HashMap<String, i64>,
+    max_digits: usize,
+}
+
+impl Calc {
+    #[parser]
+    fn name<'a>(&self, input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+        take_some_while(|c: &char| c.is_ascii_alphabetic())?
+    }
+
+    #[parser]
+    fn number<'a>(&self, input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+        let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+        if self.max_digits > 0 && digits.len() > self.max_digits {
+            parse_error!("too many digits")?;
+        }
+
+        digits.parse::<i64>().unwrap()
+    }
+
+    #[parser]
+    fn atom<'a>(&self, input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+        switch! {
+            n@self.number() => n,
+            name@self.name() => match self.vars.get(name) {
+                Some(value) => *value,
+                None => parse_error!("undefined variable")?,
+            },
+            _ => parse_error!("expected a number or a variable")?
+        }
+    }
+
+    #[parser]
+    fn sum<'a>(&self, input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+        let mut total = self.atom()?;
+        while eat(input, '+').is_ok() {
+            total += self.atom()?;
+        }
+
+        total
+    }
+
+    #[parser(rewind)]
+    fn assign<'a>(&mut self, input: &mut Text<'a>) -> Result<(), Text<'a>> {
+        let name = self.name()?;
+        eat('=')?;
+        let value = self.sum()?;
+        self.vars.insert(name.to_string(), value);
+    }
+
+    #[parser]
+    fn program<'a>(&mut self, input: &mut Text<'a>) -> Result<i64, Text<'a>> {
+        while self.assign(input).is_ok() {
+            eat(';')?;
+        }
+
+        self.sum()?
+    }
+}
+
+#[test]
+fn test_methods() {
+    let mut calc = Calc::default();
+    let result = parse!(calc.program: &mut Text::from("a=1+2;b=a+a;b+10+a"));
+    assert_eq!(result.unwrap(), 19);
+    assert_eq!(calc.vars["b"], 6);
+
+    let result = parse!(calc.sum: &mut Text::from("a+b"));
+    assert_eq!(result.unwrap(), 9);
+}
+
+#[test]
+fn test_method_errors() {
+    let calc = Calc { max_digits: 2, ..Calc::default() };
+    let error = parse!(calc.sum: &mut Text::from("1+x")).unwrap_err();
+    assert_eq!(error.to_string().lines().next(), Some("undefined variable"));
+
+    let error = parse!(calc.sum: &mut Text::from("1+100")).unwrap_err();
+    let parsers: Vec<_> = error.parsers().map(|p| p.name).collect();
+    assert_eq!(parsers, ["atom", "sum"]);
+
+    let error = parse!(calc.number: &mut Text::from("100")).unwrap_err();
+    assert_eq!(error.to_string().lines().next(), Some("too many digits"));
+}
+
+trait Grammar {
+    fn separator(&self) -> char;
+
+    #[parser]
+    fn list<'a>(&self, input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+        let first = take_some_while(|c: &char| c.is_ascii_alphanumeric())?;
+        let mut items = vec![first];
+        while eat(input, self.separator()).is_ok() {
+            let item = take_some_while(|c: &char| c.is_ascii_alphanumeric())?;
+            items.push(item);
+        }
+
+        items
+    }
+}
+
+struct Semicolons;
+
+impl Grammar for Semicolons {
+    fn separator(&self) -> char { ';' }
+}
+
+#[test]
+fn test_trait_methods() {
+    let result = parse!(Semicolons.list: &mut Text::from("a;b2;c"));
+    assert_eq!(result.unwrap(), ["a", "b2", "c"]);
+}
@@ -0,0 +1,100 @@
+#![feature(proc_macro_hygiene)]
+
+use std::path::PathBuf;
+
+use pear::input::{Cursor, FileInput, MappedFile, Text, DecodeError, Encoding, Result};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = FileInput<'a, Text<'a>>;
+
+/// A temporary file removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: &[u8]) -> TempFile {
+        let path = std::env::temp_dir().join(format!("pear-mmap-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+
+    fn map(&self) -> MappedFile {
+        // SAFETY: Nothing modifies the file while it's mapped.
+        unsafe { MappedFile::open(&self.0).unwrap() }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[parser]
+fn level<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    eat('[')?;
+    let level = take_some_while(|c: &char| c.is_ascii_uppercase())?;
+    eat(']')?;
+    skip_while(|&c| c != '\n')?;
+    eat('\n')?;
+    level
+}
+
+#[parser]
+fn levels<'a>(input: &mut Input<'a>) -> Result<Vec<&'a str>, Input<'a>> {
+    let levels: Vec<_> = pear::combinators::collect(level)?;
+    levels
+}
+
+#[test]
+fn test_mmap_text() {
+    let file = TempFile::new("log.txt", b"\xEF\xBB\xBF[INFO] up\n[WARN] disk\n");
+    let mapped = file.map();
+    assert_eq!(mapped.bytes().len(), 25);
+    assert!(mapped.name().ends_with("log.txt"));
+
+    let mut input = FileInput::mmap_text(&mapped).unwrap();
+    assert_eq!(parse!(levels: &mut input).unwrap(), ["INFO", "WARN"]);
+}
+
+#[test]
+fn test_mmap_text_offsets() {
+    let file = TempFile::new("bad.txt", b"\xEF\xBB\xBF[INFO] up\n[warn] disk\n");
+    let mapped = file.map();
+
+    let error = parse!(levels: &mut FileInput::mmap_text(&mapped).unwrap()).unwrap_err();
+    let context = error.contexts[0].context.as_ref().unwrap();
+    assert_eq!(context.name, mapped.name());
+    assert_eq!(context.context.end.2, 14);
+    assert_eq!(&mapped.bytes()[14..15], b"w");
+}
+
+#[test]
+fn test_mmap_text_errors() {
+    let file = TempFile::new("utf16.txt", b"\xFF\xFEa\0");
+    let error = FileInput::mmap_text(&file.map()).unwrap_err();
+    assert_eq!(error, DecodeError::Unsupported(Encoding::Utf16Le));
+
+    let file = TempFile::new("invalid.txt", b"ab\xFF");
+    let error = FileInput::mmap_text(&file.map()).unwrap_err();
+    assert_eq!(error, DecodeError::Invalid { encoding: Encoding::Utf8, offset: 2 });
+}
+
+#[parser]
+fn chunk<'a>(input: &mut FileInput<'a, Cursor<'a, u8>>) -> Result<&'a [u8], FileInput<'a, Cursor<'a, u8>>> {
+    let len = *eat_any()?;
+    take_n(len as usize)?
+}
+
+#[test]
+fn test_mmap_bytes() {
+    let file = TempFile::new("chunks.bin", b"\x02ab\x03cde\x09");
+    let mapped = file.map();
+    let mut input = FileInput::mmap(&mapped);
+    assert_eq!(chunk(&mut input).unwrap(), b"ab");
+    assert_eq!(chunk(&mut input).unwrap(), b"cde");
+    assert_eq!(chunk(&mut input).unwrap(), b"");
+
+    let file = TempFile::new("empty.bin", b"");
+    let mapped = file.map();
+    assert!(eof(&mut FileInput::mmap(&mapped)).is_ok());
+}
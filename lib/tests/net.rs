@@ -0,0 +1,87 @@
+#![feature(proc_macro_hygiene)]
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*};
+use pear::parsers::net::*;
+
+type Uri<'a> = (&'a str, Authority<&'a str>, &'a str, Option<&'a str>);
+
+#[parser]
+fn uri<'a>(input: &mut Text<'a>) -> Result<Uri<'a>, Text<'a>> {
+    let scheme = scheme()?;
+    eat_slice("://")?;
+    let authority = authority()?;
+    let path = path()?;
+    let query = switch! { eat('?') => Some(query()?), _ => None };
+    (scheme, authority, path, query)
+}
+
+#[test]
+fn test_ipv4() {
+    for valid in ["0.0.0.0", "127.0.0.1", "255.255.255.255", "10.20.30.40"] {
+        let expected: Ipv4Addr = valid.parse().unwrap();
+        assert_eq!(parse!(ipv4: &mut Text::from(valid)).unwrap(), expected);
+    }
+
+    for invalid in ["256.0.0.1", "1.2.3", "01.2.3.4", "1.2.3.4.5", "1..2.3"] {
+        assert!(parse!(ipv4: &mut Text::from(invalid)).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_ipv6() {
+    let valid = [
+        "::", "::1", "1::", "1:2:3:4:5:6:7:8", "1::8", "1:2:3:4:5:6:7::", "::2:3:4:5:6:7:8",
+        "fe80::1:2", "::ffff:192.0.2.1", "64:ff9b::10.0.0.1", "1:2:3:4:5:6:1.2.3.4", "ABCD:ef::0",
+    ];
+
+    for valid in valid {
+        let expected: Ipv6Addr = valid.parse().unwrap();
+        assert_eq!(parse!(ipv6: &mut Text::from(valid)).unwrap(), expected, "{}", valid);
+    }
+
+    let invalid = [
+        "", ":", ":1", "1:2:3:4:5:6:7", "1:2:3:4:5:6:7:8:9", "1::2::3", "::1:2:3:4:5:6:7:8",
+        "12345::", "1:2:3:4:5:6:7:1.2.3.4", "::1.2.3", "1:", "::01.2.3.4",
+    ];
+
+    for invalid in invalid {
+        assert!(invalid.parse::<Ipv6Addr>().is_err(), "std accepts {}", invalid);
+        assert!(parse!(ipv6: &mut Text::from(invalid)).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_socket_addr() {
+    for valid in ["127.0.0.1:80", "[::1]:65535", "[fe80::1]:0"] {
+        let expected: SocketAddr = valid.parse().unwrap();
+        assert_eq!(parse!(socket_addr: &mut Text::from(valid)).unwrap(), expected);
+    }
+
+    let error = parse!(socket_addr: &mut Text::from("127.0.0.1:65536")).unwrap_err();
+    assert!(error.to_string().contains("a port from 0 to 65535"));
+}
+
+#[test]
+fn test_uri() {
+    let source = "http://user:pw@example.com:8080/a/b%2F?q=1&r";
+    let (scheme, authority, path, query) = parse!(uri: &mut Text::from(source)).unwrap();
+    assert_eq!((scheme, path, query), ("http", "/a/b%2F", Some("q=1&r")));
+    assert_eq!(authority, Authority {
+        userinfo: Some("user:pw"),
+        host: Host::Name("example.com"),
+        port: Some(8080),
+    });
+
+    let (_, authority, ..) = parse!(uri: &mut Text::from("s://1.2.3.4:/")).unwrap();
+    assert_eq!((authority.host, authority.port), (Host::Ipv4(Ipv4Addr::new(1, 2, 3, 4)), None));
+
+    let (_, authority, ..) = parse!(uri: &mut Text::from("s://1.2.3.4.example")).unwrap();
+    assert_eq!(authority.host, Host::Name("1.2.3.4.example"));
+
+    let error = parse!(uri: &mut Text::from("s://host/a%2")).unwrap_err();
+    assert!(error.to_string().contains("percent-encoded"));
+    assert!(parse!(uri: &mut Text::from("1s://host")).is_err());
+}
@@ -0,0 +1,84 @@
+#![feature(proc_macro_hygiene)]
+
+mod common;
+
+use common::allocations;
+use pear::input::{Text, Position, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn keyword<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    eat_slice("pub")?;
+    eat_any_of(&[' ', '\t'][..])?;
+    "pub"
+}
+
+#[parser]
+fn item<'a>(input: &mut Text<'a>) -> Result<(Option<&'a str>, &'a str), Text<'a>> {
+    let visibility = opt(keyword)?;
+    (visibility, take_some_while(|c: &char| c.is_alphabetic())?)
+}
+
+#[test]
+fn test_opt() {
+    assert_eq!(parse!(item: &mut Text::from("pub fn")).unwrap(), (Some("pub"), "fn"));
+    assert_eq!(parse!(item: &mut Text::from("pubx")).unwrap(), (None, "pubx"));
+
+    let mut input = Text::from("pub-fn");
+    assert_eq!(opt(&mut input, keyword).unwrap(), None);
+    assert_eq!(input.position(), 0);
+}
+
+#[test]
+fn test_opt_does_not_allocate() {
+    for string in ["pub-fn", "pu", "fn"] {
+        let mut input = Text::from(string);
+        let (result, count) = allocations(|| opt(&mut input, keyword));
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(count, 0);
+    }
+
+    let mut input = Text::from("pub-fn");
+    // Outside of `opt`, the same failure is rendered and allocates.
+    let (result, count) = allocations(|| keyword(&mut input));
+    assert!(result.is_err());
+    assert!(count > 0);
+}
+
+#[parser]
+fn block<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    eat('{')?;
+    let body = take_while(|&c| c != '}')?;
+    cut(|i| eat(i, '}'))?;
+    body
+}
+
+#[test]
+fn test_opt_committed() {
+    assert_eq!(opt(&mut Text::from("{x}"), block).unwrap(), Some("x"));
+    assert_eq!(opt(&mut Text::from("x"), block).unwrap(), None);
+
+    // A committed error is returned as it was suppressed, without rerunning.
+    let error = opt(&mut Text::from("{x"), block).unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.expected(), Vec::<&str>::new());
+    assert!(error.parsers().all(|p| p.name != "block"));
+
+    // Outside of `opt`, the same error is rendered in full.
+    let error = block(&mut Text::from("{x")).unwrap_err();
+    assert!(error.to_string().starts_with("expected token `}`"));
+    assert_eq!(error.parsers().find(|p| !p.raw).unwrap().name, "block");
+}
+
+#[parser]
+fn counted<'a>(input: &mut Text<'a>, runs: &mut usize) -> Result<(), Text<'a>> {
+    *runs += 1;
+    cut(|i| eat(i, '!'))?;
+}
+
+#[test]
+fn test_opt_committed_runs_once() {
+    let mut runs = 0;
+    assert!(opt(&mut Text::from("?"), |i| counted(i, &mut runs)).is_err());
+    assert_eq!(runs, 1);
+}
@@ -0,0 +1,83 @@
+#![feature(proc_macro_hygiene)]
+
+use std::ops::Range;
+
+use pear::input::{Text, Cursor, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+use pear::parallel::{self, delimiter, split};
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<u64, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u64>().unwrap()
+}
+
+#[parser]
+fn field<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    switch! {
+        eat('"') => { let value = take_while(|c: &char| *c != '"')?; eat('"')?; value },
+        _ => take_while(|c: &char| *c != ',')?
+    }
+}
+
+#[parser]
+fn row<'a>(input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+    let fields: Vec<_> = series(|i| field(i), ',')?;
+    fields
+}
+
+#[parser]
+fn word<'a>(input: &mut Cursor<'a, u8>) -> Result<usize, Cursor<'a, u8>> {
+    take_some_while(|b: &&u8| b.is_ascii_alphabetic())?.len()
+}
+
+/// Ends records at newlines outside of double quotes.
+fn csv_rows(rest: &str) -> Option<Range<usize>> {
+    let mut quoted = false;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '\n' if !quoted => return Some(i..i + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test_split() {
+    let records = split("a\n\nbc\n", delimiter(b'\n'));
+    let data: Vec<_> = records.iter().map(|r| (r.index, r.offset, r.data)).collect();
+    assert_eq!(data, [(0, 0, "a"), (1, 2, ""), (2, 3, "bc")]);
+
+    let records = split("a;b", delimiter(b';'));
+    assert_eq!(records.iter().map(|r| r.data).collect::<Vec<_>>(), ["a", "b"]);
+    assert!(split("", delimiter::<str>(b'\n')).is_empty());
+}
+
+#[test]
+fn test_parallel_parse_in_order() {
+    let input: String = (0..10_000).map(|i| format!("{}\n", i)).collect();
+    let numbers = parallel::parse(&*input, delimiter(b'\n'), number).unwrap();
+    assert_eq!(numbers, (0..10_000).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_parallel_errors_in_order() {
+    let errors = parallel::parse("1\nx\n3\n4y\n", delimiter(b'\n'), number).unwrap_err();
+    let records: Vec<_> = errors.iter().map(|e| (e.index, e.offset)).collect();
+    assert_eq!(records, [(1, 2), (3, 6)]);
+}
+
+#[test]
+fn test_stateful_boundary() {
+    let csv = "a,\"b\nc\",d\ne,f\n";
+    let rows = parallel::parse(csv, csv_rows, row).unwrap();
+    assert_eq!(rows, [vec!["a", "b\nc", "d"], vec!["e", "f"]]);
+}
+
+#[test]
+fn test_parallel_bytes() {
+    let lengths = parallel::parse(&b"ab cde f"[..], delimiter(b' '), word).unwrap();
+    assert_eq!(lengths, [2, 3, 1]);
+}
@@ -0,0 +1,115 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+use pear::{FromPearStr, parse_str};
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl FromPearStr for Point {
+    #[parser]
+    fn from_pear_str<'a>(input: &mut Text<'a>) -> Result<Self, Text<'a>> {
+        eat('(')?;
+        let x = i32::from_pear_str()?;
+        eat(',')?;
+        let y = i32::from_pear_str()?;
+        eat(')')?;
+        Point { x, y }
+    }
+}
+
+#[parser]
+fn points<'a>(input: &mut Text<'a>) -> Result<Vec<Point>, Text<'a>> {
+    let points: Vec<Point> = series(Point::from_pear_str, ' ')?;
+    points
+}
+
+#[derive(Debug, PartialEq, FromPearStr)]
+#[parse("{x}x{y}")]
+struct Size {
+    x: u32,
+    y: u32,
+}
+
+#[derive(Debug, PartialEq, FromPearStr)]
+struct Pair(Size, bool);
+
+#[derive(Debug, PartialEq, FromPearStr)]
+enum Shape {
+    #[parse("empty")]
+    Empty,
+    #[parse("rect {0}")]
+    Rect(Size),
+    #[parse("poly {points}")]
+    Poly {
+        #[parse(separator = ";")]
+        points: Vec<Size>,
+        sides: usize,
+    },
+}
+
+#[test]
+fn test_parse_str_primitives() {
+    assert_eq!(parse_str::<u8>("255").unwrap(), 255);
+    assert_eq!(parse_str::<i64>("-42").unwrap(), -42);
+    assert_eq!(parse_str::<i64>("+7").unwrap(), 7);
+    assert!(!parse_str::<bool>("false").unwrap());
+    assert!(parse_str::<bool>("true").unwrap());
+
+    assert!(parse_str::<u8>("256").is_err());
+    assert!(parse_str::<u8>("-1").is_err());
+    assert!(parse_str::<i8>("1-").is_err());
+    assert!(parse_str::<bool>("yes").is_err());
+    assert!(parse_str::<u32>("").is_err());
+}
+
+#[test]
+fn test_parse_str_user_type() {
+    assert_eq!(parse_str::<Point>("(1,-2)").unwrap(), Point { x: 1, y: -2 });
+    assert!(parse_str::<Point>("(1,-2) ").is_err());
+
+    let points = parse!(points: &mut Text::from("(0,0) (3,4)")).unwrap();
+    assert_eq!(points, vec![Point { x: 0, y: 0 }, Point { x: 3, y: 4 }]);
+}
+
+#[test]
+fn test_derived_from_pear_str() {
+    assert_eq!(parse_str::<Size>("3x4").unwrap(), Size { x: 3, y: 4 });
+    assert!(parse_str::<Size>("3y4").is_err());
+    assert!(parse_str::<Size>("3x").is_err());
+
+    assert_eq!(parse_str::<Pair>("1x2true").unwrap(), Pair(Size { x: 1, y: 2 }, true));
+
+    assert_eq!(parse_str::<Shape>("empty").unwrap(), Shape::Empty);
+    assert_eq!(parse_str::<Shape>("rect 1x2").unwrap(), Shape::Rect(Size { x: 1, y: 2 }));
+    assert_eq!(parse_str::<Shape>("poly ").unwrap(), Shape::Poly { points: vec![], sides: 0 });
+
+    let poly = parse_str::<Shape>("poly 0x0;1x1").unwrap();
+    assert_eq!(poly, Shape::Poly { points: vec![Size { x: 0, y: 0 }, Size { x: 1, y: 1 }], sides: 0 });
+
+    assert!(parse_str::<Shape>("poly 0x0;").is_err());
+    assert!(parse_str::<Shape>("circle").is_err());
+}
+
+#[test]
+fn test_derived_variants_rewind() {
+    #[derive(Debug, PartialEq, FromPearStr)]
+    enum Keyword {
+        #[parse("for")]
+        For,
+        #[parse("fore")]
+        Fore,
+        #[parse("f{0}")]
+        F(u8),
+    }
+
+    assert_eq!(parse_str::<Keyword>("for").unwrap(), Keyword::For);
+    assert_eq!(parse_str::<Keyword>("f7").unwrap(), Keyword::F(7));
+
+    // `For` parses as a prefix of `fore`, so `Fore` is never reached.
+    assert!(parse_str::<Keyword>("fore").is_err());
+}
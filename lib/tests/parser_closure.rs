@@ -0,0 +1,102 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Position, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+use pear::FromPearStr;
+
+type Input<'a> = Text<'a>;
+
+fn names<'a, O>(result: &Result<O, Input<'a>>) -> Vec<&'static str> {
+    match result {
+        Ok(_) => vec![],
+        Err(e) => e.contexts.iter().filter(|c| !c.parser.raw).map(|c| c.parser.name).collect(),
+    }
+}
+
+#[parser]
+fn key<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    let key = pear::parser!("key", rewind, |input: &mut Input<'a>| {
+        let key = take_some_while(|c: &char| c.is_alphabetic())?;
+        eat('=')?;
+        key
+    });
+
+    key()?
+}
+
+#[parser]
+fn list<'a>(input: &mut Input<'a>) -> Result<Vec<u32>, Input<'a>> {
+    // The input's type is inferred from `series()`, the error's from the
+    // closure's return type.
+    let items: Vec<_> = series(pear::parser!(|input| -> Result<u32, Input<'a>> {
+        skip_while(|c: &char| *c == ' ')?;
+        switch! {
+            eat_slice("nil") => 0,
+            _ => u32::from_pear_str()?
+        }
+    }), ',')?;
+
+    items
+}
+
+#[test]
+fn test_closure_parser() {
+    let mut input = Text::from("name=pear");
+    assert_eq!(key(&mut input).unwrap(), "name");
+    assert_eq!(input.position(), 5);
+
+    let mut input = Text::from("name:pear");
+    let result = key(&mut input);
+    assert_eq!(names(&result), ["key", "key"]);
+    assert_eq!(input.position(), 0);
+
+    let mut input = Text::from("1, nil,3");
+    assert_eq!(list(&mut input).unwrap(), [1, 0, 3]);
+
+    let result = list(&mut Text::from("1,x"));
+    assert_eq!(names(&result), ["closure", "list"]);
+}
+
+#[test]
+fn test_closure_arguments() {
+    let digits = pear::parser!("digits", peek, label = "a number", |input: &mut Input<'static>| {
+        take_some_while(|c: &char| c.is_ascii_digit())?
+    });
+
+    let mut input = Text::from("12a");
+    assert_eq!(digits(&mut input).unwrap(), "12");
+    assert_eq!(input.position(), 0);
+
+    let error = digits(&mut Text::from("a")).unwrap_err();
+    assert_eq!(error.error.to_string(), "expected a number but found `a`");
+
+    let all = pear::parser!(complete, spanned, |input: &mut Input<'static>| {
+        take_while(|c: &char| c.is_alphabetic())?
+    });
+
+    let value = all(&mut Text::from("abc")).unwrap();
+    assert_eq!((value.value, value.span), ("abc", 0..3));
+    assert!(all(&mut Text::from("ab1")).is_err());
+}
+
+#[test]
+fn test_closure_captures_and_returns() {
+    let keyword = String::from("let");
+    let statement = pear::parser!(move |input: &mut Input<'static>| {
+        if eat(input, ';').is_ok() {
+            return Ok("");
+        }
+
+        switch! {
+            eat_slice(keyword.as_str()) => eat(' ')?,
+            _ => parse_error!("expected `{}`", keyword)?
+        };
+
+        take_some_while(|c: &char| c.is_alphabetic())?
+    });
+
+    assert_eq!(statement(&mut Text::from("let x")).unwrap(), "x");
+    let error = statement(&mut Text::from("fn x")).unwrap_err();
+    assert_eq!(error.error.to_string(), "expected `let`");
+    assert_eq!(statement(&mut Text::from(";")).unwrap(), "");
+}
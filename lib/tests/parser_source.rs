@@ -0,0 +1,54 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, ParserSource, Result};
+use pear::{macros::*, parsers::*};
+use grammar::{digit, pair};
+
+mod grammar {
+    use super::*;
+
+    #[parser]
+    pub fn digit<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+        eat_if(|c: &char| c.is_ascii_digit())?
+    }
+
+    #[parser]
+    pub fn pair<'a>(input: &mut Text<'a>) -> Result<(char, char), Text<'a>> {
+        (digit()?, digit()?)
+    }
+}
+
+#[test]
+fn test_parser_source() {
+    let error = parse!(pair: &mut Text::from("1x")).unwrap_err();
+    let parsers: Vec<_> = error.parsers().filter(|p| !p.raw).collect();
+    assert_eq!(parsers.iter().map(|p| p.name).collect::<Vec<_>>(), ["digit", "pair"]);
+
+    let digit = parsers[0].source.unwrap();
+    assert_eq!(digit.module, "parser_source::grammar");
+    assert!(digit.file.ends_with("parser_source.rs"));
+    assert_eq!((digit.line, digit.column), (11, 12));
+
+    let pair = parsers[1].source.unwrap();
+    assert_eq!(pair.line, 16);
+    assert_ne!(digit.id, pair.id);
+
+    // Raw parsers in the library are attributed to their module.
+    let eat_if = error.parsers().find(|p| p.name == "eat_if").unwrap();
+    assert_eq!(eat_if.source.unwrap().module, "pear::parsers");
+}
+
+#[test]
+fn test_stable_ids() {
+    let error = parse!(digit: &mut Text::from("x")).unwrap_err();
+    let info = error.parsers().find(|p| p.name == "digit").unwrap();
+    let expected = ParserSource::new("parser_source::grammar", "digit", "", 0, 0);
+    assert_eq!(info.id(), Some(expected.id));
+
+    // The ID is the FNV-1a hash of the parser's path, fixed across builds.
+    assert_eq!(expected.id, 0x470bd3b0be39a1d8);
+
+    let mut runtime = pear::wrap::parser("digit", |i: &mut Text<'_>| eat(i, '0'));
+    let error = runtime(&mut Text::from("x")).unwrap_err();
+    assert!(error.parsers().find(|p| p.name == "digit").unwrap().source.is_none());
+}
@@ -0,0 +1,92 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Position, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[derive(Debug, PartialEq)]
+struct Style<'a> {
+    color: &'a str,
+    width: u32,
+    border: Option<&'a str>,
+}
+
+#[parser]
+fn declaration<'a>(input: &mut Text<'a>, name: &str) -> Result<&'a str, Text<'a>> {
+    eat_slice(name)?;
+    eat(':')?;
+    let value = take_some_while(|&c| c != ';')?;
+    eat(';')?;
+    value
+}
+
+#[parser]
+fn width<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+    eat_slice("width:")?;
+    let value = take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>()?;
+    eat(';')?;
+    value
+}
+
+#[parser]
+fn style<'a>(input: &mut Text<'a>) -> Result<Style<'a>, Text<'a>> {
+    let (color, width, border) = permute((
+        |i: &mut Text<'a>| declaration(i, "color"),
+        width,
+        optional(|i: &mut Text<'a>| declaration(i, "border")),
+    ))?;
+
+    Style { color, width, border }
+}
+
+#[test]
+fn test_permute_orders() {
+    let expected = Style { color: "red", width: 2, border: Some("none") };
+    for text in [
+        "color:red;width:2;border:none;",
+        "width:2;border:none;color:red;",
+        "border:none;color:red;width:2;",
+    ] {
+        assert_eq!(parse!(style: &mut Text::from(text)).unwrap(), expected);
+    }
+
+    let style = parse!(style: &mut Text::from("width:2;color:red;")).unwrap();
+    assert_eq!(style, Style { color: "red", width: 2, border: None });
+}
+
+#[test]
+fn test_permute_each_once() {
+    // The second `color` isn't consumed by the permutation.
+    let mut input = Text::from("color:red;width:2;color:blue;");
+    assert_eq!(style(&mut input).unwrap().color, "red");
+    assert_eq!(input.position(), 18);
+}
+
+#[test]
+fn test_permute_missing() {
+    let mut input = Text::from("width:2;");
+    let error = style(&mut input).unwrap_err();
+    assert_eq!(error.expected(), ["`color`"]);
+
+    let error = parse!(style: &mut Text::from("border:none;")).unwrap_err();
+    assert_eq!(error.expected(), ["`color`", "`width:`"]);
+}
+
+#[parser]
+fn bang<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    eat('b')?;
+    cut(|i| eat(i, '!'))?
+}
+
+#[parser]
+fn strict<'a>(input: &mut Text<'a>) -> Result<(char, char), Text<'a>> {
+    permute((|i: &mut Text<'a>| eat(i, 'a'), bang))?
+}
+
+#[test]
+fn test_permute_committed() {
+    assert_eq!(parse!(strict: &mut Text::from("b!a")).unwrap(), ('a', '!'));
+
+    let error = parse!(strict: &mut Text::from("ab?")).unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.expected(), ["`!`"]);
+}
@@ -0,0 +1,118 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Input, Text, Position, Spanned, Result};
+use pear::macros::{parser, switch};
+use pear::parsers::*;
+use pear::pipeline::{lex, TokenStreamInput};
+
+#[derive(Debug, PartialEq)]
+enum Tok<'a> {
+    Ident(&'a str),
+    Eq,
+    Semi,
+}
+
+pear::impl_show_with!(Debug, Tok<'_>);
+
+type Tokens<'a> = TokenStreamInput<'a, Tok<'a>>;
+
+#[parser]
+fn token<'a>(input: &mut Text<'a>) -> Result<Option<Tok<'a>>, Text<'a>> {
+    switch! {
+        eat('=') => Some(Tok::Eq),
+        eat(';') => Some(Tok::Semi),
+        eat_slice("//") => { take_while(|&c| c != '\n')?; None },
+        take_some_while(|c: &char| c.is_whitespace()) => None,
+        _ => Some(Tok::Ident(take_some_while(|c: &char| c.is_alphanumeric())?))
+    }
+}
+
+#[parser]
+fn ident<'a>(input: &mut Tokens<'a>) -> Result<&'a str, Tokens<'a>> {
+    match eat_if(|t: &&Tok<'a>| matches!(t, Tok::Ident(_)))? {
+        Tok::Ident(name) => *name,
+        _ => unreachable!(),
+    }
+}
+
+#[parser]
+fn binding<'a>(input: &mut Tokens<'a>) -> Result<(&'a str, &'a str), Tokens<'a>> {
+    let name = ident()?;
+    eat(&Tok::Eq)?;
+    let value = ident()?;
+    eat(&Tok::Semi)?;
+    (name, value)
+}
+
+#[parser]
+fn bindings<'a>(input: &mut Tokens<'a>) -> Result<Vec<(&'a str, &'a str)>, Tokens<'a>> {
+    let mut bindings = vec![];
+    while input.token().is_some() {
+        bindings.push(binding()?);
+    }
+
+    bindings
+}
+
+const SOURCE: &str = "a = b; // first\nc = d;\n";
+
+#[test]
+fn test_lex() {
+    let tokens = lex(&mut Text::from(SOURCE), token).unwrap();
+    let values: Vec<_> = tokens.iter().map(|t| &t.value).collect();
+    assert_eq!(values, [
+        &Tok::Ident("a"), &Tok::Eq, &Tok::Ident("b"), &Tok::Semi,
+        &Tok::Ident("c"), &Tok::Eq, &Tok::Ident("d"), &Tok::Semi,
+    ]);
+
+    assert_eq!(tokens[4], Spanned::new(Tok::Ident("c"), 16..17));
+
+    let error = lex(&mut Text::from("a = $"), token).unwrap_err();
+    assert_eq!(error.contexts[0].context.unwrap().start, (1, 5, 4));
+}
+
+#[test]
+fn test_lex_without_progress() {
+    let nothing = |_: &mut Text<'_>| -> Result<Option<()>, Text<'_>> { Ok(None) };
+    let error = lex(&mut Text::from("a"), nothing).unwrap_err();
+    assert!(error.to_string().contains("a lexer that consumes input"));
+}
+
+#[test]
+fn test_token_stream_input() {
+    let tokens = lex(&mut Text::from(SOURCE), token).unwrap();
+    let mut input = TokenStreamInput::new(SOURCE, &tokens);
+    assert_eq!(bindings(&mut input).unwrap(), [("a", "b"), ("c", "d")]);
+    assert_eq!(input.position(), 8);
+    assert_eq!(input.source_span(4..8), 16..22);
+}
+
+#[test]
+fn test_errors_point_at_source() {
+    let source = "a = b;\nc = = d;";
+    let tokens = lex(&mut Text::from(source), token).unwrap();
+    let error = bindings(&mut TokenStreamInput::new(source, &tokens)).unwrap_err();
+
+    // The failing token is the second `=`, at 2:5, in the `binding` at 2:1.
+    let spans: Vec<_> = error.contexts.iter()
+        .filter(|c| !c.parser.raw)
+        .map(|c| (c.parser.name, c.context.unwrap()))
+        .collect();
+
+    assert_eq!(spans[0].0, "ident");
+    assert_eq!((spans[0].1.start, spans[0].1.snippet), ((2, 5, 11), Some("")));
+    assert_eq!(spans[1].0, "binding");
+    assert_eq!((spans[1].1.start, spans[1].1.end), ((2, 1, 7), (2, 4, 10)));
+    assert_eq!(spans[1].1.snippet, Some("c ="));
+
+    assert!(error.to_string().contains("binding at 2:1 to 2:4 \"c =\""));
+}
+
+#[test]
+fn test_eof_context() {
+    let source = "a = b";
+    let tokens = lex(&mut Text::from(source), token).unwrap();
+    let error = binding(&mut TokenStreamInput::new(source, &tokens)).unwrap_err();
+    let context = error.contexts[0].context.unwrap();
+    assert_eq!((context.start, context.end), ((1, 6, 5), (1, 6, 5)));
+}
@@ -0,0 +1,96 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Num(u32),
+    Neg(Box<Expr>),
+    Fact(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Pow(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn show(&self) -> String {
+        match self {
+            Expr::Num(n) => n.to_string(),
+            Expr::Neg(e) => format!("(-{})", e.show()),
+            Expr::Fact(e) => format!("({}!)", e.show()),
+            Expr::Add(a, b) => format!("({} + {})", a.show(), b.show()),
+            Expr::Sub(a, b) => format!("({} - {})", a.show(), b.show()),
+            Expr::Mul(a, b) => format!("({} * {})", a.show(), b.show()),
+            Expr::Pow(a, b) => format!("({} ^ {})", a.show(), b.show()),
+        }
+    }
+}
+
+#[parser]
+fn atom<'a>(input: &mut Text<'a>) -> Result<Expr, Text<'a>> {
+    switch! {
+        eat('(') => { let expr = expr()?; eat(')')?; expr },
+        _ => Expr::Num(take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>().unwrap())
+    }
+}
+
+#[parser]
+fn prefix<'a>(input: &mut Text<'a>) -> Result<Prefix<Expr>, Text<'a>> {
+    eat('-')?;
+    Prefix::new(3, |e| Expr::Neg(Box::new(e)))
+}
+
+#[parser]
+fn infix<'a>(input: &mut Text<'a>) -> Result<Infix<Expr>, Text<'a>> {
+    switch! {
+        eat('+') => Infix::left(1, |a, b| Expr::Add(Box::new(a), Box::new(b))),
+        eat('-') => Infix::left(1, |a, b| Expr::Sub(Box::new(a), Box::new(b))),
+        eat('*') => Infix::left(2, |a, b| Expr::Mul(Box::new(a), Box::new(b))),
+        _ => { eat('^')?; Infix::right(4, |a, b| Expr::Pow(Box::new(a), Box::new(b))) }
+    }
+}
+
+#[parser]
+fn postfix<'a>(input: &mut Text<'a>) -> Result<Postfix<Expr>, Text<'a>> {
+    eat('!')?;
+    Postfix::new(5, |e| Expr::Fact(Box::new(e)))
+}
+
+#[parser]
+fn expr<'a>(input: &mut Text<'a>) -> Result<Expr, Text<'a>> {
+    precedence(atom, prefix, infix, postfix)?
+}
+
+fn show(string: &str) -> String {
+    parse!(expr: &mut Text::from(string)).unwrap().show()
+}
+
+#[test]
+fn test_precedence_and_associativity() {
+    assert_eq!(show("1"), "1");
+    assert_eq!(show("1+2*3"), "(1 + (2 * 3))");
+    assert_eq!(show("1-2-3"), "((1 - 2) - 3)");
+    assert_eq!(show("2^3^4"), "(2 ^ (3 ^ 4))");
+    assert_eq!(show("(1+2)*3"), "((1 + 2) * 3)");
+}
+
+#[test]
+fn test_prefix_and_postfix() {
+    assert_eq!(show("-1+2"), "((-1) + 2)");
+    assert_eq!(show("-2^2"), "(-(2 ^ 2))");
+    assert_eq!(show("--3!"), "(-(-(3!)))");
+    assert_eq!(show("2*3!!"), "(2 * ((3!)!))");
+    assert_eq!(show("(1+2)!*-3"), "(((1 + 2)!) * (-3))");
+}
+
+#[test]
+fn test_precedence_errors() {
+    assert!(parse!(expr: &mut Text::from("")).is_err());
+    assert!(parse!(expr: &mut Text::from("1+")).is_err());
+    assert!(parse!(expr: &mut Text::from("1+*2")).is_err());
+
+    let err = parse!(expr: &mut Text::from("x")).unwrap_err();
+    assert!(err.expected().contains(&"`-`"));
+}
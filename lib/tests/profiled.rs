@@ -0,0 +1,96 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Profiled, Result};
+use pear::macros::{parser, parse, switch};
+use pear::parsers::*;
+
+type Input<'a> = Profiled<Text<'a>>;
+
+#[parser]
+fn number<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    take_some_while(|c| c.is_ascii_digit())?
+}
+
+#[parser]
+fn atom<'a>(input: &mut Input<'a>) -> Result<(), Input<'a>> {
+    switch! {
+        eat('(') => {
+            sum()?;
+            eat(')')?;
+        },
+        _ => { number()?; }
+    }
+}
+
+#[parser]
+fn sum<'a>(input: &mut Input<'a>) -> Result<(), Input<'a>> {
+    atom()?;
+    while eat(input, '+').is_ok() {
+        atom()?;
+    }
+}
+
+#[test]
+fn test_counts() {
+    let mut input = Profiled::new(Text::from("1+(2+3)"));
+    parse!(sum: &mut input).unwrap();
+
+    let stats = input.stats();
+    let atom = stats.get("atom").unwrap();
+    assert_eq!((atom.calls, atom.backtracks), (4, 0));
+
+    let sum = stats.get("sum").unwrap();
+    assert_eq!((sum.calls, sum.backtracks), (2, 0));
+
+    // One `eat('(')` per atom, one `eat('+')` per `+` and at each `sum`'s end,
+    // and one `eat(')')`. All of those looking for `(` but one and those
+    // looking for `+` at each end fail.
+    let eat = stats.get("eat").unwrap();
+    assert_eq!((eat.calls, eat.backtracks), (4 + 4 + 1, 3 + 2));
+    assert!(stats.get("missing").is_none());
+}
+
+#[test]
+fn test_failures_are_recorded() {
+    let mut input = Profiled::new(Text::from("1+x"));
+    assert!(parse!(sum: &mut input).is_err());
+
+    let stats = input.stats();
+    assert_eq!(stats.get("sum").unwrap().backtracks, 1);
+    assert_eq!(stats.get("number").unwrap().calls, 2);
+    assert_eq!(stats.get("number").unwrap().backtracks, 1);
+}
+
+#[test]
+fn test_times_are_inclusive() {
+    let mut input = Profiled::new(Text::from("1+2"));
+    parse!(sum: &mut input).unwrap();
+
+    let sorted = input.stats().sorted();
+    assert_eq!(sorted[0].0, "sum");
+    for (_, stats) in &sorted[1..] {
+        assert!(sorted[0].1.time >= stats.time);
+    }
+
+    let (_, mut stats) = input.into_inner();
+    stats.clear();
+    assert!(stats.sorted().is_empty());
+}
+
+#[test]
+fn test_report() {
+    let mut input = Profiled::new(Text::from("(1)"));
+    parse!(sum: &mut input).unwrap();
+
+    let report = input.stats().to_string();
+    let lines: Vec<_> = report.lines().collect();
+    assert!(lines[0].starts_with("parser"));
+    assert!(lines[0].contains("backtracks"));
+    // `parse!` additionally calls `eof`.
+    assert_eq!(lines.len(), 1 + 6);
+    assert!(lines[1].starts_with("sum "));
+
+    let eat = lines.iter().find(|l| l.starts_with("eat ")).unwrap();
+    let columns: Vec<_> = eat.split_whitespace().collect();
+    assert_eq!(&columns[1..3], ["5", "3"]);
+}
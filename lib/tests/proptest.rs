@@ -0,0 +1,120 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::macros::{grammar, parse};
+use pear::input::Text;
+use pear::proptest::{Generator, GrammarError};
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::{TestRunner, TestError};
+
+#[grammar]
+mod list {
+    use pear::input::{Text, Result};
+    use pear::{macros::*, parsers::*};
+
+    #[parser]
+    pub fn list<'a>(input: &mut Text<'a>) -> Result<Vec<u32>, Text<'a>> {
+        eat('[')?;
+        let items = items()?;
+        eat(']')?;
+        items
+    }
+
+    #[parser]
+    fn items<'a>(input: &mut Text<'a>) -> Result<Vec<u32>, Text<'a>> {
+        let first = item()?;
+        switch! {
+            eat(',') => {
+                let mut items = items()?;
+                items.insert(0, first);
+                items
+            },
+            _ => vec![first]
+        }
+    }
+
+    #[parser]
+    fn item<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+        switch! {
+            n@number() => n,
+            eat_slice("nil") => 0,
+            _ => parse_error!("expected an item")?
+        }
+    }
+
+    #[parser]
+    fn number<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+        let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+        digits.parse().unwrap_or(u32::MAX)
+    }
+}
+
+use list::list;
+
+fn generator() -> Generator {
+    Generator::new(list::GRAMMAR).unwrap().rule("number", "[0-9]{1,3}")
+}
+
+#[test]
+fn test_opaque_symbols() {
+    assert_eq!(list::GRAMMAR.lines().nth(1), Some("items = item [ ',' items ] ;"));
+
+    let generator = Generator::new(list::GRAMMAR).unwrap();
+    assert_eq!(generator.strategy("list").unwrap_err(), GrammarError::Opaque {
+        rule: "number".into(),
+        symbol: "? c.is_ascii_digit() ?".into(),
+    });
+
+    let generator = generator.rule("? c.is_ascii_digit() ?", Just("7".to_string()));
+    let mut runner = TestRunner::deterministic();
+    let tree = generator.strategy("item").unwrap().new_tree(&mut runner).unwrap();
+    let item = tree.current();
+    assert!(item == "nil" || (!item.is_empty() && item.chars().all(|c| c == '7')), "{}", item);
+
+    let error = generator.strategy("lists").unwrap_err();
+    assert_eq!(error.to_string(), "unknown rule `lists`");
+}
+
+#[test]
+fn test_round_trip() {
+    let strategy = generator().strategy("list").unwrap();
+    let mut runner = TestRunner::deterministic();
+    runner.run(&strategy, |string| {
+        let items = parse!(list: &mut Text::from(&*string));
+        prop_assert!(items.is_ok(), "{:?} failed to parse: {}", string, items.unwrap_err());
+        prop_assert_eq!(items.unwrap().len(), string.matches(',').count() + 1);
+        Ok(())
+    }).unwrap();
+}
+
+#[test]
+fn test_shrinking() {
+    let strategy = generator().strategy("list").unwrap();
+    let mut runner = TestRunner::deterministic();
+    let result = runner.run(&strategy, |string| {
+        prop_assert!(!string.contains("nil"));
+        Ok(())
+    });
+
+    match result {
+        Err(TestError::Fail(_, minimal)) => assert_eq!(minimal, "[nil]"),
+        result => panic!("expected a failure, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_bounded_recursion() {
+    let grammar = "expr = ( '(' expr ')' | term ) ;\nterm = \"x\\n\" b'y' ;";
+    let generator = Generator::new(grammar).unwrap().max_depth(3);
+    let mut runner = TestRunner::deterministic();
+    runner.run(&generator.strategy("expr").unwrap(), |string| {
+        prop_assert!(string.len() <= 2 * 3 + 3, "{:?} is too deep", string);
+        prop_assert!(string.trim_matches(|c| c == '(' || c == ')') == "x\ny");
+        Ok(())
+    }).unwrap();
+
+    let generator = Generator::new("a = '(' a ')' ;\nb = ( a | 'b' ) ;").unwrap();
+    assert_eq!(generator.strategy("a").unwrap_err(), GrammarError::Unbounded("a".into()));
+    assert_eq!(generator.strategy("b").unwrap_err(), GrammarError::Unbounded("a".into()));
+    assert!(Generator::new("a = ( 'b' ;").is_err());
+}
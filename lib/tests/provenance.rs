@@ -0,0 +1,62 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::Text;
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn digit<'a>(input: &mut Text<'a>) -> Result<'a, char> {
+    switch! {
+        c@eat('0') | c@eat('1') => c,
+        _ => parse_error!("expected a binary digit")?
+    }
+}
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<'a, String> {
+    let mut number = String::new();
+    loop {
+        switch! {
+            eat('_') => continue,
+            c@digit() => number.push(c),
+            _ => break
+        }
+    }
+
+    number
+}
+
+#[test]
+fn test_provenance() {
+    let (result, choices) = pear::provenance::record(|| {
+        parse!(number: &mut Text::from("1_0"))
+    });
+
+    assert_eq!(result.unwrap(), "10");
+
+    let path: Vec<_> = choices.iter()
+        .map(|c| (c.parser, c.case, c.pattern))
+        .collect();
+
+    assert_eq!(path, vec![
+        ("digit", 0, "c@eat('1')"),
+        ("number", 1, "c@digit()"),
+        ("number", 0, "eat('_')"),
+        ("digit", 0, "c@eat('0')"),
+        ("number", 1, "c@digit()"),
+        ("digit", 1, "_"),
+        ("number", 2, "_"),
+    ]);
+
+    assert_eq!(choices[2].context.as_deref(), Some("1:1 to 1:3 \"1_\""));
+}
+
+#[test]
+fn test_provenance_disabled() {
+    let result = parse!(number: &mut Text::from("01"));
+    assert_eq!(result.unwrap(), "01");
+
+    let (_, choices) = pear::provenance::record(|| ());
+    assert!(choices.is_empty());
+}
@@ -0,0 +1,43 @@
+use pear::input::{Text, Input, ParserInfo, Result};
+use pear::macros::{parse_marker, parse_context, parse_error};
+use pear::parsers::*;
+
+fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    let start = parse_marker!(input);
+    let result = match take_while(input, |c: &char| c.is_alphabetic()) {
+        Ok("") => {
+            let context = parse_context!(input, &start).expect("text has context");
+            parse_error!("expected a word at {}", context.start.2)
+        }
+        result => result,
+    };
+
+    input.unmark(&ParserInfo::new("parse_marker", true), result.is_ok(), start);
+    result
+}
+
+fn digit<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+    match eat_any(input)?.to_digit(10) {
+        Some(digit) => Ok(digit),
+        None => parse_error!("expected a digit"),
+    }
+}
+
+#[test]
+fn test_free_function_macros() {
+    let mut input = Text::from("abc1x");
+    assert_eq!(word(&mut input).unwrap(), "abc");
+
+    let error = word(&mut input).unwrap_err();
+    assert!(error.to_string().contains("expected a word at 3"));
+
+    assert_eq!(digit(&mut input).unwrap(), 1);
+    assert!(digit(&mut input).unwrap_err().to_string().contains("expected a digit"));
+}
+
+#[test]
+fn test_raw_parser_marker() {
+    let mut input = Text::from("aaab");
+    let window = take_while_window(&mut input, 2, |w: &&str| *w == "aa").unwrap();
+    assert_eq!(window, "aa");
+}
@@ -0,0 +1,55 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Input, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+type Recovered<'a> = (Vec<&'a str>, usize);
+
+fn recover<'a>(input: &mut Text<'a>, seperator: Option<char>) -> Result<Recovered<'a>, Text<'a>> {
+    let (words, errors): (Vec<_>, Vec<_>) =
+        delimited_collect_recover(input, '(', word, seperator, ')')?;
+
+    Ok((words, errors.len()))
+}
+
+#[test]
+fn test_recover_ok() {
+    let mut input = Text::from("(a,b,c)!");
+    assert_eq!(recover(&mut input, Some(',')).unwrap(), (vec!["a", "b", "c"], 0));
+    assert_eq!(input.token(), Some('!'));
+
+    assert_eq!(recover(&mut Text::from("()"), Some(',')).unwrap(), (vec![], 0));
+}
+
+#[test]
+fn test_recover_from_items() {
+    let mut input = Text::from("(a,1,b2,(c,d),e)!");
+    assert_eq!(recover(&mut input, Some(',')).unwrap(), (vec!["a", "b", "e"], 3));
+    assert_eq!(input.token(), Some('!'));
+}
+
+#[test]
+fn test_recover_unterminated() {
+    let mut input = Text::from("(a,b");
+    assert_eq!(recover(&mut input, Some(',')).unwrap(), (vec!["a", "b"], 1));
+
+    let mut input = Text::from("(a,1");
+    assert_eq!(recover(&mut input, Some(',')).unwrap(), (vec!["a"], 2));
+    assert_eq!(input.token(), None);
+}
+
+#[test]
+fn test_recover_without_seperator() {
+    let mut input = Text::from("(ab1)");
+    assert_eq!(recover(&mut input, None).unwrap(), (vec!["ab"], 1));
+}
+
+#[test]
+fn test_recover_missing_start() {
+    assert!(recover(&mut Text::from("a,b)"), Some(',')).is_err());
+}
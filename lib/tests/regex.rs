@@ -0,0 +1,103 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::macros::{parser, parse, switch};
+use pear::parsers::*;
+
+#[derive(Debug, PartialEq)]
+enum Lexeme<'a> {
+    Number(f64),
+    Ident(&'a str),
+    Op(&'a str),
+}
+
+#[parser]
+fn lexemes<'a>(input: &mut Text<'a>) -> Result<Vec<Lexeme<'a>>, Text<'a>> {
+    let number = Regex::new(r"[0-9]+(\.[0-9]+)?").unwrap();
+    let ident = Regex::new(r"[a-zA-Z_][a-zA-Z0-9_]*").unwrap();
+    let op = Regex::new(r"(==|[-+*/=])").unwrap();
+
+    let mut lexemes = vec![];
+    loop {
+        skip_while(|c: &char| c.is_whitespace())?;
+        let lexeme = switch! {
+            c@re(&number) => Lexeme::Number(c[0].parse::<f64>().unwrap()),
+            c@re(&ident) => Lexeme::Ident(c.get(0).unwrap()),
+            c@re(&op) => Lexeme::Op(c.get(1).unwrap()),
+            _ => break
+        };
+
+        lexemes.push(lexeme);
+    }
+
+    lexemes
+}
+
+#[test]
+fn test_lexing() {
+    let result = parse!(lexemes: &mut Text::from("x1 == 2.5 * y"));
+    assert_eq!(result.unwrap(), [
+        Lexeme::Ident("x1"),
+        Lexeme::Op("=="),
+        Lexeme::Number(2.5),
+        Lexeme::Op("*"),
+        Lexeme::Ident("y"),
+    ]);
+
+    assert!(parse!(lexemes: &mut Text::from("x $")).is_err());
+}
+
+#[test]
+fn test_anchored() {
+    let word = Regex::new(r"[a-z]+").unwrap();
+    let mut input = Text::from("12abc");
+    let error = re(&mut input, &word).unwrap_err();
+    assert!(error.to_string().starts_with("expected text matching `[a-z]+` but found `1`"));
+
+    eat_slice(&mut input, "12").unwrap();
+    let captures = re(&mut input, &word).unwrap();
+    assert_eq!(&captures[0], "abc");
+    assert!(eof(&mut input).is_ok());
+}
+
+#[test]
+fn test_captures() {
+    let pair = Regex::new(r"(?P<key>\w+)=(?P<value>\w*)").unwrap();
+    let mut input = Text::from("name=pear;");
+    let captures = re(&mut input, &pair).unwrap();
+    assert_eq!(&captures["key"], "name");
+    assert_eq!(&captures["value"], "pear");
+    assert_eq!(captures.name("value"), Some("pear"));
+    assert_eq!(captures.range(2), Some(5..9));
+    assert!(eat(&mut input, ';').is_ok());
+}
+
+#[test]
+fn test_left_context() {
+    // `\b` sees the text before the current position.
+    let word = Regex::new(r"\b[a-z]+").unwrap();
+    let mut input = Text::from("ab cd");
+    eat(&mut input, 'a').unwrap();
+    assert!(re(&mut input, &word).is_err());
+
+    eat_slice(&mut input, "b ").unwrap();
+    assert_eq!(&re(&mut input, &word).unwrap()[0], "cd");
+
+    // `^` only matches at the start of the text.
+    let start = Regex::new(r"^[a-z]+").unwrap();
+    let mut input = Text::from("ab cd");
+    assert_eq!(&re(&mut input, &start).unwrap()[0], "ab");
+    eat(&mut input, ' ').unwrap();
+    assert!(re(&mut input, &start).is_err());
+}
+
+#[test]
+fn test_captures_offset() {
+    let pair = Regex::new(r"(?P<key>\w+)=(?P<value>\w*)").unwrap();
+    let mut input = Text::from("x; name=pear");
+    eat_slice(&mut input, "x; ").unwrap();
+    let captures = re(&mut input, &pair).unwrap();
+    assert_eq!(captures.range(0), Some(0..9));
+    assert_eq!(captures.range(1), Some(0..4));
+    assert!(eof(&mut input).is_ok());
+}
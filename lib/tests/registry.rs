@@ -0,0 +1,79 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+use pear::grammar::Grammar;
+
+#[derive(Debug, PartialEq)]
+enum Directive<'a> {
+    Include(&'a str),
+    Define(&'a str, i64),
+}
+
+type Directives<'a> = Grammar<'static, Text<'a>, Directive<'a>>;
+
+#[parser]
+fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_alphanumeric() || *c == '.')?
+}
+
+#[parser]
+fn directive<'a>(input: &mut Text<'a>, rules: &mut Directives<'a>) -> Result<Directive<'a>, Text<'a>> {
+    eat('@')?;
+    let name = word()?;
+    eat(' ')?;
+    let directive = rules.parse(name, input)?;
+    eat('\n')?;
+    directive
+}
+
+#[parser]
+fn document<'a>(input: &mut Text<'a>, rules: &mut Directives<'a>) -> Result<Vec<Directive<'a>>, Text<'a>> {
+    let directives: Vec<_> = collect(|i| directive(i, rules))?;
+    directives
+}
+
+fn base<'a>() -> Directives<'a> {
+    let mut rules = Directives::new();
+    rules.register("include", |input: &mut Text<'a>| Ok(Directive::Include(word(input)?)));
+    rules
+}
+
+#[test]
+fn test_plugin_rules() {
+    let mut rules = base();
+    assert!(document(&mut Text::from("@define x 1\n"), &mut rules).is_err());
+
+    rules.register("define", |input: &mut Text<'_>| {
+        let name = word(input)?;
+        eat(input, ' ')?;
+        let value = word(input)?.parse::<i64>().unwrap();
+        Ok(Directive::Define(name, value))
+    });
+
+    assert_eq!(rules.names().collect::<Vec<_>>(), ["define", "include"]);
+    let source = "@include a.txt\n@define x 1\n";
+    let result = document(&mut Text::from(source), &mut rules).unwrap();
+    assert_eq!(result, [Directive::Include("a.txt"), Directive::Define("x", 1)]);
+
+    assert!(rules.unregister("define").is_some());
+    assert!(!rules.contains("define"));
+}
+
+#[test]
+fn test_rule_errors() {
+    let mut rules = base();
+    let error = rules.parse("include", &mut Text::from("!")).unwrap_err();
+    assert!(error.contexts.iter().any(|c| c.parser.name == "include" && !c.parser.raw));
+
+    let error = rules.parse("define", &mut Text::from("x 1")).unwrap_err();
+    assert!(error.to_string().contains("unknown rule `define`"));
+}
+
+#[test]
+fn test_replace_rule() {
+    let mut rules = base();
+    let old = rules.register("include", |_: &mut Text<'_>| Ok(Directive::Include("*")));
+    assert!(old.is_some());
+    assert_eq!(rules.parse("include", &mut Text::from("a")).unwrap(), Directive::Include("*"));
+}
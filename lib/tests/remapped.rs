@@ -0,0 +1,114 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Remapped, SourceMap, Show, Result};
+use pear::macros::{parser, parse};
+use pear::parsers::*;
+
+type Input<'a> = Remapped<'a, Text<'a>>;
+
+#[parser]
+fn digits<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?
+}
+
+#[parser]
+fn numbers<'a>(input: &mut Input<'a>) -> Result<Vec<&'a str>, Input<'a>> {
+    let mut numbers = vec![];
+    loop {
+        let number = digits()?;
+        numbers.push(number);
+        if eat(input, ',').is_err() {
+            break;
+        }
+    }
+
+    eof()?;
+    numbers
+}
+
+const MAIN: &str = "1,2,\n#include nums\n,9";
+const NUMS: &str = "3,4\n5,x\n";
+
+// The result of expanding the include in `MAIN`, without its newlines.
+fn expanded() -> (&'static str, SourceMap<'static>) {
+    let mut map = SourceMap::new();
+    map.copied("main", MAIN, 0..4)
+        .copied("nums", NUMS, 0..3)
+        .synthesized(1)
+        .copied("nums", NUMS, 4..7)
+        .copied("main", MAIN, 19..21);
+
+    ("1,2,3,4,5,x,9", map)
+}
+
+#[test]
+fn test_locate() {
+    let (text, map) = expanded();
+    assert_eq!(map.len(), text.len());
+
+    let location = map.locate(0).unwrap();
+    assert_eq!((location.file, location.line, location.column), ("main", 1, 1));
+
+    let location = map.locate(4).unwrap();
+    assert_eq!((location.file, location.offset), ("nums", 0));
+
+    let location = map.locate(10).unwrap();
+    assert_eq!((location.file, location.line, location.column), ("nums", 2, 3));
+
+    let location = map.locate(12).unwrap();
+    assert_eq!((location.file, location.line, location.column), ("main", 3, 2));
+
+    assert!(map.locate(7).is_none());
+    assert!(map.locate(text.len() + 1).is_none());
+    assert_eq!(map.locate(text.len()).unwrap().offset, MAIN.len());
+}
+
+#[test]
+fn test_span() {
+    let (_, map) = expanded();
+
+    // A span ending at a segment boundary stays in its segment.
+    let span = map.span(4..7).unwrap();
+    assert_eq!((span.start.file, span.end.file), ("nums", "nums"));
+    assert_eq!(span.snippet, Some("3,4"));
+
+    let span = map.span(2..5).unwrap();
+    assert_eq!((span.start.file, span.end.file), ("main", "nums"));
+    assert_eq!(span.snippet, None);
+    assert_eq!(format!("{}", &span as &dyn Show), "main:1:3 to nums:1:2");
+
+    // Only the ends of a span need be mapped.
+    assert!(map.span(7..9).is_none());
+    assert_eq!(map.span(6..9).unwrap().snippet, Some("4\n5"));
+}
+
+#[test]
+fn test_replaced() {
+    // `\u{41}` was decoded into `A`.
+    let source = r"x\u{41}y";
+    let mut map = SourceMap::new();
+    map.copied("escape", source, 0..1).replaced(1, "escape", source, 1..7).copied("escape", source, 7..8);
+
+    assert_eq!(map.locate(1).unwrap().offset, 1);
+    assert_eq!(map.locate(2).unwrap().offset, 7);
+    assert_eq!(map.span(1..2).unwrap().snippet, Some(r"\u{41}"));
+}
+
+#[test]
+fn test_remapped_errors() {
+    let (text, map) = expanded();
+    let mut input = Remapped::new(Text::from(text), map);
+    let error = parse!(numbers: &mut input).unwrap_err();
+
+    let context = error.contexts.iter()
+        .find(|c| c.parser.name == "numbers")
+        .and_then(|c| c.context)
+        .unwrap();
+
+    assert_eq!((context.start.file, context.start.offset), ("main", 0));
+    assert_eq!((context.end.file, context.end.line, context.end.column), ("nums", 2, 3));
+
+    let (text, map) = expanded();
+    let mut input = Remapped::new(Text::from(&text[..7]), map);
+    assert_eq!(parse!(numbers: &mut input).unwrap(), ["1", "2", "3", "4"]);
+}
@@ -0,0 +1,81 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::macros::{parser, parse};
+use pear::parsers::*;
+use pear::combinators::*;
+use pear::replay::Replay;
+use pear::trace::TraceKind;
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>().unwrap()
+}
+
+#[parser]
+fn list<'a>(input: &mut Text<'a>) -> Result<Vec<u32>, Text<'a>> {
+    let list: Vec<u32> = delimited_collect('[', number, ',', ']')?;
+    list
+}
+
+#[parser]
+fn lists<'a>(input: &mut Text<'a>) -> Result<Vec<Vec<u32>>, Text<'a>> {
+    let lists: Vec<Vec<u32>> = series(list, ';')?;
+    lists
+}
+
+#[test]
+fn test_snapshot() {
+    let source = "[1,2];[3,é4];[5]";
+    let error = parse!(lists: &mut Text::from(source)).unwrap_err();
+    let replay = Replay::new(source, &error);
+
+    assert_eq!(replay.parser, "lists");
+    assert_eq!((replay.start, replay.offset), (0, 9));
+    assert_eq!(replay.window, source);
+    assert_eq!(replay.error, error.to_string());
+
+    let stack: Vec<_> = replay.stack.iter().map(|(name, at)| (name.as_str(), *at)).collect();
+    assert_eq!(stack, [
+        ("lists", 0),
+        ("series", 0),
+        ("list", 6),
+        ("delimited_collect", 6),
+        ("number", 9),
+        ("take_some_while", 9),
+    ]);
+
+    let replay = replay.with_lookahead(1);
+    assert_eq!(replay.window, "[1,2];[3,é");
+}
+
+#[test]
+fn test_round_trip() {
+    let source = "[1];[2,\n]";
+    let error = parse!(lists: &mut Text::from(source)).unwrap_err();
+    let replay = Replay::new(source, &error);
+
+    let artifact = replay.to_string();
+    assert!(artifact.starts_with("pear-replay 1\nparser lists\nstart 0\noffset 7\n"));
+    assert_eq!(Replay::parse(&artifact).unwrap(), replay);
+
+    assert!(Replay::parse("pear-replay 2\n").is_err());
+    assert!(Replay::parse(&artifact.replace("window 9", "window 90")).is_err());
+}
+
+#[test]
+fn test_run() {
+    let source = "[1];[2,x]";
+    let error = parse!(lists: &mut Text::from(source)).unwrap_err();
+    let replay = Replay::parse(&Replay::new(source, &error).to_string()).unwrap();
+
+    let (result, trace) = replay.run(|input| lists(input));
+    assert_eq!(result.unwrap_err().to_string(), replay.error);
+
+    let failed: Vec<_> = trace.events().iter()
+        .filter(|event| event.kind == TraceKind::Backtrack && !event.parser.raw)
+        .map(|event| (event.parser.name, event.depth))
+        .collect();
+
+    assert_eq!(failed, [("number", 4), ("list", 2), ("lists", 0)]);
+}
@@ -0,0 +1,128 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Span, Result};
+use pear::error::ErrorCode;
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn key<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_alphabetic())?
+}
+
+#[parser]
+fn pair<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+    let k = key()?;
+    eat('=')?;
+    let v = key()?;
+    (k, v)
+}
+
+#[parser]
+fn word<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[parser]
+fn entry<'a>(input: &mut Text<'a>) -> Result<(&'a str, &'a str), Text<'a>> {
+    let k = word()?;
+    eat('=')?;
+    let v = word()?;
+    (k, v)
+}
+
+const SOURCE: &str = "abc=12";
+
+fn report() -> pear::report::Report {
+    parse!(pair: &mut Text::from(SOURCE)).unwrap_err().report(SOURCE)
+}
+
+#[test]
+fn test_report() {
+    let report = report();
+    assert_eq!(report.code(), ErrorCode::Unexpected);
+    assert_eq!(report.location(), 4..4);
+    assert_eq!(report.labels(), &[(0..4, "while parsing pair".to_string())]);
+    assert_eq!(report.to_string(), "unexpected token: `1`");
+}
+
+#[test]
+fn test_miette() {
+    use miette::Diagnostic;
+
+    let report = report();
+    assert_eq!(Diagnostic::code(&report).unwrap().to_string(), "unexpected");
+    assert!(report.source_code().is_some());
+
+    let labels: Vec<_> = Diagnostic::labels(&report).unwrap().collect();
+    assert_eq!(labels.len(), 2);
+    assert!(labels[0].primary());
+    assert_eq!((labels[0].offset(), labels[0].len()), (4, 0));
+    assert_eq!(labels[1].label(), Some("while parsing pair"));
+    assert_eq!((labels[1].offset(), labels[1].len()), (0, 4));
+
+    let _: miette::Report = report.into();
+}
+
+/// Renders `report` with `ariadne`, stripped of ANSI color sequences.
+fn render(report: &pear::report::Report) -> String {
+    let mut output = vec![];
+    report.to_ariadne()
+        .write(ariadne::Source::from(report.source()), &mut output)
+        .unwrap();
+
+    let mut output = String::from_utf8(output).unwrap();
+    while let Some(start) = output.find('\x1b') {
+        let end = output[start..].find('m').map(|i| start + i + 1).unwrap();
+        output.replace_range(start..end, "");
+    }
+
+    output
+}
+
+#[test]
+fn test_ariadne() {
+    let output = render(&report());
+    assert!(output.contains("unexpected token: `1`"));
+    assert!(output.contains("while parsing pair"));
+}
+
+#[test]
+fn test_ariadne_multibyte() {
+    let source = "ñandú=12";
+    let report = parse!(entry: &mut Text::from(source)).unwrap_err().report(source);
+    assert_eq!(report.location(), 8..8);
+
+    let output = render(&report);
+    assert!(output.contains("<unknown>:1:7 ]"));
+    assert!(output.contains("──┬──│"));
+}
+
+#[test]
+fn test_hints() {
+    let error = parse!(pair: &mut Text::from(SOURCE)).unwrap_err();
+    let span = Span { start: (1, 5, 4), end: (1, 7, 6), snippet: Some("12") };
+    let error = error.with_help("values are alphabetic")
+        .with_note("keys are too")
+        .with_suggestion(span, "xyz");
+
+    let suggestion = error.suggestions().next().unwrap();
+    assert_eq!(suggestion.apply(SOURCE).unwrap(), "abc=xyz");
+
+    let report = error.report(SOURCE);
+    assert_eq!(report.help(), ["values are alphabetic"]);
+    assert_eq!(report.notes(), ["keys are too"]);
+    assert_eq!(report.labels().last().unwrap(), &(4..6, "replace with `xyz`".to_string()));
+
+    use miette::Diagnostic;
+    let help = Diagnostic::help(&report).unwrap().to_string();
+    assert_eq!(help, "values are alphabetic\nnote: keys are too");
+
+    let mut output = vec![];
+    report.to_ariadne()
+        .write(ariadne::Source::from(report.source()), &mut output)
+        .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("values are alphabetic"));
+    assert!(output.contains("keys are too"));
+}
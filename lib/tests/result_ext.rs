@@ -0,0 +1,65 @@
+use pear::input::{Text, Input, ParserInfo, Result};
+use pear::result::ResultExt;
+use pear::macros::parse_marker;
+use pear::parsers::*;
+
+/// Consumes `a` before failing on anything but `ab`.
+fn ab<'a>(input: &mut Text<'a>) -> Result<&'static str, Text<'a>> {
+    eat(input, 'a').and_then_parse(input, |i, _| eat(i, 'b')).map(|_| "ab")
+}
+
+fn ac<'a>(input: &mut Text<'a>) -> Result<&'static str, Text<'a>> {
+    eat(input, 'a').and_then_parse(input, |i, _| eat(i, 'c')).map(|_| "ac")
+}
+
+fn either<'a>(input: &mut Text<'a>) -> Result<&'static str, Text<'a>> {
+    let start = parse_marker!(input);
+    let result = ab(input).or_rewind(input, &start, ac);
+    input.unmark(&ParserInfo::new("parse_marker", true), result.is_ok(), start);
+    result
+}
+
+#[test]
+fn test_or_rewind() {
+    assert_eq!(either(&mut Text::from("ab")).unwrap(), "ab");
+    assert_eq!(either(&mut Text::from("ac")).unwrap(), "ac");
+
+    let mut input = Text::from("ad");
+    assert!(either(&mut input).is_err());
+    assert_eq!(input.token(), Some('d'));
+}
+
+#[test]
+fn test_or_parse() {
+    let mut input = Text::from("ac");
+    assert!(ab(&mut input).or_parse(&mut input, ac).is_err());
+
+    let mut input = Text::from("-");
+    let sign = eat(&mut input, '+').or_parse(&mut input, |i| eat(i, '-'));
+    assert_eq!(sign.unwrap(), '-');
+
+    let mut input = Text::from("x");
+    let error = eat(&mut input, '+').or_parse(&mut input, |i| eat(i, '-')).unwrap_err();
+    assert_eq!(error.expected(), vec!["`+`", "`-`"]);
+}
+
+#[test]
+fn test_committed_not_retried() {
+    let mut input = Text::from("-");
+    let result = eat(&mut input, '+').map_err(|e| e.commit())
+        .or_parse(&mut input, |i| eat(i, '-'));
+
+    assert!(result.unwrap_err().committed);
+    assert_eq!(input.token(), Some('-'));
+}
+
+#[test]
+fn test_expected() {
+    let mut input = Text::from("x");
+    let error = eat(&mut input, '+').expected("a sign").unwrap_err();
+    assert_eq!(error.expected(), vec!["a sign"]);
+    assert_eq!(error.found(), Some(&'x'));
+
+    let mut input = Text::from("+");
+    assert_eq!(eat(&mut input, '+').expected("a sign").unwrap(), '+');
+}
@@ -0,0 +1,125 @@
+#![feature(proc_macro_hygiene)]
+
+use serde::Deserialize;
+
+use pear::input::{Text, Result};
+use pear::serde::{Value, ParserDeserializer, from_input};
+use pear::{macros::*, parsers::*};
+
+// A Lisp-ish format: atoms, integers, quoted strings, and parenthesized lists.
+// A list whose first element is a `:keyword` is a map from keywords to values.
+
+#[parser]
+fn atom<'a>(input: &mut Text<'a>) -> Result<Value, Text<'a>> {
+    let atom = take_some_while(|c: &char| c.is_alphanumeric() || *c == '-')?;
+    match atom {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "nil" => Value::Option(None),
+        _ => match atom.parse::<i64>() {
+            Ok(n) => Value::I64(n),
+            Err(_) => Value::String(atom.into()),
+        }
+    }
+}
+
+#[parser]
+fn string<'a>(input: &mut Text<'a>) -> Result<Value, Text<'a>> {
+    eat('"')?;
+    let string = take_while(|&c| c != '"')?;
+    eat('"')?;
+    Value::String(string.into())
+}
+
+#[parser]
+fn entry<'a>(input: &mut Text<'a>) -> Result<(Value, Value), Text<'a>> {
+    eat(':')?;
+    let key = take_some_while(|c: &char| c.is_alphanumeric() || *c == '_')?;
+    skip_while(|c: &char| c.is_whitespace())?;
+    (Value::String(key.into()), value()?)
+}
+
+#[parser]
+fn list<'a>(input: &mut Text<'a>) -> Result<Value, Text<'a>> {
+    eat('(')?;
+    let mut values = vec![];
+    let mut entries = vec![];
+    loop {
+        skip_while(|c: &char| c.is_whitespace())?;
+        switch! {
+            eat(')') => break,
+            peek(':') => entries.push(entry()?),
+            _ => values.push(value()?)
+        }
+    }
+
+    match entries.is_empty() {
+        true => Value::Seq(values),
+        false => Value::Map(entries),
+    }
+}
+
+#[parser]
+fn value<'a>(input: &mut Text<'a>) -> Result<Value, Text<'a>> {
+    switch! {
+        peek('(') => list()?,
+        peek('"') => string()?,
+        _ => atom()?
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Shape {
+    Point,
+    Circle(u32),
+    Rect { w: u32, h: u32 },
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Drawing {
+    title: String,
+    visible: bool,
+    layer: Option<i8>,
+    tags: Vec<String>,
+    shapes: Vec<Shape>,
+}
+
+#[test]
+fn test_deserialize_struct() {
+    let source = r#"(:title "My drawing" :visible true :layer nil :tags (a b)
+        :shapes (point (:circle 3) (:rect (:w 1 :h 2))))"#;
+
+    let drawing: Drawing = from_input(&mut Text::from(source), value).unwrap();
+    assert_eq!(drawing, Drawing {
+        title: "My drawing".into(),
+        visible: true,
+        layer: None,
+        tags: vec!["a".into(), "b".into()],
+        shapes: vec![Shape::Point, Shape::Circle(3), Shape::Rect { w: 1, h: 2 }],
+    });
+}
+
+#[test]
+fn test_deserialize_primitives() {
+    let numbers: Vec<u8> = from_input(&mut Text::from("(1 2 3)"), value).unwrap();
+    assert_eq!(numbers, vec![1, 2, 3]);
+
+    let layer: Option<i64> = from_input(&mut Text::from("-4"), value).unwrap();
+    assert_eq!(layer, Some(-4));
+
+    let parsed = ParserDeserializer::new(&mut Text::from("(x)"), value).parse();
+    assert_eq!(parsed.unwrap(), Value::Seq(vec![Value::String("x".into())]));
+}
+
+#[test]
+fn test_deserialize_errors() {
+    let error = from_input::<u8, _, _>(&mut Text::from("300"), value).unwrap_err();
+    assert!(error.message().contains("300"));
+
+    let error = from_input::<Vec<u8>, _, _>(&mut Text::from("(1 2"), value).unwrap_err();
+    assert!(error.message().contains("list"));
+
+    let error = from_input::<u8, _, _>(&mut Text::from("1 2"), value).unwrap_err();
+    assert!(error.message().contains("EOF"));
+}
@@ -0,0 +1,137 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Input, Result};
+use pear::session::{Session, Partial, Event};
+use pear::{macros::*, parsers::*};
+
+/// A subset of RESP, the Redis protocol.
+#[derive(Debug, PartialEq)]
+enum Value {
+    Simple(String),
+    Integer(i64),
+    Bulk(Vec<u8>),
+}
+
+#[parser]
+fn line<'a>(input: &mut Partial<'a>) -> Result<&'a [u8], Partial<'a>> {
+    let line = take_while(|b: &u8| *b != b'\r')?;
+    eat_slice(&b"\r\n"[..])?;
+    line
+}
+
+#[parser]
+fn integer<'a>(input: &mut Partial<'a>) -> Result<i64, Partial<'a>> {
+    let digits = line()?;
+    match std::str::from_utf8(digits).ok().and_then(|s| s.parse::<i64>().ok()) {
+        Some(n) => n,
+        None => parse_error!("invalid integer")?,
+    }
+}
+
+#[parser]
+fn value<'a>(input: &mut Partial<'a>) -> Result<Value, Partial<'a>> {
+    switch! {
+        eat(b'+') => Value::Simple(String::from_utf8_lossy(line()?).into_owned()),
+        eat(b':') => Value::Integer(integer()?),
+        eat(b'$') => {
+            let len = integer()? as usize;
+            let data = take_n(len)?;
+            eat_slice(&b"\r\n"[..])?;
+            Value::Bulk(data.to_vec())
+        },
+        _ => parse_error!("unknown type")?
+    }
+}
+
+fn messages(events: Vec<Event<Value>>) -> Vec<Value> {
+    events.into_iter()
+        .map(|event| match event {
+            Event::Message(value) => value,
+            Event::Error(e) => panic!("unexpected error: {}", e),
+        })
+        .collect()
+}
+
+#[test]
+fn test_byte_at_a_time() {
+    let stream = b"+OK\r\n:-42\r\n$5\r\nhe\r\no\r\n";
+    let mut session = Session::new(value);
+    let mut values = vec![];
+    for byte in stream.iter() {
+        values.extend(messages(session.feed(&[*byte])));
+    }
+
+    assert_eq!(values, [
+        Value::Simple("OK".into()),
+        Value::Integer(-42),
+        Value::Bulk(b"he\r\no".to_vec()),
+    ]);
+
+    assert!(session.buffered().is_empty());
+    assert!(session.finish().is_empty());
+}
+
+#[test]
+fn test_suspends_until_complete() {
+    let mut session = Session::new(value);
+    assert!(session.feed(b":12").is_empty());
+    assert!(session.feed(b"3").is_empty());
+    assert_eq!(messages(session.feed(b"\r\n:4\r\n+")), [Value::Integer(123), Value::Integer(4)]);
+    assert_eq!(session.buffered(), b"+");
+}
+
+#[test]
+fn test_error_ends_session() {
+    let mut session = Session::new(value);
+    assert_eq!(messages(session.feed(b":1\r\n")), [Value::Integer(1)]);
+
+    let events = session.feed(b":x\r\n+OK\r\n");
+    let error = match &events[..] {
+        [Event::Error(e)] => e,
+        events => panic!("expected an error, found {:?}", events),
+    };
+
+    assert_eq!(error.contexts.last().unwrap().context, Some(4..8));
+    assert!(session.is_failed());
+    assert!(session.feed(b"+OK\r\n").is_empty());
+}
+
+#[test]
+fn test_finish_with_incomplete_message() {
+    let mut session = Session::new(value);
+    assert!(session.feed(b"$10\r\nabc").is_empty());
+    match &session.finish()[..] {
+        [Event::Error(_)] => {}
+        events => panic!("expected an error, found {:?}", events),
+    }
+}
+
+/// A frame of as many bytes as its first byte says.
+#[parser]
+fn frame<'a>(input: &mut Partial<'a>) -> Result<&'a [u8], Partial<'a>> {
+    let len = eat_any()?;
+    match input.eat_slice(len as usize, |_| true) {
+        Some(data) => data,
+        None => parse_error!("truncated frame")?,
+    }
+}
+
+#[test]
+fn test_waits_for_needed_bytes() {
+    let mut runs = 0;
+    let mut session = Session::new(|input: &mut Partial<'_>| {
+        runs += 1;
+        frame(input).map(|data| data.to_vec())
+    });
+
+    assert!(session.feed(b"\x08ab").is_empty());
+    for byte in b"cdefg".iter() {
+        assert!(session.feed(&[*byte]).is_empty());
+    }
+
+    let events = session.feed(b"h\x01");
+    assert!(matches!(&events[..], [Event::Message(data)] if data == b"abcdefgh"));
+    assert_eq!(session.buffered(), b"\x01");
+    drop(session);
+    assert_eq!(runs, 3);
+}
@@ -0,0 +1,62 @@
+#![feature(proc_macro_hygiene)]
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use pear::input::{SharedText, SharedStr, SharedBytes, Position, Result};
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn word(input: &mut SharedText) -> Result<SharedStr, SharedText> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[parser]
+fn pair(input: &mut SharedText) -> Result<(SharedStr, SharedStr), SharedText> {
+    let key = word()?;
+    eat_slice(" = ")?;
+    (key, word()?)
+}
+
+#[parser]
+fn request_line(input: &mut SharedBytes) -> Result<(Bytes, Bytes), SharedBytes> {
+    let method = take_some_while(|b: &u8| b.is_ascii_uppercase())?;
+    eat(b' ')?;
+    (method, take_some_while(|b: &u8| *b != b' ')?)
+}
+
+#[test]
+fn test_shared_text() {
+    let source: Arc<str> = Arc::from("name = pear");
+    let mut input = SharedText::from(source.clone());
+    let (key, value) = parse!(pair: &mut input).unwrap();
+    assert_eq!(input.position(), 11);
+    drop(input);
+
+    assert_eq!((key.as_str(), value.range()), ("name", 7..11));
+    assert!(Arc::ptr_eq(key.source(), &source));
+
+    let handle = std::thread::spawn(move || format!("{}={}", key, value));
+    assert_eq!(handle.join().unwrap(), "name=pear");
+}
+
+#[test]
+fn test_shared_text_errors() {
+    let mut input = SharedText::from("name pear");
+    let error = parse!(pair: &mut input).unwrap_err();
+    let context = error.contexts.last().unwrap().context.as_ref().unwrap();
+    assert_eq!(context.start, (1, 1, 0));
+    assert_eq!(context.snippet.as_ref().unwrap(), "name");
+
+    let handle = std::thread::spawn(move || error.to_string());
+    assert!(handle.join().unwrap().contains("` = `"));
+}
+
+#[test]
+fn test_shared_bytes() {
+    let source = Bytes::from_static(b"GET /index.html");
+    let mut input = SharedBytes::from(source.clone());
+    let (method, path) = parse!(request_line: &mut input).unwrap();
+    assert_eq!((&method[..], &path[..]), (&b"GET"[..], &b"/index.html"[..]));
+    assert_eq!(source.slice(4..), path);
+}
@@ -0,0 +1,75 @@
+#![feature(proc_macro_hygiene)]
+
+use std::ops::RangeInclusive;
+
+use pear::input::{Text, Cursor, Position, CaseFold, SliceEq, Result};
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn select<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    let keyword = eat_slice_eq("select", CaseFold)?;
+    eat(' ')?;
+    keyword
+}
+
+#[test]
+fn test_case_fold() {
+    for source in ["select *", "SELECT *", "SeLeCt *"] {
+        let mut input = Text::from(source);
+        assert_eq!(select(&mut input).unwrap(), &source[..6]);
+        assert_eq!(input.position(), 7);
+    }
+
+    let mut input = Text::from("selects *");
+    assert!(select(&mut input).is_err());
+
+    let error = select(&mut Text::from("insert")).unwrap_err();
+    assert_eq!(error.error.to_string(), "expected slice `select` but found `insert`");
+}
+
+#[test]
+fn test_case_fold_lengths() {
+    // `K` is the Kelvin sign, three bytes long, which folds to `k`.
+    let mut input = Text::from("\u{212a}ELVIN");
+    assert_eq!(eat_slice_eq(&mut input, "kelvin", CaseFold).unwrap(), "\u{212a}ELVIN");
+    assert_eq!(input.position(), 8);
+
+    let mut input = Text::from("MASSE!");
+    assert_eq!(eat_slice_eq(&mut input, "maße", CaseFold).unwrap(), "MASSE");
+    assert_eq!(input.position(), 5);
+
+    let mut input = Text::from("maße");
+    assert!(peek_slice_eq(&mut input, "MASSE", CaseFold).is_ok());
+    assert_eq!(input.position(), 0);
+    assert!(peek_slice_eq(&mut input, "MASSA", CaseFold).is_err());
+}
+
+/// Matches byte strings regardless of runs of spaces.
+struct Spaces;
+
+impl SliceEq<&[u8]> for Spaces {
+    fn slice_eq(&self, expected: &&[u8], found: &&[u8]) -> bool {
+        let squash = |s: &[u8]| {
+            let mut squashed = s.to_vec();
+            squashed.dedup_by(|a, b| *a == b' ' && *b == b' ');
+            squashed
+        };
+
+        squash(expected) == squash(found)
+    }
+
+    fn lengths(&self, expected: &&[u8]) -> RangeInclusive<usize> {
+        expected.len()..=expected.len() + 4
+    }
+}
+
+#[test]
+fn test_custom_slice_eq() {
+    let mut input = Cursor::from(&b"GET  /  HTTP/1.1"[..]);
+    let found = eat_slice_eq(&mut input, &b"GET / HTTP"[..], Spaces).unwrap();
+    assert_eq!(found, b"GET  /  HTTP");
+
+    let mut input = Cursor::from(&b"GET  /x"[..]);
+    assert!(eat_slice_eq(&mut input, &b"GET /"[..], |a: &&[u8], b: &&[u8]| a == b).is_err());
+    assert_eq!(input.position(), 0);
+}
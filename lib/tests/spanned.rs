@@ -0,0 +1,97 @@
+#![feature(proc_macro_hygiene)]
+
+use std::ops::Range;
+
+use pear::input::{Text, Cursor, IterInput, Position, Spanned, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn ident<'a>(input: &mut Text<'a>) -> Result<(&'a str, Range<usize>), Text<'a>> {
+    skip_while(|c: &char| c.is_whitespace())?;
+    spanned(|i| take_some_while(i, |c: &char| c.is_alphanumeric()))?
+}
+
+#[parser]
+fn idents<'a>(input: &mut Text<'a>) -> Result<Vec<(&'a str, Range<usize>)>, Text<'a>> {
+    let idents: Vec<_> = collect(ident)?;
+    idents
+}
+
+#[parser]
+fn pair<'a>(input: &mut Cursor<'a, u8>) -> Result<(u8, u8), Cursor<'a, u8>> {
+    (*eat_any()?, *eat_any()?)
+}
+
+#[test]
+fn test_text_spans() {
+    let idents = parse!(idents: &mut Text::from("let  über x1")).unwrap();
+    assert_eq!(idents, vec![("let", 0..3), ("über", 5..10), ("x1", 11..13)]);
+}
+
+#[test]
+fn test_token_spans() {
+    let bytes = [1, 2, 3, 4, 5];
+    let mut input = Cursor::from(&bytes[..]);
+    eat_any(&mut input).unwrap();
+    let (value, span) = spanned(&mut input, pair).unwrap();
+    assert_eq!((value, span), ((2, 3), 1..3));
+    assert_eq!(input.position(), 3);
+
+    let mut input = IterInput::new(bytes.iter().copied());
+    let (_, span) = spanned(&mut input, |i| take_while(i, |&b| b < 4)).unwrap();
+    assert_eq!(span, 0..3);
+}
+
+#[test]
+fn test_failed_span() {
+    let mut input = Text::from("!");
+    assert!(spanned(&mut input, |i| eat(i, 'a')).is_err());
+}
+
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Num(Spanned<u32>),
+    Add(Box<Spanned<Expr>>, Box<Spanned<Expr>>),
+}
+
+#[parser(spanned)]
+fn number<'a>(input: &mut Text<'a>) -> Result<Spanned<u32>, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>().unwrap()
+}
+
+#[parser(spanned)]
+fn expr<'a>(input: &mut Text<'a>) -> Result<Spanned<Expr>, Text<'a>> {
+    let number = number()?;
+    let lhs = Spanned::new(Expr::Num(number.clone()), number.span);
+    switch! {
+        eat('+') => Expr::Add(Box::new(lhs), Box::new(expr()?)),
+        _ => lhs.value
+    }
+}
+
+impl Expr {
+    fn span(&self) -> Range<usize> {
+        match self {
+            Expr::Num(n) => n.span.clone(),
+            Expr::Add(a, b) => a.span.start..b.span.end,
+        }
+    }
+}
+
+#[test]
+fn test_spanned_parsers() {
+    let number = parse!(number: &mut Text::from("123")).unwrap();
+    assert_eq!(number, Spanned::new(123, 0..3));
+    assert_eq!(*number + 1, 124);
+
+    let expr = parse!(expr: &mut Text::from("1+23+456")).unwrap();
+    assert_eq!(expr.span, 0..8);
+    match expr.into_inner() {
+        Expr::Add(lhs, rhs) => {
+            assert_eq!(lhs.span, 0..1);
+            assert_eq!(rhs.span, 2..8);
+            assert_eq!(rhs.value.span(), 2..8);
+        }
+        e => panic!("unexpected {:?}", e),
+    }
+}
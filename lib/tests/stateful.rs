@@ -0,0 +1,103 @@
+#![feature(proc_macro_hygiene)]
+
+use std::collections::HashSet;
+
+use pear::input::{Text, Stateful};
+use pear::{macros::*, parsers::*};
+
+#[derive(Default)]
+struct State {
+    types: HashSet<&'static str>,
+    pragmas: usize,
+}
+
+type Input<'a> = Stateful<Text<'a>, State>;
+type Result<'a, T> = pear::input::Result<T, Input<'a>>;
+
+#[parser]
+fn word<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    let word = take_some_while(|c: &char| c.is_ascii_alphabetic())?;
+    skip_while(|c: &char| *c == ' ')?;
+    word
+}
+
+#[parser(rewind)]
+fn pragma<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    state!().pragmas += 1;
+    eat('#')?;
+    let name = word()?;
+    if name == "int" {
+        state!().types.insert("int");
+    }
+}
+
+#[parser]
+fn declaration<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, &'a str)> {
+    let ty = word()?;
+    if !state!().types.contains(ty) {
+        parse_error!("unknown type")?;
+    }
+
+    (ty, word()?)
+}
+
+#[parser]
+fn program<'a>(input: &mut Input<'a>) -> Result<'a, Vec<(&'a str, &'a str)>> {
+    let mut declarations = vec![];
+    loop {
+        switch! {
+            pragma() => continue,
+            eof() => break,
+            _ => {
+                let declaration = declaration()?;
+                declarations.push(declaration);
+            }
+        }
+    }
+
+    declarations
+}
+
+#[test]
+fn test_state() {
+    let mut input = Stateful::new(Text::from("#int int x int y "), State::default());
+    assert_eq!(parse!(program: &mut input).unwrap(), [("int", "x"), ("int", "y")]);
+    assert!(input.state().types.contains("int"));
+
+    let mut input = Stateful::new(Text::from("int x "), State::default());
+    let error = parse!(program: &mut input).unwrap_err();
+    assert_eq!(error.to_string().lines().next(), Some("unknown type"));
+}
+
+#[test]
+fn test_state_is_not_rewound() {
+    let mut input = Stateful::new(Text::from("#int int x "), State::default());
+    parse!(program: &mut input).unwrap();
+
+    // `pragma` ran three times, failing and rewinding twice.
+    let (_, state) = input.into_inner();
+    assert_eq!(state.pragmas, 3);
+}
+
+#[derive(Clone, Default)]
+struct Scopes(Vec<&'static str>);
+
+type Scoped<'a> = Stateful<Text<'a>, Scopes>;
+
+#[parser(rewind)]
+fn scoped<'a>(input: &mut Scoped<'a>) -> pear::input::Result<(), Scoped<'a>> {
+    state!().0.push("block");
+    eat('{')?;
+    eat('}')?;
+}
+
+#[test]
+fn test_cloneable_state_is_rewound() {
+    let mut input = Stateful::new(Text::from("{x"), Scopes::default());
+    assert!(scoped(&mut input).is_err());
+    assert!(input.state().0.is_empty());
+
+    let mut input = Stateful::new(Text::from("{}"), Scopes::default());
+    parse!(scoped: &mut input).unwrap();
+    assert_eq!(input.state().0, ["block"]);
+}
@@ -0,0 +1,79 @@
+#![feature(proc_macro_hygiene)]
+
+use std::error::Error as StdError;
+use std::{fmt, num::ParseIntError};
+
+use pear::input::{Text, Result};
+use pear::error::{Expected, ParseError};
+use pear::{macros::*, parsers::*};
+
+#[parser]
+fn pair<'a>(input: &mut Text<'a>) -> Result<(char, char), Text<'a>> {
+    let a = eat_any()?;
+    eat(',')?;
+    (a, eat_any()?)
+}
+
+fn run(source: &'static str) -> std::result::Result<(char, char), Box<dyn StdError>> {
+    Ok(parse!(pair: &mut Text::from(source))?)
+}
+
+#[test]
+fn test_boxed_error() {
+    assert_eq!(run("a,b").unwrap(), ('a', 'b'));
+
+    let error = run("a;b").unwrap_err();
+    assert!(error.to_string().starts_with("expected token `,` but found `;`"));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn test_alternate_display() {
+    let error = parse!(pair: &mut Text::from("a;b")).unwrap_err();
+    let display = format!("{:#}", error);
+    let lines: Vec<_> = display.lines().collect();
+    assert_eq!(lines[0], "expected token `,` but found `;`");
+    assert_eq!(lines[1], "  expected: `,`");
+    assert_eq!(lines[2], "  found: `;`");
+    assert!(lines[3].starts_with("  in eat at "));
+
+    let expected: Expected<char, &str> = Expected::Token(Some("`x`".into()), None);
+    assert_eq!(format!("{:#}", expected), "expected token `x` but none was found\n  \
+        expected: `x`\n  found: EOF");
+}
+
+#[derive(Debug)]
+enum NumberError {
+    Syntax(String),
+    Invalid(ParseIntError),
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberError::Syntax(message) => write!(f, "{}", message),
+            NumberError::Invalid(_) => write!(f, "invalid number"),
+        }
+    }
+}
+
+impl StdError for NumberError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            NumberError::Invalid(e) => Some(e),
+            NumberError::Syntax(_) => None,
+        }
+    }
+}
+
+#[test]
+fn test_source_chaining() {
+    let cause = "300".parse::<u8>().unwrap_err();
+    let error: ParseError<String, NumberError> = ParseError::new(NumberError::Invalid(cause));
+    let error: &dyn StdError = &error;
+    assert_eq!(error.to_string(), "invalid number");
+    assert!(error.source().unwrap().is::<ParseIntError>());
+
+    let error: ParseError<String, _> = ParseError::new(NumberError::Syntax("bad".into()));
+    assert!(error.source().is_none());
+}
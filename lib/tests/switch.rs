@@ -0,0 +1,94 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::macros::{parser, parse, switch};
+use pear::parsers::*;
+
+#[parser]
+fn digit<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    eat_if(|c: &char| c.is_ascii_digit())?
+}
+
+#[parser]
+fn letter<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    eat_if(|c: &char| c.is_alphabetic())?
+}
+
+// The alternatives bind different names, none of which the case uses.
+#[parser]
+fn kind<'a>(input: &mut Text<'a>) -> Result<&'static str, Text<'a>> {
+    switch! {
+        _d@digit() | _l@letter() => "alphanumeric",
+        eat('_') => "underscore",
+        _ => "other"
+    }
+}
+
+// The case only uses `c`, which every alternative binds.
+#[parser]
+fn upper<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+    switch! {
+        c@digit() | c@letter() | c@eat('_') => c.to_ascii_uppercase(),
+        _ => { eat_any()?; '?' }
+    }
+}
+
+// Binders used only inside of a macro are checked, too.
+#[parser]
+fn described<'a>(input: &mut Text<'a>) -> Result<String, Text<'a>> {
+    switch! {
+        c@digit() | c@letter() => format!("char {}", c),
+        _ => String::new()
+    }
+}
+
+#[test]
+fn test_different_binders() {
+    assert_eq!(parse!(kind: &mut Text::from("7")).unwrap(), "alphanumeric");
+    assert_eq!(parse!(kind: &mut Text::from("x")).unwrap(), "alphanumeric");
+    assert_eq!(parse!(kind: &mut Text::from("_")).unwrap(), "underscore");
+
+    assert_eq!(parse!(upper: &mut Text::from("x")).unwrap(), 'X');
+    assert_eq!(parse!(upper: &mut Text::from("_")).unwrap(), '_');
+    assert_eq!(parse!(upper: &mut Text::from("!")).unwrap(), '?');
+
+    assert_eq!(parse!(described: &mut Text::from("q")).unwrap(), "char q");
+}
+
+#[parser(rewind)]
+fn pair<'a>(input: &mut Text<'a>) -> Result<(char, char), Text<'a>> {
+    let key = letter()?;
+    eat('=')?;
+    (key, digit()?)
+}
+
+struct Point { x: char, y: char }
+
+#[parser]
+fn point<'a>(input: &mut Text<'a>) -> Result<Point, Text<'a>> {
+    eat('(')?;
+    let x = digit()?;
+    eat(',')?;
+    let y = digit()?;
+    eat(')')?;
+    Point { x, y }
+}
+
+#[parser]
+fn entry<'a>(input: &mut Text<'a>) -> Result<String, Text<'a>> {
+    switch! {
+        (k, v)@pair() => format!("{} is {}", k, v),
+        Point { x, y }@point() => format!("point {} {}", x, y),
+        (_, std::ops::Range { start, end })@pear::combinators::spanned(letter) => {
+            format!("{}..{}", start, end)
+        },
+        _ => String::new()
+    }
+}
+
+#[test]
+fn test_destructuring() {
+    assert_eq!(parse!(entry: &mut Text::from("a=1")).unwrap(), "a is 1");
+    assert_eq!(parse!(entry: &mut Text::from("(1,2)")).unwrap(), "point 1 2");
+    assert_eq!(parse!(entry: &mut Text::from("z")).unwrap(), "0..1");
+}
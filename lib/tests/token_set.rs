@@ -0,0 +1,87 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Cursor, TokenSet, TokenClass};
+use pear::macros::{parser, parse};
+use pear::parsers::*;
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+const OPERATOR: TokenSet = TokenSet::new(b"+-*/");
+const IDENT: TokenSet = TokenSet::range(b'a', b'z')
+    .union(TokenSet::range(b'A', b'Z'))
+    .with(b'_');
+
+#[parser]
+fn expr<'a>(input: &mut Text<'a>) -> Result<'a, Vec<char>> {
+    let mut tokens = vec![];
+    loop {
+        let operand = eat_any_of('0'..='9')?;
+        tokens.push(operand);
+        match eat_any_of(input, OPERATOR) {
+            Ok(op) => tokens.push(op),
+            Err(_) => break,
+        }
+    }
+
+    tokens
+}
+
+#[test]
+fn test_classes() {
+    let result = parse!(expr: &mut Text::from("1+2*3"));
+    assert_eq!(result.unwrap(), ['1', '+', '2', '*', '3']);
+
+    let mut input = Text::from("x_é");
+    assert_eq!(eat_any_of(&mut input, ['y', 'x']).unwrap(), 'x');
+    assert_eq!(eat_any_of(&mut input, &['_'][..]).unwrap(), '_');
+    assert!(eat_any_of(&mut input, IDENT).is_err());
+    assert!(eat_any_of(&mut input, 'a'..'x').is_err());
+    assert_eq!(eat_any_of(&mut input, |c: &char| !c.is_ascii()).unwrap(), 'é');
+    assert!(eat_any_of(&mut input, |_: &char| true).is_err());
+}
+
+#[test]
+fn test_none_of() {
+    let mut input = Text::from("ab\"");
+    assert_eq!(none_of(&mut input, ['"', '\\']).unwrap(), 'a');
+    assert_eq!(none_of(&mut input, OPERATOR).unwrap(), 'b');
+    assert!(none_of(&mut input, ['"', '\\']).is_err());
+    assert_eq!(eat(&mut input, '"').unwrap(), '"');
+    assert!(none_of(&mut input, ['"']).is_err());
+}
+
+#[test]
+fn test_byte_inputs() {
+    let mut input = Cursor::from(&b"\x7fGET /"[..]);
+    assert!(eat_any_of(&mut input, IDENT).is_err());
+    assert_eq!(eat_any_of(&mut input, TokenSet::range(0x7f, 0xff)).unwrap(), &0x7f);
+    assert_eq!(eat_any_of(&mut input, b'A'..=b'Z').unwrap(), &b'G');
+    assert_eq!(take_while_fast(&mut input, IDENT).unwrap(), b"ET");
+    assert_eq!(none_of(&mut input, TokenSet::new(b"/")).unwrap(), &b' ');
+}
+
+#[test]
+fn test_set_operations() {
+    assert_eq!(IDENT.len(), 53);
+    assert!(TokenSet::EMPTY.is_empty());
+    assert_eq!(TokenSet::range(0, 255).len(), 256);
+    assert_eq!(TokenSet::range(0, 255), TokenSet::EMPTY.complement());
+    assert!(IDENT.complement().contains_byte(b'-'));
+    assert!(!IDENT.complement().contains_byte(b'q'));
+    assert!(!TokenClass::<Text<'_>>::contains(&TokenSet::range(0, 255), &'é'));
+}
+
+#[test]
+fn test_errors() {
+    let error = eat_any_of(&mut Text::from("x"), OPERATOR).unwrap_err();
+    assert_eq!(error.error.expected(), ["`*`", "`+`", "`-`", "`/`"]);
+
+    let error = eat_any_of(&mut Text::from("x"), ['a', 'b']).unwrap_err();
+    assert_eq!(error.error.expected(), ["`a`", "`b`"]);
+
+    let error = eat_any_of(&mut Text::from("x"), 'a'..='f').unwrap_err();
+    assert_eq!(error.error.expected(), ["`a`..=`f`"]);
+
+    let error = eat_any_of(&mut Text::from("x"), IDENT.complement()).unwrap_err();
+    assert!(error.error.expected().is_empty());
+}
@@ -0,0 +1,62 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, ParserInfo, Result};
+use pear::{macros::parser, parsers::*};
+use pear::trace::TraceFilter;
+
+mod json {
+    use super::*;
+
+    #[parser]
+    pub fn value<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+        eat('x')?
+    }
+}
+
+mod http {
+    use super::*;
+
+    #[parser]
+    pub fn header<'a>(input: &mut Text<'a>) -> Result<char, Text<'a>> {
+        eat('h')?
+    }
+}
+
+// Returns the info of each non-raw parser in an error from `p`.
+fn parsers(p: fn(&mut Text<'static>) -> Result<char, Text<'static>>) -> Vec<ParserInfo> {
+    let error = p(&mut Text::from("?")).unwrap_err();
+    error.parsers().filter(|p| !p.raw).copied().collect()
+}
+
+#[test]
+fn test_patterns() {
+    let value = parsers(json::value)[0];
+    let header = parsers(http::header)[0];
+
+    let filter = TraceFilter::parse("json::*,http::header");
+    assert!(filter.matches(&value) && filter.matches(&header));
+
+    let filter = TraceFilter::parse("trace::json::value");
+    assert!(filter.matches(&value) && !filter.matches(&header));
+
+    let filter = TraceFilter::parse(" value , ");
+    assert!(filter.matches(&value) && !filter.matches(&header));
+
+    let filter = TraceFilter::parse("*");
+    assert!(filter.matches(&value) && filter.matches(&header));
+
+    // Patterns match whole path segments.
+    let filter = TraceFilter::parse("son::value,ttp::*");
+    assert!(!filter.matches(&value) && !filter.matches(&header));
+
+    let filter = TraceFilter::parse("");
+    assert!(!filter.matches(&value) && !filter.matches(&header));
+}
+
+#[test]
+fn test_runtime_parsers() {
+    let info = ParserInfo::new("value", false);
+    assert!(TraceFilter::parse("value").matches(&info));
+    assert!(TraceFilter::parse("val*").matches(&info));
+    assert!(!TraceFilter::parse("json::*").matches(&info));
+}
@@ -0,0 +1,75 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Checkpoint, Input, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn pair<'a>(input: &mut Text<'a>) -> Result<(char, char), Text<'a>> {
+    let a = eat_any()?;
+    eat(',')?;
+    (a, eat_any()?)
+}
+
+#[parser]
+fn committed_pair<'a>(input: &mut Text<'a>) -> Result<(char, char), Text<'a>> {
+    let a = eat_any()?;
+    cut(|i| eat(i, ','))?;
+    (a, eat_any()?)
+}
+
+#[test]
+fn test_transaction() {
+    let mut input = Text::from("a,b");
+    assert_eq!(transaction(&mut input, pair).unwrap(), ('a', 'b'));
+    assert!(eof(&mut input).is_ok());
+
+    let mut input = Text::from("a;b");
+    assert!(transaction(&mut input, pair).is_err());
+    assert_eq!(input.token(), Some('a'));
+
+    // Even committed errors rewind the input.
+    let mut input = Text::from("a;b");
+    assert!(transaction(&mut input, committed_pair).unwrap_err().committed);
+    assert_eq!(input.token(), Some('a'));
+}
+
+#[test]
+fn test_checkpoint() {
+    let mut input = Text::from("abc");
+
+    let mut checkpoint = Checkpoint::new(&mut input);
+    eat_slice(&mut *checkpoint, "ab").unwrap();
+    checkpoint.rollback();
+    assert_eq!(input.token(), Some('a'));
+
+    let mut checkpoint = Checkpoint::new(&mut input);
+    eat_slice(&mut *checkpoint, "ab").unwrap();
+    assert_eq!(checkpoint.token(), Some('c'));
+    checkpoint.commit();
+    assert_eq!(input.token(), Some('c'));
+}
+
+#[test]
+fn test_checkpoint_nested() {
+    let mut input = Text::from("abc");
+
+    let mut outer = Checkpoint::new(&mut input);
+    eat(&mut *outer, 'a').unwrap();
+    {
+        let mut inner = Checkpoint::new(&mut *outer);
+        eat(&mut *inner, 'b').unwrap();
+        inner.commit();
+    }
+
+    assert_eq!(outer.token(), Some('c'));
+    drop(outer);
+    assert_eq!(input.token(), Some('a'));
+}
+
+#[test]
+fn test_try_collect_rewinds() {
+    let mut input = Text::from("a,bc;d");
+    let pairs: Vec<_> = try_collect(&mut input, pair).unwrap();
+    assert_eq!(pairs, [('a', 'b')]);
+    assert_eq!(input.token(), Some('c'));
+}
@@ -0,0 +1,91 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, TreeCapture, SyntaxNode, Result};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = TreeCapture<Text<'a>>;
+
+#[parser]
+fn ws<'a>(input: &mut Input<'a>) -> Result<(), Input<'a>> {
+    skip_while(|c: &char| c.is_whitespace())?;
+}
+
+#[parser]
+fn ident<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    take_some_while(|c: &char| c.is_alphabetic())?
+}
+
+#[parser]
+fn number<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?
+}
+
+#[parser]
+fn value<'a>(input: &mut Input<'a>) -> Result<&'a str, Input<'a>> {
+    switch! {
+        n@number() => n,
+        _ => ident()?
+    }
+}
+
+#[parser]
+fn binding<'a>(input: &mut Input<'a>) -> Result<(&'a str, &'a str), Input<'a>> {
+    let name = ident()?;
+    ws()?;
+    eat('=')?;
+    ws()?;
+    (name, value()?)
+}
+
+#[parser]
+fn bindings<'a>(input: &mut Input<'a>) -> Result<Vec<(&'a str, &'a str)>, Input<'a>> {
+    let bindings: Vec<_> = series(binding, ';')?;
+    bindings
+}
+
+#[parser]
+fn attempt<'a>(input: &mut Input<'a>) -> Result<Vec<&'a str>, Input<'a>> {
+    let values: Vec<_> = try_collect(|i| { let v = value(i)?; eat(i, ',')?; Ok(v) })?;
+    values
+}
+
+fn shape(node: &SyntaxNode, source: &str) -> String {
+    let children: Vec<_> = node.children.iter().map(|c| shape(c, source)).collect();
+    match children.is_empty() {
+        true => format!("{}({:?})", node.name, node.text(source)),
+        false => format!("{}({})", node.name, children.join(" ")),
+    }
+}
+
+#[test]
+fn test_capture_tree() {
+    let source = "a = 1;b=c";
+    let mut input = TreeCapture::new(Text::from(source));
+    let values = parse!(bindings: &mut input).unwrap();
+    assert_eq!(values, vec![("a", "1"), ("b", "c")]);
+
+    let roots = input.roots();
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].text(source), source);
+    assert_eq!(shape(&roots[0], source),
+        "bindings(binding(ident(\"a\") ws(\" \") ws(\" \") value(number(\"1\"))) \
+         binding(ident(\"b\") ws(\"\") ws(\"\") value(ident(\"c\"))))");
+
+    let names: Vec<_> = roots[0].descendants().map(|n| n.name).take(4).collect();
+    assert_eq!(names, vec!["bindings", "binding", "ident", "ws"]);
+}
+
+#[test]
+fn test_failures_are_discarded() {
+    let source = "1,x,2";
+    let mut input = TreeCapture::new(Text::from(source));
+    let values = attempt(&mut input).unwrap();
+    assert_eq!(values, vec!["1", "x"]);
+
+    // The `2` parsed by the failed third attempt was rewound.
+    let (_, roots) = input.into_roots();
+    let attempt = &roots[0];
+    assert_eq!(attempt.span, 0..4);
+    let values: Vec<_> = attempt.children.iter().map(|c| c.text(source)).collect();
+    assert_eq!(values, vec!["1", "x"]);
+}
@@ -0,0 +1,51 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Result};
+use pear::macros::{parser, parse};
+use pear::parsers::*;
+use pear::parsers::unicode::*;
+
+#[parser]
+fn identifiers<'a>(input: &mut Text<'a>) -> Result<Vec<&'a str>, Text<'a>> {
+    let first = identifier()?;
+    let mut names = vec![first];
+    while unicode_whitespace(input).is_ok() {
+        skip_while(is_whitespace)?;
+        let name = identifier()?;
+        names.push(name);
+    }
+
+    names
+}
+
+#[test]
+fn test_identifiers() {
+    let result = parse!(identifiers: &mut Text::from("café Δx\u{2003}変数 a1_b"));
+    assert_eq!(result.unwrap(), ["café", "Δx", "変数", "a1_b"]);
+
+    assert!(parse!(identifiers: &mut Text::from("1abc")).is_err());
+    assert!(parse!(identifiers: &mut Text::from("_abc")).is_err());
+    assert!(parse!(identifiers: &mut Text::from("a-b")).is_err());
+}
+
+#[test]
+fn test_character_classes() {
+    let mut input = Text::from("é٣ \u{a0}x");
+    assert_eq!(unicode_alphanumeric(&mut input).unwrap(), 'é');
+    assert_eq!(unicode_alphanumeric(&mut input).unwrap(), '٣');
+    assert!(unicode_alphanumeric(&mut input).is_err());
+    assert_eq!(unicode_whitespace(&mut input).unwrap(), ' ');
+    assert_eq!(unicode_whitespace(&mut input).unwrap(), '\u{a0}');
+    assert!(xid_continue(&mut input).is_ok());
+
+    let mut input = Text::from("_9");
+    assert!(xid_start(&mut input).is_err());
+    assert_eq!(xid_continue(&mut input).unwrap(), '_');
+    assert_eq!(xid_continue(&mut input).unwrap(), '9');
+}
+
+#[test]
+fn test_error_message() {
+    let error = identifier(&mut Text::from("9")).unwrap_err();
+    assert!(error.to_string().starts_with("expected an identifier but found `9`"));
+}
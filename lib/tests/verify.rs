@@ -0,0 +1,91 @@
+#![feature(proc_macro_hygiene)]
+
+use std::borrow::Cow;
+
+use pear::input::{Input, Text, Position, Result};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[parser]
+fn number<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+    take_some_while(|c: &char| c.is_ascii_digit())?.parse::<u32>()?
+}
+
+#[parser]
+fn percentage<'a>(input: &mut Text<'a>) -> Result<u32, Text<'a>> {
+    let value = verify(number, |&n| n <= 100, "a percentage is at most 100")?;
+    eat('%')?;
+    value
+}
+
+#[parser]
+fn setting<'a>(input: &mut Text<'a>) -> Result<Option<u32>, Text<'a>> {
+    switch! {
+        eat_slice("auto") => None,
+        _ => Some(percentage()?)
+    }
+}
+
+#[test]
+fn test_verify() {
+    assert_eq!(parse!(percentage: &mut Text::from("100%")).unwrap(), 100);
+    assert_eq!(parse!(setting: &mut Text::from("auto")).unwrap(), None);
+    assert_eq!(parse!(setting: &mut Text::from("42%")).unwrap(), Some(42));
+
+    let mut input = Text::from("250%");
+    let error = percentage(&mut input).unwrap_err();
+    assert!(error.to_string().starts_with("a percentage is at most 100"));
+    assert_eq!(input.position(), 0);
+
+    let span = error.contexts[0].context.as_ref().unwrap();
+    assert_eq!(error.contexts[0].parser.name, "verify");
+    assert_eq!((span.start.2, span.end.2), (0, 3));
+}
+
+#[test]
+fn test_verify_parser_failure() {
+    // An error from the parser itself is returned as is.
+    let error = parse!(percentage: &mut Text::from("x")).unwrap_err();
+    assert_eq!(error.parsers().next().unwrap().name, "take_some_while");
+
+    let error = parse!(percentage: &mut Text::from("50")).unwrap_err();
+    assert_eq!(error.expected(), ["`%`"]);
+}
+
+#[derive(Debug, PartialEq)]
+enum RangeError {
+    Syntax,
+    Invalid(String, usize),
+}
+
+impl<'a> From<Expected<char, &'a str>> for RangeError {
+    fn from(_: Expected<char, &'a str>) -> Self {
+        RangeError::Syntax
+    }
+}
+
+impl<'a> pear::Error<Text<'a>> for RangeError {
+    fn from_context(message: Cow<'static, str>, context: Option<<Text<'a> as Input>::Context>) -> Self {
+        RangeError::Invalid(message.into(), context.map(|span| span.start.1).unwrap_or(0))
+    }
+}
+
+#[parser]
+fn digits<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>, RangeError> {
+    take_some_while(|c: &char| c.is_ascii_digit())?
+}
+
+#[parser]
+fn hour<'a>(input: &mut Text<'a>) -> Result<u8, Text<'a>, RangeError> {
+    eat_slice("at ")?;
+    let valid = |d: &&str| d.len() <= 2 && d.parse::<u8>().unwrap() < 24;
+    verify(digits, valid, String::from("an hour is from 0 to 23"))?.parse::<u8>().unwrap()
+}
+
+#[test]
+fn test_verify_custom_error() {
+    assert_eq!(parse!(hour: &mut Text::from("at 23")).unwrap(), 23);
+
+    let error = parse!(hour: &mut Text::from("at 24")).unwrap_err();
+    assert_eq!(error.error, RangeError::Invalid("an hour is from 0 to 23".into(), 4));
+}
@@ -0,0 +1,67 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Input as _, Text, Versioned};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Versioned<Text<'a>>;
+type Result<'a, T> = pear::input::Result<T, Input<'a>>;
+
+#[parser]
+fn number<'a>(input: &mut Input<'a>) -> Result<'a, u32> {
+    let digits = take_some_while(|c: &char| c.is_ascii_digit())?;
+    digits.parse::<u32>().unwrap_or(u32::MAX)
+}
+
+#[parser]
+fn header<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    eat_slice("version ")?;
+    let version = number()?;
+    eat('\n')?;
+    input.set_version(version);
+}
+
+#[parser]
+fn separator<'a>(input: &mut Input<'a>) -> Result<'a, char> {
+    // v1 separates with `,`; v2 and later allow `;` as well.
+    when_version!(input, >= 2, eat_if(|&c| c == ',' || c == ';'), eat(','))?
+}
+
+#[parser]
+fn numbers<'a>(input: &mut Input<'a>) -> Result<'a, Vec<u32>> {
+    parse_try!(header());
+    let first = number()?;
+    let mut numbers = vec![first];
+    while input.has(1) {
+        separator()?;
+        numbers.push(number()?);
+    }
+
+    numbers
+}
+
+fn parse(string: &str, version: u32) -> Result<'_, Vec<u32>> {
+    parse!(numbers: &mut Versioned::new(Text::from(string), version))
+}
+
+#[test]
+fn test_when_version() {
+    assert_eq!(parse("1,2,3", 1).unwrap(), vec![1, 2, 3]);
+    assert_eq!(parse("1,2;3", 2).unwrap(), vec![1, 2, 3]);
+    assert!(parse("1,2;3", 1).is_err());
+}
+
+#[test]
+fn test_version_header() {
+    assert_eq!(parse("version 2\n1;2", 1).unwrap(), vec![1, 2]);
+    assert!(parse("version 1\n1;2", 2).is_err());
+}
+
+#[test]
+fn test_versioned_accessors() {
+    let mut input = Versioned::new(Text::from("abc"), 3);
+    assert_eq!(*input.version(), 3);
+    input.set_version(4);
+
+    let (_, version) = input.into_inner();
+    assert_eq!(version, 4);
+}
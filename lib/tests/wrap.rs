@@ -0,0 +1,70 @@
+#![feature(proc_macro_hygiene)]
+
+use pear::input::{Text, Position, Result};
+use pear::{macros::*, parsers::*};
+use pear::wrap::{parser, rewinding, peeking};
+
+#[parser(rewind)]
+fn key<'a>(input: &mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    let key = take_some_while(|c: &char| c.is_alphabetic())?;
+    eat('=')?;
+    key
+}
+
+fn dynamic_key<'a>() -> impl FnMut(&mut Text<'a>) -> Result<&'a str, Text<'a>> {
+    rewinding(parser("key", |input: &mut Text<'a>| {
+        let key = take_some_while(input, |c: &char| c.is_alphabetic())?;
+        eat(input, '=')?;
+        Ok(key)
+    }))
+}
+
+#[test]
+fn test_matches_attribute() {
+    for source in ["name=pear", "name:pear", "=pear"] {
+        let (mut a, mut b) = (Text::from(source), Text::from(source));
+        let (expected, actual) = (key(&mut a), dynamic_key()(&mut b));
+        assert_eq!(a.position(), b.position());
+        match (expected, actual) {
+            (Ok(x), Ok(y)) => assert_eq!(x, y),
+            (Err(x), Err(y)) => {
+                assert_eq!(x.to_string(), y.to_string());
+                assert_eq!(x.contexts.len(), y.contexts.len());
+            }
+            (x, y) => panic!("{:?} != {:?}", x.is_ok(), y.is_ok()),
+        }
+    }
+}
+
+#[test]
+fn test_peeking() {
+    let mut digits = peeking(|input: &mut Text<'static>| {
+        take_some_while(input, |c: &char| c.is_ascii_digit())
+    });
+
+    let mut input = Text::from("123abc");
+    assert_eq!(digits(&mut input).unwrap(), "123");
+    assert_eq!(input.position(), 0);
+
+    let mut input = Text::from("abc");
+    assert!(digits(&mut input).is_err());
+}
+
+#[test]
+fn test_runtime_construction() {
+    let keywords = ["let", "fn", "if"];
+    let mut parsers: Vec<_> = keywords.iter()
+        .map(|&keyword| rewinding(parser(keyword, move |input: &mut Text<'static>| {
+            eat_slice(input, keyword)
+        })))
+        .collect();
+
+    let mut input = Text::from("fn");
+    let matched = parsers.iter_mut().position(|p| p(&mut input).is_ok());
+    assert_eq!(matched, Some(1));
+
+    let mut input = Text::from("loop");
+    let error = parsers[0](&mut input).unwrap_err();
+    assert_eq!(error.contexts.last().unwrap().parser.name, "let");
+    assert_eq!(input.position(), 0);
+}